@@ -0,0 +1,11 @@
+CREATE TABLE IF NOT EXISTS embed_snapshots (
+    id BIGSERIAL PRIMARY KEY,
+    message_id BIGINT NOT NULL,
+    target TEXT NOT NULL,
+    version INTEGER NOT NULL,
+    author_id BIGINT NOT NULL,
+    embed JSONB NOT NULL,
+    diff TEXT,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+    UNIQUE (message_id, version)
+);
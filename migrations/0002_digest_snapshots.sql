@@ -0,0 +1,7 @@
+CREATE TABLE IF NOT EXISTS digest_snapshots (
+    id BIGSERIAL PRIMARY KEY,
+    stars BIGINT NOT NULL,
+    contributors BIGINT NOT NULL,
+    weekly_downloads BIGINT NOT NULL,
+    posted_at TIMESTAMPTZ NOT NULL DEFAULT now()
+);
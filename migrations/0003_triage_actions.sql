@@ -0,0 +1,8 @@
+CREATE TABLE IF NOT EXISTS triage_actions (
+    id BIGSERIAL PRIMARY KEY,
+    issue_number BIGINT NOT NULL,
+    action TEXT NOT NULL,
+    detail TEXT NOT NULL,
+    actor_id BIGINT NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT now()
+);
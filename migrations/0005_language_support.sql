@@ -0,0 +1,7 @@
+CREATE TABLE IF NOT EXISTS language_support (
+    language_name TEXT PRIMARY KEY,
+    parsing SMALLINT NOT NULL,
+    formatting SMALLINT NOT NULL,
+    linting SMALLINT NOT NULL,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT now()
+);
@@ -0,0 +1,8 @@
+CREATE TABLE IF NOT EXISTS tags (
+    name TEXT PRIMARY KEY,
+    content TEXT NOT NULL,
+    author_id BIGINT NOT NULL,
+    uses BIGINT NOT NULL DEFAULT 0,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT now()
+);
@@ -0,0 +1,7 @@
+CREATE TABLE IF NOT EXISTS canned_responses (
+    name TEXT PRIMARY KEY,
+    content TEXT NOT NULL,
+    author_id BIGINT NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT now()
+);
@@ -0,0 +1,6 @@
+CREATE TABLE IF NOT EXISTS faq_questions (
+    id BIGSERIAL PRIMARY KEY,
+    question TEXT NOT NULL UNIQUE,
+    tag_name TEXT NOT NULL REFERENCES tags (name) ON DELETE CASCADE,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT now()
+);
@@ -0,0 +1,8 @@
+CREATE TABLE IF NOT EXISTS solved_threads (
+    thread_id BIGINT PRIMARY KEY,
+    forum_id BIGINT NOT NULL,
+    marked_by BIGINT NOT NULL,
+    solver_id BIGINT,
+    summary TEXT,
+    solved_at TIMESTAMPTZ NOT NULL DEFAULT now()
+);
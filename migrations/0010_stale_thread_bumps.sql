@@ -0,0 +1,5 @@
+CREATE TABLE IF NOT EXISTS stale_thread_bumps (
+    thread_id BIGINT PRIMARY KEY,
+    message_id BIGINT NOT NULL,
+    bumped_at TIMESTAMPTZ NOT NULL DEFAULT now()
+);
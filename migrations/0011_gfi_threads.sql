@@ -0,0 +1,7 @@
+CREATE TABLE IF NOT EXISTS gfi_threads (
+    repository TEXT NOT NULL,
+    issue_number BIGINT NOT NULL,
+    thread_id BIGINT NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+    PRIMARY KEY (repository, issue_number)
+);
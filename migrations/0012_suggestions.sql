@@ -0,0 +1,19 @@
+CREATE TABLE IF NOT EXISTS suggestions (
+    id BIGSERIAL PRIMARY KEY,
+    author_id BIGINT NOT NULL,
+    content TEXT NOT NULL,
+    channel_id BIGINT,
+    message_id BIGINT,
+    -- one of `open`, `accepted` or `declined`
+    status TEXT NOT NULL DEFAULT 'open',
+    decided_by BIGINT,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT now()
+);
+
+CREATE TABLE IF NOT EXISTS suggestion_votes (
+    suggestion_id BIGINT NOT NULL REFERENCES suggestions (id) ON DELETE CASCADE,
+    user_id BIGINT NOT NULL,
+    upvote BOOLEAN NOT NULL,
+    voted_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+    PRIMARY KEY (suggestion_id, user_id)
+);
@@ -0,0 +1,21 @@
+CREATE TABLE IF NOT EXISTS polls (
+    id BIGSERIAL PRIMARY KEY,
+    author_id BIGINT NOT NULL,
+    question TEXT NOT NULL,
+    options TEXT[] NOT NULL,
+    channel_id BIGINT,
+    message_id BIGINT,
+    closes_at TIMESTAMPTZ NOT NULL,
+    closed BOOLEAN NOT NULL DEFAULT false,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT now()
+);
+
+CREATE TABLE IF NOT EXISTS poll_votes (
+    poll_id BIGINT NOT NULL REFERENCES polls (id) ON DELETE CASCADE,
+    user_id BIGINT NOT NULL,
+    option_index SMALLINT NOT NULL,
+    voted_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+    PRIMARY KEY (poll_id, user_id)
+);
+
+CREATE INDEX IF NOT EXISTS polls_open_closes_at_idx ON polls (closes_at) WHERE NOT closed;
@@ -0,0 +1,12 @@
+CREATE TABLE IF NOT EXISTS reminders (
+    id BIGSERIAL PRIMARY KEY,
+    user_id BIGINT NOT NULL,
+    channel_id BIGINT NOT NULL,
+    content TEXT NOT NULL,
+    dm BOOLEAN NOT NULL DEFAULT false,
+    remind_at TIMESTAMPTZ NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT now()
+);
+
+CREATE INDEX IF NOT EXISTS reminders_remind_at_idx ON reminders (remind_at);
+CREATE INDEX IF NOT EXISTS reminders_user_id_idx ON reminders (user_id);
@@ -0,0 +1,30 @@
+CREATE TABLE IF NOT EXISTS jobs (
+    id BIGSERIAL PRIMARY KEY,
+    kind TEXT NOT NULL,
+    payload JSONB NOT NULL,
+    attempts INT NOT NULL DEFAULT 0,
+    max_attempts INT NOT NULL,
+    run_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+    -- set while a worker runs the job, an expired lock means the worker died.
+    locked_until TIMESTAMPTZ,
+    last_error TEXT,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT now()
+);
+
+CREATE INDEX IF NOT EXISTS jobs_run_at_idx ON jobs (run_at);
+
+-- jobs that failed on every attempt, kept for inspection.
+CREATE TABLE IF NOT EXISTS dead_jobs (
+    id BIGINT PRIMARY KEY,
+    kind TEXT NOT NULL,
+    payload JSONB NOT NULL,
+    attempts INT NOT NULL,
+    last_error TEXT,
+    created_at TIMESTAMPTZ NOT NULL,
+    failed_at TIMESTAMPTZ NOT NULL DEFAULT now()
+);
+
+-- reminders used to be delivered by polling their table.
+INSERT INTO jobs (kind, payload, max_attempts, run_at)
+SELECT 'deliver_reminder', jsonb_build_object('kind', 'deliver_reminder', 'reminder_id', id), 5, remind_at
+FROM reminders;
@@ -0,0 +1,13 @@
+CREATE TABLE IF NOT EXISTS mod_cases (
+    -- the case number.
+    id BIGSERIAL PRIMARY KEY,
+    -- warn, timeout, kick or ban.
+    action TEXT NOT NULL,
+    target_id BIGINT NOT NULL,
+    moderator_id BIGINT NOT NULL,
+    reason TEXT NOT NULL,
+    duration_secs BIGINT,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT now()
+);
+
+CREATE INDEX IF NOT EXISTS mod_cases_target_id_idx ON mod_cases (target_id);
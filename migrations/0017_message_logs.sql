@@ -0,0 +1,14 @@
+CREATE TABLE IF NOT EXISTS message_logs (
+    id BIGSERIAL PRIMARY KEY,
+    message_id BIGINT NOT NULL,
+    channel_id BIGINT NOT NULL,
+    author_id BIGINT NOT NULL,
+    -- edit or delete.
+    action TEXT NOT NULL,
+    -- unknown when the message was sent before the bot started.
+    content_before TEXT,
+    content_after TEXT,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT now()
+);
+
+CREATE INDEX IF NOT EXISTS message_logs_author_id_idx ON message_logs (author_id);
@@ -0,0 +1,4 @@
+CREATE TABLE IF NOT EXISTS verifications (
+    user_id BIGINT PRIMARY KEY,
+    verified_at TIMESTAMPTZ NOT NULL DEFAULT now()
+);
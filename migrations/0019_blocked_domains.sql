@@ -0,0 +1,7 @@
+CREATE TABLE IF NOT EXISTS blocked_domains (
+    -- lowercase, subdomains are blocked as well.
+    domain TEXT PRIMARY KEY,
+    added_by BIGINT NOT NULL,
+    reason TEXT,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT now()
+);
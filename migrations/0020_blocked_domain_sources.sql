@@ -0,0 +1,4 @@
+-- domains are either added by moderators or imported from the phishing feed, which only ever
+-- replaces its own domains.
+ALTER TABLE blocked_domains ADD COLUMN IF NOT EXISTS source TEXT NOT NULL DEFAULT 'manual';
+ALTER TABLE blocked_domains ALTER COLUMN added_by DROP NOT NULL;
@@ -0,0 +1,14 @@
+CREATE TABLE IF NOT EXISTS lockdowns (
+    id BIGSERIAL PRIMARY KEY,
+    reason TEXT NOT NULL,
+    -- restored once the lockdown ends.
+    previous_verification_level SMALLINT NOT NULL,
+    -- unset when the raid detection started the lockdown.
+    started_by BIGINT,
+    started_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+    ended_by BIGINT,
+    ended_at TIMESTAMPTZ
+);
+
+-- there is at most one active lockdown.
+CREATE UNIQUE INDEX IF NOT EXISTS lockdowns_active_idx ON lockdowns ((ended_at IS NULL)) WHERE ended_at IS NULL;
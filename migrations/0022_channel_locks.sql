@@ -0,0 +1,9 @@
+CREATE TABLE IF NOT EXISTS channel_locks (
+    id BIGSERIAL PRIMARY KEY,
+    channel_id BIGINT NOT NULL UNIQUE,
+    locked_by BIGINT NOT NULL,
+    reason TEXT NOT NULL,
+    -- unset when the channel stays locked until a moderator unlocks it.
+    unlock_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT now()
+);
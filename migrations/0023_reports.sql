@@ -0,0 +1,16 @@
+CREATE TABLE IF NOT EXISTS reports (
+    id BIGSERIAL PRIMARY KEY,
+    message_id BIGINT NOT NULL,
+    channel_id BIGINT NOT NULL,
+    author_id BIGINT NOT NULL,
+    reporter_id BIGINT NOT NULL,
+    reason TEXT,
+    -- hides the reporter from the moderators.
+    anonymous BOOLEAN NOT NULL DEFAULT false,
+    -- open, deleted, warned or ignored.
+    status TEXT NOT NULL DEFAULT 'open',
+    handled_by BIGINT,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+    handled_at TIMESTAMPTZ,
+    UNIQUE (message_id, reporter_id)
+);
@@ -0,0 +1,8 @@
+CREATE TABLE IF NOT EXISTS self_roles (
+    role_id BIGINT PRIMARY KEY,
+    -- shown below the role in the menu.
+    description TEXT,
+    -- a unicode emoji or a custom one like `<:name:id>`.
+    emoji TEXT,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT now()
+);
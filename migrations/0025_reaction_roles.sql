@@ -0,0 +1,9 @@
+CREATE TABLE IF NOT EXISTS reaction_roles (
+    message_id BIGINT NOT NULL,
+    channel_id BIGINT NOT NULL,
+    -- the unicode emoji, or the id of a custom one.
+    emoji TEXT NOT NULL,
+    role_id BIGINT NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+    PRIMARY KEY (message_id, emoji)
+);
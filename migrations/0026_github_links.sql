@@ -0,0 +1,7 @@
+CREATE TABLE IF NOT EXISTS github_links (
+    user_id BIGINT PRIMARY KEY,
+    -- logins can be renamed, the id stays the same.
+    github_id BIGINT NOT NULL UNIQUE,
+    github_login TEXT NOT NULL,
+    linked_at TIMESTAMPTZ NOT NULL DEFAULT now()
+);
@@ -0,0 +1,8 @@
+CREATE TABLE IF NOT EXISTS embed_templates (
+    name TEXT PRIMARY KEY,
+    -- the embed json, string values may contain `{{variable}}` placeholders.
+    embed JSONB NOT NULL,
+    author_id BIGINT NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT now()
+);
@@ -0,0 +1,6 @@
+CREATE TABLE IF NOT EXISTS channel_webhooks (
+    channel_id BIGINT PRIMARY KEY,
+    -- the webhook the bot created in the channel to post embeds, including its token.
+    webhook_url TEXT NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT now()
+);
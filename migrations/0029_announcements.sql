@@ -0,0 +1,14 @@
+CREATE TABLE IF NOT EXISTS announcements (
+    id BIGSERIAL PRIMARY KEY,
+    author_id BIGINT NOT NULL,
+    -- a configured webhook like `rules` or a channel id.
+    target TEXT NOT NULL,
+    -- the message json, as accepted by `/embed custom`.
+    payload JSONB NOT NULL,
+    next_run_at TIMESTAMPTZ NOT NULL,
+    -- a cron expression in UTC, unset for announcements that are posted once.
+    recurrence TEXT,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT now()
+);
+
+CREATE INDEX IF NOT EXISTS announcements_next_run_at_idx ON announcements (next_run_at);
@@ -0,0 +1,7 @@
+CREATE TABLE IF NOT EXISTS settings (
+    -- the name used by `/config`, like `log_channel`.
+    key TEXT PRIMARY KEY,
+    value JSONB NOT NULL,
+    updated_by BIGINT NOT NULL,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT now()
+);
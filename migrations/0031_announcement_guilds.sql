@@ -0,0 +1,3 @@
+-- the server the announcement was scheduled in, its webhooks are used. Unset for announcements
+-- scheduled before the bot served several servers, which belong to the main server.
+ALTER TABLE announcements ADD COLUMN IF NOT EXISTS guild_id BIGINT;
@@ -0,0 +1,4 @@
+-- the send permissions the @everyone overwrite of the channel explicitly allowed and denied before
+-- it was locked, restored on unlock. Zero for locks from before they were recorded.
+ALTER TABLE channel_locks ADD COLUMN IF NOT EXISTS previous_allow BIGINT NOT NULL DEFAULT 0;
+ALTER TABLE channel_locks ADD COLUMN IF NOT EXISTS previous_deny BIGINT NOT NULL DEFAULT 0;
@@ -0,0 +1,3 @@
+-- the server the action was taken in. Unset for cases recorded before the bot served several
+-- servers, which belong to the main server.
+ALTER TABLE mod_cases ADD COLUMN IF NOT EXISTS guild_id BIGINT;
@@ -0,0 +1,309 @@
+use std::{
+    collections::HashMap,
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+use poise::serenity_prelude as serenity;
+use serenity::{
+    ChannelId, CreateAllowedMentions, CreateEmbed, CreateMessage, EditMember, Mentionable,
+    MessageId, UserId,
+};
+
+use crate::{commands::moderation::case_embed, database::mod_cases, util::format::truncate, Data};
+
+/// Shorter messages like "hi" or "thanks" are too common to be spam.
+const MIN_CONTENT_LENGTH: usize = 10;
+
+#[derive(Debug)]
+struct RecentPost {
+    channel_id: ChannelId,
+    message_id: MessageId,
+    content: String,
+    sent_at: Instant,
+}
+
+/// The recent messages of every member, to notice the same message being posted in many channels.
+/// That is how compromised accounts usually spread scams.
+#[derive(Debug)]
+pub struct SpamTracker {
+    window: Duration,
+    channel_threshold: usize,
+    recent: Mutex<HashMap<UserId, Vec<RecentPost>>>,
+}
+
+impl SpamTracker {
+    pub fn new(window: Duration, channel_threshold: usize) -> Self {
+        Self {
+            window,
+            channel_threshold,
+            recent: Mutex::default(),
+        }
+    }
+
+    /// Remembers the message and returns every copy of it once it was posted in at least
+    /// `channel_threshold` different channels within the window.
+    pub fn record(
+        &self,
+        user_id: UserId,
+        channel_id: ChannelId,
+        message_id: MessageId,
+        content: &str,
+        now: Instant,
+    ) -> Option<Vec<(ChannelId, MessageId)>> {
+        let content = normalize(content);
+
+        if content.chars().count() < MIN_CONTENT_LENGTH {
+            return None;
+        }
+
+        let mut recent = self.recent.lock().unwrap();
+
+        recent.retain(|_, posts| {
+            posts.retain(|post| now.duration_since(post.sent_at) < self.window);
+            !posts.is_empty()
+        });
+
+        let posts = recent.entry(user_id).or_default();
+        posts.push(RecentPost {
+            channel_id,
+            message_id,
+            content,
+            sent_at: now,
+        });
+
+        let latest = &posts[posts.len() - 1].content;
+        let copies = posts
+            .iter()
+            .filter(|post| post.content == *latest)
+            .map(|post| (post.channel_id, post.message_id))
+            .collect::<Vec<_>>();
+
+        let mut channels = copies
+            .iter()
+            .map(|(channel_id, _)| *channel_id)
+            .collect::<Vec<_>>();
+        channels.sort_unstable();
+        channels.dedup();
+
+        if channels.len() < self.channel_threshold {
+            return None;
+        }
+
+        // the copies are handled now, more messages shouldn't report them again.
+        recent.remove(&user_id);
+
+        Some(copies)
+    }
+}
+
+/// Deletes messages that were posted in too many channels, times the author out and alerts the
+/// moderators. Returns whether the message was spam.
+pub async fn check_message(
+    message: &serenity::Message,
+    ctx: &serenity::Context,
+    data: &Data,
+) -> anyhow::Result<bool> {
+    let (Some(config), Some(tracker)) = (&data.config.anti_spam, &data.spam_tracker) else {
+        return Ok(false);
+    };
+
+    if message.author.bot || message.guild_id != Some(data.config.bot.guild_id) {
+        return Ok(false);
+    }
+
+    let roles = &data.config.main_guild.roles;
+    if message
+        .member
+        .as_ref()
+        .is_some_and(|member| roles.is_staff(&member.roles))
+    {
+        return Ok(false);
+    }
+
+    let Some(copies) = tracker.record(
+        message.author.id,
+        message.channel_id,
+        message.id,
+        &message.content,
+        Instant::now(),
+    ) else {
+        return Ok(false);
+    };
+
+    tracing::warn!(
+        "{} posted the same message in {} channels, treating it as spam.",
+        message.author.name,
+        copies.len()
+    );
+
+    for (channel_id, message_id) in &copies {
+        if let Err(e) = channel_id.delete_message(ctx, *message_id).await {
+            tracing::warn!("Failed to delete spam message {message_id}: {e}");
+        }
+    }
+
+    let timeout = chrono::Duration::seconds(config.timeout_secs as i64);
+    let reason = "Posted the same message in multiple channels.";
+
+    // the moderators are alerted either way, they have to take over when the timeout failed.
+    let timed_out = match data
+        .config
+        .bot
+        .guild_id
+        .edit_member(
+            ctx,
+            message.author.id,
+            EditMember::new()
+                .disable_communication_until_datetime((chrono::Utc::now() + timeout).into())
+                .audit_log_reason(reason),
+        )
+        .await
+    {
+        Ok(_) => true,
+        Err(e) => {
+            tracing::error!("Failed to time out {} for spam: {e}", message.author.name);
+            false
+        }
+    };
+
+    let case = if timed_out {
+        let case = mod_cases::insert(
+            &data.db_pool,
+            data.config.bot.guild_id.get() as i64,
+            "timeout",
+            message.author.id.get() as i64,
+            ctx.cache.current_user().id.get() as i64,
+            reason,
+            Some(timeout.num_seconds()),
+        )
+        .await?;
+
+        if let Some(modlog) = data.log_channel(data.config.bot.guild_id) {
+            modlog
+                .send_message(ctx, CreateMessage::new().embed(case_embed(&case)))
+                .await?;
+        }
+
+        Some(case)
+    } else {
+        None
+    };
+
+    let Some(alert_channel) = config
+        .alert_channel
+        .or(data.log_channel(data.config.bot.guild_id))
+    else {
+        return Ok(true);
+    };
+
+    let channels = copies
+        .iter()
+        .map(|(channel_id, _)| channel_id.mention().to_string())
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    let outcome = match case {
+        Some(case) => format!("they were timed out (case #{})", case.id),
+        None => "timing them out failed".to_string(),
+    };
+
+    let embed = CreateEmbed::new()
+        .color(0xFF0000)
+        .title("Spam detected")
+        .description(format!(
+            "{} posted the same message in {} channels, it was deleted and {outcome}.",
+            message.author.mention(),
+            copies.len(),
+        ))
+        .field("Channels", channels, false)
+        .field("Content", truncate(&message.content, 1000), false);
+
+    let mut alert = CreateMessage::new().embed(embed);
+
+    if let Some(moderator) = roles.moderator {
+        alert = alert
+            .content(moderator.mention().to_string())
+            .allowed_mentions(CreateAllowedMentions::new().roles([moderator]));
+    }
+
+    alert_channel.send_message(ctx, alert).await?;
+
+    Ok(true)
+}
+
+/// Lowercases the message and collapses whitespace, so small changes don't hide a copy.
+fn normalize(content: &str) -> String {
+    content
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .to_lowercase()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const WINDOW: Duration = Duration::from_secs(60);
+    const SCAM: &str = "Free nitro at https://example.com";
+
+    fn record(
+        tracker: &SpamTracker,
+        channel: u64,
+        message: u64,
+        content: &str,
+        at: Instant,
+    ) -> Option<Vec<(ChannelId, MessageId)>> {
+        tracker.record(
+            UserId::new(1),
+            ChannelId::new(channel),
+            MessageId::new(message),
+            content,
+            at,
+        )
+    }
+
+    #[test]
+    fn detects_copies_in_many_channels() {
+        let tracker = SpamTracker::new(WINDOW, 3);
+        let now = Instant::now();
+
+        assert_eq!(record(&tracker, 1, 1, SCAM, now), None);
+        assert_eq!(record(&tracker, 2, 2, "something else entirely", now), None);
+        assert_eq!(record(&tracker, 2, 3, &SCAM.to_uppercase(), now), None);
+
+        assert_eq!(
+            record(&tracker, 3, 4, &format!("  {SCAM} "), now),
+            Some(vec![
+                (ChannelId::new(1), MessageId::new(1)),
+                (ChannelId::new(2), MessageId::new(3)),
+                (ChannelId::new(3), MessageId::new(4)),
+            ])
+        );
+
+        // the detected copies are forgotten.
+        assert_eq!(record(&tracker, 4, 5, SCAM, now), None);
+    }
+
+    #[test]
+    fn ignores_copies_in_the_same_channel_or_outside_the_window() {
+        let tracker = SpamTracker::new(WINDOW, 3);
+        let now = Instant::now();
+
+        assert_eq!(record(&tracker, 1, 1, SCAM, now), None);
+        assert_eq!(record(&tracker, 1, 2, SCAM, now), None);
+        assert_eq!(record(&tracker, 2, 3, SCAM, now + WINDOW), None);
+        assert_eq!(record(&tracker, 3, 4, SCAM, now + WINDOW), None);
+    }
+
+    #[test]
+    fn ignores_short_messages() {
+        let tracker = SpamTracker::new(WINDOW, 3);
+        let now = Instant::now();
+
+        for channel in 1..=3 {
+            assert_eq!(record(&tracker, channel, channel, "thanks", now), None);
+        }
+    }
+}
@@ -0,0 +1,177 @@
+use std::{
+    collections::{HashMap, HashSet},
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use poise::serenity_prelude as serenity;
+use serenity::ChannelId;
+
+use crate::{
+    database::faq_questions::{self, FaqQuestion},
+    util::cache::TtlCache,
+    Data,
+};
+
+/// How similar a message has to be to a known question to get answered.
+const MATCH_THRESHOLD: f64 = 0.75;
+/// Messages with fewer meaningful words are too vague to match anything.
+const MIN_WORDS: usize = 3;
+
+/// Words that carry no meaning on their own and would make every question look alike.
+const STOP_WORDS: &[&str] = &[
+    "a", "an", "and", "any", "are", "be", "but", "can", "could", "do", "does", "for", "from",
+    "has", "have", "hello", "help", "hey", "hi", "how", "i", "if", "in", "is", "it", "its", "me",
+    "my", "of", "on", "or", "please", "should", "so", "that", "the", "there", "this", "to", "way",
+    "what", "when", "where", "which", "why", "with", "would", "you",
+];
+
+/// The known questions and the cooldown of every help channel.
+#[derive(Debug)]
+pub struct AutoResponder {
+    questions: TtlCache<Arc<Vec<FaqQuestion>>>,
+    last_answers: Mutex<HashMap<ChannelId, Instant>>,
+}
+
+impl Default for AutoResponder {
+    fn default() -> Self {
+        Self {
+            questions: TtlCache::new(Duration::from_secs(10 * 60)),
+            last_answers: Mutex::default(),
+        }
+    }
+}
+
+impl AutoResponder {
+    pub async fn questions(&self, data: &Data) -> anyhow::Result<Arc<Vec<FaqQuestion>>> {
+        self.questions
+            .get_or_refresh(|| async { Ok(Arc::new(faq_questions::all(&data.db_pool).await?)) })
+            .await
+    }
+
+    /// Makes the next message see added or removed questions.
+    pub async fn reload_questions(&self) {
+        self.questions.invalidate().await;
+    }
+
+    /// Starts the cooldown of the channel, `false` if it is still cooling down.
+    pub fn try_answer(&self, channel_id: ChannelId, cooldown: Duration) -> bool {
+        let mut last_answers = self.last_answers.lock().unwrap();
+
+        match last_answers.get(&channel_id) {
+            Some(answered_at) if answered_at.elapsed() < cooldown => false,
+            _ => {
+                last_answers.insert(channel_id, Instant::now());
+                true
+            }
+        }
+    }
+}
+
+/// The known question that is most similar to the message, if any is similar enough.
+pub fn best_match<'a>(message: &str, questions: &'a [FaqQuestion]) -> Option<&'a FaqQuestion> {
+    let message_words = words(message);
+
+    if message_words.len() < MIN_WORDS {
+        return None;
+    }
+
+    questions
+        .iter()
+        .map(|question| {
+            (
+                question,
+                similarity(&message_words, &words(&question.question)),
+            )
+        })
+        .filter(|(_, score)| *score >= MATCH_THRESHOLD)
+        .max_by(|(_, a), (_, b)| a.total_cmp(b))
+        .map(|(question, _)| question)
+}
+
+/// The meaningful words of a text, lowercased and without a plural `s`.
+fn words(text: &str) -> HashSet<String> {
+    text.split(|c: char| !c.is_alphanumeric())
+        .map(str::to_lowercase)
+        .filter(|word| !word.is_empty() && !STOP_WORDS.contains(&word.as_str()))
+        .map(|word| match word.strip_suffix('s') {
+            Some(stem) if stem.len() > 2 => stem.to_string(),
+            _ => word,
+        })
+        .collect()
+}
+
+/// Dice coefficient of the two word sets, where words one typo apart count as equal.
+fn similarity(message: &HashSet<String>, question: &HashSet<String>) -> f64 {
+    if message.is_empty() || question.is_empty() {
+        return 0.0;
+    }
+
+    let shared = question
+        .iter()
+        .filter(|question_word| {
+            message.iter().any(|message_word| {
+                message_word == *question_word
+                    || (message_word.len() >= 5
+                        && question_word.len() >= 5
+                        && edit_distance(message_word, question_word) <= 1)
+            })
+        })
+        .count();
+
+    2.0 * shared as f64 / (message.len() + question.len()) as f64
+}
+
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b = b.chars().collect::<Vec<_>>();
+    let mut previous = (0..=b.len()).collect::<Vec<_>>();
+
+    for (i, a_char) in a.chars().enumerate() {
+        let mut current = vec![i + 1; b.len() + 1];
+
+        for (j, b_char) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(a_char != *b_char);
+            current[j + 1] = substitution.min(previous[j + 1] + 1).min(current[j] + 1);
+        }
+
+        previous = current;
+    }
+
+    previous[b.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn question(id: i64, question: &str) -> FaqQuestion {
+        FaqQuestion {
+            id,
+            question: question.to_string(),
+            tag_name: format!("tag-{id}"),
+        }
+    }
+
+    #[test]
+    fn matches_similar_questions() {
+        let questions = vec![
+            question(1, "How to disable a rule in one file?"),
+            question(2, "How do I format on save in VS Code?"),
+        ];
+
+        let matched = best_match(
+            "hey, how can I disable rules in a single file??",
+            &questions,
+        );
+        assert_eq!(matched.map(|question| question.id), Some(1));
+
+        let matched = best_match("how can I disable a rule for one file", &questions);
+        assert_eq!(matched.map(|question| question.id), Some(1));
+
+        let matched = best_match("Is there a way to fromat on save with vs code?", &questions);
+        assert_eq!(matched.map(|question| question.id), Some(2));
+
+        assert!(best_match("how do I use biome?", &questions).is_none());
+        assert!(best_match("disable", &questions).is_none());
+    }
+}
@@ -0,0 +1,104 @@
+use std::time::Duration;
+
+use poise::serenity_prelude as serenity;
+use serenity::{GuildId, Member, RoleId, UserId};
+
+use crate::Data;
+
+/// Gives the configured roles to a new member once the delay passed.
+pub fn assign_on_join(member: &Member, ctx: &serenity::Context, data: &Data) {
+    let Some(config) = &data.config.auto_roles else {
+        return;
+    };
+
+    // the roles would bypass the membership screening.
+    if member.pending {
+        return;
+    }
+
+    let ctx = ctx.clone();
+    let guild_id = member.guild_id;
+    let user_id = member.user.id;
+    let roles = config.roles.clone();
+    let quarantine_role = quarantine_role(data);
+    let delay = Duration::from_secs(config.delay_secs);
+
+    tokio::spawn(async move {
+        tokio::time::sleep(delay).await;
+
+        if let Err(e) = assign(&ctx, guild_id, user_id, &roles, quarantine_role).await {
+            tracing::error!("Failed to assign the automatic roles to {user_id}: {e:#?}");
+        }
+    });
+}
+
+/// Gives the configured roles to a member that just completed the membership screening.
+pub async fn assign_after_screening(
+    old: Option<&Member>,
+    new: &Member,
+    ctx: &serenity::Context,
+    data: &Data,
+) -> anyhow::Result<()> {
+    let Some(config) = &data.config.auto_roles else {
+        return Ok(());
+    };
+
+    // only the update that completes the screening counts, otherwise roles the moderators removed
+    // would come back with the next nickname change.
+    if new.pending || !old.is_some_and(|old| old.pending) {
+        return Ok(());
+    }
+
+    assign(
+        ctx,
+        new.guild_id,
+        new.user.id,
+        &config.roles,
+        quarantine_role(data),
+    )
+    .await
+}
+
+async fn assign(
+    ctx: &serenity::Context,
+    guild_id: GuildId,
+    user_id: UserId,
+    roles: &[RoleId],
+    quarantine_role: Option<RoleId>,
+) -> anyhow::Result<()> {
+    // the member might have left during the delay.
+    let Ok(member) = guild_id.member(ctx, user_id).await else {
+        return Ok(());
+    };
+
+    // a member completing the screening might have been quarantined on join.
+    if member.pending || quarantine_role.is_some_and(|role| member.roles.contains(&role)) {
+        return Ok(());
+    }
+
+    let missing = roles
+        .iter()
+        .filter(|role| !member.roles.contains(role))
+        .copied()
+        .collect::<Vec<_>>();
+
+    if missing.is_empty() {
+        return Ok(());
+    }
+
+    member.add_roles(ctx, &missing).await?;
+    tracing::info!(
+        "Assigned {} automatic roles to {}.",
+        missing.len(),
+        member.user.name
+    );
+
+    Ok(())
+}
+
+fn quarantine_role(data: &Data) -> Option<RoleId> {
+    data.config
+        .new_accounts
+        .as_ref()
+        .and_then(|new_accounts| new_accounts.quarantine_role)
+}
@@ -0,0 +1,119 @@
+use std::time::Duration;
+
+use poise::serenity_prelude as serenity;
+use serenity::{
+    automod::{Action, EventType, Rule, Trigger},
+    EditAutoModRule, Http,
+};
+
+use crate::{
+    config::{AutoModConfig, AutoModRuleConfig, AutoModTrigger},
+    Data,
+};
+
+/// Discord doesn't allow longer timeouts.
+const MAX_TIMEOUT_SECS: u64 = 28 * 24 * 60 * 60;
+
+/// What a sync changed.
+#[derive(Debug, Default)]
+pub struct SyncSummary {
+    pub created: Vec<String>,
+    pub updated: Vec<String>,
+    pub deleted: Vec<String>,
+}
+
+/// Makes the AutoMod rules of the server match the config. Only rules the bot created are edited
+/// or deleted, the rules moderators created by hand are left alone.
+pub async fn sync_rules(http: &Http, data: &Data) -> anyhow::Result<SyncSummary> {
+    let Some(config) = &data.config.automod else {
+        return Ok(SyncSummary::default());
+    };
+
+    let guild_id = data.config.bot.guild_id;
+    let bot_id = http.get_current_user().await?.id;
+
+    let managed = guild_id
+        .automod_rules(http)
+        .await?
+        .into_iter()
+        .filter(|rule| rule.creator_id == bot_id)
+        .collect::<Vec<Rule>>();
+
+    let mut summary = SyncSummary::default();
+
+    for rule_config in &config.rules {
+        let builder = rule_builder(config, rule_config);
+
+        match managed.iter().find(|rule| rule.name == rule_config.name) {
+            Some(rule) => {
+                guild_id.edit_automod_rule(http, rule.id, builder).await?;
+                summary.updated.push(rule_config.name.clone());
+            }
+            None => {
+                guild_id.create_automod_rule(http, builder).await?;
+                summary.created.push(rule_config.name.clone());
+            }
+        }
+    }
+
+    for rule in managed
+        .iter()
+        .filter(|rule| !config.rules.iter().any(|desired| desired.name == rule.name))
+    {
+        guild_id.delete_automod_rule(http, rule.id).await?;
+        summary.deleted.push(rule.name.clone());
+    }
+
+    tracing::info!(
+        "Synced the AutoMod rules, {} created, {} updated and {} deleted.",
+        summary.created.len(),
+        summary.updated.len(),
+        summary.deleted.len()
+    );
+
+    Ok(summary)
+}
+
+fn rule_builder<'a>(config: &AutoModConfig, rule: &'a AutoModRuleConfig) -> EditAutoModRule<'a> {
+    let trigger = match &rule.trigger {
+        AutoModTrigger::Keyword {
+            keywords,
+            regex_patterns,
+            allow_list,
+        } => Trigger::Keyword {
+            strings: keywords.clone(),
+            regex_patterns: regex_patterns.clone(),
+            allow_list: allow_list.clone(),
+        },
+        AutoModTrigger::MentionSpam {
+            mention_limit,
+            raid_protection,
+        } => Trigger::MentionSpam {
+            mention_total_limit: *mention_limit,
+            mention_raid_protection_enabled: *raid_protection,
+        },
+    };
+
+    let mut actions = vec![Action::BlockMessage {
+        custom_message: rule.block_message.clone(),
+    }];
+
+    if let Some(alert_channel) = config.alert_channel {
+        actions.push(Action::Alert(alert_channel));
+    }
+
+    if let Some(timeout_secs) = rule.timeout_secs {
+        actions.push(Action::Timeout(Duration::from_secs(
+            timeout_secs.min(MAX_TIMEOUT_SECS),
+        )));
+    }
+
+    EditAutoModRule::new()
+        .name(&rule.name)
+        .event_type(EventType::MessageSend)
+        .trigger(trigger)
+        .actions(actions)
+        .enabled(true)
+        .exempt_roles(rule.exempt_roles.iter().copied())
+        .exempt_channels(rule.exempt_channels.iter().copied())
+}
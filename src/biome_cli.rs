@@ -0,0 +1,226 @@
+use std::{path::PathBuf, process::Stdio, time::Duration};
+
+use anyhow::Context;
+use tokio::{
+    io::{AsyncRead, AsyncReadExt, AsyncWriteExt},
+    process::Command,
+};
+
+use crate::config::BiomeCliConfig;
+
+/// Snippets larger than this are rejected before spawning the cli.
+pub const MAX_INPUT_SIZE: usize = 64 * 1024;
+const MAX_OUTPUT_SIZE: usize = 256 * 1024;
+
+/// What the cli printed for a snippet.
+#[derive(Debug)]
+pub struct CliOutput {
+    pub success: bool,
+    pub stdout: String,
+    pub stderr: String,
+}
+
+/// Formats a snippet, `extension` decides which parser biome uses.
+pub async fn format(
+    config: &BiomeCliConfig,
+    code: &str,
+    extension: &str,
+) -> anyhow::Result<CliOutput> {
+    run(
+        config,
+        &[
+            "format",
+            "--colors=off",
+            &format!("--stdin-file-path=main.{extension}"),
+        ],
+        code,
+    )
+    .await
+}
+
+/// Lints a snippet with the recommended rules.
+pub async fn lint(
+    config: &BiomeCliConfig,
+    code: &str,
+    extension: &str,
+) -> anyhow::Result<CliOutput> {
+    run(
+        config,
+        &[
+            "lint",
+            "--colors=off",
+            "--max-diagnostics=50",
+            &format!("--stdin-file-path=main.{extension}"),
+        ],
+        code,
+    )
+    .await
+}
+
+/// A diagnostic of the cli's text output, e.g. `main.js:1:5 lint/suspicious/noDoubleEquals`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diagnostic {
+    /// `line:column` of the diagnostic in the snippet.
+    pub location: String,
+    /// The category, e.g. `lint/suspicious/noDoubleEquals` or `parse`.
+    pub category: String,
+    pub message: String,
+}
+
+impl Diagnostic {
+    /// The name of the lint rule that reported the diagnostic.
+    pub fn rule_name(&self) -> Option<&str> {
+        self.category
+            .strip_prefix("lint/")
+            .and_then(|rule| rule.rsplit('/').next())
+    }
+}
+
+/// Collects the diagnostics the cli printed for the stdin file.
+pub fn parse_diagnostics(output: &str) -> Vec<Diagnostic> {
+    let mut diagnostics = Vec::new();
+    let mut lines = output.lines();
+
+    while let Some(line) = lines.next() {
+        let mut parts = line.split_whitespace();
+
+        let (Some(path), Some(category)) = (parts.next(), parts.next()) else {
+            continue;
+        };
+
+        let Some(location) = path
+            .strip_prefix("main.")
+            .and_then(|rest| rest.split_once(':'))
+            .map(|(_, location)| location)
+        else {
+            continue;
+        };
+
+        let first_line = lines
+            .by_ref()
+            .map(str::trim)
+            .find(|line| !line.is_empty())
+            .unwrap_or_default();
+
+        // the message is prefixed with a severity marker, e.g. `× Use === instead of ==`.
+        let message = match first_line.split_once(' ') {
+            Some((severity, message)) if severity.chars().count() == 1 => message.trim(),
+            _ => first_line,
+        };
+
+        diagnostics.push(Diagnostic {
+            location: location.to_string(),
+            category: category.to_string(),
+            message: message.to_string(),
+        });
+    }
+
+    diagnostics
+}
+
+/// Runs the cli with the snippet on stdin. The process runs in an empty directory without any
+/// environment, inside the configured sandbox, and gets killed once the timeout elapses. Snippets
+/// are written by anyone, so the cli never runs without a sandbox.
+async fn run(config: &BiomeCliConfig, args: &[&str], stdin: &str) -> anyhow::Result<CliOutput> {
+    if stdin.len() > MAX_INPUT_SIZE {
+        anyhow::bail!("Input of {} bytes exceeds the size limit", stdin.len());
+    }
+
+    let Some((program, sandbox_args)) = config.sandbox.split_first() else {
+        anyhow::bail!("No sandbox is configured for the biome cli");
+    };
+
+    let mut child = Command::new(program)
+        .args(sandbox_args)
+        .arg(&config.binary_path)
+        .args(args)
+        .env_clear()
+        .env("NO_COLOR", "1")
+        .current_dir(working_directory().await?)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true)
+        .spawn()
+        .context("Failed to spawn the biome cli")?;
+
+    let mut child_stdin = child
+        .stdin
+        .take()
+        .context("Missing stdin of the biome cli")?;
+    child_stdin.write_all(stdin.as_bytes()).await?;
+    // closing stdin tells biome the input is complete.
+    drop(child_stdin);
+
+    let stdout = child
+        .stdout
+        .take()
+        .context("Missing stdout of the biome cli")?;
+    let stderr = child
+        .stderr
+        .take()
+        .context("Missing stderr of the biome cli")?;
+
+    // the child gets killed when it is dropped after a timeout.
+    let (stdout, stderr, status) =
+        tokio::time::timeout(Duration::from_secs(config.timeout_secs), async {
+            tokio::try_join!(read_capped(stdout), read_capped(stderr), child.wait())
+        })
+        .await
+        .context("The biome cli timed out")??;
+
+    Ok(CliOutput {
+        success: status.success(),
+        stdout,
+        stderr,
+    })
+}
+
+/// An empty directory, so biome doesn't pick up any configuration file.
+async fn working_directory() -> anyhow::Result<PathBuf> {
+    let directory = std::env::temp_dir().join("biomebot-cli");
+    tokio::fs::create_dir_all(&directory).await?;
+
+    Ok(directory)
+}
+
+/// Stops reading at the size limit, which closes the pipe so a chatty process can't fill memory.
+async fn read_capped(reader: impl AsyncRead + Unpin) -> std::io::Result<String> {
+    let mut bytes = Vec::new();
+    reader
+        .take(MAX_OUTPUT_SIZE as u64)
+        .read_to_end(&mut bytes)
+        .await?;
+
+    Ok(String::from_utf8_lossy(&bytes).into_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_lint_diagnostics() {
+        let output = "main.js:1:7 lint/suspicious/noDoubleEquals  FIXABLE  \u{2501}\u{2501}\u{2501}\n\n  \u{00D7} Use === instead of ==\n\n  > 1 \u{2502} if (a == b) {}\n\nmain.js:2:1 parse \u{2501}\u{2501}\u{2501}\n\n  \u{00D7} Expected a statement\n\nChecked 1 file in 2ms.\n";
+
+        let diagnostics = parse_diagnostics(output);
+
+        assert_eq!(
+            diagnostics,
+            vec![
+                Diagnostic {
+                    location: "1:7".to_string(),
+                    category: "lint/suspicious/noDoubleEquals".to_string(),
+                    message: "Use === instead of ==".to_string(),
+                },
+                Diagnostic {
+                    location: "2:1".to_string(),
+                    category: "parse".to_string(),
+                    message: "Expected a statement".to_string(),
+                },
+            ]
+        );
+        assert_eq!(diagnostics[0].rule_name(), Some("noDoubleEquals"));
+        assert_eq!(diagnostics[1].rule_name(), None);
+    }
+}
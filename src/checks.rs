@@ -0,0 +1,24 @@
+use crate::{config::RoleConfig, Context};
+
+/// The roles of the server the command was used in.
+fn guild_roles(ctx: Context<'_>) -> Option<&RoleConfig> {
+    Some(&ctx.data().guild(ctx.guild_id()?)?.roles)
+}
+
+/// Passes when the author has the maintainer role of the server.
+pub async fn is_maintainer(ctx: Context<'_>) -> anyhow::Result<bool> {
+    let (Some(member), Some(roles)) = (ctx.author_member().await, guild_roles(ctx)) else {
+        return Ok(false);
+    };
+
+    Ok(roles.is_maintainer(&member.roles))
+}
+
+/// Passes when the author has the moderator role of the server.
+pub async fn is_moderator(ctx: Context<'_>) -> anyhow::Result<bool> {
+    let (Some(member), Some(roles)) = (ctx.author_member().await, guild_roles(ctx)) else {
+        return Ok(false);
+    };
+
+    Ok(roles.is_moderator(&member.roles))
+}
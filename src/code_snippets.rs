@@ -0,0 +1,273 @@
+use std::{sync::OnceLock, time::Duration};
+
+use anyhow::Context;
+use poise::serenity_prelude as serenity;
+use serenity::{CreateEmbed, CreateEmbedFooter};
+
+use crate::util::{embeds::EmbedColor, format::truncate};
+
+const PERMALINK_PREFIX: &str = "https://github.com/";
+/// Files larger than this aren't downloaded to show a few lines.
+const MAX_FILE_SIZE: u64 = 1024 * 1024;
+/// Leaves room in the embed description for the code block fences.
+const MAX_SNIPPET_LENGTH: usize = 4000;
+/// Discord rejects messages whose embeds add up to more characters.
+pub const MAX_EMBEDS_LENGTH: usize = 6000;
+/// Github is slow to serve raw files at times, a late snippet isn't worth waiting for.
+const FETCH_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// A link to lines of a file on github, like
+/// `https://github.com/biomejs/biome/blob/main/Cargo.toml#L10-L20`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Permalink {
+    pub url: String,
+    pub repository: String,
+    /// The ref followed by the path, branch names can contain slashes so they aren't told apart.
+    pub ref_and_path: String,
+    pub start: usize,
+    pub end: usize,
+}
+
+impl Permalink {
+    fn raw_url(&self) -> String {
+        format!(
+            "https://raw.githubusercontent.com/{}/{}",
+            self.repository, self.ref_and_path
+        )
+    }
+
+    fn file_name(&self) -> &str {
+        self.ref_and_path
+            .rsplit('/')
+            .next()
+            .unwrap_or(&self.ref_and_path)
+    }
+
+    /// The extension doubles as the language of the code block.
+    fn language(&self) -> &str {
+        self.file_name()
+            .rsplit_once('.')
+            .map_or("", |(_, extension)| extension)
+    }
+}
+
+/// The permalinks with line numbers in the message. Links inside of code and links wrapped in
+/// `<>` to suppress their embed are skipped.
+pub fn permalinks(content: &str, limit: usize) -> Vec<Permalink> {
+    let mut permalinks = Vec::new();
+
+    // every odd segment is inside of backticks, which covers inline code and code blocks.
+    for text in content.split('`').step_by(2) {
+        for (index, _) in text.match_indices(PERMALINK_PREFIX) {
+            if text[..index].ends_with('<') {
+                continue;
+            }
+
+            let url = text[index..]
+                .split(|c: char| c.is_whitespace() || matches!(c, '>' | ')' | ']'))
+                .next()
+                .unwrap_or_default();
+
+            let Some(permalink) = parse_permalink(url) else {
+                continue;
+            };
+
+            if !permalinks.contains(&permalink) {
+                permalinks.push(permalink);
+            }
+
+            if permalinks.len() >= limit {
+                return permalinks;
+            }
+        }
+    }
+
+    permalinks
+}
+
+fn parse_permalink(url: &str) -> Option<Permalink> {
+    let (location, fragment) = url.strip_prefix(PERMALINK_PREFIX)?.split_once('#')?;
+    let location = location.split('?').next()?;
+
+    let mut parts = location.splitn(4, '/');
+    let (owner, name, kind, ref_and_path) =
+        (parts.next()?, parts.next()?, parts.next()?, parts.next()?);
+
+    if kind != "blob" || owner.is_empty() || name.is_empty() || !ref_and_path.contains('/') {
+        return None;
+    }
+
+    let (start, end) = match fragment.split_once('-') {
+        Some((start, end)) => (line_number(start)?, line_number(end)?),
+        None => {
+            let line = line_number(fragment)?;
+            (line, line)
+        }
+    };
+
+    if start == 0 || end < start {
+        return None;
+    }
+
+    Some(Permalink {
+        url: url.to_string(),
+        repository: format!("{owner}/{name}"),
+        ref_and_path: ref_and_path.to_string(),
+        start,
+        end,
+    })
+}
+
+/// Reads `L12` or `L12C5`, the column is ignored.
+fn line_number(part: &str) -> Option<usize> {
+    let part = part.strip_prefix('L')?;
+    let digits = part.chars().take_while(char::is_ascii_digit).count();
+
+    if !part[digits..].is_empty() && !part[digits..].starts_with('C') {
+        return None;
+    }
+
+    part[..digits].parse().ok()
+}
+
+/// Fetches the linked lines and shows them as a code block, `None` for private or missing files.
+/// The title, code block and footer of the embed add up to at most `max_length` characters.
+pub async fn snippet_embed(
+    permalink: &Permalink,
+    max_lines: usize,
+    max_length: usize,
+) -> anyhow::Result<Option<CreateEmbed>> {
+    let response = http().get(permalink.raw_url()).send().await?;
+
+    if response.status() == reqwest::StatusCode::NOT_FOUND {
+        return Ok(None);
+    }
+
+    let response = response
+        .error_for_status()
+        .context("Failed to fetch the linked file")?;
+
+    if response
+        .content_length()
+        .is_some_and(|length| length > MAX_FILE_SIZE)
+    {
+        return Ok(None);
+    }
+
+    let file = response.text().await?;
+    let end = permalink
+        .end
+        .min(permalink.start.saturating_add(max_lines.saturating_sub(1)));
+
+    let lines = file
+        .lines()
+        .skip(permalink.start - 1)
+        .take(end + 1 - permalink.start)
+        .collect::<Vec<_>>();
+
+    if lines.is_empty() {
+        return Ok(None);
+    }
+
+    let end = permalink.start + lines.len() - 1;
+
+    let title = if permalink.start == end {
+        format!("{} line {}", permalink.file_name(), permalink.start)
+    } else {
+        format!("{} lines {}-{end}", permalink.file_name(), permalink.start)
+    };
+
+    let footer = if end < permalink.end {
+        format!("{} more lines on github", permalink.end - end)
+    } else {
+        permalink.repository.clone()
+    };
+
+    let fence = format!("```{}\n", permalink.language());
+    // the closing fence, its line break and the marker `truncate` appends.
+    let reserved = title.chars().count() + footer.chars().count() + fence.chars().count() + 8;
+    let max_code_length = MAX_SNIPPET_LENGTH.min(max_length.saturating_sub(reserved));
+
+    let code = dedent(&lines)
+        // a fence inside of the snippet would end the code block early.
+        .replace("```", "`\u{200B}``");
+    let code = truncate(&code, max_code_length);
+
+    let embed = CreateEmbed::new()
+        .color(EmbedColor::Biome)
+        .title(title)
+        .url(&permalink.url)
+        .description(format!("{fence}{code}\n```"))
+        .footer(CreateEmbedFooter::new(footer));
+
+    Ok(Some(embed))
+}
+
+fn http() -> &'static reqwest::Client {
+    static HTTP: OnceLock<reqwest::Client> = OnceLock::new();
+
+    HTTP.get_or_init(|| {
+        reqwest::Client::builder()
+            .timeout(FETCH_TIMEOUT)
+            .build()
+            .expect("Failed to build the http client for code snippets")
+    })
+}
+
+/// Removes the indentation all lines share, blank lines don't count.
+fn dedent(lines: &[&str]) -> String {
+    let indent = lines
+        .iter()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| line.len() - line.trim_start().len())
+        .min()
+        .unwrap_or_default();
+
+    lines
+        .iter()
+        .map(|line| line.get(indent..).unwrap_or_default().trim_end())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_line_ranges() {
+        let permalinks = permalinks(
+            "look at https://github.com/biomejs/biome/blob/main/crates/biome_cli/src/lib.rs#L10-L20 and https://github.com/biomejs/biome/blob/1a2b3c/Cargo.toml#L5C3",
+            5,
+        );
+
+        assert_eq!(permalinks.len(), 2);
+        assert_eq!(permalinks[0].repository, "biomejs/biome");
+        assert_eq!(
+            permalinks[0].ref_and_path,
+            "main/crates/biome_cli/src/lib.rs"
+        );
+        assert_eq!((permalinks[0].start, permalinks[0].end), (10, 20));
+        assert_eq!(permalinks[0].language(), "rs");
+        assert_eq!((permalinks[1].start, permalinks[1].end), (5, 5));
+    }
+
+    #[test]
+    fn skips_links_without_lines_or_suppressed() {
+        let content = "https://github.com/biomejs/biome/blob/main/README.md \
+            <https://github.com/biomejs/biome/blob/main/README.md#L1> \
+            `https://github.com/biomejs/biome/blob/main/README.md#L1` \
+            https://github.com/biomejs/biome/issues/1#L1 \
+            https://github.com/biomejs/biome/blob/main/README.md#L5-L2";
+
+        assert!(permalinks(content, 5).is_empty());
+    }
+
+    #[test]
+    fn removes_shared_indentation() {
+        assert_eq!(
+            dedent(&["    if a {", "", "        b();", "    }"]),
+            "if a {\n\n    b();\n}"
+        );
+    }
+}
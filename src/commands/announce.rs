@@ -0,0 +1,188 @@
+use chrono::{Duration, Utc};
+use poise::{serenity_prelude as serenity, CreateReply};
+use serenity::GuildChannel;
+
+use crate::{
+    commands::embed::{is_valid, parse_payload, pick_target, posting_guild, TargetChannelWebhook},
+    database::announcements,
+    respond_error, respond_mistake,
+    scheduler::cron::Cron,
+    util::{
+        embeds::default_embed,
+        format::{inline_code, time, TimestampStyle},
+        parse::parse_datetime,
+    },
+    webhooks::EmbedTarget,
+    Context,
+};
+
+/// Announcements posted later or on a schedule.
+#[poise::command(
+    slash_command,
+    guild_only = true,
+    default_member_permissions = "ADMINISTRATOR",
+    subcommands("schedule", "list", "cancel"),
+    subcommand_required
+)]
+pub async fn announce(_: Context<'_>) -> anyhow::Result<()> {
+    Ok(())
+}
+
+/// Schedule a message to be posted at a given time, once or repeatedly.
+#[poise::command(slash_command, guild_only = true, ephemeral = true)]
+pub async fn schedule(
+    ctx: Context<'_>,
+    #[description = "The json of the message, like for /embed custom."] json: String,
+    #[description = "When to post it, like `2h`, `2027-01-01 17:00` (UTC) or a discord timestamp."]
+    at: Option<String>,
+    #[description = "Repeat on a cron expression in UTC, like `0 17 * * 5` for fridays at 17:00."]
+    repeat: Option<String>,
+    #[description = "One of the configured webhooks for the message to go."] webhook: Option<
+        TargetChannelWebhook,
+    >,
+    #[description = "Any other channel for the message to go."]
+    #[channel_types("Text", "News")]
+    channel: Option<GuildChannel>,
+) -> anyhow::Result<()> {
+    let Some(target) = pick_target(webhook, channel) else {
+        respond_mistake!(&ctx, "Pick either one of the webhooks or a channel.");
+    };
+
+    let now = Utc::now();
+
+    let cron = match repeat.as_deref().map(Cron::parse) {
+        Some(Ok(cron)) => Some(cron),
+        Some(Err(_)) => {
+            respond_mistake!(
+                &ctx,
+                "Use a cron expression with five fields like `0 17 * * 5`."
+            );
+        }
+        None => None,
+    };
+
+    let next_run_at = match (at.as_deref(), &cron) {
+        (Some(at), _) => parse_datetime(at, now),
+        (None, Some(cron)) => cron.next_after(now),
+        (None, None) => {
+            respond_mistake!(&ctx, "Pick when to post the message or how to repeat it.");
+        }
+    };
+
+    let Some(next_run_at) =
+        next_run_at.filter(|time| *time > now && *time - now <= Duration::days(366))
+    else {
+        respond_mistake!(
+            &ctx,
+            "Use a time like `2h`, `2027-01-01 17:00` or a discord timestamp within the next year."
+        );
+    };
+
+    let Some(mut payload) = parse_payload(ctx, &json).await? else {
+        return Ok(());
+    };
+
+    if !is_valid(ctx, &mut payload).await? {
+        return Ok(());
+    }
+
+    let id = match announcements::insert(
+        &ctx.data().db_pool,
+        posting_guild(ctx).get() as i64,
+        ctx.author().id.get() as i64,
+        &target.to_string(),
+        &payload.to_value()?,
+        next_run_at,
+        repeat.as_deref().map(str::trim),
+    )
+    .await
+    {
+        Ok(id) => id,
+        Err(e) => {
+            respond_error!("Failed to schedule the announcement", e, &ctx);
+        }
+    };
+
+    tracing::info!(
+        "{} scheduled announcement #{id} for {next_run_at}",
+        ctx.author().name
+    );
+
+    let repeats = if cron.is_some() {
+        ", then repeated"
+    } else {
+        ""
+    };
+
+    ctx.say(format!(
+        "Announcement #{id} gets posted in {} {}{repeats}.",
+        target.describe(),
+        time(next_run_at, TimestampStyle::Relative)
+    ))
+    .await?;
+
+    Ok(())
+}
+
+/// List the scheduled announcements.
+#[poise::command(slash_command, guild_only = true, ephemeral = true)]
+pub async fn list(ctx: Context<'_>) -> anyhow::Result<()> {
+    let scheduled = match announcements::all(&ctx.data().db_pool).await {
+        Ok(scheduled) => scheduled,
+        Err(e) => {
+            respond_error!("Failed to fetch the announcements", e, &ctx);
+        }
+    };
+
+    if scheduled.is_empty() {
+        respond_mistake!(&ctx, "There are no scheduled announcements.");
+    }
+
+    let mut embed = default_embed(ctx.author()).title("Scheduled Announcements");
+
+    // embeds can hold at most 25 fields.
+    for announcement in scheduled.iter().take(25) {
+        let target = announcement
+            .target
+            .parse::<EmbedTarget>()
+            .map_or_else(|_| announcement.target.clone(), EmbedTarget::describe);
+
+        let mut value = format!(
+            "In {target} {}\nBy <@{}>",
+            time(announcement.next_run_at, TimestampStyle::Relative),
+            announcement.author_id
+        );
+
+        if let Some(recurrence) = &announcement.recurrence {
+            value.push_str(&format!("\nRepeats {}", inline_code(recurrence)));
+        }
+
+        embed = embed.field(format!("#{}", announcement.id), value, false);
+    }
+
+    ctx.send(CreateReply::default().embed(embed)).await?;
+
+    Ok(())
+}
+
+/// Cancel a scheduled announcement.
+#[poise::command(slash_command, guild_only = true, ephemeral = true)]
+pub async fn cancel(
+    ctx: Context<'_>,
+    #[description = "The number of the announcement, shown by `/announce list`."] id: i64,
+) -> anyhow::Result<()> {
+    match announcements::delete(&ctx.data().db_pool, id).await {
+        Ok(true) => {
+            tracing::info!("{} cancelled announcement #{id}", ctx.author().name);
+            ctx.say(format!("Cancelled announcement #{id}.")).await?;
+        }
+        Ok(false) => {
+            respond_mistake!(&ctx, "There is no scheduled announcement with that number.");
+        }
+        Err(e) => {
+            respond_error!("Failed to cancel the announcement", e, &ctx);
+        }
+    }
+
+    Ok(())
+}
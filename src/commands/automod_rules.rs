@@ -0,0 +1,54 @@
+use crate::{automod, checks::is_maintainer, respond_error, respond_mistake, Context};
+
+/// Manage the AutoMod rules declared in the config.
+#[poise::command(
+    slash_command,
+    guild_only = true,
+    check = "is_maintainer",
+    subcommands("sync"),
+    subcommand_required
+)]
+pub async fn automod(_: Context<'_>) -> anyhow::Result<()> {
+    Ok(())
+}
+
+/// Make the AutoMod rules of the server match the config.
+#[poise::command(
+    slash_command,
+    guild_only = true,
+    check = "is_maintainer",
+    ephemeral = true
+)]
+pub async fn sync(ctx: Context<'_>) -> anyhow::Result<()> {
+    if ctx.data().config.automod.is_none() {
+        respond_mistake!(&ctx, "No AutoMod rules are configured.");
+    }
+
+    ctx.defer_ephemeral().await?;
+
+    let summary = match automod::sync_rules(ctx.http(), ctx.data()).await {
+        Ok(summary) => summary,
+        Err(e) => {
+            respond_error!("Failed to sync the AutoMod rules", e, &ctx);
+        }
+    };
+
+    let list = |names: &[String]| {
+        if names.is_empty() {
+            "none".to_string()
+        } else {
+            names.join(", ")
+        }
+    };
+
+    tracing::info!("{} synced the AutoMod rules.", ctx.author().name);
+    ctx.say(format!(
+        "Synced the AutoMod rules.\n**Created:** {}\n**Updated:** {}\n**Deleted:** {}",
+        list(&summary.created),
+        list(&summary.updated),
+        list(&summary.deleted)
+    ))
+    .await?;
+
+    Ok(())
+}
@@ -0,0 +1,164 @@
+use poise::CreateReply;
+
+use crate::{
+    checks::is_moderator,
+    database::blocked_domains,
+    link_filter::normalize_domain,
+    respond_error, respond_mistake,
+    util::{embeds::default_embed, format::truncate},
+    Context,
+};
+
+/// Domains whose links get deleted, like known scam and phishing sites.
+#[poise::command(
+    slash_command,
+    guild_only = true,
+    check = "is_moderator",
+    subcommands("add", "remove", "list"),
+    subcommand_required
+)]
+pub async fn blocklist(_: Context<'_>) -> anyhow::Result<()> {
+    Ok(())
+}
+
+/// Block a domain and all of its subdomains.
+#[poise::command(
+    slash_command,
+    guild_only = true,
+    check = "is_moderator",
+    ephemeral = true
+)]
+pub async fn add(
+    ctx: Context<'_>,
+    #[description = "The domain, like `scam.example`."]
+    #[max_length = 253]
+    domain: String,
+    #[description = "Why the domain is blocked, only shown to moderators."]
+    #[max_length = 500]
+    reason: Option<String>,
+) -> anyhow::Result<()> {
+    let Some(domain) = normalize_domain(&domain) else {
+        respond_mistake!(
+            &ctx,
+            "That isn't a domain, use something like `scam.example`."
+        );
+    };
+
+    match blocked_domains::insert(
+        &ctx.data().db_pool,
+        &domain,
+        ctx.author().id.get() as i64,
+        reason.as_deref(),
+    )
+    .await
+    {
+        Ok(true) => {}
+        Ok(false) => {
+            respond_mistake!(&ctx, "That domain is already blocked.");
+        }
+        Err(e) => {
+            respond_error!("Failed to block the domain", e, &ctx);
+        }
+    }
+
+    ctx.data().link_filter.reload_domains().await;
+    tracing::info!("{} blocked the domain {domain}.", ctx.author().name);
+
+    ctx.say(format!("Blocked `{domain}` and its subdomains."))
+        .await?;
+
+    Ok(())
+}
+
+/// Unblock a domain.
+#[poise::command(
+    slash_command,
+    guild_only = true,
+    check = "is_moderator",
+    ephemeral = true
+)]
+pub async fn remove(
+    ctx: Context<'_>,
+    #[description = "The blocked domain."]
+    #[max_length = 253]
+    domain: String,
+) -> anyhow::Result<()> {
+    let Some(domain) = normalize_domain(&domain) else {
+        respond_mistake!(
+            &ctx,
+            "That isn't a domain, use something like `scam.example`."
+        );
+    };
+
+    match blocked_domains::delete(&ctx.data().db_pool, &domain).await {
+        Ok(true) => {}
+        Ok(false) => {
+            respond_mistake!(&ctx, "That domain isn't blocked.");
+        }
+        Err(e) => {
+            respond_error!("Failed to unblock the domain", e, &ctx);
+        }
+    }
+
+    ctx.data().link_filter.reload_domains().await;
+    tracing::info!("{} unblocked the domain {domain}.", ctx.author().name);
+
+    ctx.say(format!("Unblocked `{domain}`.")).await?;
+
+    Ok(())
+}
+
+/// List the blocked domains.
+#[poise::command(
+    slash_command,
+    guild_only = true,
+    check = "is_moderator",
+    ephemeral = true
+)]
+pub async fn list(ctx: Context<'_>) -> anyhow::Result<()> {
+    let domains = match blocked_domains::all(&ctx.data().db_pool).await {
+        Ok(domains) => domains,
+        Err(e) => {
+            respond_error!("Failed to fetch the blocked domains", e, &ctx);
+        }
+    };
+
+    // the feed holds thousands of domains, only the manually blocked ones are listed.
+    let (manual, feed): (Vec<_>, Vec<_>) = domains
+        .into_iter()
+        .partition(|blocked| blocked.source == "manual");
+
+    if manual.is_empty() && feed.is_empty() {
+        respond_mistake!(&ctx, "No domains are blocked.");
+    }
+
+    let mut description = manual
+        .iter()
+        .map(|blocked| match &blocked.reason {
+            Some(reason) => format!("`{}` \u{2013} {}", blocked.domain, truncate(reason, 100)),
+            None => format!("`{}`", blocked.domain),
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    if !feed.is_empty() {
+        description = format!(
+            "{}\n\n*And {} domains from the phishing feed.*",
+            truncate(&description, 3900),
+            feed.len()
+        );
+    }
+
+    ctx.send(
+        CreateReply::default()
+            .embed(
+                default_embed(ctx.author())
+                    .title(format!("Blocked domains ({})", manual.len()))
+                    .description(truncate(description.trim(), 4000)),
+            )
+            .ephemeral(true),
+    )
+    .await?;
+
+    Ok(())
+}
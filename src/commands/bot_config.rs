@@ -0,0 +1,155 @@
+use poise::CreateReply;
+
+use crate::{
+    respond_error, respond_mistake,
+    settings::{Setting, SettingValue},
+    util::embeds::default_embed,
+    Context,
+};
+
+/// Settings that can change while the bot is running.
+#[poise::command(
+    slash_command,
+    guild_only = true,
+    default_member_permissions = "ADMINISTRATOR",
+    subcommands("get", "set", "list"),
+    subcommand_required
+)]
+pub async fn config(_: Context<'_>) -> anyhow::Result<()> {
+    Ok(())
+}
+
+/// Show the current value of a setting.
+#[poise::command(slash_command, guild_only = true, ephemeral = true)]
+pub async fn get(
+    ctx: Context<'_>,
+    #[description = "The setting to show."] setting: Setting,
+) -> anyhow::Result<()> {
+    ctx.say(format!(
+        "`{}` is {}{}\n-# {}",
+        setting.key(),
+        ctx.data().settings.get(setting),
+        origin(ctx, setting),
+        setting.description()
+    ))
+    .await?;
+
+    Ok(())
+}
+
+/// Change a setting, `default` goes back to the value from the config file.
+#[poise::command(slash_command, guild_only = true, ephemeral = true)]
+pub async fn set(
+    ctx: Context<'_>,
+    #[description = "The setting to change."] setting: Setting,
+    #[description = "The new value, or `default`."]
+    #[max_length = 500]
+    value: String,
+) -> anyhow::Result<()> {
+    let settings = &ctx.data().settings;
+
+    if value.trim().eq_ignore_ascii_case("default") {
+        match settings.reset(&ctx.data().db_pool, setting).await {
+            Ok(true) => {}
+            Ok(false) => {
+                respond_mistake!(&ctx, "That setting already has its default value.");
+            }
+            Err(e) => {
+                respond_error!("Failed to reset the setting", e, &ctx);
+            }
+        }
+
+        tracing::info!("{} reset the setting {}.", ctx.author().name, setting.key());
+
+        ctx.say(format!(
+            "Reset `{}` to {}.",
+            setting.key(),
+            settings.get(setting)
+        ))
+        .await?;
+
+        return Ok(());
+    }
+
+    let Some(new_value) = setting.parse(&value) else {
+        ctx.say(format!(
+            "`{}` has to be {}.",
+            setting.key(),
+            setting.expected()
+        ))
+        .await?;
+        return Ok(());
+    };
+
+    if let SettingValue::Channel(Some(channel_id)) = new_value {
+        let is_in_guild = ctx
+            .guild()
+            .is_some_and(|guild| guild.channels.contains_key(&channel_id));
+
+        if !is_in_guild {
+            respond_mistake!(&ctx, "That channel isn't in this server.");
+        }
+    }
+
+    if let Err(e) = settings
+        .set(
+            &ctx.data().db_pool,
+            setting,
+            new_value.clone(),
+            ctx.author().id.get() as i64,
+        )
+        .await
+    {
+        respond_error!("Failed to change the setting", e, &ctx);
+    }
+
+    tracing::info!(
+        "{} set the setting {} to {new_value}.",
+        ctx.author().name,
+        setting.key()
+    );
+
+    ctx.say(format!("Set `{}` to {new_value}.", setting.key()))
+        .await?;
+
+    Ok(())
+}
+
+/// List every setting with its current value.
+#[poise::command(slash_command, guild_only = true, ephemeral = true)]
+pub async fn list(ctx: Context<'_>) -> anyhow::Result<()> {
+    let description = Setting::ALL
+        .into_iter()
+        .map(|setting| {
+            format!(
+                "`{}` {}{}\n-# {}",
+                setting.key(),
+                ctx.data().settings.get(setting),
+                origin(ctx, setting),
+                setting.description()
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    ctx.send(
+        CreateReply::default()
+            .embed(
+                default_embed(ctx.author())
+                    .title("Settings")
+                    .description(description),
+            )
+            .ephemeral(true),
+    )
+    .await?;
+
+    Ok(())
+}
+
+fn origin(ctx: Context<'_>, setting: Setting) -> &'static str {
+    if ctx.data().settings.is_changed(setting) {
+        ""
+    } else {
+        " (default)"
+    }
+}
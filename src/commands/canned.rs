@@ -0,0 +1,190 @@
+use poise::{serenity_prelude as serenity, CreateReply, Modal};
+use serenity::{CreateAllowedMentions, Mentionable, User};
+
+use crate::{
+    checks::is_maintainer,
+    commands::tag::{normalize_name, valid_name},
+    database::canned_responses,
+    respond_error, respond_mistake,
+    util::format::inline_code,
+    Context,
+};
+
+#[derive(Debug, Modal)]
+#[name = "Canned Response"]
+struct CannedModal {
+    #[name = "Content"]
+    #[placeholder = "The markdown that gets posted, a mentioned user is put in front of it."]
+    #[paragraph]
+    // leaves room for the mention within discord's 2000 character limit.
+    #[max_length = 1900]
+    content: String,
+}
+
+/// Answer a recurring support question with a maintainer-curated response.
+#[poise::command(slash_command, guild_only = true, check = "is_maintainer")]
+pub async fn canned(
+    ctx: Context<'_>,
+    #[description = "The name of the response."]
+    #[autocomplete = "autocomplete_canned"]
+    name: String,
+    #[description = "The user the response is meant for, they get pinged."] target: Option<User>,
+) -> anyhow::Result<()> {
+    let response = match canned_responses::get(&ctx.data().db_pool, &normalize_name(&name)).await {
+        Ok(response) => response,
+        Err(e) => {
+            respond_error!("Failed to fetch the canned response", e, &ctx);
+        }
+    };
+
+    let Some(response) = response else {
+        respond_mistake!(&ctx, "There is no canned response with that name.");
+    };
+
+    let reply = match target {
+        Some(target) => CreateReply::default()
+            .content(format!("{} {}", target.mention(), response.content))
+            .allowed_mentions(CreateAllowedMentions::new().users([target.id])),
+        None => CreateReply::default()
+            .content(response.content)
+            .allowed_mentions(CreateAllowedMentions::new()),
+    };
+
+    ctx.send(reply).await?;
+
+    Ok(())
+}
+
+/// Maintainer commands to curate the canned responses.
+#[poise::command(
+    slash_command,
+    guild_only = true,
+    rename = "canned-manage",
+    check = "is_maintainer",
+    subcommands("set", "delete"),
+    subcommand_required
+)]
+pub async fn canned_manage(_: Context<'_>) -> anyhow::Result<()> {
+    Ok(())
+}
+
+/// Create a canned response or change an existing one.
+#[poise::command(
+    slash_command,
+    guild_only = true,
+    check = "is_maintainer",
+    ephemeral = true
+)]
+pub async fn set(
+    ctx: Context<'_>,
+    #[description = "The name of the response."]
+    #[autocomplete = "autocomplete_canned"]
+    name: String,
+) -> anyhow::Result<()> {
+    let Some(name) = valid_name(&name) else {
+        respond_mistake!(
+            &ctx,
+            "Canned response names consist of up to 32 lowercase letters, digits and dashes."
+        );
+    };
+
+    let existing = match canned_responses::get(&ctx.data().db_pool, &name).await {
+        Ok(existing) => existing,
+        Err(e) => {
+            respond_error!("Failed to fetch the canned response", e, &ctx);
+        }
+    };
+
+    let poise::Context::Application(app_ctx) = ctx else {
+        return Ok(());
+    };
+
+    let modal = match existing {
+        Some(existing) => {
+            let defaults = CannedModal {
+                content: existing.content,
+            };
+
+            CannedModal::execute_with_defaults(app_ctx, defaults).await?
+        }
+        None => CannedModal::execute(app_ctx).await?,
+    };
+
+    let Some(modal) = modal else {
+        return Ok(());
+    };
+
+    if let Err(e) = canned_responses::upsert(
+        &ctx.data().db_pool,
+        &name,
+        &modal.content,
+        ctx.author().id.get() as i64,
+    )
+    .await
+    {
+        respond_error!("Failed to save the canned response", e, &ctx);
+    }
+
+    tracing::info!("{} set canned response {name}", ctx.author().name);
+
+    ctx.say(format!("Saved the canned response {}.", inline_code(&name)))
+        .await?;
+
+    Ok(())
+}
+
+/// Delete a canned response.
+#[poise::command(
+    slash_command,
+    guild_only = true,
+    check = "is_maintainer",
+    ephemeral = true
+)]
+pub async fn delete(
+    ctx: Context<'_>,
+    #[description = "The name of the response."]
+    #[autocomplete = "autocomplete_canned"]
+    name: String,
+) -> anyhow::Result<()> {
+    ctx.defer_ephemeral().await?;
+
+    let name = normalize_name(&name);
+
+    match canned_responses::delete(&ctx.data().db_pool, &name).await {
+        Ok(true) => {}
+        Ok(false) => {
+            respond_mistake!(&ctx, "There is no canned response with that name.");
+        }
+        Err(e) => {
+            respond_error!("Failed to delete the canned response", e, &ctx);
+        }
+    }
+
+    tracing::info!("{} deleted canned response {name}", ctx.author().name);
+
+    ctx.say(format!(
+        "Deleted the canned response {}.",
+        inline_code(&name)
+    ))
+    .await?;
+
+    Ok(())
+}
+
+async fn autocomplete_canned<'a>(
+    ctx: Context<'_>,
+    partial: &'a str,
+) -> impl Iterator<Item = String> + 'a {
+    let names =
+        match canned_responses::names_matching(&ctx.data().db_pool, &normalize_name(partial), 25)
+            .await
+        {
+            Ok(names) => names,
+            Err(e) => {
+                tracing::error!("Failed to fetch canned response names for autocomplete: {e:#?}");
+                Vec::new()
+            }
+        };
+
+    names.into_iter()
+}
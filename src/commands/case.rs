@@ -0,0 +1,119 @@
+use poise::{serenity_prelude as serenity, CreateReply};
+use serenity::User;
+
+use crate::{
+    checks::is_moderator,
+    commands::moderation::case_embed,
+    database::mod_cases,
+    respond_error, respond_mistake,
+    util::{
+        embeds::default_embed,
+        format::{time, truncate, TimestampStyle},
+    },
+    Context,
+};
+
+/// The most recent cases `/case history` lists.
+const MAX_HISTORY_CASES: usize = 20;
+
+/// Look up recorded moderation cases.
+#[poise::command(
+    slash_command,
+    guild_only = true,
+    check = "is_moderator",
+    subcommands("view", "history"),
+    subcommand_required
+)]
+pub async fn case(_: Context<'_>) -> anyhow::Result<()> {
+    Ok(())
+}
+
+/// Show a single case.
+#[poise::command(
+    slash_command,
+    guild_only = true,
+    check = "is_moderator",
+    ephemeral = true
+)]
+pub async fn view(
+    ctx: Context<'_>,
+    #[description = "The case number."] number: i64,
+) -> anyhow::Result<()> {
+    let case = match mod_cases::get(&ctx.data().db_pool, number).await {
+        Ok(case) => case,
+        Err(e) => {
+            respond_error!("Failed to fetch the case", e, &ctx);
+        }
+    };
+
+    let Some(case) = case else {
+        respond_mistake!(&ctx, "There is no case with that number.");
+    };
+
+    ctx.send(
+        CreateReply::default()
+            .embed(case_embed(&case))
+            .ephemeral(true),
+    )
+    .await?;
+
+    Ok(())
+}
+
+/// List the cases of a user, the newest first.
+#[poise::command(
+    slash_command,
+    guild_only = true,
+    check = "is_moderator",
+    ephemeral = true
+)]
+pub async fn history(
+    ctx: Context<'_>,
+    #[description = "The user to list the cases of."] user: User,
+) -> anyhow::Result<()> {
+    let cases = match mod_cases::by_target(&ctx.data().db_pool, user.id.get() as i64).await {
+        Ok(cases) => cases,
+        Err(e) => {
+            respond_error!("Failed to fetch the cases", e, &ctx);
+        }
+    };
+
+    if cases.is_empty() {
+        respond_mistake!(&ctx, "That user has no cases.");
+    }
+
+    let mut description = cases
+        .iter()
+        .take(MAX_HISTORY_CASES)
+        .map(|case| {
+            format!(
+                "**#{}** {} \u{00B7} {}\n{}",
+                case.id,
+                case.action,
+                time(case.created_at, TimestampStyle::ShortDate),
+                truncate(&case.reason, 100)
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n\n");
+
+    if cases.len() > MAX_HISTORY_CASES {
+        description.push_str(&format!(
+            "\n\n... and {} older cases.",
+            cases.len() - MAX_HISTORY_CASES
+        ));
+    }
+
+    ctx.send(
+        CreateReply::default()
+            .embed(
+                default_embed(ctx.author())
+                    .title(format!("Cases of {} ({})", user.name, cases.len()))
+                    .description(description),
+            )
+            .ephemeral(true),
+    )
+    .await?;
+
+    Ok(())
+}
@@ -0,0 +1,120 @@
+use poise::CreateReply;
+
+use crate::{
+    respond_error, respond_mistake,
+    util::{embeds::default_embed, format::split_markdown},
+    Context,
+};
+
+/// Discord allows at most 10 embeds per message.
+const MAX_EMBEDS: usize = 10;
+
+/// Show the changelog of a Biome version.
+#[poise::command(slash_command, guild_only = true)]
+pub async fn changelog(
+    ctx: Context<'_>,
+    #[description = "The version to show the changelog for."]
+    #[autocomplete = "autocomplete_version"]
+    version: String,
+) -> anyhow::Result<()> {
+    ctx.defer().await?;
+
+    let github = &ctx.data().github;
+
+    let changelog = match github.file_contents("CHANGELOG.md").await {
+        Ok(Some(changelog)) => changelog,
+        Ok(None) => {
+            respond_mistake!(&ctx, "The repository doesn't have a changelog.");
+        }
+        Err(e) => {
+            respond_error!("Failed to fetch the changelog from github", e, &ctx);
+        }
+    };
+
+    let version = version.trim().trim_start_matches('v');
+
+    let Some(section) = changelog_section(&changelog, version) else {
+        respond_mistake!(&ctx, "There is no changelog entry for that version.");
+    };
+
+    let chunks = split_markdown(&section, 4000);
+    let changelog_url = format!(
+        "https://github.com/{}/blob/main/CHANGELOG.md",
+        github.repository()
+    );
+
+    let mut reply = CreateReply::default();
+
+    for (i, chunk) in chunks.iter().take(MAX_EMBEDS).enumerate() {
+        let mut embed = default_embed(ctx.author()).description(chunk);
+
+        if i == 0 {
+            embed = embed
+                .title(format!("Changelog for {version}"))
+                .url(&changelog_url);
+        }
+
+        if i == MAX_EMBEDS - 1 && chunks.len() > MAX_EMBEDS {
+            embed = embed.field(
+                "Truncated",
+                format!("Read the full changelog [on GitHub]({changelog_url})."),
+                false,
+            );
+        }
+
+        reply = reply.embed(embed);
+    }
+
+    ctx.send(reply).await?;
+
+    Ok(())
+}
+
+async fn autocomplete_version<'a>(
+    ctx: Context<'_>,
+    partial: &'a str,
+) -> impl Iterator<Item = String> + 'a {
+    ctx.data()
+        .autocomplete
+        .versions
+        .suggest(partial.trim_start_matches('v'))
+        .into_iter()
+}
+
+/// Returns the body of the `## <version>` section of the changelog, without the heading.
+fn changelog_section(changelog: &str, version: &str) -> Option<String> {
+    let mut lines = changelog.lines();
+
+    lines.find(|line| {
+        line.strip_prefix("## ")
+            .and_then(|heading| heading.split_whitespace().next())
+            .is_some_and(|heading| heading.trim_start_matches('v') == version)
+    })?;
+
+    let section = lines
+        .take_while(|line| !line.starts_with("## "))
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    Some(section.trim().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn extracts_version_section() {
+        let changelog = "# Biome changelog\n\n## Unreleased\n\n- wip\n\n## 1.7.0 (2024-04-15)\n\n### CLI\n\n- New flag\n\n## 1.6.4 (2024-04-03)\n\n- Fix\n";
+
+        assert_eq!(
+            changelog_section(changelog, "1.7.0").as_deref(),
+            Some("### CLI\n\n- New flag")
+        );
+        assert_eq!(
+            changelog_section(changelog, "1.6.4").as_deref(),
+            Some("- Fix")
+        );
+        assert_eq!(changelog_section(changelog, "1.6"), None);
+    }
+}
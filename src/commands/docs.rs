@@ -0,0 +1,79 @@
+use poise::CreateReply;
+
+use crate::{
+    database::docs_pages,
+    respond_error, respond_mistake,
+    util::{embeds::default_embed, format::truncate},
+    Context,
+};
+
+const MAX_RESULTS: i64 = 5;
+
+/// Search the Biome documentation.
+#[poise::command(
+    slash_command,
+    guild_only = true,
+    subcommands("search"),
+    subcommand_required
+)]
+pub async fn docs(_: Context<'_>) -> anyhow::Result<()> {
+    Ok(())
+}
+
+/// Find the documentation pages matching your query.
+#[poise::command(slash_command, guild_only = true)]
+pub async fn search(
+    ctx: Context<'_>,
+    #[description = "What you are looking for."]
+    #[autocomplete = "autocomplete_query"]
+    query: String,
+) -> anyhow::Result<()> {
+    ctx.defer().await?;
+
+    let results = match docs_pages::search(&ctx.data().db_pool, query.trim(), MAX_RESULTS).await {
+        Ok(results) => results,
+        Err(e) => {
+            respond_error!("Failed to search the documentation", e, &ctx);
+        }
+    };
+
+    if results.is_empty() {
+        respond_mistake!(&ctx, "No documentation page matches your query.");
+    }
+
+    let mut embed = default_embed(ctx.author()).title(format!(
+        "Documentation results for \"{}\"",
+        truncate(query.trim(), 200)
+    ));
+
+    for result in results {
+        embed = embed.field(
+            truncate(&result.title, 256),
+            format!(
+                "{}\n[Read more]({})",
+                truncate(&result.snippet, 800),
+                result.url
+            ),
+            false,
+        );
+    }
+
+    ctx.send(CreateReply::default().embed(embed)).await?;
+
+    Ok(())
+}
+
+async fn autocomplete_query<'a>(
+    ctx: Context<'_>,
+    partial: &'a str,
+) -> impl Iterator<Item = String> + 'a {
+    let titles = docs_pages::titles_matching(&ctx.data().db_pool, partial.trim(), 25).await;
+
+    match titles {
+        Ok(titles) => titles.into_iter(),
+        Err(e) => {
+            tracing::error!("Failed to fetch documentation titles for autocomplete: {e:#?}");
+            Vec::new().into_iter()
+        }
+    }
+}
@@ -1,11 +1,76 @@
-use std::fmt::Display;
+use std::{
+    collections::{BTreeSet, HashMap},
+    fmt::Display,
+    str::FromStr,
+    time::Duration,
+};
 
-use poise::{serenity_prelude as serenity, CreateReply};
-use serenity::{CreateAttachment, Embed, ExecuteWebhook, Webhook};
+use poise::{serenity_prelude as serenity, CreateReply, Modal};
+use serde_json::{json, Value};
+use serenity::{
+    Attachment, ButtonStyle, ComponentInteractionCollector, ComponentInteractionDataKind,
+    CreateActionRow, CreateAttachment, CreateButton, CreateInteractionResponse,
+    CreateInteractionResponseMessage, CreateSelectMenu, CreateSelectMenuKind,
+    CreateSelectMenuOption, Embed, GuildChannel, GuildId, Message, MessageId,
+};
+use similar::TextDiff;
 
-use crate::{respond_error, respond_mistake, util::embeds::EmbedColor, Context};
+use crate::{
+    commands::tag::{normalize_name, valid_name},
+    database::{embed_snapshots, embed_templates},
+    embed_payload::EmbedPayload,
+    respond_error, respond_mistake,
+    util::{
+        embeds::{default_embed, pick_color, EmbedColor},
+        format::{inline_code, time, truncate, TimestampStyle},
+        paginate::paginate,
+        parse::parse_message_link,
+    },
+    webhooks::{self, EmbedTarget},
+    Context,
+};
 
-#[derive(Debug, Clone, Copy, poise::ChoiceParameter)]
+/// Discord's upload limit for servers without boosts.
+const MAX_IMAGE_SIZE: u32 = 10 * 1024 * 1024;
+/// Every version takes up to about 1000 characters, an embed holds 6000 in total.
+const VERSIONS_PER_PAGE: usize = 4;
+
+/// An example offered by `/embed example`.
+struct EmbedExample {
+    name: &'static str,
+    description: &'static str,
+    file_name: &'static str,
+    json: &'static str,
+}
+
+const EXAMPLES: &[EmbedExample] = &[
+    EmbedExample {
+        name: "Announcement",
+        description: "A release with link buttons, {{version}} is a placeholder for templates.",
+        file_name: "announcement",
+        json: include_str!("../assets/embed_examples/announcement.json"),
+    },
+    EmbedExample {
+        name: "Rules Section",
+        description: "One rule with examples and consequences.",
+        file_name: "rules_section",
+        json: include_str!("../assets/embed_examples/rules_section.json"),
+    },
+    EmbedExample {
+        name: "FAQ Entry",
+        description: "A question with its answer and a link to learn more.",
+        file_name: "faq_entry",
+        json: include_str!("../assets/embed_examples/faq_entry.json"),
+    },
+    EmbedExample {
+        name: "Reference",
+        description: "Every part an embed can have, with its limits.",
+        file_name: "reference",
+        json: include_str!("../assets/embed_examples/reference.json"),
+    },
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, poise::ChoiceParameter)]
 #[repr(u8)]
 pub enum TargetChannelWebhook {
     Rules,
@@ -21,12 +86,59 @@ impl Display for TargetChannelWebhook {
     }
 }
 
+impl FromStr for TargetChannelWebhook {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "roles" => Ok(Self::Roles),
+            "rules" => Ok(Self::Rules),
+            _ => {
+                anyhow::bail!("Unknown target channel webhook: {s}");
+            }
+        }
+    }
+}
+
+#[derive(Debug, Modal)]
+#[name = "Embed Builder"]
+struct EmbedBuilderModal {
+    #[name = "Title"]
+    #[max_length = 256]
+    title: Option<String>,
+    #[name = "Description"]
+    #[placeholder = "The markdown text of the embed."]
+    #[paragraph]
+    #[max_length = 4000]
+    description: String,
+    #[name = "Color"]
+    #[placeholder = "A hex color like #63A3FA, the biome color by default."]
+    #[max_length = 7]
+    color: Option<String>,
+    #[name = "Footer"]
+    #[max_length = 2048]
+    footer: Option<String>,
+    #[name = "Image URL"]
+    #[placeholder = "https://..."]
+    image_url: Option<String>,
+}
+
 /// Subcommands for manual embed creation.
 #[poise::command(
     slash_command,
     guild_only = true,
     default_member_permissions = "ADMINISTRATOR",
-    subcommands("simple", "custom", "example"),
+    subcommands(
+        "simple",
+        "custom",
+        "builder",
+        "edit",
+        "template",
+        "from_message",
+        "example",
+        "history",
+        "rollback"
+    ),
     subcommand_required
 )]
 pub async fn embed(_: Context<'_>) -> anyhow::Result<()> {
@@ -34,88 +146,1192 @@ pub async fn embed(_: Context<'_>) -> anyhow::Result<()> {
 }
 
 /// Simple with text content and a title.
-#[poise::command(slash_command, guild_only = true)]
+#[poise::command(slash_command, guild_only = true, ephemeral = true)]
 pub async fn simple(
     ctx: Context<'_>,
-    #[description = "The target channel webhook for your embed to go."]
-    channel: TargetChannelWebhook,
     #[description = "The description (text) of the embed."] content: String,
     #[description = "The title of the embed."] title: Option<String>,
     #[description = "The color of the embed side."] colour: Option<EmbedColor>,
+    #[description = "Any other color as hex, like #1a2b3c. Replaces the color choice."]
+    #[max_length = 7]
+    hex_color: Option<String>,
+    #[description = "One of the configured webhooks for your embed to go."] webhook: Option<
+        TargetChannelWebhook,
+    >,
+    #[description = "Any other channel for your embed to go."]
+    #[channel_types("Text", "News")]
+    channel: Option<GuildChannel>,
+    #[description = "Post under this name instead of the one of the webhook, like Biome Team."]
+    #[max_length = 80]
+    username: Option<String>,
+    #[description = "Post with the image at this url as avatar instead of the one of the webhook."]
+    avatar_url: Option<String>,
+    #[description = "An image to show in the embed, uploaded with it."] image: Option<Attachment>,
 ) -> anyhow::Result<()> {
+    let Some(target) = pick_target(webhook, channel) else {
+        respond_mistake!(&ctx, "Pick either one of the webhooks or a channel.");
+    };
+
+    let Some(color) = pick_color(colour, hex_color.as_deref()) else {
+        respond_mistake!(&ctx, "The color has to be a hex color like `#1a2b3c`.");
+    };
+
     ctx.defer_ephemeral().await?;
 
-    let webhook = Webhook::from_url(&ctx, ctx.data().config.webhook_url(channel)).await?;
+    let image = match image {
+        Some(image) => match load_image(ctx, &image).await? {
+            Some(image) => Some(image),
+            None => return Ok(()),
+        },
+        None => None,
+    };
 
     let mut embed = Embed::default();
     embed.kind = Some("rich".into());
     embed.description = Some(content);
     embed.title = title;
-    embed.colour = Some(colour.unwrap_or_default().into());
+    embed.colour = Some(color.into());
+
+    let mut payload = EmbedPayload::from_embed(embed).with_identity(username, avatar_url);
+
+    if let Some(image) = image {
+        payload.attach_image(image);
+    }
+
+    post_payload(ctx, target, payload).await
+}
+
+/// Pass in a json object to to send a custom embed, or several embeds with link buttons.
+#[poise::command(slash_command, guild_only = true, ephemeral = true)]
+pub async fn custom(
+    ctx: Context<'_>,
+    #[description = "The json of an embed, a message with `embeds` and link `buttons`, or a Discohook message."]
+    content: String,
+    #[description = "One of the configured webhooks for your embed to go."] webhook: Option<
+        TargetChannelWebhook,
+    >,
+    #[description = "Any other channel for your embed to go."]
+    #[channel_types("Text", "News")]
+    channel: Option<GuildChannel>,
+    #[description = "Post under this name instead of the one of the webhook, like Biome Team."]
+    #[max_length = 80]
+    username: Option<String>,
+    #[description = "Post with the image at this url as avatar instead of the one of the webhook."]
+    avatar_url: Option<String>,
+    #[description = "An image to upload, reference it as attachment://<filename> or it becomes the image of the first embed."]
+    image: Option<Attachment>,
+) -> anyhow::Result<()> {
+    let Some(target) = pick_target(webhook, channel) else {
+        respond_mistake!(&ctx, "Pick either one of the webhooks or a channel.");
+    };
+
+    ctx.defer_ephemeral().await?;
+
+    let Some(payload) = parse_payload(ctx, &content).await? else {
+        return Ok(());
+    };
+
+    let mut payload = payload.with_identity(username, avatar_url);
+
+    if let Some(image) = image {
+        let Some(image) = load_image(ctx, &image).await? else {
+            return Ok(());
+        };
+
+        payload.attach_image(image);
+    }
+
+    post_payload(ctx, target, payload).await
+}
+
+/// Build an embed in a form instead of writing json.
+#[poise::command(slash_command, guild_only = true, ephemeral = true)]
+pub async fn builder(
+    ctx: Context<'_>,
+    #[description = "One of the configured webhooks for your embed to go."] webhook: Option<
+        TargetChannelWebhook,
+    >,
+    #[description = "Any other channel for your embed to go."]
+    #[channel_types("Text", "News")]
+    channel: Option<GuildChannel>,
+    #[description = "Post under this name instead of the one of the webhook, like Biome Team."]
+    #[max_length = 80]
+    username: Option<String>,
+    #[description = "Post with the image at this url as avatar instead of the one of the webhook."]
+    avatar_url: Option<String>,
+) -> anyhow::Result<()> {
+    let Some(target) = pick_target(webhook, channel) else {
+        respond_mistake!(&ctx, "Pick either one of the webhooks or a channel.");
+    };
+
+    let poise::Context::Application(app_ctx) = ctx else {
+        return Ok(());
+    };
+
+    // the modal has to be the first response, so this can't defer.
+    let Some(modal) = EmbedBuilderModal::execute(app_ctx).await? else {
+        return Ok(());
+    };
+
+    let Some(color) = pick_color(None, non_empty(modal.color).as_deref()) else {
+        respond_mistake!(&ctx, "The color has to be a hex color like `#1a2b3c`.");
+    };
+
+    let image_url = non_empty(modal.image_url);
+
+    if image_url
+        .as_ref()
+        .is_some_and(|url| !url.starts_with("https://") && !url.starts_with("http://"))
+    {
+        respond_mistake!(&ctx, "The image URL has to start with `https://`.");
+    }
+
+    let embed = match serde_json::from_value::<Embed>(json!({
+        "type": "rich",
+        "title": non_empty(modal.title),
+        "description": modal.description,
+        "color": color,
+        "footer": non_empty(modal.footer).map(|text| json!({ "text": text })),
+        "image": image_url.map(|url| json!({ "url": url })),
+    })) {
+        Ok(embed) => embed,
+        Err(e) => {
+            respond_error!("Failed to build the embed", e, &ctx);
+        }
+    };
+
+    post_payload(
+        ctx,
+        target,
+        EmbedPayload::from_embed(embed).with_identity(username, avatar_url),
+    )
+    .await
+}
+
+/// The target picked with the two options of a command, `None` unless exactly one is set.
+pub fn pick_target(
+    webhook: Option<TargetChannelWebhook>,
+    channel: Option<GuildChannel>,
+) -> Option<EmbedTarget> {
+    match (webhook, channel) {
+        (Some(webhook), None) => Some(EmbedTarget::Webhook(webhook)),
+        (None, Some(channel)) => Some(EmbedTarget::Channel(channel.id)),
+        _ => None,
+    }
+}
+
+/// The server whose webhooks a command posts through. The commands are guild only, the main
+/// server is only a fallback.
+pub fn posting_guild(ctx: Context<'_>) -> GuildId {
+    ctx.guild_id().unwrap_or(ctx.data().config.bot.guild_id)
+}
+
+/// Downloads an uploaded image to post it again with the embed. `None` if it isn't an image the
+/// webhook can upload, the author is told why.
+async fn load_image(
+    ctx: Context<'_>,
+    image: &Attachment,
+) -> anyhow::Result<Option<CreateAttachment>> {
+    let is_image = image
+        .content_type
+        .as_deref()
+        .is_some_and(|content_type| content_type.starts_with("image/"));
+
+    if !is_image {
+        ctx.say("The attachment has to be an image.").await?;
+        return Ok(None);
+    }
+
+    if image.size > MAX_IMAGE_SIZE {
+        ctx.say(format!(
+            "The image can be at most {} MB.",
+            MAX_IMAGE_SIZE / 1024 / 1024
+        ))
+        .await?;
+        return Ok(None);
+    }
+
+    let bytes = match image.download().await {
+        Ok(bytes) => bytes,
+        Err(e) => {
+            tracing::error!("Failed to download the image: {e:#?}");
+            ctx.say("Failed to download the image.").await?;
+            return Ok(None);
+        }
+    };
+
+    Ok(Some(CreateAttachment::bytes(
+        bytes,
+        attachment_filename(&image.filename),
+    )))
+}
+
+/// Discord replaces characters it doesn't allow in filenames, which would break the
+/// `attachment://` reference of the embed.
+fn attachment_filename(filename: &str) -> String {
+    filename
+        .chars()
+        .map(|c| match c {
+            'a'..='z' | 'A'..='Z' | '0'..='9' | '.' | '-' | '_' => c,
+            _ => '_',
+        })
+        .collect()
+}
+
+/// Optional modal fields can be submitted as empty text.
+fn non_empty(field: Option<String>) -> Option<String> {
+    field
+        .map(|field| field.trim().to_string())
+        .filter(|field| !field.is_empty())
+}
+
+/// Replace the embed of a message that was posted through one of the webhooks.
+#[poise::command(slash_command, guild_only = true, ephemeral = true)]
+pub async fn edit(
+    ctx: Context<'_>,
+    #[description = "Link to or ID of the embed message."] message: String,
+    #[description = "The json representation of the new embed, replaces the whole embed."]
+    json: Option<String>,
+    #[description = "The new description (text) of the embed, keeps everything else."]
+    content: Option<String>,
+    #[description = "The webhook the embed was posted with, only needed if it has no history yet."]
+    webhook: Option<TargetChannelWebhook>,
+    #[description = "The channel the embed was posted in, only needed if it has no history yet."]
+    #[channel_types("Text", "News")]
+    channel: Option<GuildChannel>,
+) -> anyhow::Result<()> {
+    ctx.defer_ephemeral().await?;
+
+    let Some(link) = parse_message_link(&message) else {
+        respond_mistake!(&ctx, "That is not a valid message link or ID.");
+    };
+
+    let previous =
+        embed_snapshots::latest(&ctx.data().db_pool, link.message_id.get() as i64).await?;
+
+    let target = match (pick_target(webhook, channel), &previous) {
+        (Some(target), _) => target,
+        (None, Some(previous)) => EmbedTarget::from_str(&previous.target)?,
+        (None, None) => {
+            respond_mistake!(
+                &ctx,
+                "There is no history for that message, pick the webhook or channel it was posted in."
+            );
+        }
+    };
+
+    let webhook = webhooks::resolve(ctx.http(), ctx.data(), posting_guild(ctx), target).await?;
+
+    // only messages of the webhook can be fetched through it, which makes sure it can edit them.
+    let existing = match webhook.get_message(&ctx, None, link.message_id).await {
+        Ok(existing) => existing,
+        Err(e) => {
+            respond_error!(
+                "Failed to fetch the message, it has to be posted through the webhook of that channel",
+                e,
+                &ctx
+            );
+        }
+    };
+
+    let mut payload = match (json, content) {
+        (Some(json), None) => {
+            let Some(payload) = parse_payload(ctx, &json).await? else {
+                return Ok(());
+            };
+
+            payload
+        }
+        (None, Some(content)) => {
+            // the history knows the buttons of the message, they can't be read back from it.
+            let mut payload = match previous {
+                Some(previous) => EmbedPayload::from_value(previous.embed)?,
+                None => EmbedPayload {
+                    content: Some(existing.content).filter(|content| !content.is_empty()),
+                    embeds: existing.embeds,
+                    ..Default::default()
+                },
+            };
+
+            let Some(embed) = payload.embeds.first_mut() else {
+                respond_mistake!(&ctx, "That message has no embed to change the text of.");
+            };
+
+            embed.description = Some(content);
+            payload
+        }
+        _ => {
+            respond_mistake!(
+                &ctx,
+                "Provide either the json of a new embed or a new text."
+            );
+        }
+    };
+
+    if !is_valid(ctx, &mut payload).await? {
+        return Ok(());
+    }
+
+    if !confirm_preview(ctx, &payload, target).await? {
+        return Ok(());
+    }
+
+    let snapshot = payload.to_value()?;
 
     match webhook
-        .execute(&ctx, false, ExecuteWebhook::new().embed(embed.into()))
+        .edit_message(&ctx, link.message_id, payload.edit_webhook_message())
         .await
     {
-        Ok(_) => {
-            ctx.say(format!("Successfully posted embed in {channel} channel."))
-                .await?;
+        Ok(message) => {
+            save_snapshot(ctx, Some(message), target, &snapshot).await;
+            ctx.say(format!(
+                "Successfully edited the embed in {}.",
+                target.describe()
+            ))
+            .await?;
         }
         Err(e) => {
-            respond_error!("Failed to post embed in {channel} channel", e, &ctx);
+            respond_error!("Failed to edit the embed message", e, &ctx);
         }
     }
 
     Ok(())
 }
 
-/// Pass in a json object to to send a custom embed.
-#[poise::command(slash_command, guild_only = true)]
-pub async fn custom(
+/// Preview example embeds and get their json to start from.
+#[poise::command(slash_command, guild_only = true, ephemeral = true)]
+pub async fn example(ctx: Context<'_>) -> anyhow::Result<()> {
+    let ctx_id = ctx.id();
+    let menu_id = format!("{ctx_id}example");
+
+    let options = EXAMPLES
+        .iter()
+        .enumerate()
+        .map(|(index, example)| {
+            CreateSelectMenuOption::new(example.name, index.to_string())
+                .description(example.description)
+        })
+        .collect();
+
+    let menu = CreateSelectMenu::new(&menu_id, CreateSelectMenuKind::String { options })
+        .placeholder("Pick an example");
+
+    let reply_handle = ctx
+        .send(
+            CreateReply::default()
+                .content("Pick an example to preview it and get its json.")
+                .components(vec![CreateActionRow::SelectMenu(menu)]),
+        )
+        .await?;
+
+    while let Some(press) = ComponentInteractionCollector::new(ctx)
+        .author_id(ctx.author().id)
+        .filter(move |press| press.data.custom_id.starts_with(&ctx_id.to_string()))
+        .timeout(Duration::from_secs(300))
+        .await
+    {
+        let ComponentInteractionDataKind::StringSelect { values } = &press.data.kind else {
+            continue;
+        };
+
+        let Some(example) = values
+            .first()
+            .and_then(|index| index.parse::<usize>().ok())
+            .and_then(|index| EXAMPLES.get(index))
+        else {
+            continue;
+        };
+
+        let payload = EmbedPayload::from_value(serde_json::from_str(example.json)?)?;
+
+        // every example gets its own reply, so the menu stays to pick another one.
+        press
+            .create_response(
+                ctx.serenity_context(),
+                CreateInteractionResponse::Message(
+                    CreateInteractionResponseMessage::new()
+                        .content(format!(
+                            "The {} example, post it with `/embed custom` or change it first.",
+                            example.name
+                        ))
+                        .embeds(payload.create_embeds())
+                        .components(payload.components())
+                        .add_file(CreateAttachment::bytes(
+                            example.json,
+                            format!("{}.json", example.file_name),
+                        ))
+                        .ephemeral(true),
+                ),
+            )
+            .await?;
+    }
+
+    reply_handle
+        .edit(
+            ctx,
+            CreateReply::default()
+                .content("The examples timed out, use `/embed example` again to see them.")
+                .components(vec![]),
+        )
+        .await?;
+
+    Ok(())
+}
+
+/// Get the json of a posted message, to post a copy of it with /embed custom.
+#[poise::command(
+    slash_command,
+    guild_only = true,
+    ephemeral = true,
+    rename = "from-message"
+)]
+pub async fn from_message(
     ctx: Context<'_>,
-    #[description = "The target channel webhook for your embed to go."]
-    channel: TargetChannelWebhook,
-    #[description = "The json representation of the embed you want to post."] content: String,
+    #[description = "Link to or ID of the message."] message: String,
 ) -> anyhow::Result<()> {
-    ctx.defer_ephemeral().await?;
+    let Some(link) = parse_message_link(&message) else {
+        respond_mistake!(&ctx, "That is not a valid message link or ID.");
+    };
 
-    let webhook = Webhook::from_url(&ctx, ctx.data().config.webhook_url(channel)).await?;
+    let channel_id = link.channel_id.unwrap_or(ctx.channel_id());
 
-    let mut embed = match serde_json::from_str::<Embed>(&content) {
-        Ok(json) => json,
+    let message = match channel_id.message(&ctx, link.message_id).await {
+        Ok(message) => message,
         Err(e) => {
-            respond_error!("Failed to parse the provided json", e, &ctx);
+            respond_error!("Failed to fetch the message", e, &ctx);
         }
     };
 
-    embed.kind = Some("rich".into());
+    let payload = EmbedPayload::from_message(&message);
 
-    if embed.description.is_none() && embed.fields.is_empty() {
-        respond_mistake!(&ctx, "You have to provide a description or embed fields!");
+    if payload.embeds.is_empty() && payload.content.is_none() {
+        respond_mistake!(&ctx, "That message has neither embeds nor text.");
     }
 
+    let json = serde_json::to_string_pretty(&payload.to_value()?)?;
+    let attachment = CreateAttachment::bytes(json, format!("message-{}.json", message.id));
+
+    ctx.send(
+        CreateReply::default()
+            .content("Paste this into `/embed custom` to post a copy, or change it first.")
+            .attachment(attachment),
+    )
+    .await?;
+
+    Ok(())
+}
+
+/// Show the edit history of an embed that was posted by the bot.
+#[poise::command(slash_command, guild_only = true, ephemeral = true)]
+pub async fn history(
+    ctx: Context<'_>,
+    #[description = "Link to or ID of the embed message."] message: String,
+) -> anyhow::Result<()> {
+    let Some(link) = parse_message_link(&message) else {
+        respond_mistake!(&ctx, "That is not a valid message link or ID.");
+    };
+
+    let snapshots =
+        embed_snapshots::history(&ctx.data().db_pool, link.message_id.get() as i64).await?;
+
+    if snapshots.is_empty() {
+        respond_mistake!(&ctx, "There is no recorded history for that message.");
+    }
+
+    let chunks = snapshots.chunks(VERSIONS_PER_PAGE);
+    let page_count = chunks.len();
+
+    let pages = chunks
+        .enumerate()
+        .map(|(index, chunk)| {
+            let mut embed = default_embed(ctx.author())
+                .title(format!(
+                    "Embed History \u{2013} page {}/{page_count}",
+                    index + 1
+                ))
+                .description(format!(
+                    "{} version(s) recorded. Use `/embed rollback` to restore one of them.",
+                    snapshots.len()
+                ));
+
+            for snapshot in chunk {
+                let diff = match &snapshot.diff {
+                    Some(diff) => format!("```diff\n{}```", truncate(diff, 900)),
+                    None => "Initial version.".to_string(),
+                };
+
+                embed = embed.field(
+                    format!("Version {}", snapshot.version),
+                    format!(
+                        "By <@{}> {}\n{diff}",
+                        snapshot.author_id,
+                        time(snapshot.created_at, TimestampStyle::Relative)
+                    ),
+                    false,
+                );
+            }
+
+            embed
+        })
+        .collect();
+
+    paginate(ctx, pages).await?;
+
+    Ok(())
+}
+
+/// Restore a previous version of an embed that was posted by the bot.
+#[poise::command(slash_command, guild_only = true, ephemeral = true)]
+pub async fn rollback(
+    ctx: Context<'_>,
+    #[description = "Link to or ID of the embed message."] message: String,
+    #[description = "The version to restore. See /embed history."]
+    #[min = 1]
+    version: i32,
+) -> anyhow::Result<()> {
+    ctx.defer_ephemeral().await?;
+
+    let Some(link) = parse_message_link(&message) else {
+        respond_mistake!(&ctx, "That is not a valid message link or ID.");
+    };
+
+    let Some(snapshot) =
+        embed_snapshots::version(&ctx.data().db_pool, link.message_id.get() as i64, version)
+            .await?
+    else {
+        respond_mistake!(&ctx, "There is no such version recorded for that message.");
+    };
+
+    let target = EmbedTarget::from_str(&snapshot.target)?;
+    let webhook = webhooks::resolve(ctx.http(), ctx.data(), posting_guild(ctx), target).await?;
+    let payload = EmbedPayload::from_value(snapshot.embed.clone())?;
+
     match webhook
-        .execute(&ctx, false, ExecuteWebhook::new().embed(embed.into()))
+        .edit_message(&ctx, link.message_id, payload.edit_webhook_message())
         .await
     {
-        Ok(_) => {
-            ctx.say(format!("Successfully posted embed in {channel} channel."))
-                .await?;
+        Ok(message) => {
+            save_snapshot(ctx, Some(message), target, &snapshot.embed).await;
+            ctx.say(format!(
+                "Successfully restored version {version} of the embed in {}.",
+                target.describe()
+            ))
+            .await?;
         }
         Err(e) => {
-            respond_error!("Failed to post embed in {channel} channel", e, &ctx);
+            respond_error!("Failed to edit the embed message", e, &ctx);
         }
     }
 
     Ok(())
 }
 
-/// Get the json of an embed to see what it should look like.
-#[poise::command(slash_command, guild_only = true, ephemeral = true)]
-pub async fn example(ctx: Context<'_>) -> anyhow::Result<()> {
-    let attachment = CreateAttachment::path("src/assets/example_embed.json").await?;
-    ctx.send(CreateReply::default().attachment(attachment))
+/// Named embeds stored in the database, with `{{variable}}` placeholders filled in when used.
+#[poise::command(
+    slash_command,
+    guild_only = true,
+    subcommands("template_save", "template_use", "template_list", "template_delete"),
+    subcommand_required
+)]
+pub async fn template(_: Context<'_>) -> anyhow::Result<()> {
+    Ok(())
+}
+
+/// Save an embed json as a template, replacing an existing one with the same name.
+#[poise::command(slash_command, guild_only = true, ephemeral = true, rename = "save")]
+pub async fn template_save(
+    ctx: Context<'_>,
+    #[description = "The name of the template, e.g. release-notes."] name: String,
+    #[description = "The json like for /embed custom, text may contain placeholders like {{version}}."]
+    json: String,
+) -> anyhow::Result<()> {
+    let Some(name) = valid_name(&name) else {
+        respond_mistake!(
+            &ctx,
+            "Template names consist of up to 32 lowercase letters, digits and dashes."
+        );
+    };
+
+    let Some(mut payload) = parse_payload(ctx, &json).await? else {
+        return Ok(());
+    };
+
+    if !is_valid(ctx, &mut payload).await? {
+        return Ok(());
+    }
+
+    let mut template = payload.to_value()?;
+    let placeholders = substitute(&mut template, &HashMap::new());
+
+    if let Err(e) = embed_templates::upsert(
+        &ctx.data().db_pool,
+        &name,
+        &template,
+        ctx.author().id.get() as i64,
+    )
+    .await
+    {
+        respond_error!("Failed to save the template", e, &ctx);
+    }
+
+    tracing::info!("{} saved embed template {name}", ctx.author().name);
+
+    ctx.say(format!(
+        "Saved the template {} with {} placeholder(s).",
+        inline_code(&name),
+        placeholders.len()
+    ))
+    .await?;
+
+    Ok(())
+}
+
+/// Post an embed from a template.
+#[poise::command(slash_command, guild_only = true, ephemeral = true, rename = "use")]
+pub async fn template_use(
+    ctx: Context<'_>,
+    #[description = "The name of the template."]
+    #[autocomplete = "autocomplete_template"]
+    name: String,
+    #[description = "Values for the placeholders, like `version=2.0; date=May 1`."]
+    variables: Option<String>,
+    #[description = "One of the configured webhooks for your embed to go."] webhook: Option<
+        TargetChannelWebhook,
+    >,
+    #[description = "Any other channel for your embed to go."]
+    #[channel_types("Text", "News")]
+    channel: Option<GuildChannel>,
+    #[description = "Post under this name instead of the one of the webhook, like Biome Team."]
+    #[max_length = 80]
+    username: Option<String>,
+    #[description = "Post with the image at this url as avatar instead of the one of the webhook."]
+    avatar_url: Option<String>,
+) -> anyhow::Result<()> {
+    let Some(target) = pick_target(webhook, channel) else {
+        respond_mistake!(&ctx, "Pick either one of the webhooks or a channel.");
+    };
+
+    ctx.defer_ephemeral().await?;
+
+    let Some(variables) = parse_variables(variables.as_deref().unwrap_or_default()) else {
+        respond_mistake!(
+            &ctx,
+            "Separate the variables with `;` and write them like `name=value`."
+        );
+    };
+
+    let template = match embed_templates::get(&ctx.data().db_pool, &normalize_name(&name)).await {
+        Ok(template) => template,
+        Err(e) => {
+            respond_error!("Failed to fetch the template", e, &ctx);
+        }
+    };
+
+    let Some(mut template) = template else {
+        respond_mistake!(&ctx, "There is no template with that name.");
+    };
+
+    let missing = substitute(&mut template.embed, &variables);
+
+    if !missing.is_empty() {
+        ctx.say(format!(
+            "The template needs values for {}.",
+            missing
+                .iter()
+                .map(|name| inline_code(name))
+                .collect::<Vec<_>>()
+                .join(", ")
+        ))
+        .await?;
+
+        return Ok(());
+    }
+
+    let payload = match EmbedPayload::from_value(template.embed) {
+        Ok(payload) => payload,
+        Err(e) => {
+            respond_error!("Failed to build the embed from the template", e, &ctx);
+        }
+    };
+
+    post_payload(ctx, target, payload.with_identity(username, avatar_url)).await
+}
+
+/// List the saved templates and their placeholders.
+#[poise::command(slash_command, guild_only = true, ephemeral = true, rename = "list")]
+pub async fn template_list(ctx: Context<'_>) -> anyhow::Result<()> {
+    let templates = match embed_templates::all(&ctx.data().db_pool).await {
+        Ok(templates) => templates,
+        Err(e) => {
+            respond_error!("Failed to fetch the templates", e, &ctx);
+        }
+    };
+
+    if templates.is_empty() {
+        respond_mistake!(
+            &ctx,
+            "There are no templates yet, save one with `/embed template save`."
+        );
+    }
+
+    let mut embed = default_embed(ctx.author()).title("Embed Templates");
+
+    // embeds can hold at most 25 fields.
+    for mut template in templates.into_iter().take(25) {
+        let placeholders = substitute(&mut template.embed, &HashMap::new());
+
+        let placeholders = if placeholders.is_empty() {
+            "No placeholders".to_string()
+        } else {
+            placeholders
+                .iter()
+                .map(|name| inline_code(name))
+                .collect::<Vec<_>>()
+                .join(", ")
+        };
+
+        embed = embed.field(
+            template.name,
+            format!(
+                "{placeholders}\nSaved by <@{}> {}",
+                template.author_id,
+                time(template.updated_at, TimestampStyle::Relative)
+            ),
+            false,
+        );
+    }
+
+    ctx.send(CreateReply::default().embed(embed)).await?;
+
+    Ok(())
+}
+
+/// Delete a template.
+#[poise::command(slash_command, guild_only = true, ephemeral = true, rename = "delete")]
+pub async fn template_delete(
+    ctx: Context<'_>,
+    #[description = "The name of the template."]
+    #[autocomplete = "autocomplete_template"]
+    name: String,
+) -> anyhow::Result<()> {
+    let name = normalize_name(&name);
+
+    match embed_templates::delete(&ctx.data().db_pool, &name).await {
+        Ok(true) => {}
+        Ok(false) => {
+            respond_mistake!(&ctx, "There is no template with that name.");
+        }
+        Err(e) => {
+            respond_error!("Failed to delete the template", e, &ctx);
+        }
+    }
+
+    tracing::info!("{} deleted embed template {name}", ctx.author().name);
+
+    ctx.say(format!("Deleted the template {}.", inline_code(&name)))
         .await?;
 
     Ok(())
 }
+
+async fn autocomplete_template<'a>(
+    ctx: Context<'_>,
+    partial: &'a str,
+) -> impl Iterator<Item = String> + 'a {
+    let names =
+        match embed_templates::names_matching(&ctx.data().db_pool, &normalize_name(partial), 25)
+            .await
+        {
+            Ok(names) => names,
+            Err(e) => {
+                tracing::error!("Failed to fetch embed template names for autocomplete: {e:#?}");
+                Vec::new()
+            }
+        };
+
+    names.into_iter()
+}
+
+/// Parses variables like `version=2.0; date=May 1`, `None` if one of them has no name.
+fn parse_variables(input: &str) -> Option<HashMap<String, String>> {
+    input
+        .split(';')
+        .filter(|variable| !variable.trim().is_empty())
+        .map(|variable| {
+            let (name, value) = variable.split_once('=')?;
+            let name = name.trim();
+
+            (!name.is_empty()).then(|| (name.to_string(), value.trim().to_string()))
+        })
+        .collect()
+}
+
+/// Replaces the `{{name}}` placeholders in every text of the json and returns the names of the
+/// placeholders without a value, which are left as they are.
+fn substitute(value: &mut Value, variables: &HashMap<String, String>) -> BTreeSet<String> {
+    let mut missing = BTreeSet::new();
+    substitute_into(value, variables, &mut missing);
+
+    missing
+}
+
+fn substitute_into(
+    value: &mut Value,
+    variables: &HashMap<String, String>,
+    missing: &mut BTreeSet<String>,
+) {
+    match value {
+        Value::String(text) => *text = substitute_text(text, variables, missing),
+        Value::Array(values) => {
+            for value in values {
+                substitute_into(value, variables, missing);
+            }
+        }
+        Value::Object(map) => {
+            for value in map.values_mut() {
+                substitute_into(value, variables, missing);
+            }
+        }
+        _ => {}
+    }
+}
+
+fn substitute_text(
+    text: &str,
+    variables: &HashMap<String, String>,
+    missing: &mut BTreeSet<String>,
+) -> String {
+    let mut result = String::with_capacity(text.len());
+    let mut rest = text;
+
+    while let Some(start) = rest.find("{{") {
+        let Some(length) = rest[start + 2..].find("}}") else {
+            break;
+        };
+
+        let placeholder = &rest[start..start + 2 + length + 2];
+        let name = rest[start + 2..start + 2 + length].trim();
+
+        result.push_str(&rest[..start]);
+
+        match variables.get(name) {
+            Some(value) => result.push_str(value),
+            None => {
+                missing.insert(name.to_string());
+                result.push_str(placeholder);
+            }
+        }
+
+        rest = &rest[start + placeholder.len()..];
+    }
+
+    result.push_str(rest);
+    result
+}
+
+/// Parses the json of a payload, or tells the author everything that is wrong with it.
+pub async fn parse_payload(ctx: Context<'_>, json: &str) -> anyhow::Result<Option<EmbedPayload>> {
+    let value = match serde_json::from_str::<Value>(json) {
+        Ok(value) => value,
+        Err(e) => {
+            ctx.say(format!("That is not valid json: {e}.")).await?;
+            return Ok(None);
+        }
+    };
+
+    match EmbedPayload::parse(value) {
+        Ok(payload) => Ok(Some(payload)),
+        Err(problems) => {
+            let list = problems
+                .iter()
+                .map(|problem| format!("- {problem}"))
+                .collect::<Vec<_>>()
+                .join("\n");
+
+            ctx.say(truncate(
+                &format!("Found {} problem(s) in the json:\n{list}", problems.len()),
+                1900,
+            ))
+            .await?;
+
+            Ok(None)
+        }
+    }
+}
+
+/// Tells the author what is wrong with the payload, if anything.
+pub async fn is_valid(ctx: Context<'_>, payload: &mut EmbedPayload) -> anyhow::Result<bool> {
+    match payload.validate() {
+        Ok(()) => Ok(true),
+        Err(problem) => {
+            ctx.say(problem).await?;
+            Ok(false)
+        }
+    }
+}
+
+/// Posts the payload through the webhook of the target once the author confirmed the preview.
+async fn post_payload(
+    ctx: Context<'_>,
+    target: EmbedTarget,
+    mut payload: EmbedPayload,
+) -> anyhow::Result<()> {
+    if !is_valid(ctx, &mut payload).await? {
+        return Ok(());
+    }
+
+    let webhook = match webhooks::resolve(ctx.http(), ctx.data(), posting_guild(ctx), target).await
+    {
+        Ok(webhook) => webhook,
+        Err(e) => {
+            respond_error!("Failed to get a webhook for the channel", e, &ctx);
+        }
+    };
+
+    if !confirm_preview(ctx, &payload, target).await? {
+        return Ok(());
+    }
+
+    let snapshot = payload.to_value()?;
+
+    match webhook.execute(&ctx, true, payload.execute_webhook()).await {
+        Ok(message) => {
+            // discord drops the components of webhooks that weren't created by the bot.
+            let buttons_dropped = !payload.buttons.is_empty()
+                && message
+                    .as_ref()
+                    .is_some_and(|message| message.components.is_empty());
+
+            save_snapshot(ctx, message, target, &snapshot).await;
+            ctx.say(format!(
+                "Successfully posted the embed in {}.",
+                target.describe()
+            ))
+            .await?;
+
+            if buttons_dropped {
+                ctx.say("Discord dropped the buttons, post to the channel instead of its configured webhook to keep them.")
+                    .await?;
+            }
+        }
+        Err(e) => {
+            respond_error!("Failed to post the embed", e, &ctx);
+        }
+    }
+
+    Ok(())
+}
+
+/// Shows the embeds only to the author, with buttons to post it or cancel. `false` if they
+/// cancelled or didn't press a button in time.
+async fn confirm_preview(
+    ctx: Context<'_>,
+    payload: &EmbedPayload,
+    target: EmbedTarget,
+) -> anyhow::Result<bool> {
+    let ctx_id = ctx.id();
+    let confirm_button_id = format!("{ctx_id}confirm");
+    let cancel_button_id = format!("{ctx_id}cancel");
+
+    let mut components = payload.components();
+    components.push(CreateActionRow::Buttons(vec![
+        CreateButton::new(&confirm_button_id)
+            .label("Confirm")
+            .style(ButtonStyle::Success),
+        CreateButton::new(&cancel_button_id)
+            .label("Cancel")
+            .style(ButtonStyle::Danger),
+    ]));
+
+    let mut preview = format!("This is how the embed will look in {}", target.describe());
+
+    if let Some(username) = &payload.username {
+        preview.push_str(&format!(", posted as **{username}**"));
+    }
+
+    preview.push('.');
+
+    if let Some(content) = &payload.content {
+        preview.push_str(&format!("\n\n>>> {}", truncate(content, 1700)));
+    }
+
+    let reply = CreateReply::default()
+        .content(preview)
+        .embeds(payload.create_embeds())
+        .components(components)
+        .ephemeral(true);
+
+    // the preview needs the files too, the embeds can't show them otherwise.
+    let reply = payload
+        .files
+        .iter()
+        .cloned()
+        .fold(reply, CreateReply::attachment);
+
+    let reply_handle = ctx.send(reply).await?;
+
+    let press = ComponentInteractionCollector::new(ctx)
+        .author_id(ctx.author().id)
+        .filter(move |press| press.data.custom_id.starts_with(&ctx_id.to_string()))
+        .timeout(Duration::from_secs(120))
+        .await;
+
+    let confirmed = press
+        .as_ref()
+        .is_some_and(|press| press.data.custom_id == confirm_button_id);
+
+    let content = match (&press, confirmed) {
+        (_, true) => "Confirmed, posting the embed.",
+        (Some(_), false) => "Cancelled, nothing was posted.",
+        (None, _) => "Nothing was posted, the preview timed out.",
+    };
+
+    // keeps the preview, only the buttons go away.
+    match press {
+        Some(press) => {
+            press
+                .create_response(
+                    ctx.serenity_context(),
+                    CreateInteractionResponse::UpdateMessage(
+                        CreateInteractionResponseMessage::new()
+                            .content(content)
+                            .components(vec![]),
+                    ),
+                )
+                .await?;
+        }
+        None => {
+            reply_handle
+                .edit(
+                    ctx,
+                    CreateReply::default().content(content).components(vec![]),
+                )
+                .await?;
+        }
+    }
+
+    Ok(confirmed)
+}
+
+/// Stores a new version of the embed posted in the message. Failing to do so should not fail the command.
+async fn save_snapshot(
+    ctx: Context<'_>,
+    message: Option<Message>,
+    target: EmbedTarget,
+    embed: &Value,
+) {
+    let Some(message) = message else {
+        tracing::warn!("Webhook did not return the posted message, skipping embed snapshot.");
+        return;
+    };
+
+    if let Err(e) = record_snapshot(ctx, message.id, target, embed).await {
+        tracing::error!("Failed to record embed snapshot: {e:#?}");
+    }
+}
+
+async fn record_snapshot(
+    ctx: Context<'_>,
+    message_id: MessageId,
+    target: EmbedTarget,
+    embed: &Value,
+) -> anyhow::Result<()> {
+    let pool = &ctx.data().db_pool;
+    let message_id = message_id.get() as i64;
+
+    let (version, diff) = match embed_snapshots::latest(pool, message_id).await? {
+        Some(previous) => (
+            previous.version + 1,
+            Some(embed_diff(&previous.embed, embed)?),
+        ),
+        None => (1, None),
+    };
+
+    embed_snapshots::insert(
+        pool,
+        message_id,
+        &target.to_string(),
+        version,
+        ctx.author().id.get() as i64,
+        embed,
+        diff.as_deref(),
+    )
+    .await
+}
+
+fn embed_diff(previous: &Value, current: &Value) -> anyhow::Result<String> {
+    let previous = serde_json::to_string_pretty(previous)?;
+    let current = serde_json::to_string_pretty(current)?;
+
+    Ok(TextDiff::from_lines(&previous, &current)
+        .unified_diff()
+        .context_radius(1)
+        .to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_variables() {
+        let variables = parse_variables("version=2.0; date = May 1 ;").unwrap();
+
+        assert_eq!(variables["version"], "2.0");
+        assert_eq!(variables["date"], "May 1");
+        assert_eq!(parse_variables(""), Some(HashMap::new()));
+        assert_eq!(parse_variables("version"), None);
+        assert_eq!(parse_variables("=2.0"), None);
+    }
+
+    #[test]
+    fn substitutes_placeholders() {
+        let mut embed = json!({
+            "title": "Biome {{ version }}",
+            "color": 6530042,
+            "fields": [{ "name": "Released", "value": "{{date}} by {{author}}" }],
+        });
+        let variables = HashMap::from([
+            ("version".to_string(), "2.0".to_string()),
+            ("date".to_string(), "today".to_string()),
+        ]);
+
+        let missing = substitute(&mut embed, &variables);
+
+        assert_eq!(missing, BTreeSet::from(["author".to_string()]));
+        assert_eq!(embed["title"], "Biome 2.0");
+        assert_eq!(embed["fields"][0]["value"], "today by {{author}}");
+        assert_eq!(embed["color"], 6530042);
+    }
+
+    #[test]
+    fn examples_are_valid() {
+        for example in EXAMPLES {
+            let mut payload =
+                EmbedPayload::parse(serde_json::from_str(example.json).unwrap()).unwrap();
+
+            assert_eq!(payload.validate(), Ok(()), "{}", example.name);
+            assert!(example.description.chars().count() <= 100);
+        }
+    }
+
+    #[test]
+    fn sanitizes_attachment_filenames() {
+        assert_eq!(
+            attachment_filename("Biome 2.0 banner!.png"),
+            "Biome_2.0_banner_.png"
+        );
+        assert_eq!(attachment_filename("logo.webp"), "logo.webp");
+    }
+
+    #[test]
+    fn keeps_unclosed_placeholders() {
+        let mut missing = BTreeSet::new();
+
+        assert_eq!(
+            substitute_text("{{a}} {{b", &HashMap::new(), &mut missing),
+            "{{a}} {{b"
+        );
+        assert_eq!(missing, BTreeSet::from(["a".to_string()]));
+    }
+}
@@ -0,0 +1,111 @@
+use poise::{serenity_prelude as serenity, CreateReply};
+use serenity::{CreateAttachment, Message};
+use similar::TextDiff;
+
+use crate::{
+    biome_cli::{self, MAX_INPUT_SIZE},
+    respond_error, respond_mistake,
+    util::{format::truncate, parse::parse_code_blocks},
+    Context,
+};
+
+/// At most this many code blocks of a message get formatted.
+const MAX_BLOCKS: usize = 3;
+
+/// Format the code blocks of a message with Biome.
+#[poise::command(context_menu_command = "Format with Biome", guild_only = true)]
+pub async fn format_message(
+    ctx: Context<'_>,
+    #[description = "The message with the code to format."] message: Message,
+) -> anyhow::Result<()> {
+    let Some(cli) = &ctx.data().config.biome else {
+        respond_mistake!(&ctx, "Formatting snippets isn't enabled on this server.");
+    };
+
+    let blocks = parse_code_blocks(&message.content)
+        .into_iter()
+        .filter(|block| block.language.is_none() || block.file_extension().is_some())
+        .take(MAX_BLOCKS)
+        .collect::<Vec<_>>();
+
+    if blocks.is_empty() {
+        respond_mistake!(
+            &ctx,
+            "That message doesn't contain a code block in a language Biome supports."
+        );
+    }
+
+    if blocks.iter().any(|block| block.code.len() > MAX_INPUT_SIZE) {
+        respond_mistake!(&ctx, "That code block is too large to format.");
+    }
+
+    ctx.defer().await?;
+
+    let mut sections = Vec::new();
+    // the same without diffs, for when those don't fit into one message.
+    let mut short_sections = Vec::new();
+    let mut attachments = Vec::new();
+
+    for (i, block) in blocks.iter().enumerate() {
+        // code blocks without a language are most likely javascript.
+        let extension = block.file_extension().unwrap_or("js");
+
+        let output = match biome_cli::format(cli, &block.code, extension).await {
+            Ok(output) => output,
+            Err(e) => {
+                respond_error!("Failed to run the biome formatter", e, &ctx);
+            }
+        };
+
+        if !output.success {
+            let section = format!(
+                "Biome couldn't format block {}:\n```\n{}\n```",
+                i + 1,
+                truncate(output.stderr.trim(), 600)
+            );
+            sections.push(section.clone());
+            short_sections.push(section);
+            continue;
+        }
+
+        let formatted = output.stdout;
+
+        if formatted.trim_end() == block.code.trim_end() {
+            let section = format!("Block {} is already formatted.", i + 1);
+            sections.push(section.clone());
+            short_sections.push(section);
+            continue;
+        }
+
+        let file_name = format!("formatted-{}.{extension}", i + 1);
+
+        let diff = TextDiff::from_lines(block.code.as_str(), formatted.as_str())
+            .unified_diff()
+            .context_radius(2)
+            .to_string();
+
+        sections.push(format!("Block {}:\n```diff\n{}```", i + 1, diff));
+        short_sections.push(format!(
+            "Block {} was formatted, the diff is too long to show. See `{file_name}`.",
+            i + 1
+        ));
+        attachments.push(CreateAttachment::bytes(formatted, file_name));
+    }
+
+    let mut content = sections.join("\n");
+
+    // the diffs are only shown when they fit into one message, the formatted files are always attached.
+    if content.chars().count() > 2000 {
+        content = short_sections.join("\n");
+    }
+
+    let mut reply = CreateReply::default().content(content);
+
+    for attachment in attachments {
+        reply = reply.attachment(attachment);
+    }
+
+    ctx.send(reply).await?;
+
+    Ok(())
+}
@@ -0,0 +1,89 @@
+use poise::serenity_prelude as serenity;
+use serenity::CreateEmbed;
+
+use crate::{
+    github_api::Issue,
+    respond_error, respond_mistake,
+    util::{
+        embeds::default_embed,
+        format::{escape_markdown, time, TimestampStyle},
+        paginate::paginate,
+    },
+    Context,
+};
+
+const GOOD_FIRST_ISSUE_LABEL: &str = "good first issue";
+const ISSUES_PER_PAGE: usize = 5;
+
+/// Browse the currently open good first issues.
+#[poise::command(slash_command, guild_only = true)]
+pub async fn gfi(ctx: Context<'_>) -> anyhow::Result<()> {
+    ctx.defer().await?;
+
+    let issues = match ctx
+        .data()
+        .github
+        .open_issues_with_label(GOOD_FIRST_ISSUE_LABEL)
+        .await
+    {
+        Ok(issues) => issues,
+        Err(e) => {
+            respond_error!("Failed to fetch good first issues from github", e, &ctx);
+        }
+    };
+
+    if issues.is_empty() {
+        respond_mistake!(
+            &ctx,
+            "There are no open good first issues right now. Check back later!"
+        );
+    }
+
+    let pages = issues
+        .chunks(ISSUES_PER_PAGE)
+        .map(|chunk| {
+            chunk
+                .iter()
+                .map(display_issue)
+                .collect::<Vec<_>>()
+                .join("\n\n")
+        })
+        .collect::<Vec<_>>();
+
+    let build_page = |index: usize| -> CreateEmbed {
+        default_embed(ctx.author())
+            .title(format!("Good First Issues ({}/{})", index + 1, pages.len()))
+            .description(&pages[index])
+    };
+
+    paginate(ctx, (0..pages.len()).map(build_page).collect()).await?;
+
+    Ok(())
+}
+
+fn display_issue(issue: &Issue) -> String {
+    let assignees = issue.assignees.as_deref().unwrap_or_default();
+
+    let status = if assignees.is_empty() {
+        "Unassigned".to_string()
+    } else {
+        format!(
+            "Assigned to {}",
+            assignees
+                .iter()
+                .map(|user| escape_markdown(&user.login))
+                .collect::<Vec<_>>()
+                .join(", ")
+        )
+    };
+
+    format!(
+        "**[#{} {}]({})**\n{} \u{00B7} {} comments \u{00B7} opened {}",
+        issue.number,
+        issue.title,
+        issue.html_url,
+        status,
+        issue.comments,
+        time(issue.created_at, TimestampStyle::Relative)
+    )
+}
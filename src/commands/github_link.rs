@@ -0,0 +1,99 @@
+use poise::{serenity_prelude as serenity, CreateReply};
+use serenity::{CreateActionRow, CreateButton};
+
+use crate::{
+    database::github_links, respond_error, respond_mistake, routes::oauth, sponsors, Context,
+};
+
+/// Link your github account to get the contributor and sponsor roles.
+#[poise::command(
+    slash_command,
+    guild_only = true,
+    subcommands("link", "unlink"),
+    subcommand_required
+)]
+pub async fn github(_: Context<'_>) -> anyhow::Result<()> {
+    Ok(())
+}
+
+/// Link your github account. Members with merged pull requests get the contributor role.
+#[poise::command(slash_command, guild_only = true, ephemeral = true)]
+pub async fn link(ctx: Context<'_>) -> anyhow::Result<()> {
+    let Some(config) = &ctx.data().config.github.oauth else {
+        respond_mistake!(
+            &ctx,
+            "Linking github accounts isn't enabled on this server."
+        );
+    };
+
+    let current = match github_links::get(&ctx.data().db_pool, ctx.author().id.get() as i64).await {
+        Ok(current) => current,
+        Err(e) => {
+            respond_error!("Failed to fetch your linked account", e, &ctx);
+        }
+    };
+
+    let content = match current {
+        Some(current) => format!(
+            "Your linked github account is **{}**, open the link to replace it. The link is valid for 15 minutes.",
+            current.github_login
+        ),
+        None => "Open the link to sign in with github. The link is valid for 15 minutes.".to_string(),
+    };
+
+    ctx.send(
+        CreateReply::default()
+            .content(content)
+            .components(vec![CreateActionRow::Buttons(vec![
+                CreateButton::new_link(oauth::link_url(ctx.data(), config, ctx.author().id))
+                    .label("Link github account"),
+            ])])
+            .ephemeral(true),
+    )
+    .await?;
+
+    Ok(())
+}
+
+/// Unlink your github account, this removes the contributor and sponsor roles.
+#[poise::command(slash_command, guild_only = true, ephemeral = true)]
+pub async fn unlink(ctx: Context<'_>) -> anyhow::Result<()> {
+    match github_links::delete(&ctx.data().db_pool, ctx.author().id.get() as i64).await {
+        Ok(true) => {}
+        Ok(false) => {
+            respond_mistake!(&ctx, "You haven't linked a github account.");
+        }
+        Err(e) => {
+            respond_error!("Failed to unlink your account", e, &ctx);
+        }
+    }
+
+    let config = &ctx.data().config;
+    let linked_roles = config
+        .roles
+        .contributor
+        .into_iter()
+        .chain(config.sponsors.iter().flat_map(sponsors::managed_roles))
+        .collect::<Vec<_>>();
+
+    if let Some(member) = ctx.author_member().await {
+        let roles = linked_roles
+            .into_iter()
+            .filter(|role_id| member.roles.contains(role_id))
+            .collect::<Vec<_>>();
+
+        if !roles.is_empty() {
+            if let Err(e) = member.remove_roles(ctx, &roles).await {
+                tracing::warn!(
+                    "Failed to remove the roles of {}'s github account: {e}",
+                    ctx.author().name
+                );
+            }
+        }
+    }
+
+    tracing::info!("{} unlinked their github account.", ctx.author().name);
+    ctx.say("Unlinked your github account.").await?;
+
+    Ok(())
+}
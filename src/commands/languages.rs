@@ -1,46 +1,19 @@
-use std::{fmt::Display, str::FromStr};
-
-use scraper::{selectable::Selectable, Html, Selector};
-
-use crate::Context;
-
-#[derive(Debug)]
-struct LanguageFeature {
-    language_name: String,
-    parsing: LanguageSupportLevel,
-    formatting: LanguageSupportLevel,
-    linting: LanguageSupportLevel,
-}
-
-impl LanguageFeature {
-    fn support_level_to_vec(&self) -> Vec<LanguageSupportLevel> {
-        vec![self.parsing, self.formatting, self.linting]
-    }
-
-    pub fn is_fully_supported(&self) -> bool {
-        self.support_level_to_vec()
-            .iter()
-            .all(|i| matches!(i, LanguageSupportLevel::Supported))
-    }
-
-    fn is_partially_supported(&self) -> bool {
-        self.support_level_to_vec()
-            .iter()
-            .any(|i| matches!(i, LanguageSupportLevel::PartiallySupported))
-    }
-
-    fn is_work_in_progress(&self) -> bool {
-        self.support_level_to_vec()
-            .iter()
-            .any(|i| matches!(i, LanguageSupportLevel::InProgress))
-    }
-
-    fn is_not_supported(&self) -> bool {
-        self.support_level_to_vec()
-            .iter()
-            .all(|i| matches!(i, LanguageSupportLevel::NotInProgress))
-    }
-}
+use std::time::Duration;
+
+use poise::{serenity_prelude as serenity, CreateReply};
+use serenity::{
+    ButtonStyle, ComponentInteractionCollector, CreateActionRow, CreateButton, CreateEmbed,
+    CreateInteractionResponse, CreateInteractionResponseMessage,
+};
+
+use crate::{
+    language_support::{
+        cached_language_support, LanguageFeature, LanguageSupportLevel, LANGUAGE_SUPPORT_URL,
+    },
+    respond_error, respond_mistake,
+    util::{embeds::default_embed, format::truncate},
+    Context,
+};
 
 #[derive(Debug)]
 struct SupportedLanguages {
@@ -78,133 +51,220 @@ impl From<Vec<LanguageFeature>> for SupportedLanguages {
     }
 }
 
-#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Copy, Clone)]
-#[repr(u8)]
-enum LanguageSupportLevel {
-    Supported,
-    InProgress,
-    PartiallySupported,
-    NotInProgress,
-}
+/// See the status of Biome's supported languages.
+#[poise::command(slash_command, guild_only = true)]
+pub async fn languages(
+    ctx: Context<'_>,
+    #[description = "Only show the detailed support of this language."]
+    #[autocomplete = "autocomplete_language"]
+    language: Option<String>,
+) -> anyhow::Result<()> {
+    ctx.defer().await?;
 
-impl Display for LanguageSupportLevel {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        match self {
-            Self::Supported => write!(f, "\u{2705}"),
-            Self::InProgress => write!(f, "\u{231B}\u{FE0F}"),
-            Self::PartiallySupported => write!(f, "\u{26A0}\u{FE0F}"),
-            Self::NotInProgress => write!(f, "\u{1F6AB}"),
+    let language_features = match cached_language_support(ctx.data()).await {
+        Ok(language_features) => language_features,
+        Err(e) => {
+            respond_error!(
+                "Failed to fetch the language support from the website",
+                e,
+                &ctx
+            );
         }
-    }
-}
+    };
 
-impl FromStr for LanguageSupportLevel {
-    type Err = anyhow::Error;
+    let Some(language) = language else {
+        return send_overview(ctx, &language_features).await;
+    };
 
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let s = s.trim();
+    let Some(feature) = language_features.iter().find(|feature| {
+        feature
+            .language_name
+            .trim()
+            .eq_ignore_ascii_case(language.trim())
+    }) else {
+        respond_mistake!(&ctx, "There is no language with that name on the website.");
+    };
 
-        match s.trim() {
-            "\u{2705}\u{FE0F}" | "\u{2705}" => Ok(Self::Supported),
-            "\u{231B}\u{FE0F}" => Ok(Self::InProgress),
-            "\u{26A0}\u{FE0F}" => Ok(Self::PartiallySupported),
-            "\u{1F6AB}" => Ok(Self::NotInProgress),
-            _ => {
-                anyhow::bail!("Unsupported Language support level: {}", s,);
-            }
-        }
-    }
-}
+    ctx.send(
+        CreateReply::default().embed(
+            default_embed(ctx.author())
+                .title(format!("{} Support", feature.language_name.trim()))
+                .url(LANGUAGE_SUPPORT_URL)
+                .field("Parsing", display_level(feature.parsing), true)
+                .field("Formatting", display_level(feature.formatting), true)
+                .field("Linting", display_level(feature.linting), true),
+        ),
+    )
+    .await?;
 
-/// See the status of Biome's supported languages.
-#[poise::command(slash_command, guild_only = true)]
-pub async fn languages(ctx: Context<'_>) -> anyhow::Result<()> {
-    ctx.defer().await?;
+    Ok(())
+}
 
-    let language_features = scrape_language_support().await?;
+async fn autocomplete_language<'a>(
+    ctx: Context<'_>,
+    partial: &'a str,
+) -> impl Iterator<Item = String> + 'a {
+    let language_features = match cached_language_support(ctx.data()).await {
+        Ok(language_features) => language_features,
+        Err(e) => {
+            tracing::error!("Failed to fetch language support for autocomplete: {e:#?}");
+            Default::default()
+        }
+    };
 
-    ctx.say(build_language_support_message(language_features))
-        .await?;
+    let partial = partial.trim().to_lowercase();
 
-    Ok(())
+    language_features
+        .iter()
+        .map(|feature| feature.language_name.trim().to_string())
+        .filter(|name| name.to_lowercase().contains(&partial))
+        .take(25)
+        .collect::<Vec<_>>()
+        .into_iter()
 }
 
-async fn scrape_language_support() -> anyhow::Result<Vec<LanguageFeature>> {
-    let response = reqwest::get("https://biomejs.dev/internals/language-support")
-        .await?
-        .text()
-        .await?;
-
-    let document = Html::parse_document(&response);
-    let table_selector = parse_selector("table")?;
+fn display_level(level: LanguageSupportLevel) -> String {
+    format!("{level} {}", level.label())
+}
 
-    let Some(first_table) = document.select(&table_selector).next() else {
-        anyhow::bail!("Failed to find first table in the document")
+/// Sends the compact overview with a button that toggles the detailed per-capability view.
+async fn send_overview(
+    ctx: Context<'_>,
+    language_features: &[LanguageFeature],
+) -> anyhow::Result<()> {
+    let ctx_id = ctx.id();
+    let toggle_button_id = format!("{ctx_id}toggle");
+
+    let build_embeds = |detailed: bool| {
+        if detailed {
+            detailed_embeds(ctx, language_features)
+        } else {
+            vec![compact_embed(ctx, language_features)]
+        }
     };
 
-    let tr_selector = parse_selector("tr")?;
-    let td_selector = parse_selector("td")?;
+    let build_components = |detailed: bool| {
+        vec![CreateActionRow::Buttons(vec![CreateButton::new(
+            &toggle_button_id,
+        )
+        .label(if detailed {
+            "Compact View"
+        } else {
+            "Detailed View"
+        })
+        .style(ButtonStyle::Secondary)])]
+    };
 
-    let mut features = Vec::new();
+    let mut reply = CreateReply::default().components(build_components(false));
 
-    for (i, element) in first_table.select(&tr_selector).enumerate() {
-        if i == 0 {
-            continue;
-        }; // ain't nobody wanna look at that header.
+    for embed in build_embeds(false) {
+        reply = reply.embed(embed);
+    }
 
-        let columns = element.select(&td_selector).collect::<Vec<_>>();
+    let reply_handle = ctx.send(reply).await?;
+
+    let mut detailed = false;
+
+    while let Some(press) = ComponentInteractionCollector::new(ctx)
+        .filter(move |press| press.data.custom_id == format!("{ctx_id}toggle"))
+        .timeout(Duration::from_secs(300))
+        .await
+    {
+        detailed = !detailed;
+
+        press
+            .create_response(
+                ctx.serenity_context(),
+                CreateInteractionResponse::UpdateMessage(
+                    CreateInteractionResponseMessage::new()
+                        .embeds(build_embeds(detailed))
+                        .components(build_components(detailed)),
+                ),
+            )
+            .await?;
+    }
 
-        if columns.len() != 4 {
-            anyhow::bail!("Encountered unexpexted HTML in the supported languages table");
-        }
+    let mut reply = CreateReply::default().components(vec![]);
 
-        let language_name = columns[0].text().collect::<String>();
-        let parsing = LanguageSupportLevel::from_str(columns[1].text().collect::<String>().trim())?;
-        let formatting =
-            LanguageSupportLevel::from_str(columns[2].text().collect::<String>().trim())?;
-        let linting = LanguageSupportLevel::from_str(columns[3].text().collect::<String>().trim())?;
-
-        features.push(LanguageFeature {
-            language_name,
-            parsing,
-            formatting,
-            linting,
-        })
+    for embed in build_embeds(detailed) {
+        reply = reply.embed(embed);
     }
 
-    Ok(features)
+    reply_handle.edit(ctx, reply).await?;
+
+    Ok(())
 }
 
-fn parse_selector(sel: &str) -> anyhow::Result<Selector> {
-    match Selector::parse(sel) {
-        Ok(s) => Ok(s),
-        Err(e) => {
-            anyhow::bail!("Error parsing selector: {e}");
-        }
+/// One inline field per support tier.
+fn compact_embed(ctx: Context<'_>, language_features: &[LanguageFeature]) -> CreateEmbed {
+    let supported = SupportedLanguages::from(language_features.to_vec());
+
+    let tiers = [
+        ("\u{2705} Full Support", supported.full),
+        ("\u{26A0}\u{FE0F} Partial Support", supported.partial),
+        ("\u{231B}\u{FE0F} Working on it", supported.wip),
+        ("\u{1F6AB} Not Yet Supported", supported.nope),
+    ];
+
+    let mut embed = default_embed(ctx.author())
+        .title("Language Support")
+        .url(LANGUAGE_SUPPORT_URL);
+
+    for (title, languages) in tiers {
+        let languages = if languages.is_empty() {
+            "None".to_string()
+        } else {
+            languages
+                .iter()
+                .map(|language| language.trim())
+                .collect::<Vec<_>>()
+                .join("\n")
+        };
+
+        embed = embed.field(title, truncate(&languages, 1024), true);
     }
+
+    embed
 }
 
-fn build_language_support_message(language_features: Vec<LanguageFeature>) -> String {
-    let titles = [
-        "## :white_check_mark: Full Support",
-        "## :warning: Partial Support",
-        "## :hourglass_flowing_sand: Working on it",
-        "## :no_entry: Not Yet Supported",
-    ];
+/// One inline field per language with the level of every capability, split over several embeds
+/// since an embed holds at most 25 fields.
+fn detailed_embeds(ctx: Context<'_>, language_features: &[LanguageFeature]) -> Vec<CreateEmbed> {
+    language_features
+        .chunks(24)
+        .enumerate()
+        .map(|(i, chunk)| {
+            let mut embed = default_embed(ctx.author());
+
+            if i == 0 {
+                embed = embed
+                    .title("Language Support")
+                    .url(LANGUAGE_SUPPORT_URL)
+                    .description(format!(
+                        "Parsing, formatting and linting support per language. {} {}, {} {}, {} {}, {} {}.",
+                        LanguageSupportLevel::Supported,
+                        LanguageSupportLevel::Supported.label(),
+                        LanguageSupportLevel::PartiallySupported,
+                        LanguageSupportLevel::PartiallySupported.label(),
+                        LanguageSupportLevel::InProgress,
+                        LanguageSupportLevel::InProgress.label(),
+                        LanguageSupportLevel::NotInProgress,
+                        LanguageSupportLevel::NotInProgress.label(),
+                    ));
+            }
 
-    let supported = SupportedLanguages::from(language_features);
-
-    let languages = format!(
-        "{}\n{}\n{}\n{}\n{}\n{}\n{}\n{}\n",
-        titles[0],
-        supported.full.join(", "),
-        titles[1],
-        supported.partial.join(", "),
-        titles[2],
-        supported.wip.join(", "),
-        titles[3],
-        supported.nope.join(", "),
-    );
-
-    format!("{languages}\nYou can find more details on [our website](https://biomejs.dev/internals/language-support/).")
+            for feature in chunk {
+                embed = embed.field(
+                    feature.language_name.trim(),
+                    format!(
+                        "{} Parse\n{} Format\n{} Lint",
+                        feature.parsing, feature.formatting, feature.linting
+                    ),
+                    true,
+                );
+            }
+
+            embed
+        })
+        .collect()
 }
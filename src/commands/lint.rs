@@ -0,0 +1,120 @@
+use poise::{CreateReply, Modal};
+
+use crate::{
+    biome_cli::{self, parse_diagnostics, Diagnostic, MAX_INPUT_SIZE},
+    commands::playground::PlaygroundLanguage,
+    lint_rules::rule_docs_url,
+    respond_error, respond_mistake,
+    util::{
+        embeds::default_embed,
+        format::{inline_code, truncate},
+        parse::{parse_code_blocks, CodeBlock},
+    },
+    Context,
+};
+
+const MAX_DIAGNOSTICS: usize = 15;
+
+#[derive(Debug, Modal)]
+#[name = "Lint with Biome"]
+struct LintModal {
+    #[name = "Code"]
+    #[placeholder = "Paste your code, optionally as a code block like ```ts"]
+    #[paragraph]
+    #[max_length = 4000]
+    code: String,
+}
+
+/// Run Biome's linter on a snippet.
+#[poise::command(slash_command, guild_only = true)]
+pub async fn lint(
+    ctx: Context<'_>,
+    #[description = "The language of the code, detected from the code block when left empty."]
+    language: Option<PlaygroundLanguage>,
+) -> anyhow::Result<()> {
+    let Some(cli) = &ctx.data().config.biome else {
+        respond_mistake!(&ctx, "Linting snippets isn't enabled on this server.");
+    };
+
+    let poise::Context::Application(app_ctx) = ctx else {
+        return Ok(());
+    };
+
+    let Some(modal) = LintModal::execute(app_ctx).await? else {
+        return Ok(());
+    };
+
+    let block = parse_code_blocks(&modal.code)
+        .into_iter()
+        .next()
+        .unwrap_or(CodeBlock {
+            language: None,
+            code: modal.code,
+        });
+
+    if block.code.trim().is_empty() || block.code.len() > MAX_INPUT_SIZE {
+        respond_mistake!(&ctx, "Provide some code of a reasonable size to lint.");
+    }
+
+    let extension = language
+        .map(PlaygroundLanguage::file_extension)
+        .or_else(|| block.file_extension())
+        .unwrap_or("js");
+
+    let output = match biome_cli::lint(cli, &block.code, extension).await {
+        Ok(output) => output,
+        Err(e) => {
+            respond_error!("Failed to run the biome linter", e, &ctx);
+        }
+    };
+
+    let diagnostics = parse_diagnostics(&format!("{}\n{}", output.stdout, output.stderr));
+
+    let embed = if diagnostics.is_empty() {
+        default_embed(ctx.author())
+            .title("\u{2705} No diagnostics")
+            .description(format!(
+                "Biome found nothing to report in your `main.{extension}`."
+            ))
+    } else {
+        default_embed(ctx.author())
+            .title(format!(
+                "Found {} diagnostic{}",
+                diagnostics.len(),
+                if diagnostics.len() == 1 { "" } else { "s" }
+            ))
+            .description(display_diagnostics(&diagnostics))
+    };
+
+    ctx.send(CreateReply::default().embed(embed)).await?;
+
+    Ok(())
+}
+
+fn display_diagnostics(diagnostics: &[Diagnostic]) -> String {
+    let mut lines = diagnostics
+        .iter()
+        .take(MAX_DIAGNOSTICS)
+        .map(|diagnostic| {
+            let category = match diagnostic.rule_name() {
+                Some(rule) => format!("[{}]({})", inline_code(rule), rule_docs_url(rule)),
+                None => inline_code(&diagnostic.category),
+            };
+
+            format!(
+                "- {} {category}: {}",
+                inline_code(&diagnostic.location),
+                truncate(&diagnostic.message, 200)
+            )
+        })
+        .collect::<Vec<_>>();
+
+    if diagnostics.len() > MAX_DIAGNOSTICS {
+        lines.push(format!(
+            "_and {} more_",
+            diagnostics.len() - MAX_DIAGNOSTICS
+        ));
+    }
+
+    truncate(&lines.join("\n"), 4000)
+}
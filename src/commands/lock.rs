@@ -0,0 +1,321 @@
+use anyhow::Context as _;
+use chrono::{DateTime, Duration, Utc};
+use poise::serenity_prelude as serenity;
+use serenity::{
+    ChannelId, CreateEmbed, CreateMessage, GuildId, Http, Mentionable, PermissionOverwrite,
+    PermissionOverwriteType, Permissions, UserId,
+};
+
+use crate::{
+    checks::is_moderator,
+    database::channel_locks::{self, ChannelLock},
+    jobs::{self, Job},
+    respond_error, respond_mistake,
+    util::{
+        format::{time, TimestampStyle},
+        parse::parse_duration,
+    },
+    Context, Data,
+};
+
+/// Stop members from writing in this channel.
+#[poise::command(
+    slash_command,
+    guild_only = true,
+    check = "is_moderator",
+    ephemeral = true
+)]
+pub async fn lock(
+    ctx: Context<'_>,
+    #[description = "Why the channel gets locked, shown in the channel."]
+    #[max_length = 500]
+    reason: String,
+    #[description = "Unlock the channel automatically after this, like `30m` or `2h`. At most 30 days."]
+    duration: Option<String>,
+) -> anyhow::Result<()> {
+    let unlock_at = match duration {
+        Some(duration) => match parse_duration(&duration) {
+            Some(duration) if duration <= Duration::days(30) => Some(Utc::now() + duration),
+            _ => {
+                respond_mistake!(
+                    &ctx,
+                    "Use a duration like `30m`, `2h` or `1d` of at most 30 days."
+                );
+            }
+        },
+        None => None,
+    };
+
+    ctx.defer_ephemeral().await?;
+
+    let data = ctx.data();
+    let guild_id = ctx.guild_id().unwrap_or(data.config.bot.guild_id);
+    let channel_id = ctx.channel_id();
+
+    let (previous_allow, previous_deny) = match lock_channel(ctx.http(), channel_id).await {
+        Ok(previous) => previous,
+        Err(e) => {
+            respond_error!("Failed to lock the channel", e, &ctx);
+        }
+    };
+
+    let lock_id = channel_locks::insert(
+        &data.db_pool,
+        channel_id.get() as i64,
+        ctx.author().id.get() as i64,
+        &reason,
+        unlock_at,
+        previous_allow.bits() as i64,
+        previous_deny.bits() as i64,
+    )
+    .await?;
+
+    if let Some(unlock_at) = unlock_at {
+        jobs::enqueue_at(&data.db_pool, &Job::UnlockChannel { lock_id }, unlock_at).await?;
+    }
+
+    let mut embed = CreateEmbed::new()
+        .color(0xFFA500)
+        .title("\u{1F512} Channel locked")
+        .description(&reason);
+
+    if let Some(unlock_at) = unlock_at {
+        embed = embed.field("Unlocks", time(unlock_at, TimestampStyle::Relative), true);
+    }
+
+    channel_id
+        .send_message(ctx, CreateMessage::new().embed(embed))
+        .await?;
+
+    log_to_modlog(
+        ctx.http(),
+        data,
+        guild_id,
+        "Channel locked",
+        channel_id,
+        Some(ctx.author().id),
+        Some(&reason),
+        unlock_at,
+    )
+    .await?;
+
+    tracing::info!("{} locked {channel_id}.", ctx.author().name);
+    ctx.say("Locked the channel.").await?;
+
+    Ok(())
+}
+
+/// Let members write in this channel again.
+#[poise::command(
+    slash_command,
+    guild_only = true,
+    check = "is_moderator",
+    ephemeral = true
+)]
+pub async fn unlock(ctx: Context<'_>) -> anyhow::Result<()> {
+    let data = ctx.data();
+    let channel_id = ctx.channel_id();
+
+    let lock = match channel_locks::get_by_channel(&data.db_pool, channel_id.get() as i64).await {
+        Ok(Some(lock)) => lock,
+        Ok(None) => {
+            respond_mistake!(&ctx, "This channel isn't locked.");
+        }
+        Err(e) => {
+            respond_error!("Failed to unlock the channel", e, &ctx);
+        }
+    };
+
+    ctx.defer_ephemeral().await?;
+
+    // the lock is only forgotten once the channel is writable again, so a failed unlock can be
+    // retried.
+    let guild_id = match unlock_channel(ctx.http(), &lock).await {
+        Ok(guild_id) => guild_id,
+        Err(e) => {
+            respond_error!("Failed to unlock the channel", e, &ctx);
+        }
+    };
+
+    channel_locks::delete_by_channel(&data.db_pool, lock.channel_id).await?;
+
+    announce_unlock(
+        ctx.http(),
+        data,
+        guild_id,
+        channel_id,
+        Some(ctx.author().id),
+    )
+    .await?;
+
+    tracing::info!("{} unlocked {channel_id}.", ctx.author().name);
+    ctx.say("Unlocked the channel.").await?;
+
+    Ok(())
+}
+
+/// Unlocks the channel once the duration of the lock passed, queued as a job when the channel is
+/// locked. Skipped when the channel was unlocked or locked again in between.
+pub async fn auto_unlock(http: &Http, data: &Data, lock_id: i64) -> anyhow::Result<()> {
+    let Some(lock) = channel_locks::get(&data.db_pool, lock_id).await? else {
+        return Ok(());
+    };
+
+    let channel_id = ChannelId::new(lock.channel_id as u64);
+
+    let guild_id = unlock_channel(http, &lock).await?;
+    channel_locks::delete_by_channel(&data.db_pool, lock.channel_id).await?;
+
+    announce_unlock(http, data, guild_id, channel_id, None).await
+}
+
+/// The permissions that decide whether members can write.
+fn writing() -> Permissions {
+    Permissions::SEND_MESSAGES | Permissions::SEND_MESSAGES_IN_THREADS
+}
+
+/// Denies `@everyone` to write in the channel, keeping the other permissions of the overwrite.
+/// Returns the writing permissions the overwrite allowed and denied before.
+async fn lock_channel(
+    http: &Http,
+    channel_id: ChannelId,
+) -> anyhow::Result<(Permissions, Permissions)> {
+    let (guild_id, mut allow, mut deny) = everyone_overwrite(http, channel_id).await?;
+    let previous = (allow & writing(), deny & writing());
+
+    allow.remove(writing());
+    deny.insert(writing());
+
+    channel_id
+        .create_permission(
+            http,
+            PermissionOverwrite {
+                allow,
+                deny,
+                kind: PermissionOverwriteType::Role(guild_id.everyone_role()),
+            },
+        )
+        .await?;
+
+    Ok(previous)
+}
+
+/// Gives the `@everyone` overwrite back the writing permissions it had before the lock, keeping
+/// the other permissions. Returns the server of the channel.
+async fn unlock_channel(http: &Http, lock: &ChannelLock) -> anyhow::Result<GuildId> {
+    let channel_id = ChannelId::new(lock.channel_id as u64);
+    let (guild_id, mut allow, mut deny) = everyone_overwrite(http, channel_id).await?;
+
+    allow.remove(writing());
+    deny.remove(writing());
+    allow.insert(Permissions::from_bits_truncate(lock.previous_allow as u64) & writing());
+    deny.insert(Permissions::from_bits_truncate(lock.previous_deny as u64) & writing());
+
+    channel_id
+        .create_permission(
+            http,
+            PermissionOverwrite {
+                allow,
+                deny,
+                kind: PermissionOverwriteType::Role(guild_id.everyone_role()),
+            },
+        )
+        .await?;
+
+    Ok(guild_id)
+}
+
+/// The server of the channel and the allowed and denied permissions of its `@everyone`
+/// overwrite.
+async fn everyone_overwrite(
+    http: &Http,
+    channel_id: ChannelId,
+) -> anyhow::Result<(GuildId, Permissions, Permissions)> {
+    let channel = channel_id
+        .to_channel(http)
+        .await?
+        .guild()
+        .context("The channel isn't in a server")?;
+
+    let everyone = PermissionOverwriteType::Role(channel.guild_id.everyone_role());
+    let (allow, deny) = channel
+        .permission_overwrites
+        .iter()
+        .find(|overwrite| overwrite.kind == everyone)
+        .map_or((Permissions::empty(), Permissions::empty()), |overwrite| {
+            (overwrite.allow, overwrite.deny)
+        });
+
+    Ok((channel.guild_id, allow, deny))
+}
+
+/// Tells the channel and the moderators that the channel is unlocked, `None` for automatic
+/// unlocks.
+async fn announce_unlock(
+    http: &Http,
+    data: &Data,
+    guild_id: GuildId,
+    channel_id: ChannelId,
+    moderator: Option<UserId>,
+) -> anyhow::Result<()> {
+    let embed = CreateEmbed::new()
+        .color(0x00FF00)
+        .title("\u{1F513} Channel unlocked")
+        .description("You can write in this channel again.");
+
+    channel_id
+        .send_message(http, CreateMessage::new().embed(embed))
+        .await?;
+
+    log_to_modlog(
+        http,
+        data,
+        guild_id,
+        "Channel unlocked",
+        channel_id,
+        moderator,
+        None,
+        None,
+    )
+    .await
+}
+
+async fn log_to_modlog(
+    http: &Http,
+    data: &Data,
+    guild_id: GuildId,
+    title: &str,
+    channel_id: ChannelId,
+    moderator: Option<UserId>,
+    reason: Option<&str>,
+    unlock_at: Option<DateTime<Utc>>,
+) -> anyhow::Result<()> {
+    let Some(modlog) = data.log_channel(guild_id) else {
+        return Ok(());
+    };
+
+    let moderator = moderator.map_or_else(
+        || "Automatic".to_string(),
+        |moderator| moderator.mention().to_string(),
+    );
+
+    let mut embed = CreateEmbed::new()
+        .color(0xFFA500)
+        .title(title)
+        .field("Channel", channel_id.mention().to_string(), true)
+        .field("Moderator", moderator, true);
+
+    if let Some(unlock_at) = unlock_at {
+        embed = embed.field("Unlocks", time(unlock_at, TimestampStyle::Relative), true);
+    }
+
+    if let Some(reason) = reason {
+        embed = embed.field("Reason", reason, false);
+    }
+
+    modlog
+        .send_message(http, CreateMessage::new().embed(embed))
+        .await?;
+
+    Ok(())
+}
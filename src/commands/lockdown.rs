@@ -0,0 +1,71 @@
+use crate::{checks::is_moderator, raid, respond_error, respond_mistake, Context};
+
+/// Pause invites and raise the verification level during a raid.
+#[poise::command(
+    slash_command,
+    guild_only = true,
+    check = "is_moderator",
+    subcommands("on", "off"),
+    subcommand_required
+)]
+pub async fn lockdown(_: Context<'_>) -> anyhow::Result<()> {
+    Ok(())
+}
+
+/// Start a lockdown.
+#[poise::command(
+    slash_command,
+    guild_only = true,
+    check = "is_moderator",
+    ephemeral = true
+)]
+pub async fn on(
+    ctx: Context<'_>,
+    #[description = "Why the server gets locked down."]
+    #[max_length = 500]
+    reason: String,
+) -> anyhow::Result<()> {
+    ctx.defer_ephemeral().await?;
+
+    match raid::start_lockdown(ctx.http(), ctx.data(), &reason, Some(ctx.author().id)).await {
+        Ok(true) => {}
+        Ok(false) => {
+            respond_mistake!(&ctx, "The server already is in lockdown.");
+        }
+        Err(e) => {
+            respond_error!("Failed to start the lockdown", e, &ctx);
+        }
+    }
+
+    tracing::info!("{} started a lockdown.", ctx.author().name);
+    ctx.say("The server is in lockdown, lift it with `/lockdown off`.")
+        .await?;
+
+    Ok(())
+}
+
+/// Lift the lockdown.
+#[poise::command(
+    slash_command,
+    guild_only = true,
+    check = "is_moderator",
+    ephemeral = true
+)]
+pub async fn off(ctx: Context<'_>) -> anyhow::Result<()> {
+    ctx.defer_ephemeral().await?;
+
+    match raid::end_lockdown(ctx.http(), ctx.data(), ctx.author().id).await {
+        Ok(true) => {}
+        Ok(false) => {
+            respond_mistake!(&ctx, "The server isn't in lockdown.");
+        }
+        Err(e) => {
+            respond_error!("Failed to lift the lockdown", e, &ctx);
+        }
+    }
+
+    tracing::info!("{} lifted the lockdown.", ctx.author().name);
+    ctx.say("The lockdown is lifted.").await?;
+
+    Ok(())
+}
@@ -0,0 +1,298 @@
+use std::{collections::BTreeMap, sync::OnceLock};
+
+use poise::{serenity_prelude as serenity, CreateReply, Modal};
+use serde::Deserialize;
+use serde_json::{json, Map, Value};
+use serenity::{Attachment, CreateAttachment};
+
+use crate::{
+    respond_error, respond_mistake,
+    util::{
+        embeds::default_embed,
+        format::{inline_code, truncate},
+        parse::strip_json_comments,
+    },
+    Context,
+};
+
+/// Configurations larger than this are certainly not an `.eslintrc`.
+const MAX_CONFIG_SIZE: u32 = 256 * 1024;
+
+/// The ESLint rules biome has an equivalent for. Maintained by hand, see the `sources` of the rule docs.
+const ESLINT_RULES: &str = include_str!("../assets/eslint_rules.json");
+
+#[derive(Debug, Deserialize)]
+struct RuleMapping {
+    eslint: String,
+    /// `group/ruleName` of the biome rule.
+    biome: String,
+    /// The biome rule is only inspired by the ESLint rule and behaves differently.
+    inspired: bool,
+}
+
+fn rule_mappings() -> &'static [RuleMapping] {
+    static MAPPINGS: OnceLock<Vec<RuleMapping>> = OnceLock::new();
+
+    MAPPINGS.get_or_init(|| {
+        serde_json::from_str(ESLINT_RULES).expect("Failed to parse the bundled ESLint rule mapping")
+    })
+}
+
+#[derive(Debug, Modal)]
+#[name = "Migrate from ESLint"]
+struct EslintModal {
+    #[name = ".eslintrc.json"]
+    #[placeholder = "Paste the contents of your .eslintrc.json here."]
+    #[paragraph]
+    #[max_length = 4000]
+    config: String,
+}
+
+/// Help migrating to Biome.
+#[poise::command(
+    slash_command,
+    guild_only = true,
+    subcommands("eslint"),
+    subcommand_required
+)]
+pub async fn migrate(_: Context<'_>) -> anyhow::Result<()> {
+    Ok(())
+}
+
+/// Map the rules of an .eslintrc to their Biome equivalents.
+#[poise::command(slash_command, guild_only = true)]
+pub async fn eslint(
+    ctx: Context<'_>,
+    #[description = "Your .eslintrc.json, opens a text field to paste it into when left empty."]
+    file: Option<Attachment>,
+) -> anyhow::Result<()> {
+    let config = match file {
+        Some(file) => {
+            ctx.defer().await?;
+
+            if file.size > MAX_CONFIG_SIZE {
+                respond_mistake!(
+                    &ctx,
+                    "That file is too large to be an ESLint configuration."
+                );
+            }
+
+            match file.download().await.map(String::from_utf8) {
+                Ok(Ok(config)) => config,
+                Ok(Err(_)) => {
+                    respond_mistake!(&ctx, "That file isn't valid UTF-8.");
+                }
+                Err(e) => {
+                    respond_error!("Failed to download the attachment", e, &ctx);
+                }
+            }
+        }
+        None => {
+            let poise::Context::Application(app_ctx) = ctx else {
+                return Ok(());
+            };
+
+            let Some(modal) = EslintModal::execute(app_ctx).await? else {
+                return Ok(());
+            };
+
+            modal.config
+        }
+    };
+
+    let Ok(config) = serde_json::from_str::<Value>(&strip_json_comments(&config)) else {
+        respond_mistake!(&ctx, "That isn't a valid JSON ESLint configuration.");
+    };
+
+    let Some(rules) = config.get("rules").and_then(Value::as_object) else {
+        respond_mistake!(
+            &ctx,
+            "That ESLint configuration doesn't configure any rules."
+        );
+    };
+
+    let migration = migrate_rules(rules);
+    let starter = serde_json::to_string_pretty(&starter_config(&migration))?;
+
+    let embed = default_embed(ctx.author())
+        .title("ESLint Migration")
+        .description(
+            "Biome can also do this for you with `biome migrate eslint --write`. \
+             The attached `biome.json` is a starting point with the mapped rules.",
+        )
+        .field(
+            format!("Supported ({})", migration.supported.len()),
+            display_mapped(&migration.supported),
+            false,
+        )
+        .field(
+            format!("Behaves differently ({})", migration.inspired.len()),
+            display_mapped(&migration.inspired),
+            false,
+        )
+        .field(
+            format!("Unsupported ({})", migration.unsupported.len()),
+            display_list(migration.unsupported.iter().map(inline_code)),
+            false,
+        );
+
+    ctx.send(
+        CreateReply::default()
+            .embed(embed)
+            .attachment(CreateAttachment::bytes(starter, "biome.json")),
+    )
+    .await?;
+
+    Ok(())
+}
+
+/// An ESLint rule that maps onto a biome rule.
+#[derive(Debug, PartialEq)]
+struct MappedRule {
+    eslint: String,
+    biome: String,
+    /// One of `error`, `warn` or `off`.
+    severity: &'static str,
+}
+
+#[derive(Debug, Default, PartialEq)]
+struct EslintMigration {
+    supported: Vec<MappedRule>,
+    inspired: Vec<MappedRule>,
+    unsupported: Vec<String>,
+}
+
+fn migrate_rules(rules: &Map<String, Value>) -> EslintMigration {
+    let mut migration = EslintMigration::default();
+
+    for (name, setting) in rules {
+        let mapping = rule_mappings()
+            .iter()
+            .find(|mapping| &mapping.eslint == name);
+
+        let Some(mapping) = mapping else {
+            migration.unsupported.push(name.clone());
+            continue;
+        };
+
+        let rule = MappedRule {
+            eslint: name.clone(),
+            biome: mapping.biome.clone(),
+            severity: severity(setting),
+        };
+
+        if mapping.inspired {
+            migration.inspired.push(rule);
+        } else {
+            migration.supported.push(rule);
+        }
+    }
+
+    migration
+}
+
+/// ESLint severities are `"error"`, `2`, or an array with the severity first.
+fn severity(setting: &Value) -> &'static str {
+    let setting = match setting {
+        Value::Array(values) => values.first().unwrap_or(&Value::Null),
+        setting => setting,
+    };
+
+    match setting {
+        Value::String(severity) if severity == "error" => "error",
+        Value::String(severity) if severity == "warn" => "warn",
+        Value::Number(severity) if severity.as_u64() == Some(2) => "error",
+        Value::Number(severity) if severity.as_u64() == Some(1) => "warn",
+        _ => "off",
+    }
+}
+
+/// A `biome.json` keeping the recommended rules and applying the severities of the mapped ones.
+fn starter_config(migration: &EslintMigration) -> Value {
+    let mut groups: BTreeMap<&str, Map<String, Value>> = BTreeMap::new();
+
+    for rule in migration.supported.iter().chain(&migration.inspired) {
+        let Some((group, name)) = rule.biome.split_once('/') else {
+            continue;
+        };
+
+        groups
+            .entry(group)
+            .or_default()
+            .insert(name.to_string(), json!(rule.severity));
+    }
+
+    let mut rules = Map::new();
+    rules.insert("recommended".to_string(), json!(true));
+
+    for (group, group_rules) in groups {
+        rules.insert(group.to_string(), Value::Object(group_rules));
+    }
+
+    json!({
+        "linter": {
+            "enabled": true,
+            "rules": rules,
+        },
+    })
+}
+
+fn display_mapped(rules: &[MappedRule]) -> String {
+    display_list(rules.iter().map(|rule| {
+        format!(
+            "{} \u{2192} {}",
+            inline_code(&rule.eslint),
+            inline_code(&rule.biome)
+        )
+    }))
+}
+
+fn display_list(items: impl Iterator<Item = String>) -> String {
+    let list = items.collect::<Vec<_>>().join("\n");
+
+    if list.is_empty() {
+        "None".to_string()
+    } else {
+        truncate(&list, 1024)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn maps_rules_and_severities() {
+        let rules = json!({
+            "no-var": "error",
+            "eqeqeq": ["warn", "always"],
+            "no-debugger": 0,
+            "import/order": "error"
+        });
+
+        let migration = migrate_rules(rules.as_object().unwrap());
+
+        assert_eq!(
+            migration.supported,
+            vec![
+                MappedRule {
+                    eslint: "no-debugger".to_string(),
+                    biome: "suspicious/noDebugger".to_string(),
+                    severity: "off",
+                },
+                MappedRule {
+                    eslint: "no-var".to_string(),
+                    biome: "style/noVar".to_string(),
+                    severity: "error",
+                },
+            ]
+        );
+        assert_eq!(migration.inspired[0].severity, "warn");
+        assert_eq!(migration.unsupported, vec!["import/order"]);
+
+        assert_eq!(
+            starter_config(&migration)["linter"]["rules"]["suspicious"],
+            json!({ "noDebugger": "off", "noDoubleEquals": "warn" })
+        );
+    }
+}
@@ -0,0 +1,91 @@
+use poise::CreateReply;
+
+use crate::{
+    github_api::Milestone,
+    respond_error, respond_mistake,
+    util::{
+        embeds::default_embed,
+        format::{progress_bar, time, TimestampStyle},
+    },
+    Context,
+};
+
+const PROGRESS_BAR_WIDTH: usize = 12;
+
+/// Show the progress of the open milestones.
+#[poise::command(slash_command, guild_only = true)]
+pub async fn milestone(
+    ctx: Context<'_>,
+    #[description = "Only show the milestone with this title."]
+    #[autocomplete = "autocomplete_milestone"]
+    title: Option<String>,
+) -> anyhow::Result<()> {
+    ctx.defer().await?;
+
+    let milestones = match ctx.data().github.open_milestones().await {
+        Ok(milestones) => milestones,
+        Err(e) => {
+            respond_error!("Failed to fetch milestones from github", e, &ctx);
+        }
+    };
+
+    let milestones = match &title {
+        Some(title) => milestones
+            .into_iter()
+            .filter(|milestone| milestone.title.eq_ignore_ascii_case(title))
+            .collect::<Vec<_>>(),
+        None => milestones,
+    };
+
+    if milestones.is_empty() {
+        respond_mistake!(&ctx, "There are no open milestones matching your query.");
+    }
+
+    let mut embed = default_embed(ctx.author()).title("Open Milestones");
+
+    // embeds can hold at most 25 fields.
+    for milestone in milestones.iter().take(25) {
+        embed = embed.field(
+            format!(
+                "{} ({} open, {} closed)",
+                milestone.title, milestone.open_issues, milestone.closed_issues
+            ),
+            display_milestone(milestone),
+            false,
+        );
+    }
+
+    ctx.send(CreateReply::default().embed(embed)).await?;
+
+    Ok(())
+}
+
+async fn autocomplete_milestone<'a>(
+    ctx: Context<'_>,
+    partial: &'a str,
+) -> impl Iterator<Item = String> + 'a {
+    let milestones = ctx
+        .data()
+        .github
+        .open_milestones()
+        .await
+        .unwrap_or_default();
+
+    milestones
+        .into_iter()
+        .map(|milestone| milestone.title)
+        .filter(move |title| title.to_lowercase().contains(&partial.to_lowercase()))
+        .take(25)
+}
+
+fn display_milestone(milestone: &Milestone) -> String {
+    let mut lines = vec![progress_bar(milestone.progress(), PROGRESS_BAR_WIDTH)];
+
+    if let Some(due_on) = milestone.due_on {
+        lines.push(format!("Due {}", time(due_on, TimestampStyle::Relative)));
+    }
+
+    lines.push(format!("[View on GitHub]({})", milestone.html_url));
+
+    lines.join("\n")
+}
@@ -1,2 +1,44 @@
+pub mod announce;
+pub mod automod_rules;
+pub mod blocklist;
+pub mod bot_config;
+pub mod canned;
+pub mod case;
+pub mod changelog;
+pub mod docs;
 pub mod embed;
+pub mod format;
+pub mod gfi;
+pub mod github_link;
 pub mod languages;
+pub mod lint;
+pub mod lock;
+pub mod lockdown;
+pub mod migrate;
+pub mod milestone;
+pub mod moderation;
+pub mod mydata;
+pub mod playground;
+pub mod poll;
+pub mod pr;
+pub mod prettier_map;
+pub mod purge;
+pub mod reactionrole;
+pub mod release;
+pub mod remind;
+pub mod report;
+pub mod rolemenu;
+pub mod rule;
+pub mod rules;
+pub mod schema;
+pub mod slowmode;
+pub mod solved;
+pub mod stats;
+pub mod suggest;
+pub mod tag;
+pub mod triage;
+pub mod twib;
+pub mod validate_config;
+pub mod verification;
+pub mod version;
+pub mod whois;
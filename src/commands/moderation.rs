@@ -0,0 +1,365 @@
+use chrono::Duration;
+use poise::{serenity_prelude as serenity, CreateReply};
+use serenity::{CreateEmbed, CreateMessage, EditMember, GuildId, Mentionable, User, UserId};
+
+use crate::{
+    checks::is_moderator,
+    database::mod_cases::{self, ModCase},
+    respond_error, respond_mistake,
+    util::{
+        embeds::EmbedColor,
+        format::{display_duration, display_time},
+        parse::parse_duration,
+    },
+    Context,
+};
+
+/// Discord doesn't allow longer timeouts.
+const MAX_TIMEOUT_DAYS: i64 = 28;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ModAction {
+    Warn,
+    Timeout,
+    Kick,
+    Ban,
+}
+
+impl ModAction {
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Warn => "warn",
+            Self::Timeout => "timeout",
+            Self::Kick => "kick",
+            Self::Ban => "ban",
+        }
+    }
+
+    fn from_str(s: &str) -> Option<Self> {
+        match s {
+            "warn" => Some(Self::Warn),
+            "timeout" => Some(Self::Timeout),
+            "kick" => Some(Self::Kick),
+            "ban" => Some(Self::Ban),
+            _ => None,
+        }
+    }
+
+    fn label(self) -> &'static str {
+        match self {
+            Self::Warn => "Warning",
+            Self::Timeout => "Timeout",
+            Self::Kick => "Kick",
+            Self::Ban => "Ban",
+        }
+    }
+
+    fn past_tense(self) -> &'static str {
+        match self {
+            Self::Warn => "warned",
+            Self::Timeout => "timed out",
+            Self::Kick => "kicked",
+            Self::Ban => "banned",
+        }
+    }
+
+    fn color(self) -> u32 {
+        match self {
+            Self::Warn => 0xFFD700,
+            Self::Timeout => 0xFFA500,
+            Self::Kick => 0xFF7F50,
+            Self::Ban => 0xFF0000,
+        }
+    }
+}
+
+/// Warn a member. The warning is recorded as a case.
+#[poise::command(
+    slash_command,
+    guild_only = true,
+    check = "is_moderator",
+    ephemeral = true
+)]
+pub async fn warn(
+    ctx: Context<'_>,
+    #[description = "The member to warn."] user: User,
+    #[description = "Why the member gets warned, they can read this."]
+    #[max_length = 500]
+    reason: String,
+) -> anyhow::Result<()> {
+    if !can_moderate(ctx, &user).await? {
+        return Ok(());
+    }
+
+    notify_target(ctx, &user, ModAction::Warn, &reason, None).await;
+    record_case(ctx, &user, ModAction::Warn, &reason, None).await
+}
+
+/// Time out a member so they can't write or talk.
+#[poise::command(
+    slash_command,
+    guild_only = true,
+    check = "is_moderator",
+    ephemeral = true
+)]
+pub async fn timeout(
+    ctx: Context<'_>,
+    #[description = "The member to time out."] user: User,
+    #[description = "How long the timeout lasts, like `30m`, `12h` or `7d`. At most 28 days."]
+    duration: String,
+    #[description = "Why the member gets timed out, they can read this."]
+    #[max_length = 500]
+    reason: String,
+) -> anyhow::Result<()> {
+    let duration = match parse_duration(&duration) {
+        Some(duration) if duration <= Duration::days(MAX_TIMEOUT_DAYS) => duration,
+        _ => {
+            respond_mistake!(
+                &ctx,
+                "Use a duration like `30m`, `12h` or `7d` of at most 28 days."
+            );
+        }
+    };
+
+    if !can_moderate(ctx, &user).await? {
+        return Ok(());
+    }
+
+    if let Err(e) = guild_id(ctx)
+        .edit_member(
+            ctx,
+            user.id,
+            EditMember::new()
+                .disable_communication_until_datetime((chrono::Utc::now() + duration).into())
+                .audit_log_reason(&reason),
+        )
+        .await
+    {
+        respond_error!("Failed to time out the member", e, &ctx);
+    }
+
+    notify_target(ctx, &user, ModAction::Timeout, &reason, Some(duration)).await;
+    record_case(ctx, &user, ModAction::Timeout, &reason, Some(duration)).await
+}
+
+/// Kick a member from the server.
+#[poise::command(
+    slash_command,
+    guild_only = true,
+    check = "is_moderator",
+    ephemeral = true
+)]
+pub async fn kick(
+    ctx: Context<'_>,
+    #[description = "The member to kick."] user: User,
+    #[description = "Why the member gets kicked, they can read this."]
+    #[max_length = 500]
+    reason: String,
+) -> anyhow::Result<()> {
+    if !can_moderate(ctx, &user).await? {
+        return Ok(());
+    }
+
+    // the member can't be messaged anymore once they left the server.
+    notify_target(ctx, &user, ModAction::Kick, &reason, None).await;
+
+    if let Err(e) = guild_id(ctx)
+        .kick_with_reason(ctx.http(), user.id, &reason)
+        .await
+    {
+        respond_error!("Failed to kick the member", e, &ctx);
+    }
+
+    record_case(ctx, &user, ModAction::Kick, &reason, None).await
+}
+
+/// Ban a user from the server.
+#[poise::command(
+    slash_command,
+    guild_only = true,
+    check = "is_moderator",
+    ephemeral = true
+)]
+pub async fn ban(
+    ctx: Context<'_>,
+    #[description = "The user to ban."] user: User,
+    #[description = "Why the user gets banned, they can read this."]
+    #[max_length = 500]
+    reason: String,
+    #[description = "Delete the messages of the user from the last days, none by default."]
+    #[min = 0]
+    #[max = 7]
+    delete_message_days: Option<u8>,
+) -> anyhow::Result<()> {
+    if !can_moderate(ctx, &user).await? {
+        return Ok(());
+    }
+
+    notify_target(ctx, &user, ModAction::Ban, &reason, None).await;
+
+    if let Err(e) = guild_id(ctx)
+        .ban_with_reason(
+            ctx.http(),
+            user.id,
+            delete_message_days.unwrap_or_default(),
+            &reason,
+        )
+        .await
+    {
+        respond_error!("Failed to ban the user", e, &ctx);
+    }
+
+    record_case(ctx, &user, ModAction::Ban, &reason, None).await
+}
+
+/// The server the command was used in, the commands are guild only.
+fn guild_id(ctx: Context<'_>) -> GuildId {
+    ctx.guild_id().unwrap_or(ctx.data().config.bot.guild_id)
+}
+
+/// Moderators can't act on themselves, bots or other staff. Tells the moderator why not.
+async fn can_moderate(ctx: Context<'_>, user: &User) -> anyhow::Result<bool> {
+    let reason = if user.id == ctx.author().id {
+        Some("You can't moderate yourself.")
+    } else if user.bot {
+        Some("Bots can't be moderated with these commands.")
+    } else {
+        let guild_id = guild_id(ctx);
+        let member = guild_id.member(ctx, user.id).await.ok();
+
+        ctx.data()
+            .guild(guild_id)
+            .zip(member)
+            .is_some_and(|(guild, member)| guild.roles.is_staff(&member.roles))
+            .then_some("Staff members can't be moderated with these commands.")
+    };
+
+    if let Some(reason) = reason {
+        ctx.say(reason).await?;
+    }
+
+    Ok(reason.is_none())
+}
+
+/// Tells the user what happened. Users can disable direct messages, so this may fail.
+async fn notify_target(
+    ctx: Context<'_>,
+    user: &User,
+    action: ModAction,
+    reason: &str,
+    duration: Option<Duration>,
+) {
+    let guild_name = ctx
+        .partial_guild()
+        .await
+        .map_or_else(|| "the server".to_string(), |guild| guild.name);
+
+    let mut content = format!(
+        "You were {} in {guild_name}.\n**Reason:** {reason}",
+        action.past_tense()
+    );
+
+    if let Some(duration) = duration {
+        content.push_str(&format!("\n**Duration:** {}", display_duration(duration)));
+    }
+
+    if let Err(e) = user
+        .direct_message(ctx, CreateMessage::new().content(content))
+        .await
+    {
+        tracing::warn!(
+            "Failed to notify {} about a {}: {e}",
+            user.name,
+            action.as_str()
+        );
+    }
+}
+
+/// Stores the case, logs it to the modlog channel and confirms it to the moderator.
+async fn record_case(
+    ctx: Context<'_>,
+    user: &User,
+    action: ModAction,
+    reason: &str,
+    duration: Option<Duration>,
+) -> anyhow::Result<()> {
+    let case = mod_cases::insert(
+        &ctx.data().db_pool,
+        guild_id(ctx).get() as i64,
+        action.as_str(),
+        user.id.get() as i64,
+        ctx.author().id.get() as i64,
+        reason,
+        duration.map(|duration| duration.num_seconds()),
+    )
+    .await?;
+
+    tracing::info!(
+        "{} {} {} (case #{}).",
+        ctx.author().name,
+        action.past_tense(),
+        user.name,
+        case.id
+    );
+
+    if let Some(modlog) = ctx
+        .guild_id()
+        .and_then(|guild_id| ctx.data().log_channel(guild_id))
+    {
+        modlog
+            .send_message(ctx, CreateMessage::new().embed(case_embed(&case)))
+            .await?;
+    }
+
+    ctx.send(
+        CreateReply::default()
+            .content(format!(
+                "{} {} (case #{}).",
+                user.mention(),
+                action.past_tense(),
+                case.id
+            ))
+            .ephemeral(true),
+    )
+    .await?;
+
+    Ok(())
+}
+
+pub fn case_embed(case: &ModCase) -> CreateEmbed {
+    let action = ModAction::from_str(&case.action);
+
+    let mut embed = CreateEmbed::new()
+        .color(action.map_or(EmbedColor::Biome as u32, ModAction::color))
+        .title(format!(
+            "Case #{} \u{00B7} {}",
+            case.id,
+            action.map_or(case.action.as_str(), ModAction::label)
+        ))
+        .field(
+            "User",
+            format!(
+                "{} (`{}`)",
+                UserId::new(case.target_id as u64).mention(),
+                case.target_id
+            ),
+            true,
+        )
+        .field(
+            "Moderator",
+            UserId::new(case.moderator_id as u64).mention().to_string(),
+            true,
+        );
+
+    if let Some(duration_secs) = case.duration_secs {
+        embed = embed.field(
+            "Duration",
+            display_duration(Duration::seconds(duration_secs)),
+            true,
+        );
+    }
+
+    embed
+        .field("Reason", &case.reason, false)
+        .field("Date", display_time(case.created_at), false)
+}
@@ -0,0 +1,544 @@
+use poise::{serenity_prelude as serenity, CreateReply};
+use serde::Serialize;
+use serenity::{CreateAttachment, UserId};
+
+use crate::{
+    database::{
+        announcements, blocked_domains, canned_responses, channel_locks, embed_snapshots,
+        embed_templates, github_links, lockdowns, message_logs, mod_cases, polls, reminders,
+        reports, settings, solved_threads, suggestions, tags, triage_actions, verifications,
+    },
+    Context, Data,
+};
+
+/// Everything the bot has stored about a single user.
+///
+/// Every feature that persists per-user data adds its section here so the export stays complete.
+#[derive(Debug, Serialize)]
+struct UserDataExport {
+    user_id: UserId,
+    exported_at: chrono::DateTime<chrono::Utc>,
+    embed_versions_authored: Vec<EmbedVersionExport>,
+    triage_actions: Vec<TriageActionExport>,
+    tags_created: Vec<TagExport>,
+    canned_responses_written: Vec<CannedResponseExport>,
+    solved_help_posts: Vec<SolvedThreadExport>,
+    suggestions: Vec<SuggestionExport>,
+    suggestion_votes: Vec<SuggestionVoteExport>,
+    suggestions_decided: Vec<SuggestionDecisionExport>,
+    polls_created: Vec<PollExport>,
+    poll_votes: Vec<PollVoteExport>,
+    pending_reminders: Vec<ReminderExport>,
+    moderation_cases: Vec<ModCaseExport>,
+    logged_messages: Vec<MessageLogExport>,
+    verification: Option<VerificationExport>,
+    blocked_domains_added: Vec<BlockedDomainExport>,
+    lockdowns: Vec<LockdownExport>,
+    channel_locks: Vec<ChannelLockExport>,
+    reports_filed: Vec<ReportExport>,
+    reports_handled: Vec<HandledReportExport>,
+    github_account: Option<GithubLinkExport>,
+    embed_templates_saved: Vec<EmbedTemplateExport>,
+    announcements_scheduled: Vec<AnnouncementExport>,
+    settings_changed: Vec<SettingExport>,
+}
+
+#[derive(Debug, Serialize)]
+struct EmbedVersionExport {
+    message_id: i64,
+    version: i32,
+    created_at: chrono::DateTime<chrono::Utc>,
+}
+
+#[derive(Debug, Serialize)]
+struct TriageActionExport {
+    issue_number: i64,
+    action: String,
+    detail: String,
+    created_at: chrono::DateTime<chrono::Utc>,
+}
+
+#[derive(Debug, Serialize)]
+struct TagExport {
+    name: String,
+    content: String,
+    created_at: chrono::DateTime<chrono::Utc>,
+}
+
+#[derive(Debug, Serialize)]
+struct CannedResponseExport {
+    name: String,
+    content: String,
+    updated_at: chrono::DateTime<chrono::Utc>,
+}
+
+#[derive(Debug, Serialize)]
+struct SolvedThreadExport {
+    thread_id: i64,
+    marked_by: i64,
+    solver_id: Option<i64>,
+    summary: Option<String>,
+    solved_at: chrono::DateTime<chrono::Utc>,
+}
+
+#[derive(Debug, Serialize)]
+struct SuggestionExport {
+    id: i64,
+    content: String,
+    status: String,
+    created_at: chrono::DateTime<chrono::Utc>,
+}
+
+#[derive(Debug, Serialize)]
+struct SuggestionVoteExport {
+    suggestion_id: i64,
+    upvote: bool,
+    voted_at: chrono::DateTime<chrono::Utc>,
+}
+
+#[derive(Debug, Serialize)]
+struct SuggestionDecisionExport {
+    suggestion_id: i64,
+    status: String,
+}
+
+#[derive(Debug, Serialize)]
+struct PollExport {
+    id: i64,
+    question: String,
+    options: Vec<String>,
+    closes_at: chrono::DateTime<chrono::Utc>,
+    created_at: chrono::DateTime<chrono::Utc>,
+}
+
+#[derive(Debug, Serialize)]
+struct PollVoteExport {
+    poll_id: i64,
+    option_index: i16,
+    voted_at: chrono::DateTime<chrono::Utc>,
+}
+
+#[derive(Debug, Serialize)]
+struct ReminderExport {
+    id: i64,
+    content: String,
+    remind_at: chrono::DateTime<chrono::Utc>,
+    created_at: chrono::DateTime<chrono::Utc>,
+}
+
+#[derive(Debug, Serialize)]
+struct ModCaseExport {
+    case_number: i64,
+    action: String,
+    target_id: i64,
+    /// Only set for the cases the user handled, moderators of cases against them stay private.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    moderator_id: Option<i64>,
+    reason: String,
+    duration_secs: Option<i64>,
+    created_at: chrono::DateTime<chrono::Utc>,
+}
+
+#[derive(Debug, Serialize)]
+struct MessageLogExport {
+    message_id: i64,
+    channel_id: i64,
+    action: String,
+    content_before: Option<String>,
+    content_after: Option<String>,
+    created_at: chrono::DateTime<chrono::Utc>,
+}
+
+#[derive(Debug, Serialize)]
+struct VerificationExport {
+    verified_at: chrono::DateTime<chrono::Utc>,
+}
+
+#[derive(Debug, Serialize)]
+struct GithubLinkExport {
+    github_id: i64,
+    github_login: String,
+    linked_at: chrono::DateTime<chrono::Utc>,
+}
+
+#[derive(Debug, Serialize)]
+struct EmbedTemplateExport {
+    name: String,
+    embed: serde_json::Value,
+    updated_at: chrono::DateTime<chrono::Utc>,
+}
+
+#[derive(Debug, Serialize)]
+struct AnnouncementExport {
+    id: i64,
+    target: String,
+    payload: serde_json::Value,
+    next_run_at: chrono::DateTime<chrono::Utc>,
+    recurrence: Option<String>,
+    created_at: chrono::DateTime<chrono::Utc>,
+}
+
+#[derive(Debug, Serialize)]
+struct BlockedDomainExport {
+    domain: String,
+    reason: Option<String>,
+    created_at: chrono::DateTime<chrono::Utc>,
+}
+
+#[derive(Debug, Serialize)]
+struct LockdownExport {
+    reason: String,
+    started_by: Option<i64>,
+    started_at: chrono::DateTime<chrono::Utc>,
+    ended_by: Option<i64>,
+    ended_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+#[derive(Debug, Serialize)]
+struct ChannelLockExport {
+    channel_id: i64,
+    reason: String,
+    unlock_at: Option<chrono::DateTime<chrono::Utc>>,
+    created_at: chrono::DateTime<chrono::Utc>,
+}
+
+#[derive(Debug, Serialize)]
+struct ReportExport {
+    id: i64,
+    message_id: i64,
+    channel_id: i64,
+    reason: Option<String>,
+    anonymous: bool,
+    status: String,
+    created_at: chrono::DateTime<chrono::Utc>,
+}
+
+#[derive(Debug, Serialize)]
+struct HandledReportExport {
+    id: i64,
+    message_id: i64,
+    channel_id: i64,
+    status: String,
+    handled_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+#[derive(Debug, Serialize)]
+struct SettingExport {
+    key: String,
+    value: serde_json::Value,
+    updated_at: chrono::DateTime<chrono::Utc>,
+}
+
+impl UserDataExport {
+    async fn collect(user_id: UserId, data: &Data) -> anyhow::Result<Self> {
+        let db_user_id = user_id.get() as i64;
+
+        let embed_versions_authored = embed_snapshots::authored_by(&data.db_pool, db_user_id)
+            .await?
+            .into_iter()
+            .map(|snapshot| EmbedVersionExport {
+                message_id: snapshot.message_id,
+                version: snapshot.version,
+                created_at: snapshot.created_at,
+            })
+            .collect();
+
+        let triage_actions = triage_actions::by_actor(&data.db_pool, db_user_id)
+            .await?
+            .into_iter()
+            .map(|action| TriageActionExport {
+                issue_number: action.issue_number,
+                action: action.action,
+                detail: action.detail,
+                created_at: action.created_at,
+            })
+            .collect();
+
+        let tags_created = tags::authored_by(&data.db_pool, db_user_id)
+            .await?
+            .into_iter()
+            .map(|tag| TagExport {
+                name: tag.name,
+                content: tag.content,
+                created_at: tag.created_at,
+            })
+            .collect();
+
+        let canned_responses_written = canned_responses::authored_by(&data.db_pool, db_user_id)
+            .await?
+            .into_iter()
+            .map(|response| CannedResponseExport {
+                name: response.name,
+                content: response.content,
+                updated_at: response.updated_at,
+            })
+            .collect();
+
+        let solved_help_posts = solved_threads::involving(&data.db_pool, db_user_id)
+            .await?
+            .into_iter()
+            .map(|thread| SolvedThreadExport {
+                thread_id: thread.thread_id,
+                marked_by: thread.marked_by,
+                solver_id: thread.solver_id,
+                summary: thread.summary,
+                solved_at: thread.solved_at,
+            })
+            .collect();
+
+        let suggestions = suggestions::authored_by(&data.db_pool, db_user_id)
+            .await?
+            .into_iter()
+            .map(|suggestion| SuggestionExport {
+                id: suggestion.id,
+                content: suggestion.content,
+                status: suggestion.status,
+                created_at: suggestion.created_at,
+            })
+            .collect();
+
+        let suggestion_votes = suggestions::votes_by(&data.db_pool, db_user_id)
+            .await?
+            .into_iter()
+            .map(|vote| SuggestionVoteExport {
+                suggestion_id: vote.suggestion_id,
+                upvote: vote.upvote,
+                voted_at: vote.voted_at,
+            })
+            .collect();
+
+        let suggestions_decided = suggestions::decided_by(&data.db_pool, db_user_id)
+            .await?
+            .into_iter()
+            .map(|suggestion| SuggestionDecisionExport {
+                suggestion_id: suggestion.id,
+                status: suggestion.status,
+            })
+            .collect();
+
+        let polls_created = polls::authored_by(&data.db_pool, db_user_id)
+            .await?
+            .into_iter()
+            .map(|poll| PollExport {
+                id: poll.id,
+                question: poll.question,
+                options: poll.options,
+                closes_at: poll.closes_at,
+                created_at: poll.created_at,
+            })
+            .collect();
+
+        let poll_votes = polls::votes_by(&data.db_pool, db_user_id)
+            .await?
+            .into_iter()
+            .map(|vote| PollVoteExport {
+                poll_id: vote.poll_id,
+                option_index: vote.option_index,
+                voted_at: vote.voted_at,
+            })
+            .collect();
+
+        let pending_reminders = reminders::by_user(&data.db_pool, db_user_id)
+            .await?
+            .into_iter()
+            .map(|reminder| ReminderExport {
+                id: reminder.id,
+                content: reminder.content,
+                remind_at: reminder.remind_at,
+                created_at: reminder.created_at,
+            })
+            .collect();
+
+        let moderation_cases = mod_cases::involving(&data.db_pool, db_user_id)
+            .await?
+            .into_iter()
+            .map(|case| ModCaseExport {
+                case_number: case.id,
+                action: case.action,
+                target_id: case.target_id,
+                moderator_id: (case.moderator_id == db_user_id).then_some(case.moderator_id),
+                reason: case.reason,
+                duration_secs: case.duration_secs,
+                created_at: case.created_at,
+            })
+            .collect();
+
+        let logged_messages = message_logs::by_author(&data.db_pool, db_user_id)
+            .await?
+            .into_iter()
+            .map(|log| MessageLogExport {
+                message_id: log.message_id,
+                channel_id: log.channel_id,
+                action: log.action,
+                content_before: log.content_before,
+                content_after: log.content_after,
+                created_at: log.created_at,
+            })
+            .collect();
+
+        let verification =
+            verifications::get(&data.db_pool, db_user_id)
+                .await?
+                .map(|verification| VerificationExport {
+                    verified_at: verification.verified_at,
+                });
+
+        let blocked_domains_added = blocked_domains::added_by(&data.db_pool, db_user_id)
+            .await?
+            .into_iter()
+            .map(|blocked| BlockedDomainExport {
+                domain: blocked.domain,
+                reason: blocked.reason,
+                created_at: blocked.created_at,
+            })
+            .collect();
+
+        let lockdowns = lockdowns::involving(&data.db_pool, db_user_id)
+            .await?
+            .into_iter()
+            .map(|lockdown| LockdownExport {
+                reason: lockdown.reason,
+                started_by: lockdown.started_by,
+                started_at: lockdown.started_at,
+                ended_by: lockdown.ended_by,
+                ended_at: lockdown.ended_at,
+            })
+            .collect();
+
+        let channel_locks = channel_locks::locked_by(&data.db_pool, db_user_id)
+            .await?
+            .into_iter()
+            .map(|lock| ChannelLockExport {
+                channel_id: lock.channel_id,
+                reason: lock.reason,
+                unlock_at: lock.unlock_at,
+                created_at: lock.created_at,
+            })
+            .collect();
+
+        let reports_filed = reports::by_reporter(&data.db_pool, db_user_id)
+            .await?
+            .into_iter()
+            .map(|report| ReportExport {
+                id: report.id,
+                message_id: report.message_id,
+                channel_id: report.channel_id,
+                reason: report.reason,
+                anonymous: report.anonymous,
+                status: report.status,
+                created_at: report.created_at,
+            })
+            .collect();
+
+        let reports_handled = reports::handled_by(&data.db_pool, db_user_id)
+            .await?
+            .into_iter()
+            .map(|report| HandledReportExport {
+                id: report.id,
+                message_id: report.message_id,
+                channel_id: report.channel_id,
+                status: report.status,
+                handled_at: report.handled_at,
+            })
+            .collect();
+
+        let github_account = github_links::get(&data.db_pool, db_user_id)
+            .await?
+            .map(|link| GithubLinkExport {
+                github_id: link.github_id,
+                github_login: link.github_login,
+                linked_at: link.linked_at,
+            });
+
+        let embed_templates_saved = embed_templates::authored_by(&data.db_pool, db_user_id)
+            .await?
+            .into_iter()
+            .map(|template| EmbedTemplateExport {
+                name: template.name,
+                embed: template.embed,
+                updated_at: template.updated_at,
+            })
+            .collect();
+
+        let announcements_scheduled = announcements::authored_by(&data.db_pool, db_user_id)
+            .await?
+            .into_iter()
+            .map(|announcement| AnnouncementExport {
+                id: announcement.id,
+                target: announcement.target,
+                payload: announcement.payload,
+                next_run_at: announcement.next_run_at,
+                recurrence: announcement.recurrence,
+                created_at: announcement.created_at,
+            })
+            .collect();
+
+        let settings_changed = settings::updated_by(&data.db_pool, db_user_id)
+            .await?
+            .into_iter()
+            .map(|setting| SettingExport {
+                key: setting.key,
+                value: setting.value,
+                updated_at: setting.updated_at,
+            })
+            .collect();
+
+        Ok(Self {
+            user_id,
+            exported_at: chrono::Utc::now(),
+            embed_versions_authored,
+            triage_actions,
+            tags_created,
+            canned_responses_written,
+            solved_help_posts,
+            suggestions,
+            suggestion_votes,
+            suggestions_decided,
+            polls_created,
+            poll_votes,
+            pending_reminders,
+            moderation_cases,
+            logged_messages,
+            verification,
+            blocked_domains_added,
+            lockdowns,
+            channel_locks,
+            reports_filed,
+            reports_handled,
+            github_account,
+            embed_templates_saved,
+            announcements_scheduled,
+            settings_changed,
+        })
+    }
+}
+
+/// Subcommands to manage the data the bot stores about you.
+#[poise::command(slash_command, subcommands("export"), subcommand_required)]
+pub async fn mydata(_: Context<'_>) -> anyhow::Result<()> {
+    Ok(())
+}
+
+/// Get a copy of everything the bot has stored about you.
+#[poise::command(slash_command, ephemeral = true)]
+pub async fn export(ctx: Context<'_>) -> anyhow::Result<()> {
+    ctx.defer_ephemeral().await?;
+
+    let user_id = ctx.author().id;
+    let export = UserDataExport::collect(user_id, ctx.data()).await?;
+
+    let attachment = CreateAttachment::bytes(
+        serde_json::to_vec_pretty(&export)?,
+        format!("biomebot-data-{user_id}.json"),
+    );
+
+    ctx.send(
+        CreateReply::default()
+            .content("Here is everything the bot has stored about you.")
+            .attachment(attachment)
+            .ephemeral(true),
+    )
+    .await?;
+
+    Ok(())
+}
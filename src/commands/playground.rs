@@ -0,0 +1,135 @@
+use poise::CreateReply;
+use serde_json::json;
+
+use crate::{
+    respond_error, respond_mistake,
+    util::{
+        embeds::default_embed,
+        parse::{parse_code_blocks, parse_message_link, CodeBlock},
+    },
+    Context,
+};
+
+const PLAYGROUND_URL: &str = "https://biomejs.dev/playground/";
+/// Links longer than this don't fit into an embed description.
+const MAX_URL_LENGTH: usize = 4000;
+
+#[derive(Debug, Clone, Copy, poise::ChoiceParameter)]
+pub enum PlaygroundLanguage {
+    JavaScript,
+    #[name = "JSX"]
+    Jsx,
+    TypeScript,
+    #[name = "TSX"]
+    Tsx,
+    #[name = "JSON"]
+    Json,
+    #[name = "CSS"]
+    Css,
+    GraphQL,
+}
+
+impl PlaygroundLanguage {
+    pub fn file_extension(self) -> &'static str {
+        match self {
+            Self::JavaScript => "js",
+            Self::Jsx => "jsx",
+            Self::TypeScript => "ts",
+            Self::Tsx => "tsx",
+            Self::Json => "json",
+            Self::Css => "css",
+            Self::GraphQL => "graphql",
+        }
+    }
+}
+
+/// Turn a snippet into a link to the Biome playground.
+#[poise::command(slash_command, guild_only = true)]
+pub async fn playground(
+    ctx: Context<'_>,
+    #[description = "The code to open in the playground."] code: Option<String>,
+    #[description = "Link to or ID of a message with a code block to use instead."] message: Option<
+        String,
+    >,
+    #[description = "The language of the code, detected from the code block when left empty."]
+    language: Option<PlaygroundLanguage>,
+) -> anyhow::Result<()> {
+    let block = match (code, message) {
+        (Some(code), None) => parse_code_blocks(&code)
+            .into_iter()
+            .next()
+            .unwrap_or(CodeBlock {
+                language: None,
+                code,
+            }),
+        (None, Some(message)) => {
+            let Some(link) = parse_message_link(&message) else {
+                respond_mistake!(&ctx, "That is not a valid message link or ID.");
+            };
+
+            let channel_id = link.channel_id.unwrap_or(ctx.channel_id());
+
+            let message = match channel_id.message(&ctx, link.message_id).await {
+                Ok(message) => message,
+                Err(e) => {
+                    respond_error!("Failed to fetch the referenced message", e, &ctx);
+                }
+            };
+
+            let Some(block) = parse_code_blocks(&message.content).into_iter().next() else {
+                respond_mistake!(&ctx, "That message doesn't contain a code block.");
+            };
+
+            block
+        }
+        _ => {
+            respond_mistake!(
+                &ctx,
+                "Provide either some code or a message with a code block."
+            );
+        }
+    };
+
+    if block.code.trim().is_empty() {
+        respond_mistake!(&ctx, "There is no code to open in the playground.");
+    }
+
+    let extension = language
+        .map(PlaygroundLanguage::file_extension)
+        .or_else(|| block.file_extension())
+        .unwrap_or("js");
+
+    let url = playground_url(&block.code, extension);
+
+    if url.len() > MAX_URL_LENGTH {
+        respond_mistake!(&ctx, "That code is too long to share as a playground link.");
+    }
+
+    ctx.send(
+        CreateReply::default().embed(
+            default_embed(ctx.author())
+                .title("Biome Playground")
+                .description(format!(
+                    "[Open the snippet in the playground]({url}) ({} lines of `main.{extension}`)",
+                    block.code.lines().count()
+                )),
+        ),
+    )
+    .await?;
+
+    Ok(())
+}
+
+/// The playground reads its state from the url hash, compressed with lz-string.
+fn playground_url(code: &str, extension: &str) -> String {
+    let state = json!({
+        "files": {
+            format!("main.{extension}"): code,
+        },
+    });
+
+    format!(
+        "{PLAYGROUND_URL}#{}",
+        lz_str::compress_to_encoded_uri_component(&state.to_string())
+    )
+}
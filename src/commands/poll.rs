@@ -0,0 +1,301 @@
+use chrono::{Duration, Utc};
+use poise::{serenity_prelude as serenity, CreateReply};
+use serenity::{
+    ButtonStyle, ComponentInteraction, CreateActionRow, CreateButton, CreateEmbed,
+    CreateEmbedFooter, CreateInteractionResponse, CreateInteractionResponseMessage, Mentionable,
+    UserId,
+};
+
+use crate::{
+    database::polls::{self, Poll},
+    respond_error, respond_mistake,
+    util::{
+        embeds::EmbedColor,
+        format::{progress_bar, time, TimestampStyle},
+        parse::parse_duration,
+    },
+    Context, Data,
+};
+
+/// Prefix of the custom ids of the poll buttons, followed by the poll id and the option index.
+const BUTTON_PREFIX: &str = "poll";
+const MAX_OPTIONS: usize = 10;
+/// Options are used as button labels, which can't be longer than this.
+const MAX_OPTION_LENGTH: usize = 80;
+const PROGRESS_BAR_WIDTH: usize = 12;
+/// Embed titles can't be longer than this.
+const MAX_TITLE_LENGTH: usize = 256;
+
+/// Start a poll that members vote on with buttons.
+#[poise::command(slash_command, guild_only = true)]
+pub async fn poll(
+    ctx: Context<'_>,
+    #[description = "What to vote on."]
+    #[max_length = 256]
+    question: String,
+    #[description = "Between 2 and 10 options, separated by `;`."] options: String,
+    #[description = "How long the poll stays open, like `30m`, `12h` or `3d`. Defaults to one day."]
+    duration: Option<String>,
+) -> anyhow::Result<()> {
+    let Some(options) = parse_options(&options) else {
+        respond_mistake!(
+            &ctx,
+            "Provide between 2 and 10 different options separated by `;`, each at most 80 characters long."
+        );
+    };
+
+    let duration = match duration {
+        Some(duration) => match parse_duration(&duration) {
+            Some(duration) if duration <= Duration::weeks(1) => duration,
+            _ => {
+                respond_mistake!(
+                    &ctx,
+                    "Use a duration like `30m`, `12h` or `3d` of at most one week."
+                );
+            }
+        },
+        None => Duration::days(1),
+    };
+
+    ctx.defer().await?;
+
+    let pool = &ctx.data().db_pool;
+    let closes_at = Utc::now() + duration;
+
+    let id = match polls::insert(
+        pool,
+        ctx.author().id.get() as i64,
+        &question,
+        &options,
+        closes_at,
+    )
+    .await
+    {
+        Ok(id) => id,
+        Err(e) => {
+            respond_error!("Failed to save the poll", e, &ctx);
+        }
+    };
+
+    let reply = ctx
+        .send(
+            CreateReply::default()
+                .embed(poll_embed(id, &question, &options, ctx.author().id).field(
+                    "Closes",
+                    time(closes_at, TimestampStyle::Relative),
+                    true,
+                ))
+                .components(poll_components(id, &options)),
+        )
+        .await?;
+
+    let message = reply.message().await?;
+    polls::set_message(
+        pool,
+        id,
+        message.channel_id.get() as i64,
+        message.id.get() as i64,
+    )
+    .await?;
+
+    Ok(())
+}
+
+/// Handles presses of the poll buttons, which keep working across restarts.
+pub async fn handle_component(
+    component: &ComponentInteraction,
+    ctx: &serenity::Context,
+    data: &Data,
+) -> anyhow::Result<()> {
+    let Some((poll_id, option_index)) = parse_custom_id(&component.data.custom_id) else {
+        return Ok(());
+    };
+
+    let Some(poll) = polls::get(&data.db_pool, poll_id).await? else {
+        return respond_ephemeral(component, ctx, "This poll doesn't exist anymore.").await;
+    };
+
+    // the poll might not have been closed by the task yet.
+    if poll.closed || poll.closes_at <= Utc::now() {
+        return respond_ephemeral(component, ctx, "This poll is closed.").await;
+    }
+
+    let Some(option) = poll.options.get(option_index) else {
+        return Ok(());
+    };
+
+    polls::vote(
+        &data.db_pool,
+        poll_id,
+        component.user.id.get() as i64,
+        option_index as i16,
+    )
+    .await?;
+
+    respond_ephemeral(
+        component,
+        ctx,
+        &format!("You voted for **{option}**. Press another option to change your vote."),
+    )
+    .await
+}
+
+/// The embed with the results, posted when the poll closes.
+pub fn results_embed(poll: &Poll, counts: &[i64]) -> CreateEmbed {
+    let total = counts.iter().sum::<i64>();
+    let most_votes = counts.iter().copied().max().unwrap_or_default();
+
+    let results = poll
+        .options
+        .iter()
+        .zip(counts)
+        .map(|(option, &count)| {
+            let fraction = if total == 0 {
+                0.0
+            } else {
+                count as f64 / total as f64
+            };
+            let option = if count > 0 && count == most_votes {
+                format!("**{option}** \u{1F3C6}")
+            } else {
+                option.clone()
+            };
+
+            format!(
+                "{option} ({count})\n{}",
+                progress_bar(fraction, PROGRESS_BAR_WIDTH)
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n\n");
+
+    CreateEmbed::new()
+        .color(EmbedColor::Biome)
+        .title(
+            format!("Results: {}", poll.question)
+                .chars()
+                .take(MAX_TITLE_LENGTH)
+                .collect::<String>(),
+        )
+        .description(results)
+        .field("Votes", total.to_string(), true)
+        .field(
+            "Started by",
+            UserId::new(poll.author_id as u64).mention().to_string(),
+            true,
+        )
+}
+
+/// The embed of the open poll, without the field showing when it closes.
+fn poll_embed(id: i64, question: &str, options: &[String], author: UserId) -> CreateEmbed {
+    let options = options
+        .iter()
+        .enumerate()
+        .map(|(index, option)| format!("**{}.** {option}", index + 1))
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    CreateEmbed::new()
+        .color(EmbedColor::Biome)
+        .title(question)
+        .description(options)
+        .field("Started by", author.mention().to_string(), true)
+        .footer(CreateEmbedFooter::new(format!("Poll #{id}")))
+}
+
+fn poll_components(id: i64, options: &[String]) -> Vec<CreateActionRow> {
+    let buttons = options
+        .iter()
+        .enumerate()
+        .map(|(index, option)| {
+            CreateButton::new(format!("{BUTTON_PREFIX}:{id}:{index}"))
+                .label(option)
+                .style(ButtonStyle::Secondary)
+        })
+        .collect::<Vec<_>>();
+
+    // an action row holds at most five buttons.
+    buttons
+        .chunks(5)
+        .map(|row| CreateActionRow::Buttons(row.to_vec()))
+        .collect()
+}
+
+async fn respond_ephemeral(
+    component: &ComponentInteraction,
+    ctx: &serenity::Context,
+    content: &str,
+) -> anyhow::Result<()> {
+    component
+        .create_response(
+            ctx,
+            CreateInteractionResponse::Message(
+                CreateInteractionResponseMessage::new()
+                    .content(content)
+                    .ephemeral(true),
+            ),
+        )
+        .await?;
+
+    Ok(())
+}
+
+/// Parses the custom id of a poll button into the poll id and option index.
+fn parse_custom_id(custom_id: &str) -> Option<(i64, usize)> {
+    let mut parts = custom_id.split(':');
+
+    if parts.next()? != BUTTON_PREFIX {
+        return None;
+    }
+
+    let poll_id = parts.next()?.parse().ok()?;
+    let option_index = parts.next()?.parse().ok()?;
+
+    Some((poll_id, option_index))
+}
+
+/// Splits the options at `;`, `None` unless there are 2 to 10 different options that fit on a
+/// button.
+fn parse_options(input: &str) -> Option<Vec<String>> {
+    let mut options = Vec::<String>::new();
+
+    for option in input.split(';').map(str::trim).filter(|o| !o.is_empty()) {
+        if option.chars().count() > MAX_OPTION_LENGTH
+            || options.iter().any(|o| o.eq_ignore_ascii_case(option))
+        {
+            return None;
+        }
+
+        options.push(option.to_string());
+    }
+
+    (2..=MAX_OPTIONS)
+        .contains(&options.len())
+        .then_some(options)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_options() {
+        assert_eq!(
+            parse_options(" Tabs ; Spaces;; "),
+            Some(vec!["Tabs".to_string(), "Spaces".to_string()])
+        );
+
+        assert_eq!(parse_options("Tabs"), None);
+        assert_eq!(parse_options("Tabs; tabs"), None);
+        let eleven = (1..=11).map(|i| i.to_string()).collect::<Vec<_>>();
+        assert_eq!(parse_options(&eleven.join(";")), None);
+        assert_eq!(parse_options(&format!("a; {}", "b".repeat(81))), None);
+    }
+
+    #[test]
+    fn parses_custom_ids() {
+        assert_eq!(parse_custom_id("poll:7:3"), Some((7, 3)));
+        assert_eq!(parse_custom_id("suggestion:up:7"), None);
+        assert_eq!(parse_custom_id("poll:7:first"), None);
+    }
+}
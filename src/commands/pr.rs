@@ -0,0 +1,179 @@
+use poise::{serenity_prelude as serenity, CreateReply};
+use serenity::CreateEmbedAuthor;
+
+use crate::{
+    github_api::{CheckRuns, PullRequest},
+    respond_error, respond_mistake,
+    util::{embeds::default_embed, format::inline_code},
+    Context,
+};
+
+/// Show the status of a pull request on the Biome repository.
+#[poise::command(slash_command, guild_only = true)]
+pub async fn pr(
+    ctx: Context<'_>,
+    #[description = "The number of the pull request."]
+    #[min = 1]
+    number: u64,
+) -> anyhow::Result<()> {
+    ctx.defer().await?;
+
+    let github = &ctx.data().github;
+
+    let pull_request = match github.pull_request(number).await {
+        Ok(Some(pull_request)) => pull_request,
+        Ok(None) => {
+            respond_mistake!(&ctx, "There is no pull request with that number.");
+        }
+        Err(e) => {
+            respond_error!("Failed to fetch the pull request from github", e, &ctx);
+        }
+    };
+
+    let check_runs = match github.check_runs(&pull_request.head.sha).await {
+        Ok(check_runs) => Some(check_runs),
+        Err(e) => {
+            tracing::warn!("Failed to fetch check runs for PR #{number}: {e}");
+            None
+        }
+    };
+
+    let mut embed = default_embed(ctx.author())
+        .title(format!("#{} {}", pull_request.number, pull_request.title))
+        .url(&pull_request.html_url)
+        .color(state_color(&pull_request))
+        .field("State", display_state(&pull_request), true)
+        .field("Mergeable", display_mergeability(&pull_request), true)
+        .field("Changed Files", display_changes(&pull_request), true)
+        .field(
+            "Checks",
+            check_runs
+                .as_ref()
+                .map_or("Unavailable".to_string(), display_check_summary),
+            false,
+        )
+        .field(
+            "Requested Reviewers",
+            display_reviewers(&pull_request),
+            false,
+        )
+        .field(
+            "Branches",
+            format!(
+                "{} \u{2190} {}",
+                inline_code(&pull_request.base.name),
+                inline_code(&pull_request.head.name)
+            ),
+            false,
+        );
+
+    if let Some(user) = &pull_request.user {
+        let author = CreateEmbedAuthor::new(&user.login).url(&user.html_url);
+
+        embed = match &user.avatar_url {
+            Some(avatar_url) => embed.author(author.icon_url(avatar_url)),
+            None => embed.author(author),
+        };
+    }
+
+    ctx.send(CreateReply::default().embed(embed)).await?;
+
+    Ok(())
+}
+
+fn state_color(pull_request: &PullRequest) -> u32 {
+    if pull_request.is_merged() {
+        0x8957E5
+    } else if pull_request.state == "closed" {
+        0xDA3633
+    } else if pull_request.is_draft() {
+        0x6E7681
+    } else {
+        0x238636
+    }
+}
+
+fn display_state(pull_request: &PullRequest) -> String {
+    if pull_request.is_merged() {
+        "Merged".to_string()
+    } else if pull_request.state == "closed" {
+        "Closed".to_string()
+    } else if pull_request.is_draft() {
+        "Open (Draft)".to_string()
+    } else {
+        "Open".to_string()
+    }
+}
+
+fn display_mergeability(pull_request: &PullRequest) -> String {
+    if pull_request.is_merged() || pull_request.state == "closed" {
+        return "-".to_string();
+    }
+
+    let state = pull_request.mergeable_state.as_deref().unwrap_or("unknown");
+
+    match (pull_request.mergeable, state) {
+        (None, _) | (_, "unknown") => "\u{231B}\u{FE0F} Still computing".to_string(),
+        (Some(false), _) | (_, "dirty") => "\u{274C} Has conflicts".to_string(),
+        (_, "clean") => "\u{2705} Ready to merge".to_string(),
+        (_, "blocked") => "\u{1F6AB} Blocked".to_string(),
+        (_, "behind") => "\u{26A0}\u{FE0F} Behind base branch".to_string(),
+        (_, "unstable") => "\u{26A0}\u{FE0F} Failing checks".to_string(),
+        (_, other) => other.to_string(),
+    }
+}
+
+fn display_changes(pull_request: &PullRequest) -> String {
+    format!(
+        "{} (+{} -{})",
+        pull_request.changed_files.unwrap_or_default(),
+        pull_request.additions.unwrap_or_default(),
+        pull_request.deletions.unwrap_or_default()
+    )
+}
+
+fn display_check_summary(check_runs: &CheckRuns) -> String {
+    if check_runs.total_count == 0 {
+        return "No checks reported.".to_string();
+    }
+
+    let mut passed = 0;
+    let mut failed = 0;
+    let mut pending = 0;
+    let mut skipped = 0;
+
+    for run in &check_runs.check_runs {
+        match (run.status.as_str(), run.conclusion.as_deref()) {
+            ("completed", Some("success")) => passed += 1,
+            ("completed", Some("skipped" | "neutral")) => skipped += 1,
+            ("completed", _) => failed += 1,
+            _ => pending += 1,
+        }
+    }
+
+    format!(
+        "\u{2705} {passed} passed \u{00B7} \u{274C} {failed} failed \u{00B7} \u{231B}\u{FE0F} {pending} pending \u{00B7} \u{23ED}\u{FE0F} {skipped} skipped"
+    )
+}
+
+fn display_reviewers(pull_request: &PullRequest) -> String {
+    let users = pull_request
+        .requested_reviewers
+        .iter()
+        .flatten()
+        .map(|user| user.login.clone());
+
+    let teams = pull_request
+        .requested_teams
+        .iter()
+        .flatten()
+        .map(|team| format!("@{}", team.slug));
+
+    let reviewers = users.chain(teams).collect::<Vec<_>>();
+
+    if reviewers.is_empty() {
+        "None".to_string()
+    } else {
+        reviewers.join(", ")
+    }
+}
@@ -0,0 +1,111 @@
+use std::sync::OnceLock;
+
+use poise::CreateReply;
+use serde::Deserialize;
+
+use crate::{
+    config_schema::docs_url,
+    respond_mistake,
+    util::{embeds::default_embed, format::inline_code},
+    Context,
+};
+
+/// The Prettier options and how they translate to biome. Maintained by hand.
+const PRETTIER_OPTIONS: &str = include_str!("../assets/prettier_options.json");
+
+#[derive(Debug, Deserialize)]
+struct PrettierOption {
+    prettier: String,
+    /// The dotted key of the equivalent `biome.json` setting.
+    biome: Option<String>,
+    cli: Option<String>,
+    notes: String,
+}
+
+fn prettier_options() -> &'static [PrettierOption] {
+    static OPTIONS: OnceLock<Vec<PrettierOption>> = OnceLock::new();
+
+    OPTIONS.get_or_init(|| {
+        serde_json::from_str(PRETTIER_OPTIONS)
+            .expect("Failed to parse the bundled Prettier option mapping")
+    })
+}
+
+/// Explain the Biome equivalent of a Prettier option.
+#[poise::command(slash_command, guild_only = true, rename = "prettier-map")]
+pub async fn prettier_map(
+    ctx: Context<'_>,
+    #[description = "The Prettier option, e.g. printWidth."]
+    #[autocomplete = "autocomplete_option"]
+    option: String,
+) -> anyhow::Result<()> {
+    let option = option.trim();
+
+    let Some(mapping) = prettier_options()
+        .iter()
+        .find(|mapping| mapping.prettier.eq_ignore_ascii_case(option))
+    else {
+        respond_mistake!(&ctx, "There is no Prettier option with that name.");
+    };
+
+    let mut embed = default_embed(ctx.author())
+        .title(format!("Prettier's {}", mapping.prettier))
+        .description(&mapping.notes)
+        .field(
+            "Biome Setting",
+            mapping
+                .biome
+                .as_deref()
+                .map_or("No equivalent".to_string(), inline_code),
+            true,
+        )
+        .field(
+            "CLI Flag",
+            mapping
+                .cli
+                .as_deref()
+                .map_or("None".to_string(), inline_code),
+            true,
+        );
+
+    if let Some(biome) = &mapping.biome {
+        embed = embed.url(docs_url(biome));
+    }
+
+    ctx.send(CreateReply::default().embed(embed)).await?;
+
+    Ok(())
+}
+
+async fn autocomplete_option<'a>(
+    _: Context<'_>,
+    partial: &'a str,
+) -> impl Iterator<Item = String> + 'a {
+    let partial = partial.trim().to_lowercase();
+
+    prettier_options()
+        .iter()
+        .filter(move |mapping| mapping.prettier.to_lowercase().contains(&partial))
+        .map(|mapping| mapping.prettier.clone())
+        .take(25)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bundled_options_are_unique() {
+        let options = prettier_options();
+
+        for (i, option) in options.iter().enumerate() {
+            assert!(
+                options[..i]
+                    .iter()
+                    .all(|other| other.prettier != option.prettier),
+                "{} is listed twice",
+                option.prettier
+            );
+        }
+    }
+}
@@ -0,0 +1,178 @@
+use chrono::{DateTime, Duration, Utc};
+use poise::serenity_prelude as serenity;
+use serenity::{CreateEmbed, CreateMessage, GetMessages, Mentionable, Message, MessageId, User};
+
+use crate::{checks::is_moderator, respond_error, respond_mistake, Context};
+
+/// How far back the command looks for matching messages.
+const MAX_SCANNED_MESSAGES: usize = 1000;
+/// Discord refuses to bulk delete older messages, they have to be deleted one by one.
+const BULK_DELETE_MAX_AGE_DAYS: i64 = 14;
+
+/// Delete the most recent messages in this channel.
+#[poise::command(
+    slash_command,
+    guild_only = true,
+    check = "is_moderator",
+    ephemeral = true
+)]
+pub async fn purge(
+    ctx: Context<'_>,
+    #[description = "How many matching messages to delete."]
+    #[min = 1]
+    #[max = 100]
+    count: usize,
+    #[description = "Only delete messages of this user."] user: Option<User>,
+    #[description = "Only delete messages containing this text, ignoring case."]
+    #[max_length = 200]
+    contains: Option<String>,
+) -> anyhow::Result<()> {
+    ctx.defer_ephemeral().await?;
+
+    let channel_id = ctx.channel_id();
+    let contains = contains.map(|contains| contains.to_lowercase());
+
+    let mut matching = Vec::<Message>::new();
+    let mut scanned = 0;
+    let mut before = None::<MessageId>;
+
+    while matching.len() < count && scanned < MAX_SCANNED_MESSAGES {
+        let mut request = GetMessages::new().limit(100);
+        if let Some(before) = before {
+            request = request.before(before);
+        }
+
+        let messages = match channel_id.messages(ctx, request).await {
+            Ok(messages) => messages,
+            Err(e) => {
+                respond_error!("Failed to fetch the messages", e, &ctx);
+            }
+        };
+
+        let Some(oldest) = messages.last() else {
+            break;
+        };
+
+        before = Some(oldest.id);
+        scanned += messages.len();
+
+        matching.extend(
+            messages
+                .into_iter()
+                .filter(|message| {
+                    !message.pinned
+                        && user
+                            .as_ref()
+                            .map_or(true, |user| message.author.id == user.id)
+                        && contains.as_ref().map_or(true, |contains| {
+                            message.content.to_lowercase().contains(contains)
+                        })
+                })
+                .take(count - matching.len()),
+        );
+    }
+
+    if matching.is_empty() {
+        respond_mistake!(&ctx, "There are no matching messages.");
+    }
+
+    let now = Utc::now();
+    let (bulk, single): (Vec<_>, Vec<_>) = matching
+        .iter()
+        .map(|message| message.id)
+        .partition(|message_id| is_bulk_deletable(created_at(*message_id), now));
+
+    // bulk deletion needs at least two messages.
+    let result = match bulk.as_slice() {
+        [] => Ok(()),
+        [message_id] => channel_id.delete_message(ctx, *message_id).await,
+        message_ids => channel_id.delete_messages(ctx, message_ids).await,
+    };
+
+    if let Err(e) = result {
+        respond_error!("Failed to delete the messages", e, &ctx);
+    }
+
+    // the bulk deletion already happened, so the rest is attempted and logged either way.
+    let mut failed = 0;
+
+    for message_id in &single {
+        if let Err(e) = channel_id.delete_message(ctx, *message_id).await {
+            tracing::warn!("Failed to delete the older message {message_id}: {e}");
+            failed += 1;
+        }
+    }
+
+    let deleted = matching.len() - failed;
+
+    tracing::info!(
+        "{} purged {deleted} messages in {channel_id}, {failed} failed.",
+        ctx.author().name
+    );
+
+    if let Some(modlog) = ctx
+        .guild_id()
+        .and_then(|guild_id| ctx.data().log_channel(guild_id))
+    {
+        let mut embed = CreateEmbed::new()
+            .color(0xFF7F50)
+            .title("Messages purged")
+            .field("Moderator", ctx.author().mention().to_string(), true)
+            .field("Channel", channel_id.mention().to_string(), true)
+            .field("Deleted", deleted.to_string(), true);
+
+        if let Some(user) = &user {
+            embed = embed.field("User", user.mention().to_string(), true);
+        }
+
+        if let Some(contains) = &contains {
+            embed = embed.field("Containing", format!("`{contains}`"), true);
+        }
+
+        if failed > 0 {
+            embed = embed.field("Failed", failed.to_string(), true);
+        }
+
+        modlog
+            .send_message(ctx, CreateMessage::new().embed(embed))
+            .await?;
+    }
+
+    if failed > 0 {
+        ctx.say(format!(
+            "Deleted {deleted} messages, {failed} older ones couldn't be deleted."
+        ))
+        .await?;
+    } else {
+        ctx.say(format!("Deleted {deleted} messages.")).await?;
+    }
+
+    Ok(())
+}
+
+fn created_at(message_id: MessageId) -> DateTime<Utc> {
+    DateTime::from_timestamp(message_id.created_at().unix_timestamp(), 0).unwrap_or_default()
+}
+
+/// Whether the message is young enough to be bulk deleted, with a minute of leeway for the time
+/// the deletion takes.
+fn is_bulk_deletable(created_at: DateTime<Utc>, now: DateTime<Utc>) -> bool {
+    now - created_at < Duration::days(BULK_DELETE_MAX_AGE_DAYS) - Duration::minutes(1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bulk_deletes_only_recent_messages() {
+        let now = Utc::now();
+
+        assert!(is_bulk_deletable(now - Duration::days(13), now));
+        assert!(!is_bulk_deletable(
+            now - Duration::days(14) + Duration::seconds(30),
+            now
+        ));
+        assert!(!is_bulk_deletable(now - Duration::days(30), now));
+    }
+}
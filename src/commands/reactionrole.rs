@@ -0,0 +1,278 @@
+use poise::{serenity_prelude as serenity, CreateReply};
+use serenity::{ChannelId, Mentionable, Message, MessageId, Reaction, ReactionType, Role, RoleId};
+
+use crate::{
+    checks::is_maintainer,
+    commands::rolemenu::is_self_assignable,
+    database::reaction_roles,
+    respond_error, respond_mistake,
+    util::{embeds::default_embed, format::truncate},
+    Context, Data,
+};
+
+/// Roles members get by reacting to a message, an alternative to `/rolemenu`.
+#[poise::command(
+    slash_command,
+    guild_only = true,
+    check = "is_maintainer",
+    subcommands("bind", "unbind", "list"),
+    subcommand_required
+)]
+pub async fn reactionrole(_: Context<'_>) -> anyhow::Result<()> {
+    Ok(())
+}
+
+/// Give members a role when they react to a message with an emoji.
+#[poise::command(
+    slash_command,
+    guild_only = true,
+    check = "is_maintainer",
+    ephemeral = true
+)]
+pub async fn bind(
+    ctx: Context<'_>,
+    #[description = "A link to the message."] message: Message,
+    #[description = "The emoji to react with, a unicode emoji or one of the server."]
+    #[max_length = 100]
+    emoji: String,
+    #[description = "The role members get by reacting."] role: Role,
+) -> anyhow::Result<()> {
+    let Some((reaction, key)) = ReactionType::try_from(emoji.as_str())
+        .ok()
+        .and_then(|reaction| emoji_key(&reaction).map(|key| (reaction, key)))
+    else {
+        respond_mistake!(&ctx, "That isn't an emoji.");
+    };
+
+    if !is_self_assignable(&ctx.data().config, &role) {
+        respond_mistake!(&ctx, "That role can't be self-assignable.");
+    }
+
+    // reacting first also makes sure the bot can use the emoji.
+    if let Err(e) = message.react(ctx, reaction.clone()).await {
+        respond_error!("Failed to react to the message", e, &ctx);
+    }
+
+    if let Err(e) = reaction_roles::upsert(
+        &ctx.data().db_pool,
+        message.id.get() as i64,
+        message.channel_id.get() as i64,
+        &key,
+        role.id.get() as i64,
+    )
+    .await
+    {
+        respond_error!("Failed to save the reaction role", e, &ctx);
+    }
+
+    tracing::info!(
+        "{} bound the role {} to {reaction} on message {}.",
+        ctx.author().name,
+        role.name,
+        message.id
+    );
+
+    ctx.say(format!(
+        "Members reacting with {reaction} to {} get {}.",
+        message.link(),
+        role.mention()
+    ))
+    .await?;
+
+    Ok(())
+}
+
+/// Stop giving a role for reactions with an emoji. Members keep the role if they have it.
+#[poise::command(
+    slash_command,
+    guild_only = true,
+    check = "is_maintainer",
+    ephemeral = true
+)]
+pub async fn unbind(
+    ctx: Context<'_>,
+    #[description = "A link to the message."] message: Message,
+    #[description = "The emoji the role is bound to."]
+    #[max_length = 100]
+    emoji: String,
+) -> anyhow::Result<()> {
+    let Some((reaction, key)) = ReactionType::try_from(emoji.as_str())
+        .ok()
+        .and_then(|reaction| emoji_key(&reaction).map(|key| (reaction, key)))
+    else {
+        respond_mistake!(&ctx, "That isn't an emoji.");
+    };
+
+    match reaction_roles::delete(&ctx.data().db_pool, message.id.get() as i64, &key).await {
+        Ok(true) => {}
+        Ok(false) => {
+            respond_mistake!(&ctx, "No role is bound to that emoji on the message.");
+        }
+        Err(e) => {
+            respond_error!("Failed to remove the reaction role", e, &ctx);
+        }
+    }
+
+    // the reaction of the bot only invites members to react, the binding is gone either way.
+    if let Err(e) = message.delete_reaction(ctx, None, reaction.clone()).await {
+        tracing::warn!(
+            "Failed to remove the reaction from message {}: {e}",
+            message.id
+        );
+    }
+
+    tracing::info!(
+        "{} unbound {reaction} on message {}.",
+        ctx.author().name,
+        message.id
+    );
+
+    ctx.say(format!(
+        "Reacting with {reaction} to {} doesn't give a role anymore.",
+        message.link()
+    ))
+    .await?;
+
+    Ok(())
+}
+
+/// List the reaction roles.
+#[poise::command(
+    slash_command,
+    guild_only = true,
+    check = "is_maintainer",
+    ephemeral = true
+)]
+pub async fn list(ctx: Context<'_>) -> anyhow::Result<()> {
+    let reaction_roles = match reaction_roles::all(&ctx.data().db_pool).await {
+        Ok(reaction_roles) => reaction_roles,
+        Err(e) => {
+            respond_error!("Failed to fetch the reaction roles", e, &ctx);
+        }
+    };
+
+    if reaction_roles.is_empty() {
+        respond_mistake!(&ctx, "No reaction roles are bound.");
+    }
+
+    let guild_id = ctx.data().config.bot.guild_id;
+    let description = reaction_roles
+        .iter()
+        .map(|reaction_role| {
+            format!(
+                "{} {} \u{2192} {}",
+                MessageId::new(reaction_role.message_id as u64).link(
+                    ChannelId::new(reaction_role.channel_id as u64),
+                    Some(guild_id)
+                ),
+                display_emoji(&reaction_role.emoji),
+                RoleId::new(reaction_role.role_id as u64).mention()
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    ctx.send(
+        CreateReply::default()
+            .embed(
+                default_embed(ctx.author())
+                    .title(format!("Reaction roles ({})", reaction_roles.len()))
+                    .description(truncate(&description, 4000)),
+            )
+            .ephemeral(true),
+    )
+    .await?;
+
+    Ok(())
+}
+
+/// Gives or takes the role bound to the emoji when a member adds or removes a reaction.
+pub async fn handle_reaction(
+    reaction: &Reaction,
+    added: bool,
+    ctx: &serenity::Context,
+    data: &Data,
+) -> anyhow::Result<()> {
+    let (Some(guild_id), Some(user_id)) = (reaction.guild_id, reaction.user_id) else {
+        return Ok(());
+    };
+
+    if guild_id != data.config.bot.guild_id || user_id == ctx.cache.current_user().id {
+        return Ok(());
+    }
+
+    let Some(key) = emoji_key(&reaction.emoji) else {
+        return Ok(());
+    };
+
+    let Some(reaction_role) =
+        reaction_roles::get(&data.db_pool, reaction.message_id.get() as i64, &key).await?
+    else {
+        return Ok(());
+    };
+
+    let role_id = RoleId::new(reaction_role.role_id as u64);
+
+    if added {
+        // discord only sends the member for added reactions.
+        if reaction
+            .member
+            .as_ref()
+            .is_some_and(|member| member.user.bot)
+        {
+            return Ok(());
+        }
+
+        ctx.http
+            .add_member_role(guild_id, user_id, role_id, Some("Reaction role"))
+            .await?;
+    } else {
+        ctx.http
+            .remove_member_role(guild_id, user_id, role_id, Some("Reaction role"))
+            .await?;
+    }
+
+    Ok(())
+}
+
+/// Identifies the emoji of a reaction. Custom emojis are identified by their id since they can be
+/// renamed, unicode emojis without the variation selector some clients add.
+fn emoji_key(emoji: &ReactionType) -> Option<String> {
+    match emoji {
+        ReactionType::Custom { id, .. } => Some(id.to_string()),
+        ReactionType::Unicode(unicode) => Some(unicode.replace('\u{FE0F}', "")),
+        _ => None,
+    }
+}
+
+fn display_emoji(key: &str) -> String {
+    match key.parse::<u64>() {
+        Ok(id) => format!("<:emoji:{id}>"),
+        Err(_) => key.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serenity::EmojiId;
+
+    #[test]
+    fn identifies_custom_emojis_by_id() {
+        let emoji = ReactionType::Custom {
+            animated: false,
+            id: EmojiId::new(42),
+            name: Some("biome".to_string()),
+        };
+
+        assert_eq!(emoji_key(&emoji).as_deref(), Some("42"));
+    }
+
+    #[test]
+    fn ignores_variation_selector() {
+        assert_eq!(
+            emoji_key(&ReactionType::Unicode("\u{2764}\u{FE0F}".to_string())),
+            emoji_key(&ReactionType::Unicode("\u{2764}".to_string()))
+        );
+    }
+}
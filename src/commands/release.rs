@@ -0,0 +1,132 @@
+use poise::CreateReply;
+
+use crate::{
+    github_api::Release,
+    npm_api::BIOME_PACKAGE,
+    respond_error, respond_mistake,
+    util::{
+        embeds::default_embed,
+        format::{time, truncate, TimestampStyle},
+    },
+    Context,
+};
+
+const MAX_HIGHLIGHTS: usize = 8;
+
+/// Show the latest Biome release or a specific one.
+#[poise::command(slash_command, guild_only = true)]
+pub async fn release(
+    ctx: Context<'_>,
+    #[description = "The release tag, defaults to the latest release."]
+    #[autocomplete = "autocomplete_release"]
+    version: Option<String>,
+) -> anyhow::Result<()> {
+    ctx.defer().await?;
+
+    let github = &ctx.data().github;
+
+    let result = match &version {
+        Some(tag) => github.release_by_tag(tag).await,
+        None => github.latest_release().await,
+    };
+
+    let release = match result {
+        Ok(Some(release)) => release,
+        Ok(None) => {
+            respond_mistake!(&ctx, "There is no release with that tag.");
+        }
+        Err(e) => {
+            respond_error!("Failed to fetch the release from github", e, &ctx);
+        }
+    };
+
+    let highlights = release_highlights(release.body.as_deref().unwrap_or_default());
+
+    let description = if highlights.is_empty() {
+        "No highlights found in the release notes.".to_string()
+    } else {
+        highlights
+            .iter()
+            .map(|highlight| format!("- {highlight}"))
+            .collect::<Vec<_>>()
+            .join("\n")
+    };
+
+    let mut embed = default_embed(ctx.author())
+        .title(release.name.as_deref().unwrap_or(&release.tag_name))
+        .url(&release.html_url)
+        .description(truncate(&description, 4000))
+        .field("Tag", &release.tag_name, true)
+        .field("Links", display_links(&release), true);
+
+    if let Some(published_at) = release.published_at {
+        embed = embed.field(
+            "Published",
+            time(published_at, TimestampStyle::Relative),
+            true,
+        );
+    }
+
+    if release.prerelease {
+        embed = embed.field("Channel", "Pre-release", true);
+    }
+
+    ctx.send(CreateReply::default().embed(embed)).await?;
+
+    Ok(())
+}
+
+async fn autocomplete_release<'a>(
+    ctx: Context<'_>,
+    partial: &'a str,
+) -> impl Iterator<Item = String> + 'a {
+    let releases = ctx
+        .data()
+        .github
+        .recent_releases()
+        .await
+        .unwrap_or_default();
+
+    releases
+        .into_iter()
+        .filter(|release| !release.draft)
+        .map(|release| release.tag_name)
+        .filter(move |tag| tag.contains(partial))
+        .take(25)
+}
+
+/// Collects the bullet points of the release notes.
+fn release_highlights(body: &str) -> Vec<String> {
+    body.lines()
+        .filter_map(|line| {
+            let line = line.trim();
+            line.strip_prefix("- ").or_else(|| line.strip_prefix("* "))
+        })
+        .map(|line| line.trim().to_string())
+        .filter(|line| !line.is_empty())
+        .take(MAX_HIGHLIGHTS)
+        .collect()
+}
+
+fn display_links(release: &Release) -> String {
+    format!(
+        "[Release Notes]({})\n[npm](https://www.npmjs.com/package/{BIOME_PACKAGE}/v/{})",
+        release.html_url,
+        release.version()
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn extracts_bullet_points() {
+        let body = "## Analyzer\n\n### Bug fixes\n\n- Fix [#123](https://github.com) crash.\n  * Nested point\n-\nSome paragraph.\n";
+
+        assert_eq!(
+            release_highlights(body),
+            vec!["Fix [#123](https://github.com) crash.", "Nested point"]
+        );
+    }
+}
@@ -0,0 +1,212 @@
+use chrono::{Duration, Utc};
+use poise::{serenity_prelude as serenity, CreateReply};
+use serenity::{
+    ChannelId, CreateAllowedMentions, CreateEmbed, CreateMessage, Http, Mentionable, UserId,
+};
+
+use crate::{
+    database::reminders,
+    jobs::{self, Job},
+    respond_error, respond_mistake,
+    util::{
+        embeds::{default_embed, EmbedColor},
+        format::{time, truncate, TimestampStyle},
+        parse::parse_duration,
+    },
+    Context, Data,
+};
+
+const MAX_PENDING_REMINDERS: usize = 25;
+
+/// Reminders that get delivered even if the bot restarts in between.
+#[poise::command(
+    slash_command,
+    guild_only = true,
+    subcommands("me", "list", "cancel"),
+    subcommand_required
+)]
+pub async fn remind(_: Context<'_>) -> anyhow::Result<()> {
+    Ok(())
+}
+
+/// Get reminded about something later.
+#[poise::command(slash_command, guild_only = true, ephemeral = true)]
+pub async fn me(
+    ctx: Context<'_>,
+    #[description = "When to remind you, like `30m`, `2h 30m` or `3d`."]
+    #[rename = "in"]
+    duration: String,
+    #[description = "What to remind you about."]
+    #[max_length = 1000]
+    about: String,
+    #[description = "Send the reminder as a direct message instead of pinging you here."]
+    dm: Option<bool>,
+) -> anyhow::Result<()> {
+    let duration = match parse_duration(&duration) {
+        Some(duration) if duration <= Duration::days(365) => duration,
+        _ => {
+            respond_mistake!(
+                &ctx,
+                "Use a duration like `30m`, `2h 30m` or `3d` of at most one year."
+            );
+        }
+    };
+
+    let pool = &ctx.data().db_pool;
+    let user_id = ctx.author().id.get() as i64;
+
+    let pending = match reminders::by_user(pool, user_id).await {
+        Ok(pending) => pending,
+        Err(e) => {
+            respond_error!("Failed to fetch your reminders", e, &ctx);
+        }
+    };
+
+    if pending.len() >= MAX_PENDING_REMINDERS {
+        respond_mistake!(
+            &ctx,
+            "You already have 25 pending reminders, cancel one with `/remind cancel` first."
+        );
+    }
+
+    let remind_at = Utc::now() + duration;
+
+    let id = match reminders::insert(
+        pool,
+        user_id,
+        ctx.channel_id().get() as i64,
+        &about,
+        dm.unwrap_or_default(),
+        remind_at,
+    )
+    .await
+    {
+        Ok(id) => id,
+        Err(e) => {
+            respond_error!("Failed to save the reminder", e, &ctx);
+        }
+    };
+
+    if let Err(e) =
+        jobs::enqueue_at(pool, &Job::DeliverReminder { reminder_id: id }, remind_at).await
+    {
+        reminders::delete(pool, id).await?;
+        respond_error!("Failed to schedule the reminder", e, &ctx);
+    }
+
+    ctx.say(format!(
+        "I'll remind you {} (reminder #{id}).",
+        time(remind_at, TimestampStyle::Relative)
+    ))
+    .await?;
+
+    Ok(())
+}
+
+/// List your pending reminders.
+#[poise::command(slash_command, guild_only = true, ephemeral = true)]
+pub async fn list(ctx: Context<'_>) -> anyhow::Result<()> {
+    let pending = match reminders::by_user(&ctx.data().db_pool, ctx.author().id.get() as i64).await
+    {
+        Ok(pending) => pending,
+        Err(e) => {
+            respond_error!("Failed to fetch your reminders", e, &ctx);
+        }
+    };
+
+    if pending.is_empty() {
+        respond_mistake!(&ctx, "You don't have any pending reminders.");
+    }
+
+    let description = pending
+        .iter()
+        .map(|reminder| {
+            format!(
+                "**#{}** {}\n{}",
+                reminder.id,
+                time(reminder.remind_at, TimestampStyle::Relative),
+                truncate(&reminder.content, 100)
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n\n");
+
+    ctx.send(
+        CreateReply::default()
+            .embed(
+                default_embed(ctx.author())
+                    .title("Your reminders")
+                    .description(description),
+            )
+            .ephemeral(true),
+    )
+    .await?;
+
+    Ok(())
+}
+
+/// Cancel one of your pending reminders.
+#[poise::command(slash_command, guild_only = true, ephemeral = true)]
+pub async fn cancel(
+    ctx: Context<'_>,
+    #[description = "The number of the reminder, shown by `/remind list`."] id: i64,
+) -> anyhow::Result<()> {
+    match reminders::cancel(&ctx.data().db_pool, id, ctx.author().id.get() as i64).await {
+        Ok(true) => {
+            ctx.say(format!("Cancelled reminder #{id}.")).await?;
+        }
+        Ok(false) => {
+            respond_mistake!(&ctx, "You don't have a pending reminder with that number.");
+        }
+        Err(e) => {
+            respond_error!("Failed to cancel the reminder", e, &ctx);
+        }
+    }
+
+    Ok(())
+}
+
+/// Sends the reminder, queued as a job when the reminder is created. Cancelled reminders are
+/// skipped.
+pub async fn deliver_reminder(http: &Http, data: &Data, reminder_id: i64) -> anyhow::Result<()> {
+    let Some(reminder) = reminders::get(&data.db_pool, reminder_id).await? else {
+        return Ok(());
+    };
+
+    let user_id = UserId::new(reminder.user_id as u64);
+    let embed = CreateEmbed::new()
+        .color(EmbedColor::Biome)
+        .title("\u{23F0} Reminder")
+        .description(&reminder.content)
+        .field(
+            "Set",
+            time(reminder.created_at, TimestampStyle::Relative),
+            true,
+        );
+
+    if reminder.dm {
+        match user_id
+            .direct_message(http, CreateMessage::new().embed(embed.clone()))
+            .await
+        {
+            Ok(_) => return reminders::delete(&data.db_pool, reminder.id).await,
+            // users can disable direct messages, ping them in the channel instead.
+            Err(e) => tracing::warn!(
+                "Failed to send reminder #{} as a direct message: {e}",
+                reminder.id
+            ),
+        }
+    }
+
+    ChannelId::new(reminder.channel_id as u64)
+        .send_message(
+            http,
+            CreateMessage::new()
+                .content(user_id.mention().to_string())
+                .embed(embed)
+                .allowed_mentions(CreateAllowedMentions::new().users([user_id])),
+        )
+        .await?;
+
+    reminders::delete(&data.db_pool, reminder.id).await
+}
@@ -0,0 +1,371 @@
+use poise::{serenity_prelude as serenity, CreateReply, Modal};
+use serenity::{
+    ButtonStyle, ChannelId, ComponentInteraction, CreateActionRow, CreateButton, CreateEmbed,
+    CreateEmbedFooter, CreateInteractionResponse, CreateInteractionResponseMessage, CreateMessage,
+    Mentionable, Message, MessageId, UserId,
+};
+
+use crate::{
+    commands::moderation::case_embed,
+    database::{mod_cases, reports},
+    respond_error, respond_mistake,
+    util::format::truncate,
+    Context, Data,
+};
+
+/// Prefix of the custom ids of the report buttons, followed by the action and the report id.
+const BUTTON_PREFIX: &str = "report";
+
+#[derive(Debug, Modal)]
+#[name = "Report to moderators"]
+struct ReportModal {
+    #[name = "Reason"]
+    #[placeholder = "What is wrong with this message?"]
+    #[paragraph]
+    #[max_length = 1000]
+    reason: Option<String>,
+    #[name = "Stay anonymous?"]
+    #[placeholder = "Write \"yes\" to hide your name from the moderators."]
+    #[max_length = 3]
+    anonymous: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ReportAction {
+    Delete,
+    Warn,
+    Ignore,
+}
+
+impl ReportAction {
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Delete => "delete",
+            Self::Warn => "warn",
+            Self::Ignore => "ignore",
+        }
+    }
+
+    fn from_str(s: &str) -> Option<Self> {
+        match s {
+            "delete" => Some(Self::Delete),
+            "warn" => Some(Self::Warn),
+            "ignore" => Some(Self::Ignore),
+            _ => None,
+        }
+    }
+
+    /// The status of a report handled with the action.
+    fn status(self) -> &'static str {
+        match self {
+            Self::Delete => "deleted",
+            Self::Warn => "warned",
+            Self::Ignore => "ignored",
+        }
+    }
+
+    fn custom_id(self, report_id: i64) -> String {
+        format!("{BUTTON_PREFIX}:{}:{report_id}", self.as_str())
+    }
+}
+
+/// Report a message to the moderators.
+#[poise::command(context_menu_command = "Report to moderators", guild_only = true)]
+pub async fn report_message(
+    ctx: Context<'_>,
+    #[description = "The message to report."] message: Message,
+) -> anyhow::Result<()> {
+    let Some(guild_id) = ctx.guild_id() else {
+        return Ok(());
+    };
+
+    let Some(channel_id) = ctx
+        .data()
+        .guild(guild_id)
+        .and_then(|guild| guild.channels.reports)
+        .or(ctx.data().log_channel(guild_id))
+    else {
+        respond_mistake!(&ctx, "Reports aren't enabled on this server.");
+    };
+
+    if message.author.id == ctx.author().id {
+        respond_mistake!(&ctx, "You can't report your own message.");
+    }
+
+    let poise::Context::Application(app_ctx) = ctx else {
+        return Ok(());
+    };
+
+    let Some(modal) = ReportModal::execute(app_ctx).await? else {
+        return Ok(());
+    };
+
+    let reason = modal.reason.filter(|reason| !reason.trim().is_empty());
+    let anonymous = modal
+        .anonymous
+        .is_some_and(|anonymous| anonymous.trim().eq_ignore_ascii_case("yes"));
+
+    let report_id = match reports::insert(
+        &ctx.data().db_pool,
+        message.id.get() as i64,
+        message.channel_id.get() as i64,
+        message.author.id.get() as i64,
+        ctx.author().id.get() as i64,
+        reason.as_deref(),
+        anonymous,
+    )
+    .await
+    {
+        Ok(Some(report_id)) => report_id,
+        Ok(None) => {
+            respond_mistake!(&ctx, "You already reported this message.");
+        }
+        Err(e) => {
+            respond_error!("Failed to save the report", e, &ctx);
+        }
+    };
+
+    let reporter = if anonymous {
+        "Anonymous".to_string()
+    } else {
+        ctx.author().mention().to_string()
+    };
+
+    let mut embed = CreateEmbed::new()
+        .color(0xFFA500)
+        .title("Message reported")
+        .url(message.link())
+        .field(
+            "Author",
+            format!("{} (`{}`)", message.author.mention(), message.author.id),
+            true,
+        )
+        .field("Channel", message.channel_id.mention().to_string(), true)
+        .field("Reporter", reporter, true)
+        .field("Content", content_field(&message), false)
+        .footer(CreateEmbedFooter::new(format!("Report #{report_id}")))
+        .timestamp(message.timestamp);
+
+    if let Some(reason) = &reason {
+        embed = embed.field("Reason", reason, false);
+    }
+
+    let components = vec![CreateActionRow::Buttons(vec![
+        CreateButton::new(ReportAction::Delete.custom_id(report_id))
+            .label("Delete message")
+            .style(ButtonStyle::Danger),
+        CreateButton::new(ReportAction::Warn.custom_id(report_id))
+            .label("Warn author")
+            .style(ButtonStyle::Primary),
+        CreateButton::new(ReportAction::Ignore.custom_id(report_id))
+            .label("Ignore")
+            .style(ButtonStyle::Secondary),
+    ])];
+
+    if let Err(e) = channel_id
+        .send_message(
+            ctx,
+            CreateMessage::new().embed(embed).components(components),
+        )
+        .await
+    {
+        respond_error!("Failed to forward the report", e, &ctx);
+    }
+
+    tracing::info!(
+        "{} reported a message of {} (report #{report_id}).",
+        ctx.author().name,
+        message.author.name
+    );
+
+    ctx.send(
+        CreateReply::default()
+            .content("Thanks, the moderators will take a look.")
+            .ephemeral(true),
+    )
+    .await?;
+
+    Ok(())
+}
+
+/// Handles the buttons of forwarded reports, which keep working across restarts.
+pub async fn handle_component(
+    component: &ComponentInteraction,
+    ctx: &serenity::Context,
+    data: &Data,
+) -> anyhow::Result<()> {
+    let Some((action, report_id)) = parse_custom_id(&component.data.custom_id) else {
+        return Ok(());
+    };
+
+    let is_staff = component
+        .guild_id
+        .and_then(|guild_id| data.guild(guild_id))
+        .zip(component.member.as_ref())
+        .is_some_and(|(guild, member)| guild.roles.is_staff(&member.roles));
+
+    if !is_staff {
+        return respond_ephemeral(component, ctx, "Only moderators can handle reports.").await;
+    }
+
+    let moderator_id = component.user.id;
+
+    let Some(report) = reports::resolve(
+        &data.db_pool,
+        report_id,
+        action.status(),
+        moderator_id.get() as i64,
+    )
+    .await?
+    else {
+        return respond_ephemeral(component, ctx, "This report was already handled.").await;
+    };
+
+    let channel_id = ChannelId::new(report.channel_id as u64);
+    let author_id = UserId::new(report.author_id as u64);
+
+    let outcome = match action {
+        ReportAction::Delete => {
+            // the author or another moderator might have deleted the message already.
+            if let Err(e) = channel_id
+                .delete_message(ctx, MessageId::new(report.message_id as u64))
+                .await
+            {
+                tracing::warn!("Failed to delete reported message: {e}");
+            }
+
+            "deleted the message".to_string()
+        }
+        ReportAction::Warn => {
+            let reason = format!(
+                "Your message in {} was reported to the moderators.",
+                channel_id.mention()
+            );
+            let case = mod_cases::insert(
+                &data.db_pool,
+                component.guild_id.unwrap_or(data.config.bot.guild_id).get() as i64,
+                "warn",
+                report.author_id,
+                moderator_id.get() as i64,
+                &reason,
+                None,
+            )
+            .await?;
+
+            if let Err(e) = author_id
+                .direct_message(
+                    ctx,
+                    CreateMessage::new().content(format!("You were warned.\n**Reason:** {reason}")),
+                )
+                .await
+            {
+                tracing::warn!("Failed to notify {author_id} about a warn: {e}");
+            }
+
+            if let Some(modlog) = component
+                .guild_id
+                .and_then(|guild_id| data.log_channel(guild_id))
+            {
+                modlog
+                    .send_message(ctx, CreateMessage::new().embed(case_embed(&case)))
+                    .await?;
+            }
+
+            format!("warned the author (case #{})", case.id)
+        }
+        ReportAction::Ignore => "ignored the report".to_string(),
+    };
+
+    tracing::info!(
+        "{} handled report #{report_id}: {outcome}.",
+        component.user.name
+    );
+
+    let embed = component
+        .message
+        .embeds
+        .first()
+        .cloned()
+        .map_or_else(CreateEmbed::new, CreateEmbed::from)
+        .color(0x808080)
+        .field(
+            "Handled",
+            format!("{} {outcome}.", moderator_id.mention()),
+            false,
+        );
+
+    component
+        .create_response(
+            ctx,
+            CreateInteractionResponse::UpdateMessage(
+                CreateInteractionResponseMessage::new()
+                    .embed(embed)
+                    .components(Vec::new()),
+            ),
+        )
+        .await?;
+
+    Ok(())
+}
+
+/// The text of the message and how many attachments it has.
+fn content_field(message: &Message) -> String {
+    let mut content = if message.content.is_empty() {
+        "*No text*".to_string()
+    } else {
+        truncate(&message.content, 1000)
+    };
+
+    if !message.attachments.is_empty() {
+        content.push_str(&format!("\n*{} attachments*", message.attachments.len()));
+    }
+
+    content
+}
+
+async fn respond_ephemeral(
+    component: &ComponentInteraction,
+    ctx: &serenity::Context,
+    content: &str,
+) -> anyhow::Result<()> {
+    component
+        .create_response(
+            ctx,
+            CreateInteractionResponse::Message(
+                CreateInteractionResponseMessage::new()
+                    .content(content)
+                    .ephemeral(true),
+            ),
+        )
+        .await?;
+
+    Ok(())
+}
+
+fn parse_custom_id(custom_id: &str) -> Option<(ReportAction, i64)> {
+    let mut parts = custom_id.split(':');
+
+    if parts.next()? != BUTTON_PREFIX {
+        return None;
+    }
+
+    let action = ReportAction::from_str(parts.next()?)?;
+    let report_id = parts.next()?.parse().ok()?;
+
+    Some((action, report_id))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_custom_ids() {
+        assert_eq!(
+            parse_custom_id(&ReportAction::Warn.custom_id(12)),
+            Some((ReportAction::Warn, 12))
+        );
+        assert_eq!(parse_custom_id("report:ban:12"), None);
+        assert_eq!(parse_custom_id("poll:1:2"), None);
+    }
+}
@@ -0,0 +1,437 @@
+use poise::{serenity_prelude as serenity, CreateReply};
+use serenity::{
+    ActionRowComponent, ComponentInteraction, ComponentInteractionDataKind, CreateActionRow,
+    CreateEmbed, CreateInteractionResponse, CreateInteractionResponseMessage, CreateMessage,
+    CreateSelectMenu, CreateSelectMenuKind, CreateSelectMenuOption, Mentionable, ReactionType,
+    Role, RoleId,
+};
+
+use crate::{
+    checks::is_maintainer,
+    config::Config,
+    database::self_roles,
+    respond_error, respond_mistake,
+    util::{
+        embeds::{default_embed, EmbedColor},
+        format::truncate,
+    },
+    Context, Data,
+};
+
+/// Custom id of the role select menu.
+const MENU_ID: &str = "rolemenu";
+/// Discord allows at most 25 options in a select menu.
+const MAX_ROLES: usize = 25;
+
+/// Roles members can give themselves.
+#[poise::command(
+    slash_command,
+    guild_only = true,
+    check = "is_maintainer",
+    subcommands("add", "remove", "list", "create"),
+    subcommand_required
+)]
+pub async fn rolemenu(_: Context<'_>) -> anyhow::Result<()> {
+    Ok(())
+}
+
+/// Make a role self-assignable, or change how it's shown in the menu.
+#[poise::command(
+    slash_command,
+    guild_only = true,
+    check = "is_maintainer",
+    ephemeral = true
+)]
+pub async fn add(
+    ctx: Context<'_>,
+    #[description = "The role members can give themselves."] role: Role,
+    #[description = "Shown below the role in the menu."]
+    #[max_length = 100]
+    description: Option<String>,
+    #[description = "Shown next to the role, a unicode emoji or one of the server."]
+    #[max_length = 100]
+    emoji: Option<String>,
+) -> anyhow::Result<()> {
+    if !is_self_assignable(&ctx.data().config, &role) {
+        respond_mistake!(&ctx, "That role can't be self-assignable.");
+    }
+
+    if let Some(emoji) = &emoji {
+        if ReactionType::try_from(emoji.as_str()).is_err() {
+            respond_mistake!(&ctx, "That isn't an emoji.");
+        }
+    }
+
+    let pool = &ctx.data().db_pool;
+
+    let roles = match self_roles::all(pool).await {
+        Ok(roles) => roles,
+        Err(e) => {
+            respond_error!("Failed to fetch the self-assignable roles", e, &ctx);
+        }
+    };
+
+    if roles.len() >= MAX_ROLES
+        && !roles
+            .iter()
+            .any(|self_role| self_role.role_id == role.id.get() as i64)
+    {
+        respond_mistake!(
+            &ctx,
+            "A menu can't hold more than 25 roles, remove one with `/rolemenu remove` first."
+        );
+    }
+
+    if let Err(e) = self_roles::upsert(
+        pool,
+        role.id.get() as i64,
+        description.as_deref(),
+        emoji.as_deref(),
+    )
+    .await
+    {
+        respond_error!("Failed to save the role", e, &ctx);
+    }
+
+    tracing::info!(
+        "{} made the role {} self-assignable.",
+        ctx.author().name,
+        role.name
+    );
+
+    ctx.say(format!(
+        "{} is self-assignable, post a new menu with `/rolemenu create` to offer it.",
+        role.mention()
+    ))
+    .await?;
+
+    Ok(())
+}
+
+/// Stop offering a role. Members keep it if they have it.
+#[poise::command(
+    slash_command,
+    guild_only = true,
+    check = "is_maintainer",
+    ephemeral = true
+)]
+pub async fn remove(
+    ctx: Context<'_>,
+    #[description = "The self-assignable role."] role: Role,
+) -> anyhow::Result<()> {
+    match self_roles::delete(&ctx.data().db_pool, role.id.get() as i64).await {
+        Ok(true) => {}
+        Ok(false) => {
+            respond_mistake!(&ctx, "That role isn't self-assignable.");
+        }
+        Err(e) => {
+            respond_error!("Failed to remove the role", e, &ctx);
+        }
+    }
+
+    tracing::info!(
+        "{} removed the self-assignable role {}.",
+        ctx.author().name,
+        role.name
+    );
+
+    ctx.say(format!(
+        "{} isn't self-assignable anymore, menus ignore it from now on.",
+        role.mention()
+    ))
+    .await?;
+
+    Ok(())
+}
+
+/// List the self-assignable roles.
+#[poise::command(
+    slash_command,
+    guild_only = true,
+    check = "is_maintainer",
+    ephemeral = true
+)]
+pub async fn list(ctx: Context<'_>) -> anyhow::Result<()> {
+    let roles = match self_roles::all(&ctx.data().db_pool).await {
+        Ok(roles) => roles,
+        Err(e) => {
+            respond_error!("Failed to fetch the self-assignable roles", e, &ctx);
+        }
+    };
+
+    if roles.is_empty() {
+        respond_mistake!(&ctx, "No roles are self-assignable.");
+    }
+
+    let description = roles
+        .iter()
+        .map(|self_role| {
+            let mut line = RoleId::new(self_role.role_id as u64).mention().to_string();
+
+            if let Some(emoji) = &self_role.emoji {
+                line = format!("{emoji} {line}");
+            }
+
+            if let Some(description) = &self_role.description {
+                line.push_str(&format!(" \u{2013} {description}"));
+            }
+
+            line
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    ctx.send(
+        CreateReply::default()
+            .embed(
+                default_embed(ctx.author())
+                    .title(format!("Self-assignable roles ({})", roles.len()))
+                    .description(truncate(&description, 4000)),
+            )
+            .ephemeral(true),
+    )
+    .await?;
+
+    Ok(())
+}
+
+/// Post a menu members pick their roles from.
+#[poise::command(
+    slash_command,
+    guild_only = true,
+    check = "is_maintainer",
+    ephemeral = true
+)]
+pub async fn create(
+    ctx: Context<'_>,
+    #[description = "The title of the menu, `Roles` by default."]
+    #[max_length = 256]
+    title: Option<String>,
+    #[description = "The text above the menu."]
+    #[max_length = 2000]
+    message: Option<String>,
+) -> anyhow::Result<()> {
+    let self_roles = match self_roles::all(&ctx.data().db_pool).await {
+        Ok(self_roles) => self_roles,
+        Err(e) => {
+            respond_error!("Failed to fetch the self-assignable roles", e, &ctx);
+        }
+    };
+
+    let guild_roles = match ctx.data().config.bot.guild_id.roles(ctx.http()).await {
+        Ok(guild_roles) => guild_roles,
+        Err(e) => {
+            respond_error!("Failed to fetch the roles of the server", e, &ctx);
+        }
+    };
+
+    // roles deleted from the server since they were added are left out.
+    let options = self_roles
+        .iter()
+        .filter_map(|self_role| {
+            let role = guild_roles.get(&RoleId::new(self_role.role_id as u64))?;
+            let mut option = CreateSelectMenuOption::new(&role.name, role.id.to_string());
+
+            if let Some(description) = &self_role.description {
+                option = option.description(description);
+            }
+
+            if let Some(emoji) = self_role
+                .emoji
+                .as_deref()
+                .and_then(|emoji| ReactionType::try_from(emoji).ok())
+            {
+                option = option.emoji(emoji);
+            }
+
+            Some(option)
+        })
+        .take(MAX_ROLES)
+        .collect::<Vec<_>>();
+
+    if options.is_empty() {
+        respond_mistake!(
+            &ctx,
+            "No roles are self-assignable, add some with `/rolemenu add` first."
+        );
+    }
+
+    let embed = CreateEmbed::new()
+        .color(EmbedColor::Biome)
+        .title(title.unwrap_or_else(|| "Roles".to_string()))
+        .description(message.unwrap_or_else(|| {
+            "Pick the roles you want from the menu below, leave out the ones you don't.".to_string()
+        }));
+
+    let max_values = options.len() as u8;
+    let menu = CreateSelectMenu::new(MENU_ID, CreateSelectMenuKind::String { options })
+        .placeholder("Pick your roles")
+        .min_values(0)
+        .max_values(max_values);
+
+    if let Err(e) = ctx
+        .channel_id()
+        .send_message(
+            ctx,
+            CreateMessage::new()
+                .embed(embed)
+                .components(vec![CreateActionRow::SelectMenu(menu)]),
+        )
+        .await
+    {
+        respond_error!("Failed to post the role menu", e, &ctx);
+    }
+
+    ctx.say("Posted the role menu.").await?;
+
+    Ok(())
+}
+
+/// Handles selections in role menus, which keep working across restarts. The selection replaces
+/// the roles of the menu the member had before.
+pub async fn handle_component(
+    component: &ComponentInteraction,
+    ctx: &serenity::Context,
+    data: &Data,
+) -> anyhow::Result<()> {
+    let (ComponentInteractionDataKind::StringSelect { values }, Some(member)) =
+        (&component.data.kind, &component.member)
+    else {
+        return Ok(());
+    };
+
+    let self_roles = self_roles::all(&data.db_pool).await?;
+
+    // roles removed with `/rolemenu remove` stay in old menus, they aren't touched anymore.
+    let offered = menu_roles(component)
+        .into_iter()
+        .filter(|role_id| {
+            self_roles
+                .iter()
+                .any(|self_role| self_role.role_id == role_id.get() as i64)
+        })
+        .collect::<Vec<_>>();
+
+    let selected = values
+        .iter()
+        .filter_map(|value| value.parse::<u64>().ok().map(RoleId::new))
+        .collect::<Vec<_>>();
+
+    let (added, removed) = role_changes(&offered, &selected, &member.roles);
+
+    if !added.is_empty() {
+        member.add_roles(ctx, &added).await?;
+    }
+
+    if !removed.is_empty() {
+        member.remove_roles(ctx, &removed).await?;
+    }
+
+    let content = if added.is_empty() && removed.is_empty() {
+        "Your roles didn't change.".to_string()
+    } else {
+        let mentions = |role_ids: &[RoleId]| {
+            role_ids
+                .iter()
+                .map(|role_id| role_id.mention().to_string())
+                .collect::<Vec<_>>()
+                .join(", ")
+        };
+
+        let mut lines = Vec::new();
+
+        if !added.is_empty() {
+            lines.push(format!("**Added:** {}", mentions(&added)));
+        }
+
+        if !removed.is_empty() {
+            lines.push(format!("**Removed:** {}", mentions(&removed)));
+        }
+
+        lines.join("\n")
+    };
+
+    component
+        .create_response(
+            ctx,
+            CreateInteractionResponse::Message(
+                CreateInteractionResponseMessage::new()
+                    .content(content)
+                    .ephemeral(true),
+            ),
+        )
+        .await?;
+
+    Ok(())
+}
+
+/// Whether members may give themselves the role. Staff roles, the @everyone role and roles of
+/// integrations are never self-assignable.
+pub fn is_self_assignable(config: &Config, role: &Role) -> bool {
+    !role.managed
+        && role.id.get() != config.bot.guild_id.get()
+        && Some(role.id) != config.main_guild.roles.maintainer
+        && Some(role.id) != config.main_guild.roles.moderator
+}
+
+/// The roles offered by the menu the member used.
+fn menu_roles(component: &ComponentInteraction) -> Vec<RoleId> {
+    component
+        .message
+        .components
+        .iter()
+        .flat_map(|row| &row.components)
+        .filter_map(|component| match component {
+            ActionRowComponent::SelectMenu(menu) => Some(&menu.options),
+            _ => None,
+        })
+        .flatten()
+        .filter_map(|option| option.value.parse::<u64>().ok().map(RoleId::new))
+        .collect()
+}
+
+/// The roles to add and remove so the member has exactly the selected ones out of the offered.
+fn role_changes(
+    offered: &[RoleId],
+    selected: &[RoleId],
+    current: &[RoleId],
+) -> (Vec<RoleId>, Vec<RoleId>) {
+    let added = offered
+        .iter()
+        .filter(|role_id| selected.contains(role_id) && !current.contains(role_id))
+        .copied()
+        .collect();
+
+    let removed = offered
+        .iter()
+        .filter(|role_id| !selected.contains(role_id) && current.contains(role_id))
+        .copied()
+        .collect();
+
+    (added, removed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn roles(ids: &[u64]) -> Vec<RoleId> {
+        ids.iter().copied().map(RoleId::new).collect()
+    }
+
+    #[test]
+    fn replaces_offered_roles_with_selection() {
+        let (added, removed) = role_changes(&roles(&[1, 2, 3]), &roles(&[1, 3]), &roles(&[2, 3]));
+
+        assert_eq!(added, roles(&[1]));
+        assert_eq!(removed, roles(&[2]));
+    }
+
+    #[test]
+    fn ignores_roles_outside_of_menu() {
+        let (added, removed) = role_changes(&roles(&[1, 2]), &roles(&[1, 9]), &roles(&[8]));
+
+        assert_eq!(added, roles(&[1]));
+        assert!(removed.is_empty());
+    }
+}
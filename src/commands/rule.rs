@@ -0,0 +1,76 @@
+use poise::CreateReply;
+
+use crate::{
+    lint_rules::cached_lint_rules,
+    respond_error, respond_mistake,
+    util::{embeds::default_embed, format::inline_code},
+    Context,
+};
+
+/// Look up a Biome lint rule.
+#[poise::command(slash_command, guild_only = true)]
+pub async fn rule(
+    ctx: Context<'_>,
+    #[description = "The name of the rule, e.g. noAccessKey."]
+    #[autocomplete = "autocomplete_rule"]
+    name: String,
+) -> anyhow::Result<()> {
+    ctx.defer().await?;
+
+    let rules = match cached_lint_rules(ctx.data()).await {
+        Ok(rules) => rules,
+        Err(e) => {
+            respond_error!("Failed to fetch the lint rules from the website", e, &ctx);
+        }
+    };
+
+    let name = name.trim();
+
+    let Some(rule) = rules
+        .iter()
+        .find(|rule| rule.name.eq_ignore_ascii_case(name))
+    else {
+        respond_mistake!(&ctx, "There is no lint rule with that name.");
+    };
+
+    ctx.send(
+        CreateReply::default().embed(
+            default_embed(ctx.author())
+                .title(&rule.name)
+                .url(&rule.docs_url)
+                .description(
+                    rule.description
+                        .as_deref()
+                        .unwrap_or("No description available."),
+                )
+                .field("Group", inline_code(&rule.group), true)
+                .field("Recommended", display_bool(rule.recommended), true)
+                .field("Languages", rule.languages.join(", "), true)
+                .field("Since", rule.version.as_deref().unwrap_or("Unknown"), true)
+                .field("Deprecated", display_bool(rule.deprecated), true)
+                .field(
+                    "Documentation",
+                    format!("[biomejs.dev]({})", rule.docs_url),
+                    true,
+                ),
+        ),
+    )
+    .await?;
+
+    Ok(())
+}
+
+async fn autocomplete_rule<'a>(
+    ctx: Context<'_>,
+    partial: &'a str,
+) -> impl Iterator<Item = String> + 'a {
+    ctx.data().autocomplete.rules.suggest(partial).into_iter()
+}
+
+fn display_bool(value: bool) -> &'static str {
+    if value {
+        "\u{2705} Yes"
+    } else {
+        "\u{274C} No"
+    }
+}
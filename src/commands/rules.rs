@@ -0,0 +1,133 @@
+use poise::serenity_prelude as serenity;
+use serenity::CreateEmbed;
+
+use crate::{
+    lint_rules::{cached_lint_rules, LintRule},
+    respond_error, respond_mistake,
+    util::{embeds::default_embed, format::truncate, paginate::paginate},
+    Context,
+};
+
+const RULES_PER_PAGE: usize = 10;
+
+#[derive(Debug, Clone, Copy, poise::ChoiceParameter)]
+pub enum RuleGroup {
+    #[name = "a11y"]
+    A11y,
+    #[name = "complexity"]
+    Complexity,
+    #[name = "correctness"]
+    Correctness,
+    #[name = "nursery"]
+    Nursery,
+    #[name = "performance"]
+    Performance,
+    #[name = "security"]
+    Security,
+    #[name = "style"]
+    Style,
+    #[name = "suspicious"]
+    Suspicious,
+}
+
+impl RuleGroup {
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::A11y => "a11y",
+            Self::Complexity => "complexity",
+            Self::Correctness => "correctness",
+            Self::Nursery => "nursery",
+            Self::Performance => "performance",
+            Self::Security => "security",
+            Self::Style => "style",
+            Self::Suspicious => "suspicious",
+        }
+    }
+}
+
+/// Explore Biome's lint rules.
+#[poise::command(
+    slash_command,
+    guild_only = true,
+    subcommands("list"),
+    subcommand_required
+)]
+pub async fn rules(_: Context<'_>) -> anyhow::Result<()> {
+    Ok(())
+}
+
+/// List the lint rules of a group.
+#[poise::command(slash_command, guild_only = true)]
+pub async fn list(
+    ctx: Context<'_>,
+    #[description = "Only show rules of this group."] group: Option<RuleGroup>,
+    #[description = "Only show rules that are (or aren't) recommended."] recommended: Option<bool>,
+) -> anyhow::Result<()> {
+    ctx.defer().await?;
+
+    let rules = match cached_lint_rules(ctx.data()).await {
+        Ok(rules) => rules,
+        Err(e) => {
+            respond_error!("Failed to fetch the lint rules from the website", e, &ctx);
+        }
+    };
+
+    let matching = rules
+        .iter()
+        .filter(|rule| group.map_or(true, |group| rule.group == group.as_str()))
+        .filter(|rule| recommended.map_or(true, |recommended| rule.recommended == recommended))
+        .collect::<Vec<_>>();
+
+    if matching.is_empty() {
+        respond_mistake!(&ctx, "There are no lint rules matching your filters.");
+    }
+
+    let title = match (group, recommended) {
+        (Some(group), Some(true)) => format!("Recommended {} rules", group.as_str()),
+        (Some(group), Some(false)) => format!("Not recommended {} rules", group.as_str()),
+        (Some(group), None) => format!("{} rules", group.as_str()),
+        (None, Some(true)) => "Recommended rules".to_string(),
+        (None, Some(false)) => "Not recommended rules".to_string(),
+        (None, None) => "All rules".to_string(),
+    };
+
+    let pages = matching
+        .chunks(RULES_PER_PAGE)
+        .map(|chunk| {
+            chunk
+                .iter()
+                .map(|rule| display_rule(rule))
+                .collect::<Vec<_>>()
+                .join("\n")
+        })
+        .collect::<Vec<_>>();
+
+    let build_page = |index: usize| -> CreateEmbed {
+        default_embed(ctx.author())
+            .title(format!(
+                "{title} ({}) \u{2013} page {}/{}",
+                matching.len(),
+                index + 1,
+                pages.len()
+            ))
+            .description(&pages[index])
+    };
+
+    paginate(ctx, (0..pages.len()).map(build_page).collect()).await?;
+
+    Ok(())
+}
+
+fn display_rule(rule: &LintRule) -> String {
+    let recommended = if rule.recommended { " \u{2B50}" } else { "" };
+    let description = rule
+        .description
+        .as_deref()
+        .map(|description| format!(" \u{2013} {}", truncate(description, 80).replace('\n', " ")))
+        .unwrap_or_default();
+
+    format!(
+        "[`{}`]({}){recommended}{description}",
+        rule.name, rule.docs_url
+    )
+}
@@ -0,0 +1,104 @@
+use poise::CreateReply;
+
+use crate::{
+    config_schema::{cached_config_schema, docs_url, SchemaEntry},
+    respond_error, respond_mistake,
+    util::{
+        embeds::default_embed,
+        format::{inline_code, truncate},
+    },
+    Context,
+};
+
+/// Explain a setting of biome.json.
+#[poise::command(slash_command, guild_only = true)]
+pub async fn schema(
+    ctx: Context<'_>,
+    #[description = "The setting, e.g. formatter.indentStyle."]
+    #[autocomplete = "autocomplete_key"]
+    key: String,
+) -> anyhow::Result<()> {
+    ctx.defer().await?;
+
+    let schema = match cached_config_schema(ctx.data()).await {
+        Ok(schema) => schema,
+        Err(e) => {
+            respond_error!("Failed to fetch the configuration schema", e, &ctx);
+        }
+    };
+
+    let Some(entry) = schema.entry(key.trim()) else {
+        respond_mistake!(&ctx, "There is no setting with that key.");
+    };
+
+    let mut embed = default_embed(ctx.author())
+        .title(&entry.key)
+        .url(docs_url(&entry.key))
+        .description(truncate(
+            entry
+                .description
+                .as_deref()
+                .unwrap_or("No description available."),
+            4000,
+        ))
+        .field("Type", display_types(entry), true)
+        .field(
+            "Default",
+            entry
+                .default
+                .as_ref()
+                .map_or("Not specified".to_string(), |value| {
+                    inline_code(value.to_string())
+                }),
+            true,
+        );
+
+    embed = embed.field("Schema Version", &schema.version, true);
+
+    if !entry.allowed_values.is_empty() {
+        let allowed_values = entry
+            .allowed_values
+            .iter()
+            .map(inline_code)
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        embed = embed.field("Allowed Values", truncate(&allowed_values, 1024), false);
+    }
+
+    if entry.deprecated {
+        embed = embed.field(
+            "Deprecated",
+            "\u{26A0}\u{FE0F} This setting is deprecated.",
+            false,
+        );
+    }
+
+    ctx.send(CreateReply::default().embed(embed)).await?;
+
+    Ok(())
+}
+
+async fn autocomplete_key<'a>(
+    ctx: Context<'_>,
+    partial: &'a str,
+) -> impl Iterator<Item = String> + 'a {
+    ctx.data()
+        .autocomplete
+        .config_keys
+        .suggest(partial)
+        .into_iter()
+}
+
+fn display_types(entry: &SchemaEntry) -> String {
+    if entry.types.is_empty() {
+        return "Unknown".to_string();
+    }
+
+    entry
+        .types
+        .iter()
+        .map(inline_code)
+        .collect::<Vec<_>>()
+        .join(" | ")
+}
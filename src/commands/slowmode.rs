@@ -0,0 +1,203 @@
+use anyhow::Context as _;
+use chrono::{Duration, Utc};
+use poise::serenity_prelude as serenity;
+use serenity::{
+    ChannelId, CreateEmbed, CreateMessage, EditChannel, GuildId, Http, Mentionable, UserId,
+};
+
+use crate::{
+    checks::is_moderator,
+    jobs::{self, Job},
+    respond_error, respond_mistake,
+    util::{
+        format::{display_duration, time, TimestampStyle},
+        parse::parse_duration,
+    },
+    Context, Data,
+};
+
+#[derive(Debug, Clone, Copy, poise::ChoiceParameter)]
+pub enum SlowmodePreset {
+    Off,
+    #[name = "5 seconds"]
+    FiveSeconds,
+    #[name = "10 seconds"]
+    TenSeconds,
+    #[name = "30 seconds"]
+    ThirtySeconds,
+    #[name = "1 minute"]
+    OneMinute,
+    #[name = "5 minutes"]
+    FiveMinutes,
+    #[name = "15 minutes"]
+    FifteenMinutes,
+    #[name = "1 hour"]
+    OneHour,
+    #[name = "6 hours"]
+    SixHours,
+}
+
+impl SlowmodePreset {
+    fn seconds(self) -> u16 {
+        match self {
+            Self::Off => 0,
+            Self::FiveSeconds => 5,
+            Self::TenSeconds => 10,
+            Self::ThirtySeconds => 30,
+            Self::OneMinute => 60,
+            Self::FiveMinutes => 5 * 60,
+            Self::FifteenMinutes => 15 * 60,
+            Self::OneHour => 60 * 60,
+            // the longest slowmode discord allows.
+            Self::SixHours => 6 * 60 * 60,
+        }
+    }
+}
+
+/// Set how long members have to wait between their messages in this channel.
+#[poise::command(
+    slash_command,
+    guild_only = true,
+    check = "is_moderator",
+    ephemeral = true
+)]
+pub async fn slowmode(
+    ctx: Context<'_>,
+    #[description = "How long members have to wait between messages."] delay: SlowmodePreset,
+    #[description = "Turn the slowmode off again after this, like `30m` or `2h`. At most 7 days."]
+    reset_after: Option<String>,
+) -> anyhow::Result<()> {
+    let seconds = delay.seconds();
+
+    let reset_at = match reset_after {
+        Some(_) if seconds == 0 => {
+            respond_mistake!(&ctx, "There is nothing to reset when turning slowmode off.");
+        }
+        Some(reset_after) => match parse_duration(&reset_after) {
+            Some(duration) if duration <= Duration::days(7) => Some(Utc::now() + duration),
+            _ => {
+                respond_mistake!(
+                    &ctx,
+                    "Use a duration like `30m`, `2h` or `1d` of at most 7 days."
+                );
+            }
+        },
+        None => None,
+    };
+
+    let channel_id = ctx.channel_id();
+
+    if let Err(e) = channel_id
+        .edit(ctx, EditChannel::new().rate_limit_per_user(seconds))
+        .await
+    {
+        respond_error!("Failed to change the slowmode", e, &ctx);
+    }
+
+    if let Some(reset_at) = reset_at {
+        jobs::enqueue_at(
+            &ctx.data().db_pool,
+            &Job::ResetSlowmode {
+                channel_id: channel_id.get(),
+                seconds,
+            },
+            reset_at,
+        )
+        .await?;
+    }
+
+    log_to_modlog(
+        ctx.http(),
+        ctx.data(),
+        ctx.guild_id().unwrap_or(ctx.data().config.bot.guild_id),
+        channel_id,
+        seconds,
+        Some(ctx.author().id),
+    )
+    .await?;
+
+    tracing::info!(
+        "{} set the slowmode of {channel_id} to {seconds} seconds.",
+        ctx.author().name
+    );
+
+    let reply = match reset_at {
+        _ if seconds == 0 => "Turned the slowmode off.".to_string(),
+        Some(reset_at) => format!(
+            "Set the slowmode to {}, it turns off {}.",
+            display_duration(Duration::seconds(seconds.into())),
+            time(reset_at, TimestampStyle::Relative)
+        ),
+        None => format!(
+            "Set the slowmode to {}.",
+            display_duration(Duration::seconds(seconds.into()))
+        ),
+    };
+
+    ctx.say(reply).await?;
+
+    Ok(())
+}
+
+/// Turns the slowmode off, queued as a job when a slowmode with a reset is set. Skipped when the
+/// slowmode was changed in between.
+pub async fn reset_slowmode(
+    http: &Http,
+    data: &Data,
+    channel_id: ChannelId,
+    seconds: u16,
+) -> anyhow::Result<()> {
+    let channel = channel_id
+        .to_channel(http)
+        .await?
+        .guild()
+        .context("The channel isn't in a server")?;
+
+    if channel.rate_limit_per_user != Some(seconds) {
+        return Ok(());
+    }
+
+    channel_id
+        .edit(http, EditChannel::new().rate_limit_per_user(0))
+        .await?;
+
+    log_to_modlog(http, data, channel.guild_id, channel_id, 0, None).await
+}
+
+/// Logs the new slowmode, `None` for automatic resets.
+async fn log_to_modlog(
+    http: &Http,
+    data: &Data,
+    guild_id: GuildId,
+    channel_id: ChannelId,
+    seconds: u16,
+    moderator: Option<UserId>,
+) -> anyhow::Result<()> {
+    let Some(modlog) = data.log_channel(guild_id) else {
+        return Ok(());
+    };
+
+    let slowmode = if seconds == 0 {
+        "Off".to_string()
+    } else {
+        display_duration(Duration::seconds(seconds.into()))
+    };
+
+    let moderator = moderator.map_or_else(
+        || "Automatic".to_string(),
+        |moderator| moderator.mention().to_string(),
+    );
+
+    let embed = CreateEmbed::new()
+        .color(0xFFD700)
+        .title("Slowmode changed")
+        .field("Channel", channel_id.mention().to_string(), true)
+        .field("Slowmode", slowmode, true)
+        .field("Moderator", moderator, true);
+
+    modlog
+        .send_message(http, CreateMessage::new().embed(embed))
+        .await?;
+
+    Ok(())
+}
@@ -0,0 +1,78 @@
+use poise::{serenity_prelude as serenity, CreateReply};
+use serenity::User;
+
+use crate::{
+    help_forum::{close_solved_thread, forum_of, record_solved, solved_embed},
+    respond_error, respond_mistake, Context,
+};
+
+/// Mark a help post as solved and archive it.
+#[poise::command(slash_command, guild_only = true)]
+pub async fn solved(
+    ctx: Context<'_>,
+    #[description = "The user whose answer solved the problem."] solver: Option<User>,
+    #[description = "A short summary of the solution for people with the same problem."]
+    #[max_length = 1000]
+    summary: Option<String>,
+) -> anyhow::Result<()> {
+    let Some(thread) = ctx.guild_channel().await else {
+        respond_mistake!(&ctx, "This command only works in posts of the help forum.");
+    };
+
+    let Some(forum) = forum_of(ctx.data(), &thread) else {
+        respond_mistake!(&ctx, "This command only works in posts of the help forum.");
+    };
+
+    let member = ctx.author_member().await;
+    let is_staff = ctx
+        .guild_id()
+        .and_then(|guild_id| ctx.data().guild(guild_id))
+        .zip(member)
+        .is_some_and(|(guild, member)| guild.roles.is_staff(&member.roles));
+
+    if thread.owner_id != Some(ctx.author().id) && !is_staff {
+        respond_mistake!(
+            &ctx,
+            "Only the author of the post and the moderators can mark it as solved."
+        );
+    }
+
+    let solver = solver.map(|solver| solver.id);
+
+    match record_solved(
+        ctx.data(),
+        &thread,
+        ctx.author().id,
+        solver,
+        summary.as_deref(),
+    )
+    .await
+    {
+        Ok(true) => {}
+        Ok(false) => {
+            respond_mistake!(&ctx, "This post is already marked as solved.");
+        }
+        Err(e) => {
+            respond_error!("Failed to mark the post as solved", e, &ctx);
+        }
+    }
+
+    tracing::info!(
+        "{} marked thread {} as solved",
+        ctx.author().name,
+        thread.id
+    );
+
+    ctx.send(CreateReply::default().embed(solved_embed(
+        ctx.author().id,
+        solver,
+        summary.as_deref(),
+    )))
+    .await?;
+
+    if let Err(e) = close_solved_thread(ctx.serenity_context(), &thread, forum).await {
+        respond_error!("Failed to tag and archive the post", e, &ctx);
+    }
+
+    Ok(())
+}
@@ -0,0 +1,129 @@
+use std::time::Duration;
+
+use poise::CreateReply;
+
+use crate::{
+    github_api::Repository,
+    npm_api::{self, BIOME_PACKAGE},
+    util::{
+        cache::TtlCache,
+        embeds::default_embed,
+        format::{display_count, time, TimestampStyle},
+    },
+    Context,
+};
+
+/// Independently cached sources for `/stats`, so a slow or failing source doesn't hold up the others.
+#[derive(Debug)]
+pub struct StatsCache {
+    repository: TtlCache<Repository>,
+    weekly_downloads: TtlCache<u64>,
+    contributors: TtlCache<u64>,
+}
+
+impl Default for StatsCache {
+    fn default() -> Self {
+        Self {
+            repository: TtlCache::new(Duration::from_secs(10 * 60)),
+            weekly_downloads: TtlCache::new(Duration::from_secs(60 * 60)),
+            contributors: TtlCache::new(Duration::from_secs(6 * 60 * 60)),
+        }
+    }
+}
+
+/// Show metrics about the Biome project.
+#[poise::command(slash_command, guild_only = true)]
+pub async fn stats(ctx: Context<'_>) -> anyhow::Result<()> {
+    ctx.defer().await?;
+
+    let data = ctx.data();
+    let cache = &data.stats_cache;
+
+    let (repository, weekly_downloads, contributors) = tokio::join!(
+        cache
+            .repository
+            .get_or_refresh(|| data.github.repository_info()),
+        cache.weekly_downloads.get_or_refresh(|| async {
+            Ok(npm_api::weekly_downloads(BIOME_PACKAGE).await?.downloads)
+        }),
+        cache
+            .contributors
+            .get_or_refresh(|| data.github.contributor_count()),
+    );
+
+    let mut embed = default_embed(ctx.author()).title("Biome Stats");
+
+    match repository {
+        Ok(repository) => {
+            embed = embed
+                .url(&repository.html_url)
+                .field("Stars", display_count(repository.stargazers_count), true)
+                .field("Forks", display_count(repository.forks_count), true)
+                .field(
+                    "Open Issues & PRs",
+                    display_count(repository.open_issues_count),
+                    true,
+                );
+        }
+        Err(e) => {
+            tracing::error!("Failed to fetch repository stats: {e:#?}");
+            embed = embed.field("Repository", "Unavailable", true);
+        }
+    }
+
+    embed = embed.field(
+        "Weekly Downloads",
+        display_result(weekly_downloads, "npm downloads"),
+        true,
+    );
+    embed = embed.field(
+        "Contributors",
+        display_result(contributors, "contributor count"),
+        true,
+    );
+
+    if let Some(rate_limit) = data.github.rate_limit("core") {
+        embed = embed.field(
+            "GitHub API",
+            format!(
+                "{}/{} requests left, resets {}",
+                rate_limit.remaining,
+                rate_limit.limit,
+                time(rate_limit.reset, TimestampStyle::Relative)
+            ),
+            false,
+        );
+    }
+
+    let shards = data.shards.snapshot().await;
+
+    if !shards.is_empty() {
+        let gateway = shards
+            .iter()
+            .map(|(id, status)| {
+                let latency = status.latency.map_or("-".to_string(), |latency| {
+                    format!("{}ms", latency.as_millis())
+                });
+
+                format!("Shard {id}: {latency} ({:?})", status.stage)
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        embed = embed.field("Gateway", gateway, false);
+    }
+
+    ctx.send(CreateReply::default().embed(embed)).await?;
+
+    Ok(())
+}
+
+fn display_result(result: anyhow::Result<u64>, source: &str) -> String {
+    match result {
+        Ok(count) => display_count(count),
+        Err(e) => {
+            tracing::error!("Failed to fetch {source}: {e:#?}");
+            "Unavailable".to_string()
+        }
+    }
+}
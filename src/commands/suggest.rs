@@ -0,0 +1,383 @@
+use poise::{serenity_prelude as serenity, CreateReply};
+use serenity::{
+    ButtonStyle, ComponentInteraction, CreateActionRow, CreateButton, CreateEmbed,
+    CreateEmbedAuthor, CreateInteractionResponse, CreateInteractionResponseMessage, CreateMessage,
+    Mentionable, UserId,
+};
+
+use crate::{
+    database::suggestions::{self, Suggestion},
+    jobs, respond_error, respond_mistake,
+    util::{embeds::EmbedColor, format::truncate},
+    Context, Data,
+};
+
+/// Prefix of the custom ids of the suggestion buttons, followed by the action and the id.
+const BUTTON_PREFIX: &str = "suggestion";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SuggestionAction {
+    Upvote,
+    Downvote,
+    Accept,
+    Decline,
+}
+
+impl SuggestionAction {
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Upvote => "up",
+            Self::Downvote => "down",
+            Self::Accept => "accept",
+            Self::Decline => "decline",
+        }
+    }
+
+    fn from_str(s: &str) -> Option<Self> {
+        match s {
+            "up" => Some(Self::Upvote),
+            "down" => Some(Self::Downvote),
+            "accept" => Some(Self::Accept),
+            "decline" => Some(Self::Decline),
+            _ => None,
+        }
+    }
+
+    fn custom_id(self, suggestion_id: i64) -> String {
+        format!("{BUTTON_PREFIX}:{}:{suggestion_id}", self.as_str())
+    }
+}
+
+/// Parses the custom id of a suggestion button.
+fn parse_custom_id(custom_id: &str) -> Option<(SuggestionAction, i64)> {
+    let mut parts = custom_id.split(':');
+
+    if parts.next()? != BUTTON_PREFIX {
+        return None;
+    }
+
+    let action = SuggestionAction::from_str(parts.next()?)?;
+    let suggestion_id = parts.next()?.parse().ok()?;
+
+    Some((action, suggestion_id))
+}
+
+/// Suggest something for Biome or this server.
+#[poise::command(slash_command, guild_only = true, ephemeral = true)]
+pub async fn suggest(
+    ctx: Context<'_>,
+    #[description = "What you would like to see."]
+    #[max_length = 2000]
+    suggestion: String,
+) -> anyhow::Result<()> {
+    let Some(channel_id) = ctx
+        .guild_id()
+        .and_then(|guild_id| ctx.data().guild(guild_id))
+        .and_then(|guild| guild.channels.suggestions)
+    else {
+        respond_mistake!(&ctx, "Suggestions aren't enabled on this server.");
+    };
+
+    ctx.defer_ephemeral().await?;
+
+    let author = ctx.author();
+    let pool = &ctx.data().db_pool;
+
+    let id = match suggestions::insert(pool, author.id.get() as i64, &suggestion).await {
+        Ok(id) => id,
+        Err(e) => {
+            respond_error!("Failed to save your suggestion", e, &ctx);
+        }
+    };
+
+    let embed_author =
+        CreateEmbedAuthor::new(author.global_name.as_deref().unwrap_or(&author.name))
+            .icon_url(author.face());
+
+    let message = channel_id
+        .send_message(
+            ctx,
+            CreateMessage::new()
+                .embed(suggestion_embed(
+                    id,
+                    &suggestion,
+                    embed_author,
+                    "open",
+                    None,
+                    (0, 0),
+                ))
+                .components(suggestion_components(id)),
+        )
+        .await?;
+
+    suggestions::set_message(pool, id, channel_id.get() as i64, message.id.get() as i64).await?;
+
+    ctx.send(
+        CreateReply::default()
+            .content(format!("Posted your suggestion: {}", message.link()))
+            .ephemeral(true),
+    )
+    .await?;
+
+    Ok(())
+}
+
+/// Handles presses of the suggestion buttons, which keep working across restarts.
+pub async fn handle_component(
+    component: &ComponentInteraction,
+    ctx: &serenity::Context,
+    data: &Data,
+) -> anyhow::Result<()> {
+    let Some((action, suggestion_id)) = parse_custom_id(&component.data.custom_id) else {
+        return Ok(());
+    };
+
+    let Some(suggestion) = suggestions::get(&data.db_pool, suggestion_id).await? else {
+        return respond_ephemeral(component, ctx, "This suggestion doesn't exist anymore.").await;
+    };
+
+    if suggestion.status != "open" {
+        return respond_ephemeral(component, ctx, "This suggestion was already decided on.").await;
+    }
+
+    let user_id = component.user.id.get() as i64;
+
+    match action {
+        SuggestionAction::Upvote | SuggestionAction::Downvote => {
+            let upvote = action == SuggestionAction::Upvote;
+            let previous =
+                suggestions::previous_vote(&data.db_pool, suggestion_id, user_id).await?;
+
+            if previous == Some(upvote) {
+                return respond_ephemeral(component, ctx, "You already voted for that.").await;
+            }
+
+            suggestions::vote(&data.db_pool, suggestion_id, user_id, upvote).await?;
+        }
+        SuggestionAction::Accept | SuggestionAction::Decline => {
+            let is_staff = component
+                .guild_id
+                .and_then(|guild_id| data.guild(guild_id))
+                .zip(component.member.as_ref())
+                .is_some_and(|(guild, member)| guild.roles.is_staff(&member.roles));
+
+            if !is_staff {
+                return respond_ephemeral(
+                    component,
+                    ctx,
+                    "Only moderators can accept or decline suggestions.",
+                )
+                .await;
+            }
+
+            let status = if action == SuggestionAction::Accept {
+                "accepted"
+            } else {
+                "declined"
+            };
+
+            if !suggestions::decide(&data.db_pool, suggestion_id, status, user_id).await? {
+                return respond_ephemeral(
+                    component,
+                    ctx,
+                    "This suggestion was already decided on.",
+                )
+                .await;
+            }
+
+            tracing::info!(
+                "{} {status} suggestion #{suggestion_id}",
+                component.user.name
+            );
+
+            // the decision is already stored, a failed notification shouldn't hide it.
+            if let Err(e) = notify_supporters(component, data, &suggestion, status).await {
+                tracing::error!(
+                    "Failed to notify the supporters of suggestion #{suggestion_id}: {e:#?}"
+                );
+            }
+        }
+    }
+
+    update_message(component, ctx, data, suggestion_id).await
+}
+
+/// Lets the author and everyone who upvoted know about the decision, through the job queue so
+/// closed direct messages and restarts don't lose any.
+async fn notify_supporters(
+    component: &ComponentInteraction,
+    data: &Data,
+    suggestion: &Suggestion,
+    status: &str,
+) -> anyhow::Result<()> {
+    let mut user_ids = suggestions::upvoters(&data.db_pool, suggestion.id).await?;
+    user_ids.push(suggestion.author_id);
+    user_ids.sort_unstable();
+    user_ids.dedup();
+
+    let content = format!(
+        "A suggestion you made or upvoted was {status}: {}\n>>> {}",
+        component.message.link(),
+        truncate(&suggestion.content, 1500)
+    );
+
+    jobs::enqueue_direct_messages(
+        &data.db_pool,
+        user_ids
+            .into_iter()
+            .map(|user_id| UserId::new(user_id as u64)),
+        &content,
+    )
+    .await
+}
+
+/// Rebuilds the embed with the current votes and status.
+async fn update_message(
+    component: &ComponentInteraction,
+    ctx: &serenity::Context,
+    data: &Data,
+    suggestion_id: i64,
+) -> anyhow::Result<()> {
+    let Some(suggestion) = suggestions::get(&data.db_pool, suggestion_id).await? else {
+        return Ok(());
+    };
+
+    let tally = suggestions::tally(&data.db_pool, suggestion.id).await?;
+
+    // the author of the suggestion is only known from the posted embed.
+    let embed_author = match component
+        .message
+        .embeds
+        .first()
+        .and_then(|embed| embed.author.as_ref())
+    {
+        Some(author) => {
+            let embed_author = CreateEmbedAuthor::new(&author.name);
+
+            match &author.icon_url {
+                Some(icon_url) => embed_author.icon_url(icon_url),
+                None => embed_author,
+            }
+        }
+        None => CreateEmbedAuthor::new("Unknown"),
+    };
+
+    let decided_by = suggestion
+        .decided_by
+        .map(|decided_by| UserId::new(decided_by as u64));
+
+    let components = if suggestion.status == "open" {
+        suggestion_components(suggestion.id)
+    } else {
+        Vec::new()
+    };
+
+    component
+        .create_response(
+            ctx,
+            CreateInteractionResponse::UpdateMessage(
+                CreateInteractionResponseMessage::new()
+                    .embed(suggestion_embed(
+                        suggestion.id,
+                        &suggestion.content,
+                        embed_author,
+                        &suggestion.status,
+                        decided_by,
+                        tally,
+                    ))
+                    .components(components),
+            ),
+        )
+        .await?;
+
+    Ok(())
+}
+
+async fn respond_ephemeral(
+    component: &ComponentInteraction,
+    ctx: &serenity::Context,
+    content: &str,
+) -> anyhow::Result<()> {
+    component
+        .create_response(
+            ctx,
+            CreateInteractionResponse::Message(
+                CreateInteractionResponseMessage::new()
+                    .content(content)
+                    .ephemeral(true),
+            ),
+        )
+        .await?;
+
+    Ok(())
+}
+
+fn suggestion_embed(
+    id: i64,
+    content: &str,
+    author: CreateEmbedAuthor,
+    status: &str,
+    decided_by: Option<UserId>,
+    (upvotes, downvotes): (i64, i64),
+) -> CreateEmbed {
+    let (status, color) = match (status, decided_by) {
+        ("accepted", Some(decided_by)) => (
+            format!("\u{2705} Accepted by {}", decided_by.mention()),
+            EmbedColor::Green,
+        ),
+        ("declined", Some(decided_by)) => (
+            format!("\u{274C} Declined by {}", decided_by.mention()),
+            EmbedColor::Red,
+        ),
+        _ => ("Open for votes".to_string(), EmbedColor::Biome),
+    };
+
+    CreateEmbed::new()
+        .color(color)
+        .author(author)
+        .title(format!("Suggestion #{id}"))
+        .description(content)
+        .field(
+            "Votes",
+            format!("\u{1F44D} {upvotes} \u{00B7} \u{1F44E} {downvotes}"),
+            true,
+        )
+        .field("Status", status, true)
+}
+
+fn suggestion_components(id: i64) -> Vec<CreateActionRow> {
+    vec![CreateActionRow::Buttons(vec![
+        CreateButton::new(SuggestionAction::Upvote.custom_id(id))
+            .emoji('\u{1F44D}')
+            .style(ButtonStyle::Secondary),
+        CreateButton::new(SuggestionAction::Downvote.custom_id(id))
+            .emoji('\u{1F44E}')
+            .style(ButtonStyle::Secondary),
+        CreateButton::new(SuggestionAction::Accept.custom_id(id))
+            .label("Accept")
+            .style(ButtonStyle::Success),
+        CreateButton::new(SuggestionAction::Decline.custom_id(id))
+            .label("Decline")
+            .style(ButtonStyle::Danger),
+    ])]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trips_custom_ids() {
+        for action in [
+            SuggestionAction::Upvote,
+            SuggestionAction::Downvote,
+            SuggestionAction::Accept,
+            SuggestionAction::Decline,
+        ] {
+            assert_eq!(parse_custom_id(&action.custom_id(42)), Some((action, 42)));
+        }
+
+        assert_eq!(parse_custom_id("1234prev"), None);
+        assert_eq!(parse_custom_id("suggestion:up:abc"), None);
+    }
+}
@@ -0,0 +1,430 @@
+use poise::{serenity_prelude as serenity, CreateReply, Modal};
+use serenity::CreateEmbed;
+
+use crate::{
+    checks::is_moderator,
+    database::{
+        faq_questions,
+        tags::{self, Tag},
+    },
+    respond_error, respond_mistake,
+    tasks::autocomplete,
+    util::{embeds::default_embed, format::inline_code, paginate::paginate},
+    Context,
+};
+
+const TAGS_PER_PAGE: usize = 20;
+const MAX_NAME_LENGTH: usize = 32;
+
+#[derive(Debug, Modal)]
+#[name = "Tag"]
+struct TagModal {
+    #[name = "Content"]
+    #[placeholder = "The markdown that gets posted when the tag is used."]
+    #[paragraph]
+    #[max_length = 4000]
+    content: String,
+}
+
+/// Frequently needed answers, written by the moderators.
+#[poise::command(
+    slash_command,
+    guild_only = true,
+    subcommands("show", "list", "create", "edit", "delete", "question"),
+    subcommand_required
+)]
+pub async fn tag(_: Context<'_>) -> anyhow::Result<()> {
+    Ok(())
+}
+
+/// Post a tag.
+#[poise::command(slash_command, guild_only = true)]
+pub async fn show(
+    ctx: Context<'_>,
+    #[description = "The name of the tag."]
+    #[autocomplete = "autocomplete_tag"]
+    name: String,
+) -> anyhow::Result<()> {
+    let tag = match tags::use_tag(&ctx.data().db_pool, &normalize_name(&name)).await {
+        Ok(tag) => tag,
+        Err(e) => {
+            respond_error!("Failed to fetch the tag", e, &ctx);
+        }
+    };
+
+    let Some(tag) = tag else {
+        respond_mistake!(&ctx, "There is no tag with that name.");
+    };
+
+    ctx.send(
+        CreateReply::default().embed(
+            default_embed(ctx.author())
+                .title(tag.name)
+                .description(tag.content),
+        ),
+    )
+    .await?;
+
+    Ok(())
+}
+
+/// List all tags, the most used first.
+#[poise::command(slash_command, guild_only = true)]
+pub async fn list(ctx: Context<'_>) -> anyhow::Result<()> {
+    ctx.defer().await?;
+
+    let tags = match tags::all(&ctx.data().db_pool).await {
+        Ok(tags) => tags,
+        Err(e) => {
+            respond_error!("Failed to fetch the tags", e, &ctx);
+        }
+    };
+
+    if tags.is_empty() {
+        respond_mistake!(&ctx, "There are no tags yet.");
+    }
+
+    let pages = tags
+        .chunks(TAGS_PER_PAGE)
+        .map(|chunk| chunk.iter().map(display_tag).collect::<Vec<_>>().join("\n"))
+        .collect::<Vec<_>>();
+
+    let build_page = |index: usize| -> CreateEmbed {
+        default_embed(ctx.author())
+            .title(format!(
+                "Tags ({}) \u{2013} page {}/{}",
+                tags.len(),
+                index + 1,
+                pages.len()
+            ))
+            .description(&pages[index])
+    };
+
+    paginate(ctx, (0..pages.len()).map(build_page).collect()).await?;
+
+    Ok(())
+}
+
+/// Create a new tag.
+#[poise::command(
+    slash_command,
+    guild_only = true,
+    check = "is_moderator",
+    ephemeral = true
+)]
+pub async fn create(
+    ctx: Context<'_>,
+    #[description = "The name of the tag, e.g. vscode-setup."] name: String,
+) -> anyhow::Result<()> {
+    let Some(name) = valid_name(&name) else {
+        respond_mistake!(
+            &ctx,
+            "Tag names consist of up to 32 lowercase letters, digits and dashes."
+        );
+    };
+
+    let poise::Context::Application(app_ctx) = ctx else {
+        return Ok(());
+    };
+
+    let Some(modal) = TagModal::execute(app_ctx).await? else {
+        return Ok(());
+    };
+
+    match tags::insert(
+        &ctx.data().db_pool,
+        &name,
+        &modal.content,
+        ctx.author().id.get() as i64,
+    )
+    .await
+    {
+        Ok(true) => {}
+        Ok(false) => {
+            respond_mistake!(&ctx, "A tag with that name already exists.");
+        }
+        Err(e) => {
+            respond_error!("Failed to create the tag", e, &ctx);
+        }
+    }
+
+    if let Err(e) = autocomplete::refresh_tags(ctx.data()).await {
+        tracing::error!("Failed to refresh tag names for autocomplete: {e:#?}");
+    }
+
+    tracing::info!("{} created tag {name}", ctx.author().name);
+
+    ctx.say(format!("Created the tag {}.", inline_code(&name)))
+        .await?;
+
+    Ok(())
+}
+
+/// Edit the content of a tag.
+#[poise::command(
+    slash_command,
+    guild_only = true,
+    check = "is_moderator",
+    ephemeral = true
+)]
+pub async fn edit(
+    ctx: Context<'_>,
+    #[description = "The name of the tag."]
+    #[autocomplete = "autocomplete_tag"]
+    name: String,
+) -> anyhow::Result<()> {
+    let tag = match tags::get(&ctx.data().db_pool, &normalize_name(&name)).await {
+        Ok(tag) => tag,
+        Err(e) => {
+            respond_error!("Failed to fetch the tag", e, &ctx);
+        }
+    };
+
+    let Some(tag) = tag else {
+        respond_mistake!(&ctx, "There is no tag with that name.");
+    };
+
+    let poise::Context::Application(app_ctx) = ctx else {
+        return Ok(());
+    };
+
+    let defaults = TagModal {
+        content: tag.content,
+    };
+
+    let Some(modal) = TagModal::execute_with_defaults(app_ctx, defaults).await? else {
+        return Ok(());
+    };
+
+    match tags::update(&ctx.data().db_pool, &tag.name, &modal.content).await {
+        Ok(true) => {}
+        Ok(false) => {
+            respond_mistake!(&ctx, "The tag got deleted in the meantime.");
+        }
+        Err(e) => {
+            respond_error!("Failed to update the tag", e, &ctx);
+        }
+    }
+
+    tracing::info!("{} edited tag {}", ctx.author().name, tag.name);
+
+    ctx.say(format!("Updated the tag {}.", inline_code(&tag.name)))
+        .await?;
+
+    Ok(())
+}
+
+/// Delete a tag.
+#[poise::command(
+    slash_command,
+    guild_only = true,
+    check = "is_moderator",
+    ephemeral = true
+)]
+pub async fn delete(
+    ctx: Context<'_>,
+    #[description = "The name of the tag."]
+    #[autocomplete = "autocomplete_tag"]
+    name: String,
+) -> anyhow::Result<()> {
+    ctx.defer_ephemeral().await?;
+
+    let name = normalize_name(&name);
+
+    match tags::delete(&ctx.data().db_pool, &name).await {
+        Ok(true) => {}
+        Ok(false) => {
+            respond_mistake!(&ctx, "There is no tag with that name.");
+        }
+        Err(e) => {
+            respond_error!("Failed to delete the tag", e, &ctx);
+        }
+    }
+
+    // the questions answered by the tag were deleted along with it.
+    ctx.data().auto_responder.reload_questions().await;
+
+    if let Err(e) = autocomplete::refresh_tags(ctx.data()).await {
+        tracing::error!("Failed to refresh tag names for autocomplete: {e:#?}");
+    }
+
+    tracing::info!("{} deleted tag {name}", ctx.author().name);
+
+    ctx.say(format!("Deleted the tag {}.", inline_code(&name)))
+        .await?;
+
+    Ok(())
+}
+
+/// Questions in the help channels that get answered with a tag automatically.
+#[poise::command(
+    slash_command,
+    guild_only = true,
+    check = "is_moderator",
+    subcommands("add", "remove"),
+    subcommand_required
+)]
+pub async fn question(_: Context<'_>) -> anyhow::Result<()> {
+    Ok(())
+}
+
+/// Answer a question with a tag whenever it gets asked in a help channel.
+#[poise::command(
+    slash_command,
+    guild_only = true,
+    check = "is_moderator",
+    ephemeral = true
+)]
+pub async fn add(
+    ctx: Context<'_>,
+    #[description = "The tag that answers the question."]
+    #[autocomplete = "autocomplete_tag"]
+    name: String,
+    #[description = "The question as people usually ask it, e.g. how to disable a rule in one file."]
+    // autocomplete choices are limited to 100 characters, keeps the question removable.
+    #[max_length = 100]
+    question: String,
+) -> anyhow::Result<()> {
+    ctx.defer_ephemeral().await?;
+
+    let name = normalize_name(&name);
+    let question = question.trim();
+
+    match tags::get(&ctx.data().db_pool, &name).await {
+        Ok(Some(_)) => {}
+        Ok(None) => {
+            respond_mistake!(&ctx, "There is no tag with that name.");
+        }
+        Err(e) => {
+            respond_error!("Failed to fetch the tag", e, &ctx);
+        }
+    }
+
+    match faq_questions::insert(&ctx.data().db_pool, question, &name).await {
+        Ok(true) => {}
+        Ok(false) => {
+            respond_mistake!(&ctx, "That question is already answered by a tag.");
+        }
+        Err(e) => {
+            respond_error!("Failed to save the question", e, &ctx);
+        }
+    }
+
+    ctx.data().auto_responder.reload_questions().await;
+
+    tracing::info!(
+        "{} added question \"{question}\" to tag {name}",
+        ctx.author().name
+    );
+
+    ctx.say(format!(
+        "Questions like {} now get answered with the tag {}.",
+        inline_code(question),
+        inline_code(&name)
+    ))
+    .await?;
+
+    Ok(())
+}
+
+/// Stop answering a question automatically.
+#[poise::command(
+    slash_command,
+    guild_only = true,
+    check = "is_moderator",
+    ephemeral = true
+)]
+pub async fn remove(
+    ctx: Context<'_>,
+    #[description = "The question to stop answering."]
+    #[autocomplete = "autocomplete_question"]
+    question: String,
+) -> anyhow::Result<()> {
+    ctx.defer_ephemeral().await?;
+
+    match faq_questions::delete(&ctx.data().db_pool, question.trim()).await {
+        Ok(true) => {}
+        Ok(false) => {
+            respond_mistake!(&ctx, "That question isn't answered automatically.");
+        }
+        Err(e) => {
+            respond_error!("Failed to remove the question", e, &ctx);
+        }
+    }
+
+    ctx.data().auto_responder.reload_questions().await;
+
+    tracing::info!(
+        "{} removed question \"{}\"",
+        ctx.author().name,
+        question.trim()
+    );
+
+    ctx.say(format!(
+        "Stopped answering {} automatically.",
+        inline_code(question.trim())
+    ))
+    .await?;
+
+    Ok(())
+}
+
+async fn autocomplete_question<'a>(
+    ctx: Context<'_>,
+    partial: &'a str,
+) -> impl Iterator<Item = String> + 'a {
+    let questions =
+        match faq_questions::questions_matching(&ctx.data().db_pool, partial.trim(), 25).await {
+            Ok(questions) => questions,
+            Err(e) => {
+                tracing::error!("Failed to fetch questions for autocomplete: {e:#?}");
+                Vec::new()
+            }
+        };
+
+    questions.into_iter()
+}
+
+async fn autocomplete_tag<'a>(
+    ctx: Context<'_>,
+    partial: &'a str,
+) -> impl Iterator<Item = String> + 'a {
+    ctx.data().autocomplete.tags.suggest(partial).into_iter()
+}
+
+fn display_tag(tag: &Tag) -> String {
+    let uses = if tag.uses == 1 { "use" } else { "uses" };
+
+    format!("{} \u{2013} {} {uses}", inline_code(&tag.name), tag.uses)
+}
+
+pub fn normalize_name(name: &str) -> String {
+    name.trim().to_lowercase()
+}
+
+/// Normalizes a name for a new tag, `None` if it contains anything but letters, digits and dashes.
+pub fn valid_name(name: &str) -> Option<String> {
+    let name = normalize_name(name);
+
+    let valid = !name.is_empty()
+        && name.len() <= MAX_NAME_LENGTH
+        && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '-');
+
+    valid.then_some(name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn validates_tag_names() {
+        assert_eq!(
+            valid_name(" VSCode-Setup "),
+            Some("vscode-setup".to_string())
+        );
+        assert_eq!(valid_name("two words"), None);
+        assert_eq!(valid_name(""), None);
+        assert_eq!(valid_name(&"a".repeat(33)), None);
+    }
+}
@@ -0,0 +1,135 @@
+use crate::{
+    checks::is_maintainer, database::triage_actions, respond_error, util::format::inline_code,
+    Context,
+};
+
+#[derive(Debug, Clone, Copy, poise::ChoiceParameter)]
+pub enum LabelAction {
+    Add,
+    Remove,
+}
+
+#[derive(Debug, Clone, Copy, poise::ChoiceParameter)]
+pub enum CloseReason {
+    Completed,
+    #[name = "Not Planned"]
+    NotPlanned,
+}
+
+impl CloseReason {
+    /// The `state_reason` github expects.
+    fn as_state_reason(self) -> &'static str {
+        match self {
+            Self::Completed => "completed",
+            Self::NotPlanned => "not_planned",
+        }
+    }
+}
+
+/// Maintainer commands to triage issues on GitHub.
+#[poise::command(
+    slash_command,
+    guild_only = true,
+    check = "is_maintainer",
+    subcommands("label", "assign", "close"),
+    subcommand_required
+)]
+pub async fn triage(_: Context<'_>) -> anyhow::Result<()> {
+    Ok(())
+}
+
+/// Add or remove a label on an issue.
+#[poise::command(slash_command, guild_only = true, check = "is_maintainer")]
+pub async fn label(
+    ctx: Context<'_>,
+    #[description = "The number of the issue."]
+    #[min = 1]
+    issue: u64,
+    #[description = "Whether to add or remove the label."] action: LabelAction,
+    #[description = "The name of the label."] label: String,
+) -> anyhow::Result<()> {
+    ctx.defer_ephemeral().await?;
+
+    let github = &ctx.data().github;
+
+    let (result, action_name) = match action {
+        LabelAction::Add => (github.add_labels(issue, &[label.as_str()]).await, "label"),
+        LabelAction::Remove => (github.remove_label(issue, &label).await, "unlabel"),
+    };
+
+    complete(ctx, issue, action_name, &label, result).await
+}
+
+/// Assign a GitHub user to an issue.
+#[poise::command(slash_command, guild_only = true, check = "is_maintainer")]
+pub async fn assign(
+    ctx: Context<'_>,
+    #[description = "The number of the issue."]
+    #[min = 1]
+    issue: u64,
+    #[description = "The GitHub login of the assignee."] login: String,
+) -> anyhow::Result<()> {
+    ctx.defer_ephemeral().await?;
+
+    let login = login.trim().trim_start_matches('@');
+    let result = ctx.data().github.add_assignees(issue, &[login]).await;
+
+    complete(ctx, issue, "assign", login, result).await
+}
+
+/// Close an issue.
+#[poise::command(slash_command, guild_only = true, check = "is_maintainer")]
+pub async fn close(
+    ctx: Context<'_>,
+    #[description = "The number of the issue."]
+    #[min = 1]
+    issue: u64,
+    #[description = "Why the issue gets closed."] reason: CloseReason,
+) -> anyhow::Result<()> {
+    ctx.defer_ephemeral().await?;
+
+    let reason = reason.as_state_reason();
+    let result = ctx.data().github.close_issue(issue, reason).await;
+
+    complete(ctx, issue, "close", reason, result).await
+}
+
+/// Records a successful action in the audit log and reports the outcome to the maintainer.
+async fn complete(
+    ctx: Context<'_>,
+    issue: u64,
+    action: &str,
+    detail: &str,
+    result: anyhow::Result<()>,
+) -> anyhow::Result<()> {
+    if let Err(e) = result {
+        respond_error!("Failed to update the issue on github", e, &ctx);
+    }
+
+    tracing::info!(
+        "{} triaged issue #{issue}: {action} {detail}",
+        ctx.author().name
+    );
+
+    if let Err(e) = triage_actions::insert(
+        &ctx.data().db_pool,
+        issue as i64,
+        action,
+        detail,
+        ctx.author().id.get() as i64,
+    )
+    .await
+    {
+        tracing::error!("Failed to record triage action: {e:#?}");
+    }
+
+    ctx.say(format!(
+        "Applied {} with {} to [issue #{issue}](https://github.com/{}/issues/{issue}).",
+        inline_code(action),
+        inline_code(detail),
+        ctx.data().github.repository()
+    ))
+    .await?;
+
+    Ok(())
+}
@@ -0,0 +1,107 @@
+use chrono::{Duration, Utc};
+use poise::{serenity_prelude as serenity, CreateReply};
+use serenity::CreateAttachment;
+
+use crate::{github_api::Issue, respond_error, Context};
+
+/// Sections of the draft in the order they are rendered. Pull requests land in the first section with a matching label.
+const SECTIONS: [(&str, &[&str]); 3] = [
+    ("Formatter", &["formatter"]),
+    ("Linter", &["linter", "lint"]),
+    ("Parser", &["parser"]),
+];
+
+/// Draft a "This Week in Biome" post from the pull requests merged in the last 7 days.
+#[poise::command(slash_command, guild_only = true, ephemeral = true)]
+pub async fn twib(ctx: Context<'_>) -> anyhow::Result<()> {
+    ctx.defer_ephemeral().await?;
+
+    let since = Utc::now() - Duration::days(7);
+
+    let results = match ctx
+        .data()
+        .github
+        .search_issues(
+            &format!("is:pr is:merged merged:>={}", since.format("%Y-%m-%d")),
+            100,
+        )
+        .await
+    {
+        Ok(results) => results,
+        Err(e) => {
+            respond_error!("Failed to fetch merged pull requests from github", e, &ctx);
+        }
+    };
+
+    let mut draft = build_draft(&results.items, since, Utc::now());
+
+    if results.total_count > results.items.len() as u64 {
+        draft.push_str(&format!(
+            "\n_Only the first {} of {} merged pull requests are included._\n",
+            results.items.len(),
+            results.total_count
+        ));
+    }
+
+    let attachment = CreateAttachment::bytes(draft, "this-week-in-biome.md");
+
+    ctx.send(
+        CreateReply::default()
+            .content(format!(
+                "Drafted from {} merged pull requests.",
+                results.items.len()
+            ))
+            .attachment(attachment),
+    )
+    .await?;
+
+    Ok(())
+}
+
+fn build_draft(
+    pull_requests: &[Issue],
+    since: chrono::DateTime<Utc>,
+    until: chrono::DateTime<Utc>,
+) -> String {
+    let mut sections = vec![Vec::new(); SECTIONS.len() + 1];
+
+    for pull_request in pull_requests {
+        let section = SECTIONS
+            .iter()
+            .position(|(_, keywords)| {
+                pull_request.labels.iter().any(|label| {
+                    let label = label.name.to_lowercase();
+                    keywords.iter().any(|keyword| label.contains(keyword))
+                })
+            })
+            .unwrap_or(SECTIONS.len());
+
+        let author = pull_request
+            .user
+            .as_ref()
+            .map_or(String::new(), |user| format!(" by @{}", user.login));
+
+        sections[section].push(format!(
+            "- {} ([#{}]({})){author}",
+            pull_request.title, pull_request.number, pull_request.html_url
+        ));
+    }
+
+    let mut draft = format!(
+        "# This Week in Biome ({} \u{2013} {})\n",
+        since.format("%b %-d"),
+        until.format("%b %-d, %Y")
+    );
+
+    let titles = SECTIONS.iter().map(|(title, _)| *title).chain(["Other"]);
+
+    for (title, entries) in titles.zip(sections) {
+        if entries.is_empty() {
+            continue;
+        }
+
+        draft.push_str(&format!("\n## {title}\n\n{}\n", entries.join("\n")));
+    }
+
+    draft
+}
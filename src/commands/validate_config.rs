@@ -0,0 +1,154 @@
+use poise::{serenity_prelude as serenity, CreateReply, Modal};
+use serenity::Attachment;
+
+use crate::{
+    config_schema::{cached_config_schema, IssueKind, ValidationIssue},
+    respond_error, respond_mistake,
+    util::{
+        embeds::default_embed,
+        format::{inline_code, truncate},
+        parse::strip_json_comments,
+    },
+    Context,
+};
+
+const MAX_ISSUES: usize = 20;
+/// Configurations larger than this are certainly not a `biome.json`.
+const MAX_CONFIG_SIZE: u32 = 256 * 1024;
+
+#[derive(Debug, Modal)]
+#[name = "Validate biome.json"]
+struct ConfigModal {
+    #[name = "biome.json"]
+    #[placeholder = "Paste the contents of your biome.json here."]
+    #[paragraph]
+    #[max_length = 4000]
+    config: String,
+}
+
+/// Check a biome.json against the configuration schema.
+#[poise::command(slash_command, guild_only = true, rename = "validate-config")]
+pub async fn validate_config(
+    ctx: Context<'_>,
+    #[description = "Your biome.json, opens a text field to paste it into when left empty."]
+    file: Option<Attachment>,
+) -> anyhow::Result<()> {
+    let config = match file {
+        Some(file) => {
+            ctx.defer().await?;
+
+            if file.size > MAX_CONFIG_SIZE {
+                respond_mistake!(&ctx, "That file is too large to be a biome.json.");
+            }
+
+            match file.download().await.map(String::from_utf8) {
+                Ok(Ok(config)) => config,
+                Ok(Err(_)) => {
+                    respond_mistake!(&ctx, "That file isn't valid UTF-8.");
+                }
+                Err(e) => {
+                    respond_error!("Failed to download the attachment", e, &ctx);
+                }
+            }
+        }
+        None => {
+            let poise::Context::Application(app_ctx) = ctx else {
+                return Ok(());
+            };
+
+            let Some(modal) = ConfigModal::execute(app_ctx).await? else {
+                return Ok(());
+            };
+
+            modal.config
+        }
+    };
+
+    let schema = match cached_config_schema(ctx.data()).await {
+        Ok(schema) => schema,
+        Err(e) => {
+            respond_error!("Failed to fetch the configuration schema", e, &ctx);
+        }
+    };
+
+    let embed = match serde_json::from_str(&strip_json_comments(&config)) {
+        Ok(config) => {
+            let issues = schema.validate(&config);
+
+            if issues.is_empty() {
+                default_embed(ctx.author())
+                    .title("\u{2705} Valid configuration")
+                    .description(format!(
+                        "No problems found against the schema of Biome {}.",
+                        schema.version
+                    ))
+            } else {
+                default_embed(ctx.author())
+                    .title(format!(
+                        "\u{26A0}\u{FE0F} Found {} problem{}",
+                        issues.len(),
+                        if issues.len() == 1 { "" } else { "s" }
+                    ))
+                    .description(display_issues(&issues))
+                    .field("Schema Version", &schema.version, true)
+            }
+        }
+        Err(e) => default_embed(ctx.author())
+            .title("\u{274C} Invalid JSON")
+            .description(format!("Your configuration can't be parsed: {e}.")),
+    };
+
+    ctx.send(CreateReply::default().embed(embed)).await?;
+
+    Ok(())
+}
+
+fn display_issues(issues: &[ValidationIssue]) -> String {
+    let mut lines = issues
+        .iter()
+        .take(MAX_ISSUES)
+        .map(|issue| {
+            format!(
+                "- {}: {}",
+                inline_code(&issue.path),
+                display_kind(&issue.kind)
+            )
+        })
+        .collect::<Vec<_>>();
+
+    if issues.len() > MAX_ISSUES {
+        lines.push(format!("_and {} more_", issues.len() - MAX_ISSUES));
+    }
+
+    truncate(&lines.join("\n"), 4000)
+}
+
+fn display_kind(kind: &IssueKind) -> String {
+    match kind {
+        IssueKind::UnknownKey => "unknown key".to_string(),
+        IssueKind::WrongType { expected, found } if expected.is_empty() => {
+            format!("unexpected {}", inline_code(*found))
+        }
+        IssueKind::WrongType { expected, found } => format!(
+            "expected {}, found {}",
+            expected
+                .iter()
+                .map(inline_code)
+                .collect::<Vec<_>>()
+                .join(" or "),
+            inline_code(*found)
+        ),
+        IssueKind::InvalidValue { allowed } => format!(
+            "must be one of {}",
+            truncate(
+                &allowed
+                    .iter()
+                    .map(inline_code)
+                    .collect::<Vec<_>>()
+                    .join(", "),
+                300
+            )
+        ),
+        IssueKind::Deprecated => "deprecated, check the docs for its replacement".to_string(),
+    }
+}
@@ -0,0 +1,113 @@
+use poise::serenity_prelude as serenity;
+use serenity::{
+    ButtonStyle, ComponentInteraction, CreateActionRow, CreateButton, CreateEmbed,
+    CreateInteractionResponse, CreateInteractionResponseMessage, CreateMessage,
+};
+
+use crate::{
+    checks::is_maintainer, database::verifications, respond_error, respond_mistake,
+    util::embeds::EmbedColor, Context, Data,
+};
+
+/// Custom id of the verify button.
+const BUTTON_ID: &str = "verify";
+
+/// Post the verify button that gives members access to the server.
+#[poise::command(
+    slash_command,
+    guild_only = true,
+    rename = "setup-verification",
+    check = "is_maintainer",
+    ephemeral = true
+)]
+pub async fn setup_verification(
+    ctx: Context<'_>,
+    #[description = "The text above the button, explains what members agree to by verifying."]
+    #[max_length = 2000]
+    message: Option<String>,
+) -> anyhow::Result<()> {
+    let verified = ctx
+        .guild_id()
+        .and_then(|guild_id| ctx.data().guild(guild_id))
+        .and_then(|guild| guild.roles.verified);
+
+    if verified.is_none() {
+        respond_mistake!(
+            &ctx,
+            "There is no verified role configured, members couldn't be given access."
+        );
+    }
+
+    let embed = CreateEmbed::new()
+        .color(EmbedColor::Biome)
+        .title("Verification")
+        .description(message.unwrap_or_else(|| {
+            "Read the rules, then press the button below to get access to the server.".to_string()
+        }));
+
+    let components = vec![CreateActionRow::Buttons(vec![CreateButton::new(BUTTON_ID)
+        .label("Verify")
+        .emoji('\u{2705}')
+        .style(ButtonStyle::Success)])];
+
+    if let Err(e) = ctx
+        .channel_id()
+        .send_message(
+            ctx,
+            CreateMessage::new().embed(embed).components(components),
+        )
+        .await
+    {
+        respond_error!("Failed to post the verification message", e, &ctx);
+    }
+
+    ctx.say("Posted the verification message.").await?;
+
+    Ok(())
+}
+
+/// Handles presses of the verify button, which keeps working across restarts.
+pub async fn handle_component(
+    component: &ComponentInteraction,
+    ctx: &serenity::Context,
+    data: &Data,
+) -> anyhow::Result<()> {
+    let verified = component
+        .guild_id
+        .and_then(|guild_id| data.guild(guild_id))
+        .and_then(|guild| guild.roles.verified);
+
+    let (Some(role_id), Some(member)) = (verified, &component.member) else {
+        return respond_ephemeral(component, ctx, "Verification is currently disabled.").await;
+    };
+
+    if member.roles.contains(&role_id) {
+        return respond_ephemeral(component, ctx, "You are already verified.").await;
+    }
+
+    member.add_role(ctx, role_id).await?;
+    verifications::insert(&data.db_pool, member.user.id.get() as i64).await?;
+
+    tracing::info!("{} verified.", member.user.name);
+
+    respond_ephemeral(component, ctx, "You are verified, welcome!").await
+}
+
+async fn respond_ephemeral(
+    component: &ComponentInteraction,
+    ctx: &serenity::Context,
+    content: &str,
+) -> anyhow::Result<()> {
+    component
+        .create_response(
+            ctx,
+            CreateInteractionResponse::Message(
+                CreateInteractionResponseMessage::new()
+                    .content(content)
+                    .ephemeral(true),
+            ),
+        )
+        .await?;
+
+    Ok(())
+}
@@ -0,0 +1,73 @@
+use poise::CreateReply;
+
+use crate::{
+    npm_api::{self, BIOME_PACKAGE},
+    respond_error,
+    util::{embeds::default_embed, format::inline_code},
+    Context,
+};
+
+/// Show the latest published versions of Biome.
+#[poise::command(slash_command, guild_only = true)]
+pub async fn version(ctx: Context<'_>) -> anyhow::Result<()> {
+    ctx.defer().await?;
+
+    let data = ctx.data();
+
+    let (metadata, release) = tokio::join!(
+        data.npm_package
+            .get_or_refresh(|| npm_api::package_metadata(BIOME_PACKAGE)),
+        data.github.latest_release(),
+    );
+
+    let metadata = match metadata {
+        Ok(metadata) => metadata,
+        Err(e) => {
+            respond_error!("Failed to fetch the published versions from npm", e, &ctx);
+        }
+    };
+
+    let release = match release {
+        Ok(release) => release,
+        Err(e) => {
+            tracing::error!("Failed to fetch the latest release from github: {e:#?}");
+            None
+        }
+    };
+
+    let stable = metadata.dist_tags.get("latest");
+    let nightly = metadata.dist_tags.get("nightly");
+    let github_version = release.as_ref().map(|release| release.version());
+
+    let status = match (stable, github_version) {
+        (Some(stable), Some(github_version)) if stable == github_version => {
+            "\u{2705} npm and GitHub are in sync.".to_string()
+        }
+        (Some(stable), Some(github_version)) => format!(
+            "\u{26A0}\u{FE0F} Out of sync: npm `latest` is {} but the latest GitHub release is {}.",
+            inline_code(stable),
+            inline_code(github_version)
+        ),
+        _ => "\u{2754} Couldn't compare npm with GitHub.".to_string(),
+    };
+
+    let embed = default_embed(ctx.author())
+        .title(format!("Latest versions of {BIOME_PACKAGE}"))
+        .url(format!("https://www.npmjs.com/package/{BIOME_PACKAGE}"))
+        .description(status)
+        .field("Stable", display_version(stable.map(String::as_str)), true)
+        .field(
+            "Nightly",
+            display_version(nightly.map(String::as_str)),
+            true,
+        )
+        .field("GitHub Release", display_version(github_version), true);
+
+    ctx.send(CreateReply::default().embed(embed)).await?;
+
+    Ok(())
+}
+
+fn display_version(version: Option<&str>) -> String {
+    version.map_or("Unknown".to_string(), inline_code)
+}
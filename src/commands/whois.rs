@@ -0,0 +1,127 @@
+use poise::{serenity_prelude as serenity, CreateReply};
+use serenity::{Mentionable, User, UserId};
+
+use crate::{
+    database::github_links,
+    respond_error, respond_mistake,
+    util::{
+        embeds::default_embed,
+        format::{time, truncate, TimestampStyle},
+    },
+    Context,
+};
+
+/// How many merged pull requests are listed.
+const RECENT_CONTRIBUTIONS: u8 = 5;
+
+/// Find out which github account belongs to a member, or which member to a github account.
+#[poise::command(slash_command, guild_only = true)]
+pub async fn whois(
+    ctx: Context<'_>,
+    #[description = "The member to look up."] user: Option<User>,
+    #[description = "The github login to look up."]
+    #[max_length = 39]
+    github: Option<String>,
+) -> anyhow::Result<()> {
+    let pool = &ctx.data().db_pool;
+
+    let link = match (&user, &github) {
+        (Some(user), None) => github_links::get(pool, user.id.get() as i64).await,
+        (None, Some(login)) => github_links::by_login(pool, login.trim_start_matches('@')).await,
+        _ => {
+            respond_mistake!(&ctx, "Look up either a member or a github login.");
+        }
+    };
+
+    let link = match link {
+        Ok(Some(link)) => link,
+        Ok(None) if user.is_some() => {
+            respond_mistake!(&ctx, "That member hasn't linked a github account.");
+        }
+        Ok(None) => {
+            respond_mistake!(&ctx, "No member linked that github account.");
+        }
+        Err(e) => {
+            respond_error!("Failed to fetch the linked account", e, &ctx);
+        }
+    };
+
+    ctx.defer().await?;
+
+    let github_api = &ctx.data().github;
+    let (is_contributor, merged) = tokio::join!(
+        github_api.has_commits(&link.github_login),
+        github_api.search_issues(
+            &format!("is:pr is:merged author:{}", link.github_login),
+            RECENT_CONTRIBUTIONS
+        ),
+    );
+
+    let mut embed = default_embed(ctx.author())
+        .title(format!("Who is {}", link.github_login))
+        .url(format!("https://github.com/{}", link.github_login))
+        .field(
+            "Member",
+            UserId::new(link.user_id as u64).mention().to_string(),
+            true,
+        )
+        .field(
+            "Github",
+            format!("[{0}](https://github.com/{0})", link.github_login),
+            true,
+        )
+        .field(
+            "Linked",
+            time(link.linked_at, TimestampStyle::Relative),
+            true,
+        );
+
+    let is_contributor = match is_contributor {
+        Ok(true) => "Yes",
+        Ok(false) => "No",
+        Err(e) => {
+            tracing::error!(
+                "Failed to check the commits of {}: {e:#?}",
+                link.github_login
+            );
+            "Unavailable"
+        }
+    };
+
+    embed = embed.field("Contributor", is_contributor, true);
+
+    match merged {
+        Ok(merged) => {
+            embed = embed.field("Merged pull requests", merged.total_count.to_string(), true);
+
+            if !merged.items.is_empty() {
+                let recent = merged
+                    .items
+                    .iter()
+                    .map(|pull_request| {
+                        format!(
+                            "[#{}]({}) {}",
+                            pull_request.number,
+                            pull_request.html_url,
+                            truncate(&pull_request.title, 80)
+                        )
+                    })
+                    .collect::<Vec<_>>()
+                    .join("\n");
+
+                embed = embed.field("Recent contributions", recent, false);
+            }
+        }
+        Err(e) => {
+            tracing::error!(
+                "Failed to search the pull requests of {}: {e:#?}",
+                link.github_login
+            );
+            embed = embed.field("Merged pull requests", "Unavailable", true);
+        }
+    }
+
+    ctx.send(CreateReply::default().embed(embed)).await?;
+
+    Ok(())
+}
@@ -1,27 +1,277 @@
 use poise::serenity_prelude as serenity;
-use serde::Deserialize;
-use serenity::{GuildId, RoleId};
+use std::{collections::BTreeMap, fmt::Display, path::Path};
 
-use crate::commands::embed::TargetChannelWebhook;
+use anyhow::Context as _;
+use reqwest::Url;
+use serde::{
+    de::{
+        value::{MapDeserializer, SeqDeserializer},
+        DeserializeOwned, DeserializeSeed, IntoDeserializer, MapAccess, Visitor,
+    },
+    Deserialize, Deserializer,
+};
+use serde_json::{Map, Value};
+use serenity::{ChannelId, GuildId, RoleId};
+
+use crate::{commands::embed::TargetChannelWebhook, util::ip_range::IpRange};
+
+/// Environment variables starting with this override the config, nested fields are separated by
+/// `__` like `BIOMEBOT_BOT__TOKEN` for `bot.token`.
+const ENV_PREFIX: &str = "BIOMEBOT_";
+const DEFAULT_PATH: &str = "config.json";
+/// A field with this suffix names a file the field without it is read from, like `bot.token_file`
+/// for `bot.token`.
+const FILE_SUFFIX: &str = "_file";
+/// Shorter admin tokens are too easy to guess.
+const MIN_ADMIN_TOKEN_LENGTH: usize = 32;
 
 #[derive(Debug, Clone, Deserialize)]
 pub struct Config {
     pub bot: BotConfig,
-    pub roles: RoleConfig,
+    /// The `roles`, `webhooks` and `channels` of the main server, `bot.guild_id`.
+    #[serde(flatten)]
+    pub main_guild: GuildConfig,
+    /// Further servers the bot serves, like a staging server, keyed by their id. Features that
+    /// are configured outside of `roles`, `webhooks` and `channels` only run in the main server.
+    #[serde(default)]
+    pub guilds: BTreeMap<GuildId, GuildConfig>,
     pub github: GithubConfig,
     pub webserver: WebserverConfig,
     pub database: DatabaseConfig,
-    pub webhooks: WebhookConfig,
+    /// The biome cli used to format and lint snippets. Those commands are disabled when unset.
+    pub biome: Option<BiomeCliConfig>,
+    #[serde(default)]
+    pub cache: CacheConfig,
+    /// Answers known questions in help channels. Disabled when unset.
+    pub auto_responder: Option<AutoResponderConfig>,
+    /// The forum channels people ask for help in.
+    #[serde(default)]
+    pub help_forums: Vec<HelpForumConfig>,
+    /// Logs edited and deleted messages. Disabled when unset.
+    pub message_log: Option<MessageLogConfig>,
+    /// Greets new members. Disabled when unset.
+    pub welcome: Option<WelcomeConfig>,
+    /// Roles every new member gets. Disabled when unset.
+    pub auto_roles: Option<AutoRoleConfig>,
+    /// Removes messages posted in many channels at once. Disabled when unset.
+    pub anti_spam: Option<AntiSpamConfig>,
+    /// Keeps the blocklist up to date with a public list of phishing domains. Disabled when unset.
+    pub phishing_feed: Option<PhishingFeedConfig>,
+    /// Removes invites to other servers. Disabled when unset.
+    pub invite_filter: Option<InviteFilterConfig>,
+    /// Flags accounts that were created shortly before joining. Disabled when unset.
+    pub new_accounts: Option<NewAccountConfig>,
+    /// Starts a lockdown when many members join at once. Disabled when unset.
+    pub raid_detection: Option<RaidDetectionConfig>,
+    /// AutoMod rules the bot keeps in sync. Rules are left alone when unset.
+    pub automod: Option<AutoModConfig>,
+    /// Gives github sponsors who linked their account a role. Disabled when unset.
+    pub sponsors: Option<SponsorConfig>,
+    /// Replies to issue and pull request references like `#1234`. Disabled when unset.
+    pub issue_links: Option<IssueLinkConfig>,
+    /// Shows the lines github permalinks point to. Disabled when unset.
+    pub code_snippets: Option<CodeSnippetConfig>,
+    /// Uploads long code blocks to a paste service. Disabled when unset.
+    pub paste: Option<PasteConfig>,
+    /// Publishes messages in announcement channels to the servers following them. Disabled when
+    /// unset.
+    pub auto_publish: Option<AutoPublishConfig>,
 }
 
 impl Config {
-    pub fn load() -> Self {
-        let config_file = std::fs::File::open("config.json").expect("Failed to open config.json");
-        let reader = std::io::BufReader::new(config_file);
+    /// Reads the file passed with `--config <path>` or `config.json`, as toml if the file ends
+    /// with `.toml` and as json otherwise, and applies the overrides from the environment on top.
+    /// Without `config.json` the whole config has to come from the environment. Secrets can be
+    /// read from mounted files by setting `<field>_file` instead of the field.
+    pub fn load() -> anyhow::Result<Self> {
+        let passed_path = config_path(std::env::args().skip(1))?;
+        let path = passed_path.as_deref().unwrap_or(DEFAULT_PATH);
+
+        let mut config = match std::fs::read_to_string(path) {
+            Ok(config) => parse(path, &config)?,
+            // only a config file that was passed explicitly has to exist.
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound && passed_path.is_none() => {
+                Value::Object(Map::new())
+            }
+            Err(e) => return Err(e).context(format!("Failed to read {path}")),
+        };
+
+        apply_env_overrides(&mut config, std::env::vars());
+        read_secret_files(&mut config, "", &|path| std::fs::read_to_string(path))?;
+
+        // lists every section with an invalid field, parsing the whole config stops at the first.
+        let mut problems = parse_problems(&config);
+
+        if problems.is_empty() {
+            let config =
+                Self::deserialize(EnvValue(config)).context("Failed to load the config")?;
+            problems = config.validate();
+
+            if problems.is_empty() {
+                return Ok(config);
+            }
+        }
+
+        anyhow::bail!(
+            "The config has {} problem(s):\n{}",
+            problems.len(),
+            problems
+                .iter()
+                .map(|problem| format!("- {problem}"))
+                .collect::<Vec<_>>()
+                .join("\n")
+        );
+    }
+
+    /// Everything in the config that would only fail once the bot uses it, so the bot doesn't
+    /// start with it.
+    pub fn validate(&self) -> Vec<ConfigProblem> {
+        let mut problems = Vec::new();
+        let mut check = |path: &str, problem: Option<String>| {
+            if let Some(message) = problem {
+                problems.push(ConfigProblem {
+                    path: path.to_string(),
+                    message,
+                });
+            }
+        };
+
+        check(
+            "bot.token",
+            self.bot
+                .token
+                .trim()
+                .is_empty()
+                .then(|| "can't be empty".to_string()),
+        );
+        check(
+            "bot.guild_id",
+            (self.bot.guild_id.get() == 0).then(|| "can't be 0".to_string()),
+        );
+        if self.guilds.contains_key(&self.bot.guild_id) {
+            check(
+                &format!("guilds.{}", self.bot.guild_id),
+                Some("is the main server, its config belongs at the top level".to_string()),
+            );
+        }
+
+        for (guild_id, guild) in self.guilds() {
+            let prefix = if guild_id == self.bot.guild_id {
+                String::new()
+            } else {
+                format!("guilds.{guild_id}.")
+            };
+
+            check(
+                &format!("{prefix}webhooks.rules"),
+                webhook_problem(&guild.webhooks.rules),
+            );
+            check(
+                &format!("{prefix}webhooks.roles"),
+                webhook_problem(&guild.webhooks.roles),
+            );
+        }
+
+        check(
+            "github.activity_webhook",
+            webhook_problem(&self.github.activity_webhook),
+        );
+        check(
+            "github.issues_webhook",
+            webhook_problem(&self.github.issues_webhook),
+        );
+        check(
+            "github.repository",
+            (self.github.repository.split('/').count() != 2
+                || self.github.repository.split('/').any(str::is_empty))
+            .then(|| "has to look like `owner/name`".to_string()),
+        );
+
+        if let Some(oauth) = &self.github.oauth {
+            check(
+                "github.oauth.public_url",
+                url_problem(&oauth.public_url, &["https", "http"]),
+            );
+        }
 
-        serde_json::from_reader(reader).expect("Failed to parse config.json")
+        check(
+            "github.max_payload_bytes",
+            (self.github.max_payload_bytes == 0).then(|| "can't be 0".to_string()),
+        );
+        check(
+            "database.url",
+            url_problem(&self.database.url, &["postgres", "postgresql"]),
+        );
+        check(
+            "webserver.port",
+            (self.webserver.port == 0).then(|| "can't be 0".to_string()),
+        );
+        if let Some(admin_token) = &self.webserver.admin_token {
+            check(
+                "webserver.admin_token",
+                (admin_token.len() < MIN_ADMIN_TOKEN_LENGTH).then(|| {
+                    format!("has to be at least {MIN_ADMIN_TOKEN_LENGTH} characters long")
+                }),
+            );
+        }
+
+        check(
+            "webserver.rate_limit.burst",
+            (self.webserver.rate_limit.burst == 0).then(|| "can't be 0".to_string()),
+        );
+        check(
+            "webserver.rate_limit.per_minute",
+            (self.webserver.rate_limit.per_minute == 0).then(|| "can't be 0".to_string()),
+        );
+
+        if let Some(biome) = &self.biome {
+            check(
+                "biome.sandbox",
+                biome.sandbox.is_empty().then(|| {
+                    "can't be empty, snippets are never run without a sandbox".to_string()
+                }),
+            );
+        }
+
+        if let Some(phishing_feed) = &self.phishing_feed {
+            check(
+                "phishing_feed.url",
+                url_problem(&phishing_feed.url, &["https", "http"]),
+            );
+        }
+
+        problems
     }
 
+    /// The config of a server the bot serves, `None` for any other server.
+    pub fn guild(&self, guild_id: GuildId) -> Option<&GuildConfig> {
+        if guild_id == self.bot.guild_id {
+            Some(&self.main_guild)
+        } else {
+            self.guilds.get(&guild_id)
+        }
+    }
+
+    /// Every server the bot serves, the main server first.
+    pub fn guilds(&self) -> impl Iterator<Item = (GuildId, &GuildConfig)> {
+        std::iter::once((self.bot.guild_id, &self.main_guild)).chain(
+            self.guilds
+                .iter()
+                .map(|(guild_id, guild)| (*guild_id, guild)),
+        )
+    }
+}
+
+/// What differs between the servers the bot serves.
+#[derive(Debug, Clone, Deserialize)]
+pub struct GuildConfig {
+    pub roles: RoleConfig,
+    pub webhooks: WebhookConfig,
+    #[serde(default)]
+    pub channels: ChannelConfig,
+}
+
+impl GuildConfig {
     pub fn webhook_url(&self, channel: TargetChannelWebhook) -> &str {
         match channel {
             TargetChannelWebhook::Roles => &self.webhooks.roles,
@@ -30,15 +280,430 @@ impl Config {
     }
 }
 
+/// A field of the config with a value the bot can't work with.
+#[derive(Debug, PartialEq, Eq)]
+pub struct ConfigProblem {
+    pub path: String,
+    pub message: String,
+}
+
+impl Display for ConfigProblem {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} {}", self.path, self.message)
+    }
+}
+
+/// The path passed with `--config <path>` or `--config=<path>`.
+fn config_path(mut args: impl Iterator<Item = String>) -> anyhow::Result<Option<String>> {
+    while let Some(arg) = args.next() {
+        if arg == "--config" {
+            return args
+                .next()
+                .map(Some)
+                .context("--config needs the path of the config file");
+        }
+
+        if let Some(path) = arg.strip_prefix("--config=") {
+            return Ok(Some(path.to_string()));
+        }
+    }
+
+    Ok(None)
+}
+
+fn parse(path: &str, config: &str) -> anyhow::Result<Value> {
+    let is_toml = Path::new(path)
+        .extension()
+        .is_some_and(|extension| extension.eq_ignore_ascii_case("toml"));
+
+    if is_toml {
+        toml::from_str(config).with_context(|| format!("Failed to parse {path}"))
+    } else {
+        serde_json::from_str(config).with_context(|| format!("Failed to parse {path}"))
+    }
+}
+
+fn url_problem(url: &str, schemes: &[&str]) -> Option<String> {
+    match Url::parse(url) {
+        Ok(url) if schemes.contains(&url.scheme()) => None,
+        Ok(_) => Some(format!("has to be a {} url", schemes.join(" or "))),
+        Err(e) => Some(format!("is not a valid url: {e}")),
+    }
+}
+
+fn webhook_problem(url: &str) -> Option<String> {
+    url_problem(url, &["https"]).or_else(|| {
+        let url = Url::parse(url).ok()?;
+
+        (!url.path().starts_with("/api/webhooks/")).then(|| {
+            "has to be a discord webhook url like `https://discord.com/api/webhooks/<id>/<token>`"
+                .to_string()
+        })
+    })
+}
+
+/// Replaces every `<field>_file` with `<field>` set to the content of the file, without the
+/// trailing newline most secret files end with. Setting both is a mistake.
+fn read_secret_files(
+    config: &mut Value,
+    path: &str,
+    read: &impl Fn(&str) -> std::io::Result<String>,
+) -> anyhow::Result<()> {
+    match config {
+        Value::Object(fields) => {
+            let file_fields = fields
+                .iter()
+                .filter_map(|(key, value)| {
+                    let field = key.strip_suffix(FILE_SUFFIX)?;
+                    Some((key.clone(), field.to_string(), value.as_str()?.to_string()))
+                })
+                .collect::<Vec<_>>();
+
+            for (key, field, file) in file_fields {
+                if fields.get(&field).is_some_and(|value| !value.is_null()) {
+                    anyhow::bail!("Set either {path}{field} or {path}{key}, not both");
+                }
+
+                let secret = read(&file).with_context(|| format!("Failed to read {path}{key}"))?;
+
+                fields.remove(&key);
+                fields.insert(field, Value::String(secret.trim_end().to_string()));
+            }
+
+            for (key, value) in fields {
+                read_secret_files(value, &format!("{path}{key}."), read)?;
+            }
+        }
+        Value::Array(items) => {
+            for (index, item) in items.iter_mut().enumerate() {
+                read_secret_files(item, &format!("{path}{index}."), read)?;
+            }
+        }
+        _ => {}
+    }
+
+    Ok(())
+}
+
+/// Reads every section of the config on its own, so an invalid field in one section doesn't hide
+/// those in the others. Missing sections are left to the parse of the whole config.
+fn parse_problems(config: &Value) -> Vec<ConfigProblem> {
+    // the servers are read one by one below, so their problems point at the server.
+    let mut sections = config.clone();
+    if let Some(sections) = sections.as_object_mut() {
+        sections.remove("guilds");
+    }
+
+    let mut problems = field_problems::<Config>(&sections, "");
+    // serde reads the flattened sections of the main server only after every other field, as one.
+    problems.extend(field_problems::<GuildConfig>(&sections, ""));
+
+    let guilds = config
+        .get("guilds")
+        .and_then(Value::as_object)
+        .into_iter()
+        .flatten();
+
+    for (guild_id, guild) in guilds {
+        problems.extend(field_problems::<GuildConfig>(
+            guild,
+            &format!("guilds.{guild_id}."),
+        ));
+    }
+
+    problems
+}
+
+/// The fields of the object that `T` fails to read. `T` is read again without the failed field
+/// until it fails on something else, like a missing field, so the fields come from `T` itself.
+fn field_problems<T: DeserializeOwned>(object: &Value, prefix: &str) -> Vec<ConfigProblem> {
+    let Some(mut fields) = object.as_object().cloned() else {
+        return Vec::new();
+    };
+    let mut problems = Vec::new();
+
+    loop {
+        let mut failed = None;
+        let _ = T::deserialize(FieldProbe {
+            fields: fields.clone().into_iter(),
+            field: None,
+            failed: &mut failed,
+        });
+
+        let Some((key, message)) = failed else {
+            return problems;
+        };
+
+        fields.remove(&key);
+        problems.push(ConfigProblem {
+            path: format!("{prefix}{key}"),
+            message: format!("is invalid: {message}"),
+        });
+    }
+}
+
+/// Reads an object like [`EnvValue`] does and remembers the field that failed to parse.
+struct FieldProbe<'a> {
+    fields: serde_json::map::IntoIter,
+    field: Option<(String, Value)>,
+    failed: &'a mut Option<(String, String)>,
+}
+
+impl<'de> Deserializer<'de> for FieldProbe<'_> {
+    type Error = serde_json::Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        visitor.visit_map(self)
+    }
+
+    serde::forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string bytes byte_buf option
+        unit unit_struct newtype_struct seq tuple tuple_struct map struct enum identifier
+        ignored_any
+    }
+}
+
+impl<'de> MapAccess<'de> for FieldProbe<'_> {
+    type Error = serde_json::Error;
+
+    fn next_key_seed<K: DeserializeSeed<'de>>(
+        &mut self,
+        seed: K,
+    ) -> Result<Option<K::Value>, Self::Error> {
+        let Some((key, value)) = self.fields.next() else {
+            return Ok(None);
+        };
+
+        let parsed = seed.deserialize(EnvValue(Value::String(key.clone())))?;
+        self.field = Some((key, value));
+
+        Ok(Some(parsed))
+    }
+
+    fn next_value_seed<V: DeserializeSeed<'de>>(
+        &mut self,
+        seed: V,
+    ) -> Result<V::Value, Self::Error> {
+        let (key, value) = self
+            .field
+            .take()
+            .expect("serde reads the key of a field before its value");
+
+        seed.deserialize(EnvValue(value)).inspect_err(|error| {
+            *self.failed = Some((key, error.to_string()));
+        })
+    }
+}
+
+/// Sets the field named by every variable with the prefix. Values are always set as text, the
+/// config is read through [`EnvValue`] which turns them into the type of the field, so a token
+/// consisting of digits stays text while a port becomes a number.
+fn apply_env_overrides(config: &mut Value, vars: impl IntoIterator<Item = (String, String)>) {
+    for (name, value) in vars {
+        let Some(path) = name
+            .strip_prefix(ENV_PREFIX)
+            .filter(|path| !path.is_empty())
+        else {
+            continue;
+        };
+
+        let mut field = &mut *config;
+
+        for key in path.split("__") {
+            if !field.is_object() {
+                *field = Value::Object(Map::new());
+            }
+
+            field = field
+                .as_object_mut()
+                .expect("The field was just made an object")
+                .entry(key.to_lowercase())
+                .or_insert(Value::Null);
+        }
+
+        *field = Value::String(value);
+    }
+}
+
+/// Reads the config like `serde_json::Value` does, except that text is read as a number, boolean,
+/// list or object when the field expects one, as overrides from the environment are always text.
+struct EnvValue(Value);
+
+impl EnvValue {
+    /// A list or object that was set as json text.
+    fn structured(self) -> Self {
+        match self.0 {
+            Value::String(text) => match serde_json::from_str::<Value>(&text) {
+                Ok(parsed @ (Value::Array(_) | Value::Object(_))) => Self(parsed),
+                _ => Self(Value::String(text)),
+            },
+            value => Self(value),
+        }
+    }
+}
+
+impl<'de> IntoDeserializer<'de, serde_json::Error> for EnvValue {
+    type Deserializer = Self;
+
+    fn into_deserializer(self) -> Self {
+        self
+    }
+}
+
+macro_rules! deserialize_parsed {
+    ($($method:ident => $visit:ident($ty:ty)),* $(,)?) => {$(
+        fn $method<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+            match &self.0 {
+                Value::String(text) => match text.trim().parse::<$ty>() {
+                    Ok(parsed) => visitor.$visit(parsed),
+                    // reports the text with the expected type.
+                    Err(_) => self.0.$method(visitor),
+                },
+                _ => self.0.$method(visitor),
+            }
+        }
+    )*};
+}
+
+impl<'de> Deserializer<'de> for EnvValue {
+    type Error = serde_json::Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        match self.0 {
+            Value::Array(items) => {
+                let mut items = SeqDeserializer::new(items.into_iter().map(Self));
+                let value = visitor.visit_seq(&mut items)?;
+                items.end()?;
+                Ok(value)
+            }
+            Value::Object(fields) => {
+                // keys are read leniently as well, like the server ids under `guilds`.
+                let mut fields = MapDeserializer::new(
+                    fields
+                        .into_iter()
+                        .map(|(key, value)| (Self(Value::String(key)), Self(value))),
+                );
+                let value = visitor.visit_map(&mut fields)?;
+                fields.end()?;
+                Ok(value)
+            }
+            value => value.deserialize_any(visitor),
+        }
+    }
+
+    deserialize_parsed! {
+        deserialize_bool => visit_bool(bool),
+        deserialize_i8 => visit_i8(i8),
+        deserialize_i16 => visit_i16(i16),
+        deserialize_i32 => visit_i32(i32),
+        deserialize_i64 => visit_i64(i64),
+        deserialize_u8 => visit_u8(u8),
+        deserialize_u16 => visit_u16(u16),
+        deserialize_u32 => visit_u32(u32),
+        deserialize_u64 => visit_u64(u64),
+        deserialize_f32 => visit_f32(f32),
+        deserialize_f64 => visit_f64(f64),
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        match self.0 {
+            Value::Null => visitor.visit_none(),
+            value => visitor.visit_some(Self(value)),
+        }
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_seq<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        self.structured().deserialize_any(visitor)
+    }
+
+    fn deserialize_tuple<V: Visitor<'de>>(
+        self,
+        _: usize,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        self.deserialize_seq(visitor)
+    }
+
+    fn deserialize_tuple_struct<V: Visitor<'de>>(
+        self,
+        _: &'static str,
+        _: usize,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        self.deserialize_seq(visitor)
+    }
+
+    fn deserialize_map<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        self.structured().deserialize_any(visitor)
+    }
+
+    fn deserialize_struct<V: Visitor<'de>>(
+        self,
+        _: &'static str,
+        _: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        self.deserialize_map(visitor)
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        name: &'static str,
+        variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        self.0.deserialize_enum(name, variants, visitor)
+    }
+
+    serde::forward_to_deserialize_any! {
+        i128 u128 char str string bytes byte_buf unit unit_struct identifier ignored_any
+    }
+}
+
 #[derive(Debug, Clone, Deserialize)]
 pub struct BotConfig {
     pub token: String,
     pub guild_id: GuildId,
+    /// Fixed number of gateway shards. Lets discord recommend the amount when unset.
+    pub shard_count: Option<u32>,
 }
 
 #[derive(Debug, Clone, Deserialize)]
 pub struct RoleConfig {
     pub good_first_issue: RoleId,
+    /// Members with this role may use maintainer-only commands like `/triage`. Nobody may when
+    /// unset.
+    pub maintainer: Option<RoleId>,
+    /// Members with this role may manage community content like `/tag` and get pinged with
+    /// moderation alerts. Nobody may and alerts ping nobody when unset.
+    pub moderator: Option<RoleId>,
+    /// Members get this role by pressing the verify button. Verification is disabled when unset.
+    pub verified: Option<RoleId>,
+    /// Given to members who linked a github account with merged commits. Not synced when unset.
+    pub contributor: Option<RoleId>,
+}
+
+impl RoleConfig {
+    pub fn is_maintainer(&self, roles: &[RoleId]) -> bool {
+        self.maintainer.is_some_and(|role| roles.contains(&role))
+    }
+
+    pub fn is_moderator(&self, roles: &[RoleId]) -> bool {
+        self.moderator.is_some_and(|role| roles.contains(&role))
+    }
+
+    /// Whether a member with these roles is a maintainer or moderator.
+    pub fn is_staff(&self, roles: &[RoleId]) -> bool {
+        self.is_maintainer(roles) || self.is_moderator(roles)
+    }
 }
 
 #[derive(Debug, Clone, Deserialize)]
@@ -46,12 +711,102 @@ pub struct GithubConfig {
     pub webhook_secret: String,
     pub activity_webhook: String,
     pub issues_webhook: String,
+    /// Personal access token used for api requests. Unauthenticated requests are heavily rate limited.
+    pub api_token: Option<String>,
+    /// The `owner/name` of the repository that commands like `/pr` look at.
+    #[serde(default = "default_repository")]
+    pub repository: String,
+    /// Github app installation used for write actions like `/triage`.
+    pub app: Option<GithubAppConfig>,
+    /// OAuth app members link their github account with. Linking is disabled when unset.
+    pub oauth: Option<GithubOAuthConfig>,
+    /// Rejects `/github` requests that don't come from the addresses github sends webhooks from,
+    /// before their signature is checked. Needs `webserver.trusted_proxies` behind a reverse proxy.
+    #[serde(default)]
+    pub verify_source_ip: bool,
+    /// Larger `/github` requests are answered with `413` instead of being read into memory.
+    #[serde(default = "default_max_payload_bytes")]
+    pub max_payload_bytes: usize,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct GithubAppConfig {
+    pub app_id: u64,
+    pub installation_id: u64,
+    /// Path to the PEM encoded private key of the app.
+    pub private_key_path: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct GithubOAuthConfig {
+    pub client_id: String,
+    pub client_secret: String,
+    /// The url the webserver is reachable at, like `https://bot.biomejs.dev`. The callback url of
+    /// the OAuth app has to be this followed by `/oauth/github/callback`.
+    pub public_url: String,
+}
+
+fn default_repository() -> String {
+    "biomejs/biome".to_string()
+}
+
+/// Github caps deliveries at 25 MB, but even pushes with many commits stay far below this.
+fn default_max_payload_bytes() -> usize {
+    5 * 1024 * 1024
 }
 
 #[derive(Debug, Clone, Deserialize)]
 pub struct WebserverConfig {
     pub host: [u8; 4],
     pub port: u16,
+    /// Addresses or CIDR ranges of reverse proxies in front of the webserver, like
+    /// `["127.0.0.1", "10.0.0.0/8"]`. The client address of their requests is read from
+    /// `X-Forwarded-For` or `Forwarded`, other requests can't fake it with those headers.
+    #[serde(default)]
+    pub trusted_proxies: Vec<IpRange>,
+    #[serde(default)]
+    pub rate_limit: RateLimitConfig,
+    /// Serves https with this certificate instead of plain http, for deployments without a
+    /// reverse proxy.
+    pub tls: Option<TlsConfig>,
+    /// Bearer token of the `/admin` api, which is disabled when unset.
+    pub admin_token: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct TlsConfig {
+    /// Path to the PEM encoded certificate chain, read again every day to pick up renewals.
+    pub cert_path: String,
+    /// Path to the PEM encoded private key of the certificate.
+    pub key_path: String,
+}
+
+/// Limits how often a single address may call the public routes like `/github` and the oauth
+/// flow. Every address gets a bucket of `burst` requests that refills at `per_minute`.
+#[derive(Debug, Clone, Deserialize)]
+pub struct RateLimitConfig {
+    #[serde(default = "default_rate_limit_burst")]
+    pub burst: u32,
+    #[serde(default = "default_rate_limit_per_minute")]
+    pub per_minute: u32,
+}
+
+impl Default for RateLimitConfig {
+    fn default() -> Self {
+        Self {
+            burst: default_rate_limit_burst(),
+            per_minute: default_rate_limit_per_minute(),
+        }
+    }
+}
+
+/// Github sends the deliveries of a busy repository from only a few addresses.
+fn default_rate_limit_burst() -> u32 {
+    60
+}
+
+fn default_rate_limit_per_minute() -> u32 {
+    120
 }
 
 #[derive(Debug, Clone, Deserialize)]
@@ -64,3 +819,610 @@ pub struct WebhookConfig {
     pub rules: String,
     pub roles: String,
 }
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct ChannelConfig {
+    /// Where the weekly project digest gets posted. The digest is disabled when unset. Only the
+    /// default of the `digest_channel` setting, see `/config`.
+    pub digest: Option<ChannelId>,
+    /// Where changes of the language support table get announced. Announcements are disabled when unset.
+    pub language_updates: Option<ChannelId>,
+    /// Where `/suggest` posts suggestions. The command is disabled when unset.
+    pub suggestions: Option<ChannelId>,
+    /// Where moderation cases get logged. Cases are only stored in the database when unset. Only
+    /// the default of the `log_channel` setting, see `/config`.
+    pub modlog: Option<ChannelId>,
+    /// Where members joining and leaving get logged. Logging is disabled when unset.
+    pub member_log: Option<ChannelId>,
+    /// Where reported messages get forwarded, the modlog channel when unset.
+    pub reports: Option<ChannelId>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct BiomeCliConfig {
+    pub binary_path: String,
+    /// Command the cli gets wrapped in to sandbox it, e.g. `["bwrap", "--ro-bind", "/", "/", "--unshare-all", "--"]`.
+    /// Required, snippets are never run without one.
+    #[serde(default)]
+    pub sandbox: Vec<String>,
+    #[serde(default = "default_cli_timeout")]
+    pub timeout_secs: u64,
+}
+
+fn default_cli_timeout() -> u64 {
+    10
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct AutoResponderConfig {
+    /// The help channels to watch, including the threads and forum posts inside of them.
+    pub channels: Vec<ChannelId>,
+    /// Minimum time between two automatic answers in the same channel.
+    #[serde(default = "default_auto_responder_cooldown")]
+    pub cooldown_secs: u64,
+}
+
+fn default_auto_responder_cooldown() -> u64 {
+    5 * 60
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct HelpForumConfig {
+    pub channel: ChannelId,
+    /// The name of the forum tag applied to solved posts.
+    #[serde(default = "default_solved_tag")]
+    pub solved_tag: String,
+    /// Bumps and archives posts nobody replied to in a while. Disabled when unset.
+    pub stale: Option<StaleThreadConfig>,
+    /// Forum tags applied to new posts that mention one of their keywords.
+    #[serde(default)]
+    pub topic_tags: Vec<TopicTagConfig>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct TopicTagConfig {
+    /// The name of the forum tag, e.g. `Formatter`.
+    pub tag: String,
+    /// Case-insensitive words or phrases like `vs code`. Keywords starting with ```` ``` ````
+    /// match the language of a code block instead, e.g. ```` ```yaml ```` for CI configurations.
+    pub keywords: Vec<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct StaleThreadConfig {
+    /// Days without activity after which the author gets asked whether they still need help.
+    pub bump_after_days: u64,
+    /// Days without activity after the bump after which the post gets archived.
+    pub archive_after_days: u64,
+}
+
+fn default_solved_tag() -> String {
+    "Solved".to_string()
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct CacheConfig {
+    /// How long the scraped language support stays fresh. It is refreshed in the background before it expires.
+    #[serde(default = "default_language_support_ttl")]
+    pub language_support_ttl_secs: u64,
+}
+
+impl Default for CacheConfig {
+    fn default() -> Self {
+        Self {
+            language_support_ttl_secs: default_language_support_ttl(),
+        }
+    }
+}
+
+fn default_language_support_ttl() -> u64 {
+    60 * 60
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct MessageLogConfig {
+    /// Where edited and deleted messages get logged.
+    pub channel: ChannelId,
+    /// Channels whose messages aren't logged, including the threads inside of them.
+    #[serde(default)]
+    pub ignored_channels: Vec<ChannelId>,
+    /// How many recent messages are kept in memory to show their content once they are edited or
+    /// deleted.
+    #[serde(default = "default_cached_messages")]
+    pub cached_messages: usize,
+    /// Messages are dropped from memory after this many hours, their edits and deletions are
+    /// logged without the previous content.
+    #[serde(default = "default_cached_message_hours")]
+    pub cached_message_hours: u64,
+}
+
+fn default_cached_messages() -> usize {
+    5000
+}
+
+fn default_cached_message_hours() -> u64 {
+    24
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct WelcomeConfig {
+    pub channel: ChannelId,
+    /// The markdown of the welcome embed. `{name}`, `{mention}` and `{member_count}` get replaced.
+    #[serde(default = "default_welcome_message")]
+    pub message: String,
+}
+
+fn default_welcome_message() -> String {
+    "Welcome to the Biome server, {mention}! You are member number {member_count}. Check out the rules and feel free to ask anything about Biome in the help forum.".to_string()
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct AutoRoleConfig {
+    pub roles: Vec<RoleId>,
+    /// How long to wait after someone joined. Members that still have to complete the membership
+    /// screening get the roles once they did.
+    #[serde(default)]
+    pub delay_secs: u64,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct AntiSpamConfig {
+    /// In how many different channels the same message has to be posted to count as spam.
+    #[serde(default = "default_spam_channels")]
+    pub channels: usize,
+    /// How close together the copies have to be posted.
+    #[serde(default = "default_spam_window")]
+    pub window_secs: u64,
+    /// How long spammers get timed out.
+    #[serde(default = "default_spam_timeout")]
+    pub timeout_secs: u64,
+    /// Where moderators get pinged about spam, the modlog channel when unset.
+    pub alert_channel: Option<ChannelId>,
+}
+
+fn default_spam_channels() -> usize {
+    3
+}
+
+fn default_spam_window() -> u64 {
+    60
+}
+
+fn default_spam_timeout() -> u64 {
+    24 * 60 * 60
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct PhishingFeedConfig {
+    /// A JSON array of domains or a text file with one domain per line.
+    pub url: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct InviteFilterConfig {
+    /// Channels invites may be posted in, including the threads inside of them.
+    #[serde(default)]
+    pub allowed_channels: Vec<ChannelId>,
+    /// Members with one of these roles may post invites anywhere. Staff always may.
+    #[serde(default)]
+    pub allowed_roles: Vec<RoleId>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct NewAccountConfig {
+    /// Accounts younger than this are flagged in the modlog channel.
+    #[serde(default = "default_min_account_age")]
+    pub min_age_days: u64,
+    /// Given to flagged members instead of the automatic roles. They are only flagged when unset.
+    pub quarantine_role: Option<RoleId>,
+}
+
+fn default_min_account_age() -> u64 {
+    7
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct RaidDetectionConfig {
+    /// More joins than this within the window start a lockdown.
+    #[serde(default = "default_raid_joins")]
+    pub joins: usize,
+    #[serde(default = "default_raid_window")]
+    pub window_secs: u64,
+}
+
+fn default_raid_joins() -> usize {
+    10
+}
+
+fn default_raid_window() -> u64 {
+    30
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct AutoModConfig {
+    /// Where AutoMod posts the blocked messages.
+    pub alert_channel: Option<ChannelId>,
+    /// Rules the bot created that aren't listed here anymore get deleted.
+    #[serde(default)]
+    pub rules: Vec<AutoModRuleConfig>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct AutoModRuleConfig {
+    /// Identifies the rule, renaming it replaces the rule.
+    pub name: String,
+    pub trigger: AutoModTrigger,
+    /// Shown to members whose message was blocked.
+    pub block_message: Option<String>,
+    /// Also times out the author of a blocked message.
+    pub timeout_secs: Option<u64>,
+    #[serde(default)]
+    pub exempt_roles: Vec<RoleId>,
+    #[serde(default)]
+    pub exempt_channels: Vec<ChannelId>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum AutoModTrigger {
+    /// Blocks messages containing one of the keywords, which support `*` wildcards.
+    Keyword {
+        #[serde(default)]
+        keywords: Vec<String>,
+        #[serde(default)]
+        regex_patterns: Vec<String>,
+        #[serde(default)]
+        allow_list: Vec<String>,
+    },
+    /// Blocks messages mentioning more than `mention_limit` users and roles.
+    MentionSpam {
+        mention_limit: u8,
+        /// Lets discord detect mention raids on its own as well.
+        #[serde(default)]
+        raid_protection: bool,
+    },
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct SponsorConfig {
+    /// The github organization that gets sponsored. Reading its sponsors needs an api token of an
+    /// organization owner.
+    pub organization: String,
+    /// Every sponsor gets this role.
+    pub role: RoleId,
+    /// Sponsors also get the role of the highest tier their monthly amount reaches.
+    #[serde(default)]
+    pub tiers: Vec<SponsorTierConfig>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct SponsorTierConfig {
+    pub min_monthly_dollars: u32,
+    pub role: RoleId,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct IssueLinkConfig {
+    /// The channels to watch, including the threads and forum posts inside of them.
+    pub channels: Vec<ChannelId>,
+    /// Minimum time between two replies in the same channel.
+    #[serde(default = "default_issue_link_cooldown")]
+    pub cooldown_secs: u64,
+    /// At most this many references of a message are linked.
+    #[serde(default = "default_issue_link_references")]
+    pub max_references: usize,
+}
+
+fn default_issue_link_cooldown() -> u64 {
+    15
+}
+
+fn default_issue_link_references() -> usize {
+    3
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct CodeSnippetConfig {
+    /// Longer ranges are cut off.
+    #[serde(default = "default_snippet_lines")]
+    pub max_lines: usize,
+    /// At most this many permalinks of a message are shown.
+    #[serde(default = "default_snippet_links")]
+    pub max_links: usize,
+}
+
+fn default_snippet_lines() -> usize {
+    30
+}
+
+fn default_snippet_links() -> usize {
+    2
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct PasteConfig {
+    /// The channels to watch, including the threads and forum posts inside of them.
+    pub channels: Vec<ChannelId>,
+    /// Code blocks with more lines get uploaded.
+    #[serde(default = "default_paste_lines")]
+    pub max_lines: usize,
+    pub backend: PasteBackend,
+}
+
+fn default_paste_lines() -> usize {
+    40
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum PasteBackend {
+    /// A hastebin compatible server, like a self-hosted one.
+    Hastebin { url: String },
+    /// Secret gists, created with the github api token which needs the `gist` scope.
+    Gist,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct AutoPublishConfig {
+    /// Announcement channels whose messages from bots, webhooks and maintainers get published.
+    pub channels: Vec<ChannelId>,
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn reads_config_path() {
+        let args = |args: &[&str]| args.iter().map(|arg| arg.to_string()).collect::<Vec<_>>();
+
+        assert_eq!(config_path(args(&[]).into_iter()).unwrap(), None);
+        assert_eq!(
+            config_path(args(&["--config", "/etc/biomebot.toml"]).into_iter()).unwrap(),
+            Some("/etc/biomebot.toml".to_string())
+        );
+        assert_eq!(
+            config_path(args(&["--config=prod.json"]).into_iter()).unwrap(),
+            Some("prod.json".to_string())
+        );
+        assert!(config_path(args(&["--config"]).into_iter()).is_err());
+    }
+
+    #[test]
+    fn parses_toml() {
+        let config = parse(
+            "config.toml",
+            "[bot]\ntoken = \"abc\"\nguild_id = 1\n\n[webserver]\nport = 3000\n",
+        )
+        .unwrap();
+
+        assert_eq!(
+            config,
+            json!({ "bot": { "token": "abc", "guild_id": 1 }, "webserver": { "port": 3000 } })
+        );
+    }
+
+    #[test]
+    fn reports_every_problem() {
+        let config = serde_json::from_value::<Config>(json!({
+            "bot": { "token": "", "guild_id": 0 },
+            "roles": { "good_first_issue": 1 },
+            "github": {
+                "webhook_secret": "secret",
+                "activity_webhook": "https://discord.com/api/webhooks/1/token/github",
+                "issues_webhook": "https://example.com/hook",
+                "repository": "biome",
+            },
+            "webserver": { "host": [0, 0, 0, 0], "port": 0 },
+            "database": { "url": "localhost:5432" },
+            "webhooks": {
+                "rules": "https://discord.com/api/webhooks/2/token",
+                "roles": "http://discord.com/api/webhooks/3/token",
+            },
+        }))
+        .unwrap();
+
+        let paths = config
+            .validate()
+            .into_iter()
+            .map(|problem| problem.path)
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            paths,
+            [
+                "bot.token",
+                "bot.guild_id",
+                "webhooks.roles",
+                "github.issues_webhook",
+                "github.repository",
+                "database.url",
+                "webserver.port",
+            ]
+        );
+    }
+
+    #[test]
+    fn reports_every_invalid_section() {
+        let config = json!({
+            "bot": { "token": "abc", "guild_id": "main" },
+            "roles": { "good_first_issue": 1 },
+            "webserver": { "host": [0, 0, 0, 0], "port": "http" },
+            "guilds": { "20": { "roles": { "good_first_issue": 2 }, "channels": "none" } },
+            "cache": { "language_support_ttl_secs": "120" },
+        });
+
+        let paths = parse_problems(&config)
+            .into_iter()
+            .map(|problem| problem.path)
+            .collect::<Vec<_>>();
+
+        assert_eq!(paths, ["bot", "webserver", "guilds.20.channels"]);
+    }
+
+    #[test]
+    fn reports_invalid_main_guild_sections() {
+        let config = json!({
+            "bot": { "token": "abc", "guild_id": 1 },
+            "roles": { "good_first_issue": "first" },
+            "webhooks": 3,
+        });
+
+        let paths = parse_problems(&config)
+            .into_iter()
+            .map(|problem| problem.path)
+            .collect::<Vec<_>>();
+
+        assert_eq!(paths, ["roles", "webhooks"]);
+    }
+
+    #[test]
+    fn overrides_nested_fields() {
+        let mut config = json!({
+            "bot": { "token": "from-file", "guild_id": 1 },
+            "cache": { "language_support_ttl_secs": 60 },
+        });
+
+        apply_env_overrides(
+            &mut config,
+            [
+                ("BIOMEBOT_BOT__TOKEN", "1234"),
+                ("BIOMEBOT_CACHE__LANGUAGE_SUPPORT_TTL_SECS", "120"),
+                ("BIOMEBOT_GITHUB__WEBHOOK_SECRET", "secret"),
+                ("BIOMEBOT_AUTO_PUBLISH__CHANNELS", "[1, 2]"),
+                ("HOME", "/root"),
+            ]
+            .map(|(name, value)| (name.to_string(), value.to_string())),
+        );
+
+        assert_eq!(
+            config,
+            json!({
+                "bot": { "token": "1234", "guild_id": 1 },
+                "cache": { "language_support_ttl_secs": "120" },
+                "github": { "webhook_secret": "secret" },
+                "auto_publish": { "channels": "[1, 2]" },
+            })
+        );
+    }
+
+    #[test]
+    fn reads_overrides_as_the_field_type() {
+        #[derive(Debug, Deserialize)]
+        struct Fields {
+            token: String,
+            port: u16,
+            verify: bool,
+            host: [u8; 4],
+            channels: Vec<ChannelId>,
+            shard_count: Option<u32>,
+            guilds: BTreeMap<GuildId, String>,
+        }
+
+        let fields = Fields::deserialize(EnvValue(json!({
+            "token": "12345678",
+            "port": "3000",
+            "verify": "true",
+            "host": "[127, 0, 0, 1]",
+            "channels": "[1, \"2\"]",
+            "shard_count": "4",
+            "guilds": { "20": "null" },
+        })))
+        .unwrap();
+
+        assert_eq!(fields.token, "12345678");
+        assert_eq!(fields.port, 3000);
+        assert!(fields.verify);
+        assert_eq!(fields.host, [127, 0, 0, 1]);
+        assert_eq!(fields.channels, [ChannelId::new(1), ChannelId::new(2)]);
+        assert_eq!(fields.shard_count, Some(4));
+        assert_eq!(fields.guilds[&GuildId::new(20)], "null");
+
+        let invalid = json!({ "port": "http", "token": "", "verify": true, "host": [0, 0, 0, 0] });
+        assert!(Fields::deserialize(EnvValue(invalid)).is_err());
+    }
+
+    #[test]
+    fn reads_secret_files() {
+        let read = |path: &str| match path {
+            "/run/secrets/token" => Ok("abc\n".to_string()),
+            _ => Err(std::io::Error::from(std::io::ErrorKind::NotFound)),
+        };
+
+        let mut config = json!({
+            "bot": { "token_file": "/run/secrets/token", "guild_id": 1 },
+            "help_forums": [{ "secret_file": "/run/secrets/token" }],
+        });
+        read_secret_files(&mut config, "", &read).unwrap();
+
+        assert_eq!(
+            config,
+            json!({
+                "bot": { "token": "abc", "guild_id": 1 },
+                "help_forums": [{ "secret": "abc" }],
+            })
+        );
+
+        let mut both = json!({ "bot": { "token": "abc", "token_file": "/run/secrets/token" } });
+        assert!(read_secret_files(&mut both, "", &read).is_err());
+
+        let mut missing = json!({ "database": { "url_file": "/run/secrets/db" } });
+        assert_eq!(
+            read_secret_files(&mut missing, "", &read)
+                .unwrap_err()
+                .to_string(),
+            "Failed to read database.url_file"
+        );
+    }
+
+    #[test]
+    fn looks_up_guilds() {
+        let guild = |webhook: &str| {
+            json!({
+                "roles": { "good_first_issue": 1, "maintainer": 2, "moderator": 3 },
+                "webhooks": { "rules": webhook, "roles": webhook },
+            })
+        };
+
+        let mut config = json!({
+            "bot": { "token": "abc", "guild_id": 10 },
+            "github": {
+                "webhook_secret": "secret",
+                "activity_webhook": "https://discord.com/api/webhooks/1/token",
+                "issues_webhook": "https://discord.com/api/webhooks/1/token",
+            },
+            "webserver": { "host": [0, 0, 0, 0], "port": 3000 },
+            "database": { "url": "postgres://localhost/biomebot" },
+            "guilds": { "20": guild("https://example.com/hook") },
+        });
+        config.as_object_mut().unwrap().extend(
+            guild("https://discord.com/api/webhooks/2/token")
+                .as_object()
+                .unwrap()
+                .clone(),
+        );
+
+        let config = serde_json::from_value::<Config>(config).unwrap();
+
+        assert!(config.guild(GuildId::new(10)).is_some());
+        assert!(config.guild(GuildId::new(20)).is_some());
+        assert!(config.guild(GuildId::new(30)).is_none());
+        assert_eq!(
+            config
+                .guilds()
+                .map(|(guild_id, _)| guild_id.get())
+                .collect::<Vec<_>>(),
+            [10, 20]
+        );
+        assert_eq!(
+            config
+                .validate()
+                .into_iter()
+                .map(|problem| problem.path)
+                .collect::<Vec<_>>(),
+            ["guilds.20.webhooks.rules", "guilds.20.webhooks.roles"]
+        );
+    }
+}
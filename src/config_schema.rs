@@ -0,0 +1,561 @@
+use std::sync::Arc;
+
+use anyhow::Context;
+use serde_json::Value;
+
+use crate::{
+    npm_api::{self, BIOME_PACKAGE},
+    Data,
+};
+
+/// Nested settings deeper than this aren't listed, it also guards against recursive definitions.
+const MAX_DEPTH: usize = 6;
+
+/// The JSON schema of `biome.json` for the latest release.
+#[derive(Debug)]
+pub struct ConfigSchema {
+    pub version: String,
+    /// The raw schema document.
+    pub root: Value,
+    /// Every setting of the configuration, sorted by key.
+    pub entries: Vec<SchemaEntry>,
+}
+
+/// A single setting of the configuration, e.g. `formatter.indentStyle`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SchemaEntry {
+    pub key: String,
+    pub description: Option<String>,
+    /// The JSON types the setting accepts, e.g. `string` or `boolean`.
+    pub types: Vec<String>,
+    pub allowed_values: Vec<String>,
+    pub default: Option<Value>,
+    pub deprecated: bool,
+}
+
+impl ConfigSchema {
+    pub fn new(version: String, root: Value) -> Self {
+        let mut entries = Vec::new();
+        collect_entries(&root, &root, "", 0, &mut entries);
+        entries.sort_by(|a, b| a.key.cmp(&b.key));
+
+        Self {
+            version,
+            root,
+            entries,
+        }
+    }
+
+    /// Finds a setting by its dotted key, ignoring case.
+    pub fn entry(&self, key: &str) -> Option<&SchemaEntry> {
+        self.entries
+            .iter()
+            .find(|entry| entry.key.eq_ignore_ascii_case(key))
+    }
+
+    /// Checks a configuration against the schema and returns every problem found, in document order.
+    pub fn validate(&self, config: &Value) -> Vec<ValidationIssue> {
+        let mut issues = Vec::new();
+        validate_node(&self.root, &self.root, config, "", 0, &mut issues);
+        issues
+    }
+}
+
+/// A problem found in a configuration, `path` is the dotted key it was found at.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ValidationIssue {
+    pub path: String,
+    pub kind: IssueKind,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum IssueKind {
+    UnknownKey,
+    WrongType {
+        expected: Vec<String>,
+        found: &'static str,
+    },
+    InvalidValue {
+        allowed: Vec<String>,
+    },
+    Deprecated,
+}
+
+/// Returns the cached schema, fetching it again once the cache expired.
+pub async fn cached_config_schema(data: &Data) -> anyhow::Result<Arc<ConfigSchema>> {
+    data.config_schema
+        .get_or_refresh(|| async {
+            let metadata = data
+                .npm_package
+                .get_or_refresh(|| npm_api::package_metadata(BIOME_PACKAGE))
+                .await?;
+
+            let Some(version) = metadata.dist_tags.get("latest") else {
+                anyhow::bail!("The package has no latest version");
+            };
+
+            Ok(Arc::new(fetch_config_schema(version).await?))
+        })
+        .await
+}
+
+/// Fetches the schema the website publishes for a release.
+pub async fn fetch_config_schema(version: &str) -> anyhow::Result<ConfigSchema> {
+    let root = reqwest::get(format!("https://biomejs.dev/schemas/{version}/schema.json"))
+        .await?
+        .error_for_status()
+        .context("Failed to fetch the configuration schema")?
+        .json::<Value>()
+        .await?;
+
+    Ok(ConfigSchema::new(version.to_string(), root))
+}
+
+/// The section of the configuration reference documenting the setting.
+pub fn docs_url(key: &str) -> String {
+    format!(
+        "https://biomejs.dev/reference/configuration/#{}",
+        key.to_lowercase().replace('.', "")
+    )
+}
+
+fn resolve<'a>(root: &'a Value, mut node: &'a Value) -> &'a Value {
+    // bounded so a self referencing definition can't loop forever.
+    for _ in 0..MAX_DEPTH {
+        if let Some(reference) = node.get("$ref").and_then(Value::as_str) {
+            let Some(target) = reference
+                .strip_prefix('#')
+                .and_then(|pointer| root.pointer(pointer))
+            else {
+                break;
+            };
+
+            node = target;
+            continue;
+        }
+
+        let variants = ["anyOf", "oneOf", "allOf"]
+            .iter()
+            .find_map(|combinator| node.get(*combinator).and_then(Value::as_array));
+
+        let Some(variants) = variants else {
+            break;
+        };
+
+        let mut non_null = variants
+            .iter()
+            .filter(|variant| variant.get("type").and_then(Value::as_str) != Some("null"));
+
+        match (non_null.next(), non_null.next()) {
+            (Some(single), None) => node = single,
+            _ => break,
+        }
+    }
+
+    node
+}
+
+fn collect_entries(
+    root: &Value,
+    node: &Value,
+    prefix: &str,
+    depth: usize,
+    entries: &mut Vec<SchemaEntry>,
+) {
+    if depth >= MAX_DEPTH {
+        return;
+    }
+
+    let Some(properties) = resolve(root, node)
+        .get("properties")
+        .and_then(Value::as_object)
+    else {
+        return;
+    };
+
+    for (name, property) in properties {
+        // `$schema` only points editors at the schema itself.
+        if name.starts_with('$') {
+            continue;
+        }
+
+        let key = if prefix.is_empty() {
+            name.clone()
+        } else {
+            format!("{prefix}.{name}")
+        };
+
+        let resolved = resolve(root, property);
+
+        let description = property
+            .get("description")
+            .or_else(|| resolved.get("description"))
+            .and_then(Value::as_str)
+            .map(str::to_string);
+
+        entries.push(SchemaEntry {
+            key: key.clone(),
+            description,
+            types: types(root, property),
+            allowed_values: allowed_values(root, resolved),
+            default: property
+                .get("default")
+                .or_else(|| resolved.get("default"))
+                .cloned(),
+            deprecated: [property, resolved].iter().any(|node| {
+                node.get("deprecated")
+                    .and_then(Value::as_bool)
+                    .unwrap_or_default()
+            }),
+        });
+
+        collect_entries(root, resolved, &key, depth + 1, entries);
+    }
+}
+
+/// The JSON types a node accepts, looking into every variant of a union.
+fn types(root: &Value, node: &Value) -> Vec<String> {
+    let node = resolve(root, node);
+    let mut kinds = Vec::new();
+
+    match node.get("type") {
+        Some(Value::String(kind)) => kinds.push(kind.clone()),
+        Some(Value::Array(values)) => {
+            kinds.extend(values.iter().filter_map(Value::as_str).map(str::to_string))
+        }
+        _ => {}
+    }
+
+    for combinator in ["anyOf", "oneOf"] {
+        for variant in node
+            .get(combinator)
+            .and_then(Value::as_array)
+            .into_iter()
+            .flatten()
+        {
+            for kind in types(root, variant) {
+                if !kinds.contains(&kind) {
+                    kinds.push(kind);
+                }
+            }
+        }
+    }
+
+    kinds.retain(|kind| kind != "null");
+    kinds
+}
+
+/// The values of an enum, spelled out as JSON.
+fn allowed_values(root: &Value, node: &Value) -> Vec<String> {
+    let node = resolve(root, node);
+
+    if let Some(values) = node.get("enum").and_then(Value::as_array) {
+        return values.iter().map(Value::to_string).collect();
+    }
+
+    if let Some(value) = node.get("const") {
+        return vec![value.to_string()];
+    }
+
+    let mut values = Vec::new();
+
+    for combinator in ["anyOf", "oneOf"] {
+        for variant in node
+            .get(combinator)
+            .and_then(Value::as_array)
+            .into_iter()
+            .flatten()
+        {
+            values.extend(allowed_values(root, variant));
+        }
+    }
+
+    values
+}
+
+/// Nesting deeper than this isn't validated, biome's configuration never gets close.
+const MAX_VALIDATION_DEPTH: usize = 16;
+
+fn validate_node(
+    root: &Value,
+    schema: &Value,
+    value: &Value,
+    path: &str,
+    depth: usize,
+    issues: &mut Vec<ValidationIssue>,
+) {
+    if depth >= MAX_VALIDATION_DEPTH {
+        return;
+    }
+
+    let resolved = resolve(root, schema);
+
+    let deprecated = [schema, resolved].iter().any(|node| {
+        node.get("deprecated")
+            .and_then(Value::as_bool)
+            .unwrap_or_default()
+    });
+
+    if deprecated {
+        issues.push(ValidationIssue {
+            path: path.to_string(),
+            kind: IssueKind::Deprecated,
+        });
+    }
+
+    let found = json_type(value);
+
+    let candidates = variants(root, schema)
+        .into_iter()
+        .filter(|variant| accepts_type(variant, found))
+        .collect::<Vec<_>>();
+
+    if candidates.is_empty() {
+        issues.push(ValidationIssue {
+            path: path.to_string(),
+            kind: IssueKind::WrongType {
+                expected: types(root, resolved),
+                found,
+            },
+        });
+        return;
+    }
+
+    match value {
+        Value::Object(object) => {
+            let Some(candidate) = candidates
+                .iter()
+                .find(|variant| variant.get("properties").is_some())
+                .or_else(|| candidates.first())
+            else {
+                return;
+            };
+
+            let properties = candidate.get("properties").and_then(Value::as_object);
+            let additional = candidate.get("additionalProperties");
+
+            for (key, child) in object {
+                if key.starts_with('$') {
+                    continue;
+                }
+
+                let child_path = if path.is_empty() {
+                    key.clone()
+                } else {
+                    format!("{path}.{key}")
+                };
+
+                match (properties.and_then(|p| p.get(key)), additional) {
+                    (Some(property), _) => {
+                        validate_node(root, property, child, &child_path, depth + 1, issues)
+                    }
+                    (None, Some(additional @ Value::Object(_))) => {
+                        validate_node(root, additional, child, &child_path, depth + 1, issues)
+                    }
+                    (None, Some(Value::Bool(true))) => {}
+                    // schemas without properties, like free form options, accept anything.
+                    (None, None) if properties.is_none() => {}
+                    (None, _) => issues.push(ValidationIssue {
+                        path: child_path,
+                        kind: IssueKind::UnknownKey,
+                    }),
+                }
+            }
+        }
+        Value::Array(items) => {
+            let Some(item_schema) = candidates.iter().find_map(|variant| variant.get("items"))
+            else {
+                return;
+            };
+
+            for (i, item) in items.iter().enumerate() {
+                validate_node(
+                    root,
+                    item_schema,
+                    item,
+                    &format!("{path}[{i}]"),
+                    depth + 1,
+                    issues,
+                );
+            }
+        }
+        _ => {
+            let enumerated = candidates
+                .iter()
+                .all(|variant| variant.get("enum").is_some() || variant.get("const").is_some());
+
+            let allowed = allowed_values(root, resolved);
+
+            if enumerated && !allowed.contains(&value.to_string()) {
+                issues.push(ValidationIssue {
+                    path: path.to_string(),
+                    kind: IssueKind::InvalidValue { allowed },
+                });
+            }
+        }
+    }
+}
+
+/// The resolved alternatives of a union, or the node itself when it isn't one.
+fn variants<'a>(root: &'a Value, node: &'a Value) -> Vec<&'a Value> {
+    let node = resolve(root, node);
+
+    let union = ["anyOf", "oneOf"]
+        .iter()
+        .find_map(|combinator| node.get(*combinator).and_then(Value::as_array));
+
+    match union {
+        Some(union) => union
+            .iter()
+            .flat_map(|variant| variants(root, variant))
+            .collect(),
+        None => vec![node],
+    }
+}
+
+fn accepts_type(node: &Value, found: &str) -> bool {
+    let accepts = |kind: &str| kind == found || (kind == "number" && found == "integer");
+
+    match node.get("type") {
+        Some(Value::String(kind)) => accepts(kind),
+        Some(Value::Array(kinds)) => kinds.iter().filter_map(Value::as_str).any(accepts),
+        // enums without a type still only accept their own values.
+        _ => true,
+    }
+}
+
+fn json_type(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "boolean",
+        Value::Number(number) if number.is_f64() => "number",
+        Value::Number(_) => "integer",
+        Value::String(_) => "string",
+        Value::Array(_) => "array",
+        Value::Object(_) => "object",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn schema() -> ConfigSchema {
+        ConfigSchema::new(
+            "1.7.0".to_string(),
+            json!({
+                "properties": {
+                    "$schema": { "type": ["string", "null"] },
+                    "formatter": {
+                        "description": "The configuration of the formatter",
+                        "anyOf": [{ "$ref": "#/definitions/FormatterConfiguration" }, { "type": "null" }]
+                    }
+                },
+                "definitions": {
+                    "FormatterConfiguration": {
+                        "type": "object",
+                        "properties": {
+                            "indentStyle": {
+                                "description": "The indent style.",
+                                "anyOf": [{ "$ref": "#/definitions/PlainIndentStyle" }, { "type": "null" }]
+                            },
+                            "indentSize": {
+                                "description": "The size of the indentation.",
+                                "type": ["integer", "null"],
+                                "deprecated": true
+                            }
+                        }
+                    },
+                    "PlainIndentStyle": {
+                        "oneOf": [
+                            { "description": "Tab", "type": "string", "enum": ["tab"] },
+                            { "description": "Space", "type": "string", "enum": ["space"] }
+                        ]
+                    }
+                }
+            }),
+        )
+    }
+
+    #[test]
+    fn flattens_nested_settings() {
+        let schema = schema();
+
+        assert_eq!(
+            schema
+                .entries
+                .iter()
+                .map(|entry| entry.key.as_str())
+                .collect::<Vec<_>>(),
+            vec!["formatter", "formatter.indentSize", "formatter.indentStyle"]
+        );
+
+        let indent_style = schema.entry("formatter.indentstyle").unwrap();
+        assert_eq!(
+            indent_style.description.as_deref(),
+            Some("The indent style.")
+        );
+        assert_eq!(indent_style.types, vec!["string"]);
+        assert_eq!(indent_style.allowed_values, vec!["\"tab\"", "\"space\""]);
+
+        let indent_size = schema.entry("formatter.indentSize").unwrap();
+        assert_eq!(indent_size.types, vec!["integer"]);
+        assert!(indent_size.deprecated);
+    }
+
+    #[test]
+    fn reports_invalid_settings() {
+        let issues = schema().validate(&json!({
+            "$schema": "https://biomejs.dev/schemas/1.7.0/schema.json",
+            "formatter": {
+                "indentStyle": "tabs",
+                "indentSize": 2,
+                "lineWidth": 80
+            },
+            "linter": {}
+        }));
+
+        assert_eq!(
+            issues,
+            vec![
+                ValidationIssue {
+                    path: "formatter.indentSize".to_string(),
+                    kind: IssueKind::Deprecated,
+                },
+                ValidationIssue {
+                    path: "formatter.indentStyle".to_string(),
+                    kind: IssueKind::InvalidValue {
+                        allowed: vec!["\"tab\"".to_string(), "\"space\"".to_string()],
+                    },
+                },
+                ValidationIssue {
+                    path: "formatter.lineWidth".to_string(),
+                    kind: IssueKind::UnknownKey,
+                },
+                ValidationIssue {
+                    path: "linter".to_string(),
+                    kind: IssueKind::UnknownKey,
+                },
+            ]
+        );
+
+        let issues = schema().validate(&json!({ "formatter": { "indentSize": "2" } }));
+        assert_eq!(
+            issues[1].kind,
+            IssueKind::WrongType {
+                expected: vec!["integer".to_string()],
+                found: "string",
+            }
+        );
+    }
+
+    #[test]
+    fn links_configuration_reference() {
+        assert_eq!(
+            docs_url("formatter.indentStyle"),
+            "https://biomejs.dev/reference/configuration/#formatterindentstyle"
+        );
+    }
+}
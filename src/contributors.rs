@@ -0,0 +1,94 @@
+use poise::serenity_prelude as serenity;
+use serenity::{Http, UserId};
+
+use crate::{
+    database::github_links::{self, GithubLink},
+    Data,
+};
+
+/// Gives the contributor role to every member whose linked github account has merged commits,
+/// and takes it from linked members without any.
+pub async fn sync_all(http: &Http, data: &Data) -> anyhow::Result<()> {
+    if data.config.main_guild.roles.contributor.is_none() {
+        return Ok(());
+    }
+
+    let links = github_links::all(&data.db_pool).await?;
+    let mut failed = 0;
+
+    for mut link in links {
+        // the id survives renames, the commit search needs the current login.
+        match data.github.user_by_id(link.github_id as u64).await {
+            Ok(Some(user)) if user.login != link.github_login => {
+                github_links::update_login(&data.db_pool, link.user_id, &user.login).await?;
+                link.github_login = user.login;
+            }
+            Ok(_) => {}
+            Err(e) => {
+                tracing::warn!("Failed to look up github user {}: {e:#}", link.github_id);
+                failed += 1;
+                continue;
+            }
+        }
+
+        if let Err(e) = sync_member(http, data, &link).await {
+            tracing::warn!(
+                "Failed to sync the contributor role of {}: {e:#}",
+                link.user_id
+            );
+            failed += 1;
+        }
+    }
+
+    if failed > 0 {
+        anyhow::bail!("Failed to sync the contributor role of {failed} members");
+    }
+
+    Ok(())
+}
+
+/// Adds or removes the contributor role of the linked member. Members who left the server are
+/// skipped.
+pub async fn sync_member(http: &Http, data: &Data, link: &GithubLink) -> anyhow::Result<()> {
+    let Some(role_id) = data.config.main_guild.roles.contributor else {
+        return Ok(());
+    };
+
+    let guild_id = data.config.bot.guild_id;
+    let user_id = UserId::new(link.user_id as u64);
+
+    let Ok(member) = guild_id.member(http, user_id).await else {
+        return Ok(());
+    };
+
+    let is_contributor = data.github.has_commits(&link.github_login).await?;
+
+    match (is_contributor, member.roles.contains(&role_id)) {
+        (true, false) => {
+            http.add_member_role(guild_id, user_id, role_id, Some("Merged commits on github"))
+                .await?;
+            tracing::info!(
+                "Gave the contributor role to {} ({}).",
+                member.user.name,
+                link.github_login
+            );
+        }
+        (false, true) => {
+            http.remove_member_role(
+                guild_id,
+                user_id,
+                role_id,
+                Some("No merged commits on github"),
+            )
+            .await?;
+            tracing::info!(
+                "Took the contributor role from {} ({}).",
+                member.user.name,
+                link.github_login
+            );
+        }
+        _ => {}
+    }
+
+    Ok(())
+}
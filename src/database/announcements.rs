@@ -0,0 +1,95 @@
+use chrono::{DateTime, Utc};
+use serde_json::Value;
+use sqlx::PgPool;
+
+#[derive(Debug, sqlx::FromRow)]
+pub struct Announcement {
+    pub id: i64,
+    pub author_id: i64,
+    pub target: String,
+    pub payload: Value,
+    pub next_run_at: DateTime<Utc>,
+    /// A cron expression, `None` if the announcement is posted once.
+    pub recurrence: Option<String>,
+    pub created_at: DateTime<Utc>,
+    /// The server it was scheduled in, `None` for the main server.
+    pub guild_id: Option<i64>,
+}
+
+/// Stores a new announcement and returns its id.
+pub async fn insert(
+    pool: &PgPool,
+    guild_id: i64,
+    author_id: i64,
+    target: &str,
+    payload: &Value,
+    next_run_at: DateTime<Utc>,
+    recurrence: Option<&str>,
+) -> anyhow::Result<i64> {
+    let id = sqlx::query_scalar::<_, i64>(
+        "INSERT INTO announcements (guild_id, author_id, target, payload, next_run_at, recurrence) VALUES ($1, $2, $3, $4, $5, $6) RETURNING id",
+    )
+    .bind(guild_id)
+    .bind(author_id)
+    .bind(target)
+    .bind(payload)
+    .bind(next_run_at)
+    .bind(recurrence)
+    .fetch_one(pool)
+    .await?;
+
+    Ok(id)
+}
+
+/// All announcements, the next one first.
+pub async fn all(pool: &PgPool) -> anyhow::Result<Vec<Announcement>> {
+    let announcements =
+        sqlx::query_as::<_, Announcement>("SELECT * FROM announcements ORDER BY next_run_at")
+            .fetch_all(pool)
+            .await?;
+
+    Ok(announcements)
+}
+
+/// Announcements whose time came.
+pub async fn due(pool: &PgPool) -> anyhow::Result<Vec<Announcement>> {
+    let announcements = sqlx::query_as::<_, Announcement>(
+        "SELECT * FROM announcements WHERE next_run_at <= now() ORDER BY next_run_at",
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(announcements)
+}
+
+pub async fn authored_by(pool: &PgPool, author_id: i64) -> anyhow::Result<Vec<Announcement>> {
+    let announcements = sqlx::query_as::<_, Announcement>(
+        "SELECT * FROM announcements WHERE author_id = $1 ORDER BY created_at",
+    )
+    .bind(author_id)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(announcements)
+}
+
+/// Moves a recurring announcement to its next run.
+pub async fn reschedule(pool: &PgPool, id: i64, next_run_at: DateTime<Utc>) -> anyhow::Result<()> {
+    sqlx::query("UPDATE announcements SET next_run_at = $2 WHERE id = $1")
+        .bind(id)
+        .bind(next_run_at)
+        .execute(pool)
+        .await?;
+
+    Ok(())
+}
+
+/// Returns `false` when there is no announcement with that id.
+pub async fn delete(pool: &PgPool, id: i64) -> anyhow::Result<bool> {
+    let result = sqlx::query("DELETE FROM announcements WHERE id = $1")
+        .bind(id)
+        .execute(pool)
+        .await?;
+
+    Ok(result.rows_affected() == 1)
+}
@@ -0,0 +1,89 @@
+use chrono::{DateTime, Utc};
+use sqlx::PgPool;
+
+#[derive(Debug, sqlx::FromRow)]
+pub struct BlockedDomain {
+    pub domain: String,
+    /// `None` for domains imported from the feed.
+    pub added_by: Option<i64>,
+    /// `manual` or `feed`.
+    pub source: String,
+    pub reason: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Blocks the domain, `false` if a moderator already blocked it. A domain imported from the feed
+/// becomes a manual one, so it stays blocked when the feed drops it.
+pub async fn insert(
+    pool: &PgPool,
+    domain: &str,
+    added_by: i64,
+    reason: Option<&str>,
+) -> anyhow::Result<bool> {
+    let result = sqlx::query(
+        "INSERT INTO blocked_domains (domain, added_by, reason) VALUES ($1, $2, $3) ON CONFLICT (domain) DO UPDATE SET source = 'manual', added_by = $2, reason = $3, created_at = now() WHERE blocked_domains.source = 'feed'",
+    )
+    .bind(domain)
+    .bind(added_by)
+    .bind(reason)
+    .execute(pool)
+    .await?;
+
+    Ok(result.rows_affected() > 0)
+}
+
+/// Unblocks the domain, `false` if it wasn't blocked.
+pub async fn delete(pool: &PgPool, domain: &str) -> anyhow::Result<bool> {
+    let result = sqlx::query("DELETE FROM blocked_domains WHERE domain = $1")
+        .bind(domain)
+        .execute(pool)
+        .await?;
+
+    Ok(result.rows_affected() > 0)
+}
+
+/// All blocked domains in alphabetical order.
+pub async fn all(pool: &PgPool) -> anyhow::Result<Vec<BlockedDomain>> {
+    let domains =
+        sqlx::query_as::<_, BlockedDomain>("SELECT * FROM blocked_domains ORDER BY domain")
+            .fetch_all(pool)
+            .await?;
+
+    Ok(domains)
+}
+
+/// Replaces the domains imported from the feed, manually blocked domains are left alone. Returns
+/// how many domains were added and removed.
+pub async fn sync_feed(pool: &PgPool, domains: &[String]) -> anyhow::Result<(u64, u64)> {
+    let mut transaction = pool.begin().await?;
+
+    let removed =
+        sqlx::query("DELETE FROM blocked_domains WHERE source = 'feed' AND domain <> ALL($1)")
+            .bind(domains)
+            .execute(&mut *transaction)
+            .await?
+            .rows_affected();
+
+    let added = sqlx::query(
+        "INSERT INTO blocked_domains (domain, source) SELECT unnest($1::TEXT[]), 'feed' ON CONFLICT (domain) DO NOTHING",
+    )
+    .bind(domains)
+    .execute(&mut *transaction)
+    .await?
+    .rows_affected();
+
+    transaction.commit().await?;
+
+    Ok((added, removed))
+}
+
+pub async fn added_by(pool: &PgPool, user_id: i64) -> anyhow::Result<Vec<BlockedDomain>> {
+    let domains = sqlx::query_as::<_, BlockedDomain>(
+        "SELECT * FROM blocked_domains WHERE added_by = $1 ORDER BY created_at",
+    )
+    .bind(user_id)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(domains)
+}
@@ -0,0 +1,80 @@
+use chrono::{DateTime, Utc};
+use sqlx::PgPool;
+
+#[derive(Debug, sqlx::FromRow)]
+pub struct CannedResponse {
+    pub name: String,
+    pub content: String,
+    /// The maintainer who last wrote the response.
+    pub author_id: i64,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+pub async fn get(pool: &PgPool, name: &str) -> anyhow::Result<Option<CannedResponse>> {
+    let response =
+        sqlx::query_as::<_, CannedResponse>("SELECT * FROM canned_responses WHERE name = $1")
+            .bind(name)
+            .fetch_optional(pool)
+            .await?;
+
+    Ok(response)
+}
+
+/// Response names containing `partial`, used for autocomplete.
+pub async fn names_matching(
+    pool: &PgPool,
+    partial: &str,
+    limit: i64,
+) -> anyhow::Result<Vec<String>> {
+    let names = sqlx::query_scalar::<_, String>(
+        "SELECT name FROM canned_responses WHERE name ILIKE '%' || $1 || '%' ORDER BY name LIMIT $2",
+    )
+    .bind(partial)
+    .bind(limit)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(names)
+}
+
+pub async fn authored_by(pool: &PgPool, author_id: i64) -> anyhow::Result<Vec<CannedResponse>> {
+    let responses = sqlx::query_as::<_, CannedResponse>(
+        "SELECT * FROM canned_responses WHERE author_id = $1 ORDER BY created_at",
+    )
+    .bind(author_id)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(responses)
+}
+
+/// Creates the response or replaces the content of an existing one.
+pub async fn upsert(
+    pool: &PgPool,
+    name: &str,
+    content: &str,
+    author_id: i64,
+) -> anyhow::Result<()> {
+    sqlx::query(
+        "INSERT INTO canned_responses (name, content, author_id) VALUES ($1, $2, $3)
+         ON CONFLICT (name) DO UPDATE SET content = $2, author_id = $3, updated_at = now()",
+    )
+    .bind(name)
+    .bind(content)
+    .bind(author_id)
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+/// Returns `false` when there is no response with that name.
+pub async fn delete(pool: &PgPool, name: &str) -> anyhow::Result<bool> {
+    let result = sqlx::query("DELETE FROM canned_responses WHERE name = $1")
+        .bind(name)
+        .execute(pool)
+        .await?;
+
+    Ok(result.rows_affected() == 1)
+}
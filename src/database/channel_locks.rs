@@ -0,0 +1,82 @@
+use chrono::{DateTime, Utc};
+use sqlx::PgPool;
+
+#[derive(Debug, sqlx::FromRow)]
+pub struct ChannelLock {
+    pub id: i64,
+    pub channel_id: i64,
+    pub locked_by: i64,
+    pub reason: String,
+    pub unlock_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    /// The send permissions `@everyone` was explicitly allowed before the lock.
+    pub previous_allow: i64,
+    /// The send permissions `@everyone` was explicitly denied before the lock.
+    pub previous_deny: i64,
+}
+
+/// Records the lock of the channel and returns its id. Locking a locked channel again replaces
+/// the lock but keeps the permissions from before the first one.
+pub async fn insert(
+    pool: &PgPool,
+    channel_id: i64,
+    locked_by: i64,
+    reason: &str,
+    unlock_at: Option<DateTime<Utc>>,
+    previous_allow: i64,
+    previous_deny: i64,
+) -> anyhow::Result<i64> {
+    let id = sqlx::query_scalar::<_, i64>(
+        "INSERT INTO channel_locks (channel_id, locked_by, reason, unlock_at, previous_allow, previous_deny) VALUES ($1, $2, $3, $4, $5, $6) ON CONFLICT (channel_id) DO UPDATE SET id = nextval('channel_locks_id_seq'), locked_by = $2, reason = $3, unlock_at = $4, created_at = now() RETURNING id",
+    )
+    .bind(channel_id)
+    .bind(locked_by)
+    .bind(reason)
+    .bind(unlock_at)
+    .bind(previous_allow)
+    .bind(previous_deny)
+    .fetch_one(pool)
+    .await?;
+
+    Ok(id)
+}
+
+pub async fn get(pool: &PgPool, id: i64) -> anyhow::Result<Option<ChannelLock>> {
+    let lock = sqlx::query_as::<_, ChannelLock>("SELECT * FROM channel_locks WHERE id = $1")
+        .bind(id)
+        .fetch_optional(pool)
+        .await?;
+
+    Ok(lock)
+}
+
+pub async fn get_by_channel(pool: &PgPool, channel_id: i64) -> anyhow::Result<Option<ChannelLock>> {
+    let lock =
+        sqlx::query_as::<_, ChannelLock>("SELECT * FROM channel_locks WHERE channel_id = $1")
+            .bind(channel_id)
+            .fetch_optional(pool)
+            .await?;
+
+    Ok(lock)
+}
+
+/// Forgets the lock of the channel, `false` if it wasn't locked.
+pub async fn delete_by_channel(pool: &PgPool, channel_id: i64) -> anyhow::Result<bool> {
+    let result = sqlx::query("DELETE FROM channel_locks WHERE channel_id = $1")
+        .bind(channel_id)
+        .execute(pool)
+        .await?;
+
+    Ok(result.rows_affected() > 0)
+}
+
+pub async fn locked_by(pool: &PgPool, user_id: i64) -> anyhow::Result<Vec<ChannelLock>> {
+    let locks = sqlx::query_as::<_, ChannelLock>(
+        "SELECT * FROM channel_locks WHERE locked_by = $1 ORDER BY created_at",
+    )
+    .bind(user_id)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(locks)
+}
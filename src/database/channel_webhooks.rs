@@ -0,0 +1,33 @@
+use chrono::{DateTime, Utc};
+use sqlx::PgPool;
+
+#[derive(Debug, sqlx::FromRow)]
+pub struct ChannelWebhook {
+    pub channel_id: i64,
+    pub webhook_url: String,
+    pub created_at: DateTime<Utc>,
+}
+
+pub async fn get(pool: &PgPool, channel_id: i64) -> anyhow::Result<Option<ChannelWebhook>> {
+    let webhook =
+        sqlx::query_as::<_, ChannelWebhook>("SELECT * FROM channel_webhooks WHERE channel_id = $1")
+            .bind(channel_id)
+            .fetch_optional(pool)
+            .await?;
+
+    Ok(webhook)
+}
+
+/// Remembers the webhook of the channel, replacing one that was deleted.
+pub async fn upsert(pool: &PgPool, channel_id: i64, webhook_url: &str) -> anyhow::Result<()> {
+    sqlx::query(
+        "INSERT INTO channel_webhooks (channel_id, webhook_url) VALUES ($1, $2)
+         ON CONFLICT (channel_id) DO UPDATE SET webhook_url = $2, created_at = now()",
+    )
+    .bind(channel_id)
+    .bind(webhook_url)
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
@@ -0,0 +1,40 @@
+use chrono::{DateTime, Utc};
+use sqlx::PgPool;
+
+/// The metrics at the time a weekly digest was posted, used to compute the deltas of the next one.
+#[derive(Debug, sqlx::FromRow)]
+pub struct DigestSnapshot {
+    pub id: i64,
+    pub stars: i64,
+    pub contributors: i64,
+    pub weekly_downloads: i64,
+    pub posted_at: DateTime<Utc>,
+}
+
+pub async fn latest(pool: &PgPool) -> anyhow::Result<Option<DigestSnapshot>> {
+    let snapshot = sqlx::query_as::<_, DigestSnapshot>(
+        "SELECT * FROM digest_snapshots ORDER BY posted_at DESC LIMIT 1",
+    )
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(snapshot)
+}
+
+pub async fn insert(
+    pool: &PgPool,
+    stars: i64,
+    contributors: i64,
+    weekly_downloads: i64,
+) -> anyhow::Result<()> {
+    sqlx::query(
+        "INSERT INTO digest_snapshots (stars, contributors, weekly_downloads) VALUES ($1, $2, $3)",
+    )
+    .bind(stars)
+    .bind(contributors)
+    .bind(weekly_downloads)
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
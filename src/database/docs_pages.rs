@@ -0,0 +1,73 @@
+use chrono::{DateTime, Utc};
+use sqlx::PgPool;
+
+/// A page of the documentation matching a search, with the matching words in bold.
+#[derive(Debug, sqlx::FromRow)]
+pub struct DocsSearchResult {
+    pub url: String,
+    pub title: String,
+    pub snippet: String,
+}
+
+pub async fn upsert(pool: &PgPool, url: &str, title: &str, content: &str) -> anyhow::Result<()> {
+    sqlx::query(
+        "INSERT INTO docs_pages (url, title, content) VALUES ($1, $2, $3)
+         ON CONFLICT (url) DO UPDATE SET title = $2, content = $3, indexed_at = now()",
+    )
+    .bind(url)
+    .bind(title)
+    .bind(content)
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+/// Removes pages that weren't seen by the crawl that started at `before`.
+pub async fn remove_stale(pool: &PgPool, before: DateTime<Utc>) -> anyhow::Result<u64> {
+    let result = sqlx::query("DELETE FROM docs_pages WHERE indexed_at < $1")
+        .bind(before)
+        .execute(pool)
+        .await?;
+
+    Ok(result.rows_affected())
+}
+
+pub async fn search(
+    pool: &PgPool,
+    query: &str,
+    limit: i64,
+) -> anyhow::Result<Vec<DocsSearchResult>> {
+    let results = sqlx::query_as::<_, DocsSearchResult>(
+        "SELECT url, title,
+             ts_headline('english', content, websearch_to_tsquery('english', $1),
+                 'StartSel=**, StopSel=**, MaxWords=30, MinWords=15, MaxFragments=1') AS snippet
+         FROM docs_pages
+         WHERE search @@ websearch_to_tsquery('english', $1)
+         ORDER BY ts_rank(search, websearch_to_tsquery('english', $1)) DESC
+         LIMIT $2",
+    )
+    .bind(query)
+    .bind(limit)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(results)
+}
+
+/// Page titles containing `partial`, used as query suggestions.
+pub async fn titles_matching(
+    pool: &PgPool,
+    partial: &str,
+    limit: i64,
+) -> anyhow::Result<Vec<String>> {
+    let titles = sqlx::query_scalar::<_, String>(
+        "SELECT DISTINCT title FROM docs_pages WHERE title ILIKE '%' || $1 || '%' ORDER BY title LIMIT $2",
+    )
+    .bind(partial)
+    .bind(limit)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(titles)
+}
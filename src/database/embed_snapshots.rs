@@ -0,0 +1,90 @@
+use chrono::{DateTime, Utc};
+use serde_json::Value;
+use sqlx::PgPool;
+
+#[derive(Debug, sqlx::FromRow)]
+pub struct EmbedSnapshot {
+    pub id: i64,
+    pub message_id: i64,
+    /// The webhook target the message was posted through.
+    pub target: String,
+    pub version: i32,
+    pub author_id: i64,
+    pub embed: Value,
+    /// Unified diff against the previous version, `None` for the first one.
+    pub diff: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+pub async fn latest(pool: &PgPool, message_id: i64) -> anyhow::Result<Option<EmbedSnapshot>> {
+    let snapshot = sqlx::query_as::<_, EmbedSnapshot>(
+        "SELECT * FROM embed_snapshots WHERE message_id = $1 ORDER BY version DESC LIMIT 1",
+    )
+    .bind(message_id)
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(snapshot)
+}
+
+pub async fn version(
+    pool: &PgPool,
+    message_id: i64,
+    version: i32,
+) -> anyhow::Result<Option<EmbedSnapshot>> {
+    let snapshot = sqlx::query_as::<_, EmbedSnapshot>(
+        "SELECT * FROM embed_snapshots WHERE message_id = $1 AND version = $2",
+    )
+    .bind(message_id)
+    .bind(version)
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(snapshot)
+}
+
+pub async fn history(pool: &PgPool, message_id: i64) -> anyhow::Result<Vec<EmbedSnapshot>> {
+    let snapshots = sqlx::query_as::<_, EmbedSnapshot>(
+        "SELECT * FROM embed_snapshots WHERE message_id = $1 ORDER BY version DESC",
+    )
+    .bind(message_id)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(snapshots)
+}
+
+pub async fn authored_by(pool: &PgPool, author_id: i64) -> anyhow::Result<Vec<EmbedSnapshot>> {
+    let snapshots = sqlx::query_as::<_, EmbedSnapshot>(
+        "SELECT * FROM embed_snapshots WHERE author_id = $1 ORDER BY created_at",
+    )
+    .bind(author_id)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(snapshots)
+}
+
+pub async fn insert(
+    pool: &PgPool,
+    message_id: i64,
+    target: &str,
+    version: i32,
+    author_id: i64,
+    embed: &Value,
+    diff: Option<&str>,
+) -> anyhow::Result<()> {
+    sqlx::query(
+        "INSERT INTO embed_snapshots (message_id, target, version, author_id, embed, diff) VALUES ($1, $2, $3, $4, $5, $6)",
+    )
+    .bind(message_id)
+    .bind(target)
+    .bind(version)
+    .bind(author_id)
+    .bind(embed)
+    .bind(diff)
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
@@ -0,0 +1,90 @@
+use chrono::{DateTime, Utc};
+use serde_json::Value;
+use sqlx::PgPool;
+
+#[derive(Debug, sqlx::FromRow)]
+pub struct EmbedTemplate {
+    pub name: String,
+    pub embed: Value,
+    /// The admin who last saved the template.
+    pub author_id: i64,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+pub async fn get(pool: &PgPool, name: &str) -> anyhow::Result<Option<EmbedTemplate>> {
+    let template =
+        sqlx::query_as::<_, EmbedTemplate>("SELECT * FROM embed_templates WHERE name = $1")
+            .bind(name)
+            .fetch_optional(pool)
+            .await?;
+
+    Ok(template)
+}
+
+pub async fn all(pool: &PgPool) -> anyhow::Result<Vec<EmbedTemplate>> {
+    let templates =
+        sqlx::query_as::<_, EmbedTemplate>("SELECT * FROM embed_templates ORDER BY name")
+            .fetch_all(pool)
+            .await?;
+
+    Ok(templates)
+}
+
+/// Template names containing `partial`, used for autocomplete.
+pub async fn names_matching(
+    pool: &PgPool,
+    partial: &str,
+    limit: i64,
+) -> anyhow::Result<Vec<String>> {
+    let names = sqlx::query_scalar::<_, String>(
+        "SELECT name FROM embed_templates WHERE name ILIKE '%' || $1 || '%' ORDER BY name LIMIT $2",
+    )
+    .bind(partial)
+    .bind(limit)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(names)
+}
+
+pub async fn authored_by(pool: &PgPool, author_id: i64) -> anyhow::Result<Vec<EmbedTemplate>> {
+    let templates = sqlx::query_as::<_, EmbedTemplate>(
+        "SELECT * FROM embed_templates WHERE author_id = $1 ORDER BY created_at",
+    )
+    .bind(author_id)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(templates)
+}
+
+/// Creates the template or replaces the embed of an existing one.
+pub async fn upsert(
+    pool: &PgPool,
+    name: &str,
+    embed: &Value,
+    author_id: i64,
+) -> anyhow::Result<()> {
+    sqlx::query(
+        "INSERT INTO embed_templates (name, embed, author_id) VALUES ($1, $2, $3)
+         ON CONFLICT (name) DO UPDATE SET embed = $2, author_id = $3, updated_at = now()",
+    )
+    .bind(name)
+    .bind(embed)
+    .bind(author_id)
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+/// Returns `false` when there is no template with that name.
+pub async fn delete(pool: &PgPool, name: &str) -> anyhow::Result<bool> {
+    let result = sqlx::query("DELETE FROM embed_templates WHERE name = $1")
+        .bind(name)
+        .execute(pool)
+        .await?;
+
+    Ok(result.rows_affected() == 1)
+}
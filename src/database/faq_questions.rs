@@ -0,0 +1,58 @@
+use sqlx::PgPool;
+
+/// A known question the auto-responder answers with a tag.
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct FaqQuestion {
+    pub id: i64,
+    pub question: String,
+    pub tag_name: String,
+}
+
+pub async fn all(pool: &PgPool) -> anyhow::Result<Vec<FaqQuestion>> {
+    let questions =
+        sqlx::query_as::<_, FaqQuestion>("SELECT id, question, tag_name FROM faq_questions")
+            .fetch_all(pool)
+            .await?;
+
+    Ok(questions)
+}
+
+/// Questions containing `partial`, used for autocomplete.
+pub async fn questions_matching(
+    pool: &PgPool,
+    partial: &str,
+    limit: i64,
+) -> anyhow::Result<Vec<String>> {
+    let questions = sqlx::query_scalar::<_, String>(
+        "SELECT question FROM faq_questions WHERE question ILIKE '%' || $1 || '%' ORDER BY question LIMIT $2",
+    )
+    .bind(partial)
+    .bind(limit)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(questions)
+}
+
+/// Returns `false` when the question is already known.
+pub async fn insert(pool: &PgPool, question: &str, tag_name: &str) -> anyhow::Result<bool> {
+    let result = sqlx::query(
+        "INSERT INTO faq_questions (question, tag_name) VALUES ($1, $2) ON CONFLICT (question) DO NOTHING",
+    )
+    .bind(question)
+    .bind(tag_name)
+    .execute(pool)
+    .await?;
+
+    Ok(result.rows_affected() == 1)
+}
+
+/// Returns `false` when the question isn't known.
+pub async fn delete(pool: &PgPool, question: &str) -> anyhow::Result<bool> {
+    let result = sqlx::query("DELETE FROM faq_questions WHERE question = $1")
+        .bind(question)
+        .execute(pool)
+        .await?;
+
+    Ok(result.rows_affected() == 1)
+}
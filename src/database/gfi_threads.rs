@@ -0,0 +1,47 @@
+use sqlx::PgPool;
+
+/// The discussion thread started on the good first issue alert of an issue.
+pub async fn thread_id(
+    pool: &PgPool,
+    repository: &str,
+    issue_number: i64,
+) -> anyhow::Result<Option<i64>> {
+    let thread_id = sqlx::query_scalar::<_, i64>(
+        "SELECT thread_id FROM gfi_threads WHERE repository = $1 AND issue_number = $2",
+    )
+    .bind(repository)
+    .bind(issue_number)
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(thread_id)
+}
+
+pub async fn insert(
+    pool: &PgPool,
+    repository: &str,
+    issue_number: i64,
+    thread_id: i64,
+) -> anyhow::Result<()> {
+    sqlx::query(
+        "INSERT INTO gfi_threads (repository, issue_number, thread_id) VALUES ($1, $2, $3)
+         ON CONFLICT (repository, issue_number) DO UPDATE SET thread_id = $3",
+    )
+    .bind(repository)
+    .bind(issue_number)
+    .bind(thread_id)
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+pub async fn delete(pool: &PgPool, repository: &str, issue_number: i64) -> anyhow::Result<()> {
+    sqlx::query("DELETE FROM gfi_threads WHERE repository = $1 AND issue_number = $2")
+        .bind(repository)
+        .bind(issue_number)
+        .execute(pool)
+        .await?;
+
+    Ok(())
+}
@@ -0,0 +1,96 @@
+use chrono::{DateTime, Utc};
+use sqlx::PgPool;
+
+#[derive(Debug, sqlx::FromRow)]
+pub struct GithubLink {
+    pub user_id: i64,
+    pub github_id: i64,
+    pub github_login: String,
+    pub linked_at: DateTime<Utc>,
+}
+
+/// Links the github account to the user, replacing the account linked before. `false` if the
+/// github account is already linked to another user, it has to be unlinked there first.
+pub async fn upsert(
+    pool: &PgPool,
+    user_id: i64,
+    github_id: i64,
+    github_login: &str,
+) -> anyhow::Result<bool> {
+    let mut transaction = pool.begin().await?;
+
+    let linked_elsewhere = sqlx::query_scalar::<_, i64>(
+        "SELECT user_id FROM github_links WHERE github_id = $1 AND user_id <> $2",
+    )
+    .bind(github_id)
+    .bind(user_id)
+    .fetch_optional(&mut *transaction)
+    .await?;
+
+    if linked_elsewhere.is_some() {
+        return Ok(false);
+    }
+
+    sqlx::query(
+        "INSERT INTO github_links (user_id, github_id, github_login) VALUES ($1, $2, $3) ON CONFLICT (user_id) DO UPDATE SET github_id = $2, github_login = $3, linked_at = now()",
+    )
+    .bind(user_id)
+    .bind(github_id)
+    .bind(github_login)
+    .execute(&mut *transaction)
+    .await?;
+
+    transaction.commit().await?;
+
+    Ok(true)
+}
+
+pub async fn get(pool: &PgPool, user_id: i64) -> anyhow::Result<Option<GithubLink>> {
+    let link = sqlx::query_as::<_, GithubLink>("SELECT * FROM github_links WHERE user_id = $1")
+        .bind(user_id)
+        .fetch_optional(pool)
+        .await?;
+
+    Ok(link)
+}
+
+/// `false` if the user hadn't linked an account.
+pub async fn delete(pool: &PgPool, user_id: i64) -> anyhow::Result<bool> {
+    let result = sqlx::query("DELETE FROM github_links WHERE user_id = $1")
+        .bind(user_id)
+        .execute(pool)
+        .await?;
+
+    Ok(result.rows_affected() > 0)
+}
+
+pub async fn all(pool: &PgPool) -> anyhow::Result<Vec<GithubLink>> {
+    let links = sqlx::query_as::<_, GithubLink>("SELECT * FROM github_links ORDER BY linked_at")
+        .fetch_all(pool)
+        .await?;
+
+    Ok(links)
+}
+
+/// Remembers the new login after the user renamed their github account.
+pub async fn update_login(pool: &PgPool, user_id: i64, github_login: &str) -> anyhow::Result<()> {
+    sqlx::query("UPDATE github_links SET github_login = $2 WHERE user_id = $1")
+        .bind(user_id)
+        .bind(github_login)
+        .execute(pool)
+        .await?;
+
+    Ok(())
+}
+
+/// Logins are case insensitive on github.
+pub async fn by_login(pool: &PgPool, github_login: &str) -> anyhow::Result<Option<GithubLink>> {
+    let link = sqlx::query_as::<_, GithubLink>(
+        "SELECT * FROM github_links WHERE lower(github_login) = lower($1)",
+    )
+    .bind(github_login)
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(link)
+}
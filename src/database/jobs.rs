@@ -0,0 +1,110 @@
+use chrono::{DateTime, Utc};
+use sqlx::PgPool;
+
+#[derive(Debug, sqlx::FromRow)]
+pub struct JobRow {
+    pub id: i64,
+    pub kind: String,
+    pub payload: serde_json::Value,
+    /// Includes the current attempt once the job is claimed.
+    pub attempts: i32,
+    pub max_attempts: i32,
+    pub run_at: DateTime<Utc>,
+    pub locked_until: Option<DateTime<Utc>>,
+    pub last_error: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+pub async fn insert(
+    pool: &PgPool,
+    kind: &str,
+    payload: &serde_json::Value,
+    max_attempts: i32,
+    run_at: DateTime<Utc>,
+) -> anyhow::Result<i64> {
+    let id = sqlx::query_scalar::<_, i64>(
+        "INSERT INTO jobs (kind, payload, max_attempts, run_at) VALUES ($1, $2, $3, $4) RETURNING id",
+    )
+    .bind(kind)
+    .bind(payload)
+    .bind(max_attempts)
+    .bind(run_at)
+    .fetch_one(pool)
+    .await?;
+
+    Ok(id)
+}
+
+/// Locks the next due job for `lock_secs` seconds and counts the attempt. Jobs locked by another
+/// worker are skipped, jobs whose lock expired are claimed again.
+pub async fn claim(pool: &PgPool, lock_secs: i64) -> anyhow::Result<Option<JobRow>> {
+    let job = sqlx::query_as::<_, JobRow>(
+        "UPDATE jobs SET attempts = attempts + 1, locked_until = now() + make_interval(secs => $1)
+         WHERE id = (
+             SELECT id FROM jobs
+             WHERE run_at <= now() AND (locked_until IS NULL OR locked_until < now())
+             ORDER BY run_at
+             LIMIT 1
+             FOR UPDATE SKIP LOCKED
+         )
+         RETURNING *",
+    )
+    .bind(lock_secs as f64)
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(job)
+}
+
+pub async fn complete(pool: &PgPool, id: i64) -> anyhow::Result<()> {
+    sqlx::query("DELETE FROM jobs WHERE id = $1")
+        .bind(id)
+        .execute(pool)
+        .await?;
+
+    Ok(())
+}
+
+/// Unlocks the job so it runs again at `retry_at`.
+pub async fn retry(
+    pool: &PgPool,
+    id: i64,
+    error: &str,
+    retry_at: DateTime<Utc>,
+) -> anyhow::Result<()> {
+    sqlx::query("UPDATE jobs SET locked_until = NULL, last_error = $2, run_at = $3 WHERE id = $1")
+        .bind(id)
+        .bind(error)
+        .bind(retry_at)
+        .execute(pool)
+        .await?;
+
+    Ok(())
+}
+
+/// Moves the job into the dead letter table.
+pub async fn bury(pool: &PgPool, id: i64, error: &str) -> anyhow::Result<()> {
+    sqlx::query(
+        "WITH dead AS (DELETE FROM jobs WHERE id = $1 RETURNING *)
+         INSERT INTO dead_jobs (id, kind, payload, attempts, last_error, created_at)
+         SELECT id, kind, payload, attempts, $2, created_at FROM dead",
+    )
+    .bind(id)
+    .bind(error)
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+/// Jobs that are due but not claimed by a worker yet, scheduled jobs like reminders don't count
+/// until their time comes.
+pub async fn count_due(pool: &PgPool) -> anyhow::Result<i64> {
+    let count = sqlx::query_scalar::<_, i64>(
+        "SELECT COUNT(*) FROM jobs WHERE run_at <= now() AND (locked_until IS NULL OR locked_until < now())",
+    )
+    .fetch_one(pool)
+    .await?;
+
+    Ok(count)
+}
@@ -0,0 +1,63 @@
+use chrono::{DateTime, Utc};
+use sqlx::PgPool;
+
+use crate::language_support::{LanguageFeature, LanguageSupportLevel};
+
+/// The last scraped support of a language, the levels are stored as their discriminant.
+#[derive(Debug, sqlx::FromRow)]
+pub struct LanguageSupportRow {
+    pub language_name: String,
+    pub parsing: i16,
+    pub formatting: i16,
+    pub linting: i16,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl TryFrom<LanguageSupportRow> for LanguageFeature {
+    type Error = anyhow::Error;
+
+    fn try_from(row: LanguageSupportRow) -> Result<Self, Self::Error> {
+        Ok(Self {
+            language_name: row.language_name,
+            parsing: LanguageSupportLevel::try_from(row.parsing)?,
+            formatting: LanguageSupportLevel::try_from(row.formatting)?,
+            linting: LanguageSupportLevel::try_from(row.linting)?,
+        })
+    }
+}
+
+pub async fn all(pool: &PgPool) -> anyhow::Result<Vec<LanguageFeature>> {
+    let rows = sqlx::query_as::<_, LanguageSupportRow>(
+        "SELECT * FROM language_support ORDER BY language_name",
+    )
+    .fetch_all(pool)
+    .await?;
+
+    rows.into_iter().map(LanguageFeature::try_from).collect()
+}
+
+/// Replaces the stored table with the freshly scraped one.
+pub async fn replace_all(pool: &PgPool, features: &[LanguageFeature]) -> anyhow::Result<()> {
+    let mut transaction = pool.begin().await?;
+
+    sqlx::query("DELETE FROM language_support")
+        .execute(&mut *transaction)
+        .await?;
+
+    for feature in features {
+        sqlx::query(
+            "INSERT INTO language_support (language_name, parsing, formatting, linting) VALUES ($1, $2, $3, $4)
+             ON CONFLICT (language_name) DO UPDATE SET parsing = $2, formatting = $3, linting = $4, updated_at = now()",
+        )
+        .bind(&feature.language_name)
+        .bind(feature.parsing as i16)
+        .bind(feature.formatting as i16)
+        .bind(feature.linting as i16)
+        .execute(&mut *transaction)
+        .await?;
+    }
+
+    transaction.commit().await?;
+
+    Ok(())
+}
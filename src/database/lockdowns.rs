@@ -0,0 +1,63 @@
+use chrono::{DateTime, Utc};
+use sqlx::PgPool;
+
+#[derive(Debug, sqlx::FromRow)]
+pub struct Lockdown {
+    pub id: i64,
+    pub reason: String,
+    pub previous_verification_level: i16,
+    /// `None` when the raid detection started the lockdown.
+    pub started_by: Option<i64>,
+    pub started_at: DateTime<Utc>,
+    pub ended_by: Option<i64>,
+    pub ended_at: Option<DateTime<Utc>>,
+}
+
+/// Records the start of a lockdown.
+pub async fn start(
+    pool: &PgPool,
+    reason: &str,
+    previous_verification_level: i16,
+    started_by: Option<i64>,
+) -> anyhow::Result<()> {
+    sqlx::query(
+        "INSERT INTO lockdowns (reason, previous_verification_level, started_by) VALUES ($1, $2, $3)",
+    )
+    .bind(reason)
+    .bind(previous_verification_level)
+    .bind(started_by)
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+pub async fn active(pool: &PgPool) -> anyhow::Result<Option<Lockdown>> {
+    let lockdown = sqlx::query_as::<_, Lockdown>("SELECT * FROM lockdowns WHERE ended_at IS NULL")
+        .fetch_optional(pool)
+        .await?;
+
+    Ok(lockdown)
+}
+
+pub async fn end(pool: &PgPool, id: i64, ended_by: i64) -> anyhow::Result<()> {
+    sqlx::query("UPDATE lockdowns SET ended_by = $2, ended_at = now() WHERE id = $1")
+        .bind(id)
+        .bind(ended_by)
+        .execute(pool)
+        .await?;
+
+    Ok(())
+}
+
+/// Lockdowns the user started or ended.
+pub async fn involving(pool: &PgPool, user_id: i64) -> anyhow::Result<Vec<Lockdown>> {
+    let lockdowns = sqlx::query_as::<_, Lockdown>(
+        "SELECT * FROM lockdowns WHERE started_by = $1 OR ended_by = $1 ORDER BY started_at",
+    )
+    .bind(user_id)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(lockdowns)
+}
@@ -0,0 +1,49 @@
+use chrono::{DateTime, Utc};
+use sqlx::PgPool;
+
+#[derive(Debug, sqlx::FromRow)]
+pub struct MessageLog {
+    pub id: i64,
+    pub message_id: i64,
+    pub channel_id: i64,
+    pub author_id: i64,
+    pub action: String,
+    pub content_before: Option<String>,
+    pub content_after: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+pub async fn insert(
+    pool: &PgPool,
+    message_id: i64,
+    channel_id: i64,
+    author_id: i64,
+    action: &str,
+    content_before: Option<&str>,
+    content_after: Option<&str>,
+) -> anyhow::Result<()> {
+    sqlx::query(
+        "INSERT INTO message_logs (message_id, channel_id, author_id, action, content_before, content_after) VALUES ($1, $2, $3, $4, $5, $6)",
+    )
+    .bind(message_id)
+    .bind(channel_id)
+    .bind(author_id)
+    .bind(action)
+    .bind(content_before)
+    .bind(content_after)
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+pub async fn by_author(pool: &PgPool, author_id: i64) -> anyhow::Result<Vec<MessageLog>> {
+    let logs = sqlx::query_as::<_, MessageLog>(
+        "SELECT * FROM message_logs WHERE author_id = $1 ORDER BY created_at",
+    )
+    .bind(author_id)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(logs)
+}
@@ -0,0 +1,29 @@
+pub mod announcements;
+pub mod blocked_domains;
+pub mod canned_responses;
+pub mod channel_locks;
+pub mod channel_webhooks;
+pub mod digest_snapshots;
+pub mod docs_pages;
+pub mod embed_snapshots;
+pub mod embed_templates;
+pub mod faq_questions;
+pub mod gfi_threads;
+pub mod github_links;
+pub mod jobs;
+pub mod language_support;
+pub mod lockdowns;
+pub mod message_logs;
+pub mod mod_cases;
+pub mod polls;
+pub mod reaction_roles;
+pub mod reminders;
+pub mod reports;
+pub mod self_roles;
+pub mod settings;
+pub mod solved_threads;
+pub mod stale_thread_bumps;
+pub mod suggestions;
+pub mod tags;
+pub mod triage_actions;
+pub mod verifications;
@@ -0,0 +1,75 @@
+use chrono::{DateTime, Utc};
+use sqlx::PgPool;
+
+#[derive(Debug, sqlx::FromRow)]
+pub struct ModCase {
+    /// The case number.
+    pub id: i64,
+    pub action: String,
+    pub target_id: i64,
+    pub moderator_id: i64,
+    pub reason: String,
+    /// How long a timeout lasts, `None` for the other actions.
+    pub duration_secs: Option<i64>,
+    pub created_at: DateTime<Utc>,
+    /// The server the action was taken in, `None` for the main server.
+    pub guild_id: Option<i64>,
+}
+
+/// Records a moderation action and returns the case.
+pub async fn insert(
+    pool: &PgPool,
+    guild_id: i64,
+    action: &str,
+    target_id: i64,
+    moderator_id: i64,
+    reason: &str,
+    duration_secs: Option<i64>,
+) -> anyhow::Result<ModCase> {
+    let case = sqlx::query_as::<_, ModCase>(
+        "INSERT INTO mod_cases (guild_id, action, target_id, moderator_id, reason, duration_secs) VALUES ($1, $2, $3, $4, $5, $6) RETURNING *",
+    )
+    .bind(guild_id)
+    .bind(action)
+    .bind(target_id)
+    .bind(moderator_id)
+    .bind(reason)
+    .bind(duration_secs)
+    .fetch_one(pool)
+    .await?;
+
+    Ok(case)
+}
+
+pub async fn get(pool: &PgPool, id: i64) -> anyhow::Result<Option<ModCase>> {
+    let case = sqlx::query_as::<_, ModCase>("SELECT * FROM mod_cases WHERE id = $1")
+        .bind(id)
+        .fetch_optional(pool)
+        .await?;
+
+    Ok(case)
+}
+
+/// The cases against the user, the newest first.
+pub async fn by_target(pool: &PgPool, target_id: i64) -> anyhow::Result<Vec<ModCase>> {
+    let cases = sqlx::query_as::<_, ModCase>(
+        "SELECT * FROM mod_cases WHERE target_id = $1 ORDER BY id DESC",
+    )
+    .bind(target_id)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(cases)
+}
+
+/// The cases against or by the user.
+pub async fn involving(pool: &PgPool, user_id: i64) -> anyhow::Result<Vec<ModCase>> {
+    let cases = sqlx::query_as::<_, ModCase>(
+        "SELECT * FROM mod_cases WHERE target_id = $1 OR moderator_id = $1 ORDER BY id",
+    )
+    .bind(user_id)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(cases)
+}
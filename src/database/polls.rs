@@ -0,0 +1,149 @@
+use chrono::{DateTime, Utc};
+use sqlx::PgPool;
+
+#[derive(Debug, sqlx::FromRow)]
+pub struct Poll {
+    pub id: i64,
+    pub author_id: i64,
+    pub question: String,
+    pub options: Vec<String>,
+    pub channel_id: Option<i64>,
+    pub message_id: Option<i64>,
+    pub closes_at: DateTime<Utc>,
+    pub closed: bool,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, sqlx::FromRow)]
+pub struct PollVote {
+    pub poll_id: i64,
+    pub option_index: i16,
+    pub voted_at: DateTime<Utc>,
+}
+
+/// Stores a new poll and returns its id.
+pub async fn insert(
+    pool: &PgPool,
+    author_id: i64,
+    question: &str,
+    options: &[String],
+    closes_at: DateTime<Utc>,
+) -> anyhow::Result<i64> {
+    let id = sqlx::query_scalar::<_, i64>(
+        "INSERT INTO polls (author_id, question, options, closes_at) VALUES ($1, $2, $3, $4) RETURNING id",
+    )
+    .bind(author_id)
+    .bind(question)
+    .bind(options)
+    .bind(closes_at)
+    .fetch_one(pool)
+    .await?;
+
+    Ok(id)
+}
+
+/// Remembers where the poll got posted.
+pub async fn set_message(
+    pool: &PgPool,
+    id: i64,
+    channel_id: i64,
+    message_id: i64,
+) -> anyhow::Result<()> {
+    sqlx::query("UPDATE polls SET channel_id = $2, message_id = $3 WHERE id = $1")
+        .bind(id)
+        .bind(channel_id)
+        .bind(message_id)
+        .execute(pool)
+        .await?;
+
+    Ok(())
+}
+
+pub async fn get(pool: &PgPool, id: i64) -> anyhow::Result<Option<Poll>> {
+    let poll = sqlx::query_as::<_, Poll>("SELECT * FROM polls WHERE id = $1")
+        .bind(id)
+        .fetch_optional(pool)
+        .await?;
+
+    Ok(poll)
+}
+
+/// Open polls whose time ran out.
+pub async fn due(pool: &PgPool) -> anyhow::Result<Vec<Poll>> {
+    let polls =
+        sqlx::query_as::<_, Poll>("SELECT * FROM polls WHERE NOT closed AND closes_at <= now()")
+            .fetch_all(pool)
+            .await?;
+
+    Ok(polls)
+}
+
+pub async fn close(pool: &PgPool, id: i64) -> anyhow::Result<()> {
+    sqlx::query("UPDATE polls SET closed = true WHERE id = $1")
+        .bind(id)
+        .execute(pool)
+        .await?;
+
+    Ok(())
+}
+
+/// Casts the vote of the user, replacing the one they cast before.
+pub async fn vote(
+    pool: &PgPool,
+    poll_id: i64,
+    user_id: i64,
+    option_index: i16,
+) -> anyhow::Result<()> {
+    sqlx::query(
+        "INSERT INTO poll_votes (poll_id, user_id, option_index) VALUES ($1, $2, $3)
+         ON CONFLICT (poll_id, user_id) DO UPDATE SET option_index = $3, voted_at = now()",
+    )
+    .bind(poll_id)
+    .bind(user_id)
+    .bind(option_index)
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+/// The number of votes of every option, in the order of the options.
+pub async fn tally(pool: &PgPool, poll_id: i64, option_count: usize) -> anyhow::Result<Vec<i64>> {
+    let rows = sqlx::query_as::<_, (i16, i64)>(
+        "SELECT option_index, COUNT(*) FROM poll_votes WHERE poll_id = $1 GROUP BY option_index",
+    )
+    .bind(poll_id)
+    .fetch_all(pool)
+    .await?;
+
+    let mut counts = vec![0; option_count];
+
+    for (option_index, count) in rows {
+        if let Some(slot) = counts.get_mut(option_index as usize) {
+            *slot = count;
+        }
+    }
+
+    Ok(counts)
+}
+
+pub async fn authored_by(pool: &PgPool, author_id: i64) -> anyhow::Result<Vec<Poll>> {
+    let polls =
+        sqlx::query_as::<_, Poll>("SELECT * FROM polls WHERE author_id = $1 ORDER BY created_at")
+            .bind(author_id)
+            .fetch_all(pool)
+            .await?;
+
+    Ok(polls)
+}
+
+pub async fn votes_by(pool: &PgPool, user_id: i64) -> anyhow::Result<Vec<PollVote>> {
+    let votes = sqlx::query_as::<_, PollVote>(
+        "SELECT poll_id, option_index, voted_at FROM poll_votes WHERE user_id = $1 ORDER BY voted_at",
+    )
+    .bind(user_id)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(votes)
+}
@@ -0,0 +1,70 @@
+use chrono::{DateTime, Utc};
+use sqlx::PgPool;
+
+#[derive(Debug, sqlx::FromRow)]
+pub struct ReactionRole {
+    pub message_id: i64,
+    pub channel_id: i64,
+    pub emoji: String,
+    pub role_id: i64,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Binds the role to reactions with the emoji on the message, replacing the role bound before.
+pub async fn upsert(
+    pool: &PgPool,
+    message_id: i64,
+    channel_id: i64,
+    emoji: &str,
+    role_id: i64,
+) -> anyhow::Result<()> {
+    sqlx::query(
+        "INSERT INTO reaction_roles (message_id, channel_id, emoji, role_id) VALUES ($1, $2, $3, $4) ON CONFLICT (message_id, emoji) DO UPDATE SET role_id = $4",
+    )
+    .bind(message_id)
+    .bind(channel_id)
+    .bind(emoji)
+    .bind(role_id)
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+pub async fn get(
+    pool: &PgPool,
+    message_id: i64,
+    emoji: &str,
+) -> anyhow::Result<Option<ReactionRole>> {
+    let reaction_role = sqlx::query_as::<_, ReactionRole>(
+        "SELECT * FROM reaction_roles WHERE message_id = $1 AND emoji = $2",
+    )
+    .bind(message_id)
+    .bind(emoji)
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(reaction_role)
+}
+
+/// `false` if nothing was bound to the emoji on the message.
+pub async fn delete(pool: &PgPool, message_id: i64, emoji: &str) -> anyhow::Result<bool> {
+    let result = sqlx::query("DELETE FROM reaction_roles WHERE message_id = $1 AND emoji = $2")
+        .bind(message_id)
+        .bind(emoji)
+        .execute(pool)
+        .await?;
+
+    Ok(result.rows_affected() > 0)
+}
+
+/// All reaction roles, grouped by message.
+pub async fn all(pool: &PgPool) -> anyhow::Result<Vec<ReactionRole>> {
+    let reaction_roles = sqlx::query_as::<_, ReactionRole>(
+        "SELECT * FROM reaction_roles ORDER BY message_id, created_at",
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(reaction_roles)
+}
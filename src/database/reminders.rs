@@ -0,0 +1,78 @@
+use chrono::{DateTime, Utc};
+use sqlx::PgPool;
+
+#[derive(Debug, sqlx::FromRow)]
+pub struct Reminder {
+    pub id: i64,
+    pub user_id: i64,
+    /// The channel the reminder was created in, pinged unless the reminder is sent as a DM.
+    pub channel_id: i64,
+    pub content: String,
+    pub dm: bool,
+    pub remind_at: DateTime<Utc>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Stores a new reminder and returns its id.
+pub async fn insert(
+    pool: &PgPool,
+    user_id: i64,
+    channel_id: i64,
+    content: &str,
+    dm: bool,
+    remind_at: DateTime<Utc>,
+) -> anyhow::Result<i64> {
+    let id = sqlx::query_scalar::<_, i64>(
+        "INSERT INTO reminders (user_id, channel_id, content, dm, remind_at) VALUES ($1, $2, $3, $4, $5) RETURNING id",
+    )
+    .bind(user_id)
+    .bind(channel_id)
+    .bind(content)
+    .bind(dm)
+    .bind(remind_at)
+    .fetch_one(pool)
+    .await?;
+
+    Ok(id)
+}
+
+/// The pending reminders of the user, the next one first.
+pub async fn by_user(pool: &PgPool, user_id: i64) -> anyhow::Result<Vec<Reminder>> {
+    let reminders = sqlx::query_as::<_, Reminder>(
+        "SELECT * FROM reminders WHERE user_id = $1 ORDER BY remind_at",
+    )
+    .bind(user_id)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(reminders)
+}
+
+pub async fn get(pool: &PgPool, id: i64) -> anyhow::Result<Option<Reminder>> {
+    let reminder = sqlx::query_as::<_, Reminder>("SELECT * FROM reminders WHERE id = $1")
+        .bind(id)
+        .fetch_optional(pool)
+        .await?;
+
+    Ok(reminder)
+}
+
+/// Deletes the reminder if it belongs to the user, `false` if there was no such reminder.
+pub async fn cancel(pool: &PgPool, id: i64, user_id: i64) -> anyhow::Result<bool> {
+    let result = sqlx::query("DELETE FROM reminders WHERE id = $1 AND user_id = $2")
+        .bind(id)
+        .bind(user_id)
+        .execute(pool)
+        .await?;
+
+    Ok(result.rows_affected() == 1)
+}
+
+pub async fn delete(pool: &PgPool, id: i64) -> anyhow::Result<()> {
+    sqlx::query("DELETE FROM reminders WHERE id = $1")
+        .bind(id)
+        .execute(pool)
+        .await?;
+
+    Ok(())
+}
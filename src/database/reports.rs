@@ -0,0 +1,83 @@
+use chrono::{DateTime, Utc};
+use sqlx::PgPool;
+
+#[derive(Debug, sqlx::FromRow)]
+pub struct Report {
+    pub id: i64,
+    pub message_id: i64,
+    pub channel_id: i64,
+    pub author_id: i64,
+    pub reporter_id: i64,
+    pub reason: Option<String>,
+    pub anonymous: bool,
+    pub status: String,
+    pub handled_by: Option<i64>,
+    pub created_at: DateTime<Utc>,
+    pub handled_at: Option<DateTime<Utc>>,
+}
+
+/// Stores the report and returns its id, `None` if the reporter already reported the message.
+pub async fn insert(
+    pool: &PgPool,
+    message_id: i64,
+    channel_id: i64,
+    author_id: i64,
+    reporter_id: i64,
+    reason: Option<&str>,
+    anonymous: bool,
+) -> anyhow::Result<Option<i64>> {
+    let id = sqlx::query_scalar::<_, i64>(
+        "INSERT INTO reports (message_id, channel_id, author_id, reporter_id, reason, anonymous) VALUES ($1, $2, $3, $4, $5, $6) ON CONFLICT (message_id, reporter_id) DO NOTHING RETURNING id",
+    )
+    .bind(message_id)
+    .bind(channel_id)
+    .bind(author_id)
+    .bind(reporter_id)
+    .bind(reason)
+    .bind(anonymous)
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(id)
+}
+
+/// Marks the open report as handled, `None` if it doesn't exist or was already handled.
+pub async fn resolve(
+    pool: &PgPool,
+    id: i64,
+    status: &str,
+    handled_by: i64,
+) -> anyhow::Result<Option<Report>> {
+    let report = sqlx::query_as::<_, Report>(
+        "UPDATE reports SET status = $2, handled_by = $3, handled_at = now() WHERE id = $1 AND status = 'open' RETURNING *",
+    )
+    .bind(id)
+    .bind(status)
+    .bind(handled_by)
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(report)
+}
+
+pub async fn by_reporter(pool: &PgPool, reporter_id: i64) -> anyhow::Result<Vec<Report>> {
+    let reports = sqlx::query_as::<_, Report>(
+        "SELECT * FROM reports WHERE reporter_id = $1 ORDER BY created_at",
+    )
+    .bind(reporter_id)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(reports)
+}
+
+pub async fn handled_by(pool: &PgPool, user_id: i64) -> anyhow::Result<Vec<Report>> {
+    let reports = sqlx::query_as::<_, Report>(
+        "SELECT * FROM reports WHERE handled_by = $1 ORDER BY created_at",
+    )
+    .bind(user_id)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(reports)
+}
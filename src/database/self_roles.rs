@@ -0,0 +1,48 @@
+use chrono::{DateTime, Utc};
+use sqlx::PgPool;
+
+#[derive(Debug, sqlx::FromRow)]
+pub struct SelfRole {
+    pub role_id: i64,
+    pub description: Option<String>,
+    pub emoji: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Makes the role self-assignable, or replaces its description and emoji if it already is.
+pub async fn upsert(
+    pool: &PgPool,
+    role_id: i64,
+    description: Option<&str>,
+    emoji: Option<&str>,
+) -> anyhow::Result<()> {
+    sqlx::query(
+        "INSERT INTO self_roles (role_id, description, emoji) VALUES ($1, $2, $3) ON CONFLICT (role_id) DO UPDATE SET description = $2, emoji = $3",
+    )
+    .bind(role_id)
+    .bind(description)
+    .bind(emoji)
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+/// `false` if the role wasn't self-assignable.
+pub async fn delete(pool: &PgPool, role_id: i64) -> anyhow::Result<bool> {
+    let result = sqlx::query("DELETE FROM self_roles WHERE role_id = $1")
+        .bind(role_id)
+        .execute(pool)
+        .await?;
+
+    Ok(result.rows_affected() > 0)
+}
+
+/// All self-assignable roles in the order they were added.
+pub async fn all(pool: &PgPool) -> anyhow::Result<Vec<SelfRole>> {
+    let roles = sqlx::query_as::<_, SelfRole>("SELECT * FROM self_roles ORDER BY created_at")
+        .fetch_all(pool)
+        .await?;
+
+    Ok(roles)
+}
@@ -0,0 +1,60 @@
+use chrono::{DateTime, Utc};
+use serde_json::Value;
+use sqlx::PgPool;
+
+/// A setting changed with `/config`, overriding the default from the config file.
+#[derive(Debug, sqlx::FromRow)]
+pub struct StoredSetting {
+    pub key: String,
+    pub value: Value,
+    pub updated_by: i64,
+    pub updated_at: DateTime<Utc>,
+}
+
+pub async fn all(pool: &PgPool) -> anyhow::Result<Vec<StoredSetting>> {
+    let settings = sqlx::query_as::<_, StoredSetting>("SELECT * FROM settings ORDER BY key")
+        .fetch_all(pool)
+        .await?;
+
+    Ok(settings)
+}
+
+pub async fn upsert(
+    pool: &PgPool,
+    key: &str,
+    value: &Value,
+    updated_by: i64,
+) -> anyhow::Result<()> {
+    sqlx::query(
+        "INSERT INTO settings (key, value, updated_by) VALUES ($1, $2, $3)
+         ON CONFLICT (key) DO UPDATE SET value = $2, updated_by = $3, updated_at = now()",
+    )
+    .bind(key)
+    .bind(value)
+    .bind(updated_by)
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+pub async fn updated_by(pool: &PgPool, user_id: i64) -> anyhow::Result<Vec<StoredSetting>> {
+    let settings = sqlx::query_as::<_, StoredSetting>(
+        "SELECT * FROM settings WHERE updated_by = $1 ORDER BY key",
+    )
+    .bind(user_id)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(settings)
+}
+
+/// Goes back to the default from the config file, `false` if the setting wasn't changed.
+pub async fn delete(pool: &PgPool, key: &str) -> anyhow::Result<bool> {
+    let result = sqlx::query("DELETE FROM settings WHERE key = $1")
+        .bind(key)
+        .execute(pool)
+        .await?;
+
+    Ok(result.rows_affected() > 0)
+}
@@ -0,0 +1,50 @@
+use chrono::{DateTime, Utc};
+use sqlx::PgPool;
+
+#[derive(Debug, sqlx::FromRow)]
+pub struct SolvedThread {
+    pub thread_id: i64,
+    pub forum_id: i64,
+    /// The user that marked the thread as solved, usually the author of the post.
+    pub marked_by: i64,
+    /// The user whose answer solved the problem.
+    pub solver_id: Option<i64>,
+    pub summary: Option<String>,
+    pub solved_at: DateTime<Utc>,
+}
+
+/// Returns `false` when the thread was already marked as solved.
+pub async fn insert(
+    pool: &PgPool,
+    thread_id: i64,
+    forum_id: i64,
+    marked_by: i64,
+    solver_id: Option<i64>,
+    summary: Option<&str>,
+) -> anyhow::Result<bool> {
+    let result = sqlx::query(
+        "INSERT INTO solved_threads (thread_id, forum_id, marked_by, solver_id, summary) VALUES ($1, $2, $3, $4, $5)
+         ON CONFLICT (thread_id) DO NOTHING",
+    )
+    .bind(thread_id)
+    .bind(forum_id)
+    .bind(marked_by)
+    .bind(solver_id)
+    .bind(summary)
+    .execute(pool)
+    .await?;
+
+    Ok(result.rows_affected() == 1)
+}
+
+/// Threads the user marked as solved or solved for someone else.
+pub async fn involving(pool: &PgPool, user_id: i64) -> anyhow::Result<Vec<SolvedThread>> {
+    let threads = sqlx::query_as::<_, SolvedThread>(
+        "SELECT * FROM solved_threads WHERE marked_by = $1 OR solver_id = $1 ORDER BY solved_at",
+    )
+    .bind(user_id)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(threads)
+}
@@ -0,0 +1,35 @@
+use sqlx::PgPool;
+
+/// The id of the last "still need help?" message posted into the thread.
+pub async fn message_id(pool: &PgPool, thread_id: i64) -> anyhow::Result<Option<i64>> {
+    let message_id = sqlx::query_scalar::<_, i64>(
+        "SELECT message_id FROM stale_thread_bumps WHERE thread_id = $1",
+    )
+    .bind(thread_id)
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(message_id)
+}
+
+pub async fn upsert(pool: &PgPool, thread_id: i64, message_id: i64) -> anyhow::Result<()> {
+    sqlx::query(
+        "INSERT INTO stale_thread_bumps (thread_id, message_id) VALUES ($1, $2)
+         ON CONFLICT (thread_id) DO UPDATE SET message_id = $2, bumped_at = now()",
+    )
+    .bind(thread_id)
+    .bind(message_id)
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+pub async fn delete(pool: &PgPool, thread_id: i64) -> anyhow::Result<()> {
+    sqlx::query("DELETE FROM stale_thread_bumps WHERE thread_id = $1")
+        .bind(thread_id)
+        .execute(pool)
+        .await?;
+
+    Ok(())
+}
@@ -0,0 +1,170 @@
+use chrono::{DateTime, Utc};
+use sqlx::PgPool;
+
+#[derive(Debug, sqlx::FromRow)]
+pub struct Suggestion {
+    pub id: i64,
+    pub author_id: i64,
+    pub content: String,
+    pub channel_id: Option<i64>,
+    pub message_id: Option<i64>,
+    /// One of `open`, `accepted` or `declined`.
+    pub status: String,
+    pub decided_by: Option<i64>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, sqlx::FromRow)]
+pub struct SuggestionVote {
+    pub suggestion_id: i64,
+    pub upvote: bool,
+    pub voted_at: DateTime<Utc>,
+}
+
+/// Stores a new suggestion and returns its id.
+pub async fn insert(pool: &PgPool, author_id: i64, content: &str) -> anyhow::Result<i64> {
+    let id = sqlx::query_scalar::<_, i64>(
+        "INSERT INTO suggestions (author_id, content) VALUES ($1, $2) RETURNING id",
+    )
+    .bind(author_id)
+    .bind(content)
+    .fetch_one(pool)
+    .await?;
+
+    Ok(id)
+}
+
+/// Remembers where the suggestion got posted.
+pub async fn set_message(
+    pool: &PgPool,
+    id: i64,
+    channel_id: i64,
+    message_id: i64,
+) -> anyhow::Result<()> {
+    sqlx::query("UPDATE suggestions SET channel_id = $2, message_id = $3 WHERE id = $1")
+        .bind(id)
+        .bind(channel_id)
+        .bind(message_id)
+        .execute(pool)
+        .await?;
+
+    Ok(())
+}
+
+pub async fn get(pool: &PgPool, id: i64) -> anyhow::Result<Option<Suggestion>> {
+    let suggestion = sqlx::query_as::<_, Suggestion>("SELECT * FROM suggestions WHERE id = $1")
+        .bind(id)
+        .fetch_optional(pool)
+        .await?;
+
+    Ok(suggestion)
+}
+
+/// Accepts or declines an open suggestion, `false` if it was already decided.
+pub async fn decide(pool: &PgPool, id: i64, status: &str, decided_by: i64) -> anyhow::Result<bool> {
+    let result = sqlx::query(
+        "UPDATE suggestions SET status = $2, decided_by = $3 WHERE id = $1 AND status = 'open'",
+    )
+    .bind(id)
+    .bind(status)
+    .bind(decided_by)
+    .execute(pool)
+    .await?;
+
+    Ok(result.rows_affected() == 1)
+}
+
+/// The vote the user cast before, `None` if they didn't vote yet.
+pub async fn previous_vote(
+    pool: &PgPool,
+    suggestion_id: i64,
+    user_id: i64,
+) -> anyhow::Result<Option<bool>> {
+    let upvote = sqlx::query_scalar::<_, bool>(
+        "SELECT upvote FROM suggestion_votes WHERE suggestion_id = $1 AND user_id = $2",
+    )
+    .bind(suggestion_id)
+    .bind(user_id)
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(upvote)
+}
+
+/// Casts the vote of the user, replacing the one they cast before.
+pub async fn vote(
+    pool: &PgPool,
+    suggestion_id: i64,
+    user_id: i64,
+    upvote: bool,
+) -> anyhow::Result<()> {
+    sqlx::query(
+        "INSERT INTO suggestion_votes (suggestion_id, user_id, upvote) VALUES ($1, $2, $3)
+         ON CONFLICT (suggestion_id, user_id) DO UPDATE SET upvote = $3, voted_at = now()",
+    )
+    .bind(suggestion_id)
+    .bind(user_id)
+    .bind(upvote)
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+/// Everyone who upvoted the suggestion.
+pub async fn upvoters(pool: &PgPool, suggestion_id: i64) -> anyhow::Result<Vec<i64>> {
+    let user_ids = sqlx::query_scalar::<_, i64>(
+        "SELECT user_id FROM suggestion_votes WHERE suggestion_id = $1 AND upvote",
+    )
+    .bind(suggestion_id)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(user_ids)
+}
+
+/// The number of upvotes and downvotes.
+pub async fn tally(pool: &PgPool, suggestion_id: i64) -> anyhow::Result<(i64, i64)> {
+    let tally = sqlx::query_as::<_, (i64, i64)>(
+        "SELECT COUNT(*) FILTER (WHERE upvote), COUNT(*) FILTER (WHERE NOT upvote)
+         FROM suggestion_votes WHERE suggestion_id = $1",
+    )
+    .bind(suggestion_id)
+    .fetch_one(pool)
+    .await?;
+
+    Ok(tally)
+}
+
+pub async fn authored_by(pool: &PgPool, author_id: i64) -> anyhow::Result<Vec<Suggestion>> {
+    let suggestions = sqlx::query_as::<_, Suggestion>(
+        "SELECT * FROM suggestions WHERE author_id = $1 ORDER BY created_at",
+    )
+    .bind(author_id)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(suggestions)
+}
+
+pub async fn votes_by(pool: &PgPool, user_id: i64) -> anyhow::Result<Vec<SuggestionVote>> {
+    let votes = sqlx::query_as::<_, SuggestionVote>(
+        "SELECT suggestion_id, upvote, voted_at FROM suggestion_votes WHERE user_id = $1 ORDER BY voted_at",
+    )
+    .bind(user_id)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(votes)
+}
+
+pub async fn decided_by(pool: &PgPool, user_id: i64) -> anyhow::Result<Vec<Suggestion>> {
+    let suggestions = sqlx::query_as::<_, Suggestion>(
+        "SELECT * FROM suggestions WHERE decided_by = $1 ORDER BY created_at",
+    )
+    .bind(user_id)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(suggestions)
+}
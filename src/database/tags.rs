@@ -0,0 +1,92 @@
+use chrono::{DateTime, Utc};
+use sqlx::PgPool;
+
+#[derive(Debug, sqlx::FromRow)]
+pub struct Tag {
+    pub name: String,
+    /// The markdown that gets posted when the tag is used.
+    pub content: String,
+    pub author_id: i64,
+    pub uses: i64,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+pub async fn get(pool: &PgPool, name: &str) -> anyhow::Result<Option<Tag>> {
+    let tag = sqlx::query_as::<_, Tag>("SELECT * FROM tags WHERE name = $1")
+        .bind(name)
+        .fetch_optional(pool)
+        .await?;
+
+    Ok(tag)
+}
+
+/// Counts a use of the tag and returns it.
+pub async fn use_tag(pool: &PgPool, name: &str) -> anyhow::Result<Option<Tag>> {
+    let tag =
+        sqlx::query_as::<_, Tag>("UPDATE tags SET uses = uses + 1 WHERE name = $1 RETURNING *")
+            .bind(name)
+            .fetch_optional(pool)
+            .await?;
+
+    Ok(tag)
+}
+
+/// All tags, the most used first.
+pub async fn all(pool: &PgPool) -> anyhow::Result<Vec<Tag>> {
+    let tags = sqlx::query_as::<_, Tag>("SELECT * FROM tags ORDER BY uses DESC, name")
+        .fetch_all(pool)
+        .await?;
+
+    Ok(tags)
+}
+
+pub async fn authored_by(pool: &PgPool, author_id: i64) -> anyhow::Result<Vec<Tag>> {
+    let tags =
+        sqlx::query_as::<_, Tag>("SELECT * FROM tags WHERE author_id = $1 ORDER BY created_at")
+            .bind(author_id)
+            .fetch_all(pool)
+            .await?;
+
+    Ok(tags)
+}
+
+/// Returns `false` when a tag with that name already exists.
+pub async fn insert(
+    pool: &PgPool,
+    name: &str,
+    content: &str,
+    author_id: i64,
+) -> anyhow::Result<bool> {
+    let result = sqlx::query(
+        "INSERT INTO tags (name, content, author_id) VALUES ($1, $2, $3) ON CONFLICT (name) DO NOTHING",
+    )
+    .bind(name)
+    .bind(content)
+    .bind(author_id)
+    .execute(pool)
+    .await?;
+
+    Ok(result.rows_affected() == 1)
+}
+
+/// Returns `false` when there is no tag with that name.
+pub async fn update(pool: &PgPool, name: &str, content: &str) -> anyhow::Result<bool> {
+    let result = sqlx::query("UPDATE tags SET content = $2, updated_at = now() WHERE name = $1")
+        .bind(name)
+        .bind(content)
+        .execute(pool)
+        .await?;
+
+    Ok(result.rows_affected() == 1)
+}
+
+/// Returns `false` when there is no tag with that name.
+pub async fn delete(pool: &PgPool, name: &str) -> anyhow::Result<bool> {
+    let result = sqlx::query("DELETE FROM tags WHERE name = $1")
+        .bind(name)
+        .execute(pool)
+        .await?;
+
+    Ok(result.rows_affected() == 1)
+}
@@ -0,0 +1,45 @@
+use chrono::{DateTime, Utc};
+use sqlx::PgPool;
+
+/// Audit log entry for an issue change made through `/triage`.
+#[derive(Debug, sqlx::FromRow)]
+pub struct TriageAction {
+    pub id: i64,
+    pub issue_number: i64,
+    /// One of `label`, `unlabel`, `assign` or `close`.
+    pub action: String,
+    pub detail: String,
+    pub actor_id: i64,
+    pub created_at: DateTime<Utc>,
+}
+
+pub async fn insert(
+    pool: &PgPool,
+    issue_number: i64,
+    action: &str,
+    detail: &str,
+    actor_id: i64,
+) -> anyhow::Result<()> {
+    sqlx::query(
+        "INSERT INTO triage_actions (issue_number, action, detail, actor_id) VALUES ($1, $2, $3, $4)",
+    )
+    .bind(issue_number)
+    .bind(action)
+    .bind(detail)
+    .bind(actor_id)
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+pub async fn by_actor(pool: &PgPool, actor_id: i64) -> anyhow::Result<Vec<TriageAction>> {
+    let actions = sqlx::query_as::<_, TriageAction>(
+        "SELECT * FROM triage_actions WHERE actor_id = $1 ORDER BY created_at",
+    )
+    .bind(actor_id)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(actions)
+}
@@ -0,0 +1,28 @@
+use chrono::{DateTime, Utc};
+use sqlx::PgPool;
+
+#[derive(Debug, sqlx::FromRow)]
+pub struct Verification {
+    pub user_id: i64,
+    pub verified_at: DateTime<Utc>,
+}
+
+/// Records that the user verified. Verifying again keeps the first timestamp.
+pub async fn insert(pool: &PgPool, user_id: i64) -> anyhow::Result<()> {
+    sqlx::query("INSERT INTO verifications (user_id) VALUES ($1) ON CONFLICT (user_id) DO NOTHING")
+        .bind(user_id)
+        .execute(pool)
+        .await?;
+
+    Ok(())
+}
+
+pub async fn get(pool: &PgPool, user_id: i64) -> anyhow::Result<Option<Verification>> {
+    let verification =
+        sqlx::query_as::<_, Verification>("SELECT * FROM verifications WHERE user_id = $1")
+            .bind(user_id)
+            .fetch_optional(pool)
+            .await?;
+
+    Ok(verification)
+}
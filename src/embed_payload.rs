@@ -0,0 +1,867 @@
+use std::fmt::Display;
+
+use chrono::DateTime;
+use poise::serenity_prelude as serenity;
+use serde::{de::Error as _, Deserialize, Serialize};
+use serde_json::{json, Value};
+use serenity::{
+    ActionRowComponent, Button, ButtonKind, CreateActionRow, CreateAttachment, CreateButton,
+    CreateEmbed, EditWebhookMessage, Embed, ExecuteWebhook, Message, ReactionType,
+};
+
+use crate::util::parse::parse_hex_color;
+
+/// Discord doesn't allow more embeds per message.
+const MAX_EMBEDS: usize = 10;
+/// The combined length of all texts in the embeds of a message.
+const MAX_EMBED_CHARACTERS: usize = 6000;
+const BUTTONS_PER_ROW: usize = 5;
+/// Messages hold five rows, one is left for the confirm buttons of the preview.
+const MAX_BUTTON_ROWS: usize = 4;
+const MAX_LABEL_LENGTH: usize = 80;
+const MAX_CONTENT_LENGTH: usize = 2000;
+const MAX_USERNAME_LENGTH: usize = 80;
+const MAX_TITLE_LENGTH: usize = 256;
+const MAX_DESCRIPTION_LENGTH: usize = 4096;
+const MAX_FIELDS: usize = 25;
+const MAX_FIELD_NAME_LENGTH: usize = 256;
+const MAX_FIELD_VALUE_LENGTH: usize = 1024;
+const MAX_FOOTER_LENGTH: usize = 2048;
+const MAX_AUTHOR_LENGTH: usize = 256;
+const MAX_COLOR: u64 = 0xFFFFFF;
+/// Images of embeds can also show files uploaded with the message.
+const IMAGE_SCHEMES: &[&str] = &["https://", "http://", "attachment://"];
+const LINK_SCHEMES: &[&str] = &["https://", "http://"];
+const BUTTON_SCHEMES: &[&str] = &["https://", "http://", "discord://"];
+/// The style of link buttons in discord's component json.
+const LINK_BUTTON_STYLE: u8 = 5;
+
+/// A message posted through one of the webhooks. Also reads the message json of Discohook.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct EmbedPayload {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub content: Option<String>,
+    #[serde(default)]
+    pub embeds: Vec<Embed>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub buttons: Vec<LinkButton>,
+    /// Replaces the name of the webhook for this message.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub username: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub avatar_url: Option<String>,
+    /// Uploaded with the message, embeds show them through `attachment://<filename>` urls. They
+    /// aren't stored, edits keep the files of the message.
+    #[serde(skip)]
+    pub files: Vec<CreateAttachment>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct LinkButton {
+    pub label: String,
+    pub url: String,
+    /// A unicode emoji or a custom one like `<:name:id>`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub emoji: Option<String>,
+}
+
+/// A row of buttons in discord's component json, as exported by Discohook.
+#[derive(Debug, Deserialize)]
+struct ComponentRow {
+    #[serde(default)]
+    components: Vec<ComponentButton>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ComponentButton {
+    style: Option<u8>,
+    label: Option<String>,
+    url: Option<String>,
+    emoji: Option<ComponentEmoji>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ComponentEmoji {
+    id: Option<String>,
+    name: Option<String>,
+    #[serde(default)]
+    animated: bool,
+}
+
+/// Something wrong with the json of a payload, with the path to it like `$.embeds[0].title`.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Problem {
+    pub path: String,
+    pub message: String,
+}
+
+impl Display for Problem {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "`{}` {}", self.path, self.message)
+    }
+}
+
+impl EmbedPayload {
+    /// Like [`Self::from_value`], but checks the json against the limits of discord first and
+    /// returns everything that is wrong with it at once.
+    pub fn parse(value: Value) -> Result<Self, Vec<Problem>> {
+        let whole = |e: serde_json::Error| {
+            vec![Problem {
+                path: "$".to_string(),
+                message: e.to_string(),
+            }]
+        };
+
+        let value = unwrap_message(value).map_err(whole)?;
+        let problems = check(&value);
+
+        if !problems.is_empty() {
+            return Err(problems);
+        }
+
+        Self::from_value(value).map_err(whole)
+    }
+
+    /// Parses a single embed, a message object with `content`, `embeds`, `buttons` or discord's
+    /// `components`, or a Discohook backup holding one message.
+    pub fn from_value(value: Value) -> serde_json::Result<Self> {
+        let mut value = unwrap_message(value)?;
+
+        if !is_message(&value) {
+            return Ok(Self::from_embed(serde_json::from_value(value)?));
+        }
+
+        let components = value.get_mut("components").map(Value::take);
+        let mut payload = serde_json::from_value::<Self>(value)?;
+
+        if let Some(components) = components {
+            payload.buttons.extend(link_buttons(components)?);
+        }
+
+        Ok(payload)
+    }
+
+    pub fn from_embed(embed: Embed) -> Self {
+        Self {
+            embeds: vec![embed],
+            ..Default::default()
+        }
+    }
+
+    /// The parts of a posted message that can be posted again. Link previews are left out, they
+    /// aren't part of the message but added by discord.
+    pub fn from_message(message: &Message) -> Self {
+        let buttons = message
+            .components
+            .iter()
+            .flat_map(|row| &row.components)
+            .filter_map(|component| match component {
+                ActionRowComponent::Button(Button {
+                    data: ButtonKind::Link { url },
+                    label,
+                    emoji,
+                    ..
+                }) => Some(LinkButton {
+                    label: label.clone().unwrap_or_default(),
+                    url: url.clone(),
+                    emoji: emoji.as_ref().map(ToString::to_string),
+                }),
+                _ => None,
+            })
+            .collect();
+
+        // webhook messages show the name and avatar they were posted with as author.
+        let (username, avatar_url) = match message.webhook_id {
+            Some(_) => (
+                Some(message.author.name.clone()),
+                message.author.avatar_url(),
+            ),
+            None => (None, None),
+        };
+
+        Self {
+            content: Some(message.content.clone()).filter(|content| !content.is_empty()),
+            embeds: message
+                .embeds
+                .iter()
+                .filter(|embed| embed.kind.as_deref() == Some("rich"))
+                .cloned()
+                .collect(),
+            buttons,
+            username,
+            avatar_url,
+            ..Default::default()
+        }
+    }
+
+    /// Overrides the name and avatar the message is posted with, keeps them when unset.
+    pub fn with_identity(mut self, username: Option<String>, avatar_url: Option<String>) -> Self {
+        self.username = username.or(self.username);
+        self.avatar_url = avatar_url.or(self.avatar_url);
+        self
+    }
+
+    /// Uploads the image with the message. Unless an embed already references it, it becomes the
+    /// image of the first embed.
+    pub fn attach_image(&mut self, file: CreateAttachment) {
+        let reference = format!("attachment://{}", file.filename);
+
+        let referenced = serde_json::to_string(&self.embeds)
+            .is_ok_and(|embeds| embeds.contains(&format!("\"{reference}\"")));
+
+        if !referenced {
+            if let Some(embed) = self.embeds.first_mut() {
+                embed.image = serde_json::from_value(json!({ "url": reference })).ok();
+            }
+        }
+
+        self.files.push(file);
+    }
+
+    /// A single embed is stored as just the embed, like before messages could hold more.
+    pub fn to_value(&self) -> serde_json::Result<Value> {
+        match self.embeds.as_slice() {
+            [embed]
+                if self.content.is_none()
+                    && self.buttons.is_empty()
+                    && self.username.is_none()
+                    && self.avatar_url.is_none() =>
+            {
+                serde_json::to_value(embed)
+            }
+            _ => serde_json::to_value(self),
+        }
+    }
+
+    /// Checks the payload against the limits of discord, the error explains what is wrong.
+    pub fn validate(&mut self) -> Result<(), String> {
+        if self.embeds.is_empty() && self.content.is_none() {
+            return Err("You have to provide at least one embed or a content!".to_string());
+        }
+
+        if self
+            .content
+            .as_ref()
+            .is_some_and(|content| content.chars().count() > MAX_CONTENT_LENGTH)
+        {
+            return Err(format!(
+                "The content can have at most {MAX_CONTENT_LENGTH} characters."
+            ));
+        }
+
+        if let Some(username) = &self.username {
+            let lowercase = username.to_lowercase();
+
+            if username.trim().is_empty()
+                || username.chars().count() > MAX_USERNAME_LENGTH
+                || lowercase.contains("discord")
+                || lowercase.contains("clyde")
+            {
+                return Err(format!(
+                    "The username needs 1 to {MAX_USERNAME_LENGTH} characters and can't contain discord or clyde."
+                ));
+            }
+        }
+
+        if self
+            .avatar_url
+            .as_ref()
+            .is_some_and(|url| !url.starts_with("https://") && !url.starts_with("http://"))
+        {
+            return Err("The avatar url has to start with `https://`.".to_string());
+        }
+
+        if self.embeds.len() > MAX_EMBEDS {
+            return Err(format!("A message can hold at most {MAX_EMBEDS} embeds."));
+        }
+
+        for (index, embed) in self.embeds.iter_mut().enumerate() {
+            embed.kind = Some("rich".into());
+
+            if embed.description.is_none() && embed.fields.is_empty() {
+                return Err(format!(
+                    "Embed {} needs a description or embed fields!",
+                    index + 1
+                ));
+            }
+        }
+
+        let characters = self.embeds.iter().map(embed_characters).sum::<usize>();
+
+        if characters > MAX_EMBED_CHARACTERS {
+            return Err(format!(
+                "The embeds contain {characters} characters, at most {MAX_EMBED_CHARACTERS} are allowed."
+            ));
+        }
+
+        if self.buttons.len() > MAX_BUTTON_ROWS * BUTTONS_PER_ROW {
+            return Err(format!(
+                "A message can hold at most {} buttons.",
+                MAX_BUTTON_ROWS * BUTTONS_PER_ROW
+            ));
+        }
+
+        for button in &self.buttons {
+            let label_length = button.label.chars().count();
+
+            if label_length == 0 || label_length > MAX_LABEL_LENGTH {
+                return Err(format!(
+                    "Button labels have 1 to {MAX_LABEL_LENGTH} characters."
+                ));
+            }
+
+            if !["https://", "http://", "discord://"]
+                .iter()
+                .any(|scheme| button.url.starts_with(scheme))
+            {
+                return Err(format!(
+                    "The button {} needs an url starting with `https://`.",
+                    button.label
+                ));
+            }
+
+            if button
+                .emoji
+                .as_deref()
+                .is_some_and(|emoji| ReactionType::try_from(emoji).is_err())
+            {
+                return Err(format!(
+                    "The emoji of the button {} is invalid.",
+                    button.label
+                ));
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn execute_webhook(&self) -> ExecuteWebhook {
+        let mut execute = ExecuteWebhook::new()
+            .embeds(self.create_embeds())
+            .components(self.components())
+            .add_files(self.files.clone());
+
+        if let Some(content) = &self.content {
+            execute = execute.content(content);
+        }
+
+        if let Some(username) = &self.username {
+            execute = execute.username(username);
+        }
+
+        if let Some(avatar_url) = &self.avatar_url {
+            execute = execute.avatar_url(avatar_url);
+        }
+
+        execute
+    }
+
+    /// Replaces everything of the message, discord keeps the name and avatar it was posted with.
+    pub fn edit_webhook_message(&self) -> EditWebhookMessage {
+        EditWebhookMessage::new()
+            .content(self.content.clone().unwrap_or_default())
+            .embeds(self.create_embeds())
+            .components(self.components())
+    }
+
+    pub fn create_embeds(&self) -> Vec<CreateEmbed> {
+        self.embeds.iter().cloned().map(Into::into).collect()
+    }
+
+    /// The buttons in rows of five.
+    pub fn components(&self) -> Vec<CreateActionRow> {
+        self.buttons
+            .chunks(BUTTONS_PER_ROW)
+            .map(|row| {
+                CreateActionRow::Buttons(
+                    row.iter()
+                        .map(|button| {
+                            let create = CreateButton::new_link(&button.url).label(&button.label);
+
+                            match button
+                                .emoji
+                                .as_deref()
+                                .and_then(|emoji| ReactionType::try_from(emoji).ok())
+                            {
+                                Some(emoji) => create.emoji(emoji),
+                                None => create,
+                            }
+                        })
+                        .collect(),
+                )
+            })
+            .collect()
+    }
+}
+
+/// Discohook backups and share links wrap the message like `{"messages": [{"data": {}}]}`.
+fn unwrap_message(mut value: Value) -> serde_json::Result<Value> {
+    if let Some(messages) = value.get_mut("messages").and_then(Value::as_array_mut) {
+        if messages.len() != 1 {
+            return Err(serde_json::Error::custom(
+                "only one message can be posted at once",
+            ));
+        }
+
+        value = messages[0]
+            .get_mut("data")
+            .map(Value::take)
+            .unwrap_or_default();
+    }
+
+    // discohook writes `null` for everything that isn't set.
+    if let Some(object) = value.as_object_mut() {
+        object.retain(|_, value| !value.is_null());
+    }
+
+    Ok(value)
+}
+
+/// Whether the json is a whole message rather than a single embed.
+fn is_message(value: &Value) -> bool {
+    ["content", "embeds", "buttons", "components"]
+        .iter()
+        .any(|key| value.get(key).is_some())
+}
+
+/// Everything in the json that discord would reject, or that wouldn't parse as an embed.
+fn check(value: &Value) -> Vec<Problem> {
+    let mut problems = Problems::default();
+
+    if !value.is_object() {
+        problems.add("$", "has to be an object");
+        return problems.0;
+    }
+
+    if !is_message(value) {
+        check_embed(&mut problems, value, "$");
+        return problems.0;
+    }
+
+    problems.text(value, "content", "$", MAX_CONTENT_LENGTH, false);
+    problems.text(value, "username", "$", MAX_USERNAME_LENGTH, false);
+    problems.url(value, "avatar_url", "$", LINK_SCHEMES, false);
+
+    for (index, embed) in problems
+        .array(value, "embeds", "$", MAX_EMBEDS)
+        .iter()
+        .enumerate()
+    {
+        check_embed(&mut problems, embed, &format!("$.embeds[{index}]"));
+    }
+
+    let max_buttons = MAX_BUTTON_ROWS * BUTTONS_PER_ROW;
+
+    for (index, button) in problems
+        .array(value, "buttons", "$", max_buttons)
+        .iter()
+        .enumerate()
+    {
+        let path = format!("$.buttons[{index}]");
+
+        if !button.is_object() {
+            problems.add(&path, "has to be an object");
+            continue;
+        }
+
+        problems.text(button, "label", &path, MAX_LABEL_LENGTH, true);
+        problems.url(button, "url", &path, BUTTON_SCHEMES, true);
+        problems.text(button, "emoji", &path, usize::MAX, false);
+    }
+
+    problems.0
+}
+
+fn check_embed(problems: &mut Problems, embed: &Value, path: &str) {
+    if !embed.is_object() {
+        problems.add(path, "has to be an object");
+        return;
+    }
+
+    problems.text(embed, "title", path, MAX_TITLE_LENGTH, false);
+    problems.text(embed, "description", path, MAX_DESCRIPTION_LENGTH, false);
+    problems.url(embed, "url", path, LINK_SCHEMES, false);
+
+    match embed.get("color") {
+        None | Some(Value::Null) => {}
+        Some(Value::Number(color)) if color.as_u64().is_some_and(|color| color <= MAX_COLOR) => {}
+        Some(Value::String(color)) if parse_hex_color(color).is_some() => problems.add(
+            &format!("{path}.color"),
+            format!(
+                "has to be a number, write {} instead of {color:?}",
+                parse_hex_color(color).unwrap_or_default()
+            ),
+        ),
+        Some(_) => problems.add(
+            &format!("{path}.color"),
+            format!("has to be a number from 0 to {MAX_COLOR}"),
+        ),
+    }
+
+    match embed.get("timestamp") {
+        None | Some(Value::Null) => {}
+        Some(Value::String(timestamp)) if DateTime::parse_from_rfc3339(timestamp).is_ok() => {}
+        Some(_) => problems.add(
+            &format!("{path}.timestamp"),
+            "has to be a timestamp like \"2027-01-01T17:00:00Z\"",
+        ),
+    }
+
+    if let Some(footer) = problems.object(embed, "footer", path) {
+        let path = format!("{path}.footer");
+
+        problems.text(footer, "text", &path, MAX_FOOTER_LENGTH, true);
+        problems.url(footer, "icon_url", &path, IMAGE_SCHEMES, false);
+    }
+
+    if let Some(author) = problems.object(embed, "author", path) {
+        let path = format!("{path}.author");
+
+        problems.text(author, "name", &path, MAX_AUTHOR_LENGTH, true);
+        problems.url(author, "url", &path, LINK_SCHEMES, false);
+        problems.url(author, "icon_url", &path, IMAGE_SCHEMES, false);
+    }
+
+    for key in ["image", "thumbnail"] {
+        if let Some(image) = problems.object(embed, key, path) {
+            problems.url(image, "url", &format!("{path}.{key}"), IMAGE_SCHEMES, true);
+        }
+    }
+
+    for (index, field) in problems
+        .array(embed, "fields", path, MAX_FIELDS)
+        .iter()
+        .enumerate()
+    {
+        let path = format!("{path}.fields[{index}]");
+
+        if !field.is_object() {
+            problems.add(&path, "has to be an object");
+            continue;
+        }
+
+        problems.text(field, "name", &path, MAX_FIELD_NAME_LENGTH, true);
+        problems.text(field, "value", &path, MAX_FIELD_VALUE_LENGTH, true);
+
+        if field
+            .get("inline")
+            .is_some_and(|inline| !inline.is_null() && !inline.is_boolean())
+        {
+            problems.add(&format!("{path}.inline"), "has to be true or false");
+        }
+    }
+}
+
+#[derive(Default)]
+struct Problems(Vec<Problem>);
+
+impl Problems {
+    fn add(&mut self, path: &str, message: impl Into<String>) {
+        self.0.push(Problem {
+            path: path.to_string(),
+            message: message.into(),
+        });
+    }
+
+    /// Checks the text at `key`, required ones can't be missing or blank.
+    fn text(&mut self, parent: &Value, key: &str, path: &str, max_length: usize, required: bool) {
+        let path = format!("{path}.{key}");
+
+        match parent.get(key) {
+            None | Some(Value::Null) if required => self.add(&path, "is required"),
+            None | Some(Value::Null) => {}
+            Some(Value::String(text)) if required && text.trim().is_empty() => {
+                self.add(&path, "can't be empty")
+            }
+            Some(Value::String(text)) => {
+                let length = text.chars().count();
+
+                if length > max_length {
+                    self.add(
+                        &path,
+                        format!("has {length} characters, at most {max_length} are allowed"),
+                    );
+                }
+            }
+            Some(_) => self.add(&path, "has to be a string"),
+        }
+    }
+
+    fn url(&mut self, parent: &Value, key: &str, path: &str, schemes: &[&str], required: bool) {
+        self.text(parent, key, path, usize::MAX, required);
+
+        if let Some(url) = parent.get(key).and_then(Value::as_str) {
+            if !url.trim().is_empty() && !schemes.iter().any(|scheme| url.starts_with(scheme)) {
+                self.add(
+                    &format!("{path}.{key}"),
+                    format!("has to start with {}", schemes.join(" or ")),
+                );
+            }
+        }
+    }
+
+    /// The object at `key`, if there is one.
+    fn object<'a>(&mut self, parent: &'a Value, key: &str, path: &str) -> Option<&'a Value> {
+        match parent.get(key)? {
+            Value::Null => None,
+            object @ Value::Object(_) => Some(object),
+            _ => {
+                self.add(&format!("{path}.{key}"), "has to be an object");
+                None
+            }
+        }
+    }
+
+    /// The items of the array at `key`, empty if there is none.
+    fn array<'a>(&mut self, parent: &'a Value, key: &str, path: &str, max: usize) -> &'a [Value] {
+        match parent.get(key) {
+            None | Some(Value::Null) => &[],
+            Some(Value::Array(items)) => {
+                if items.len() > max {
+                    self.add(
+                        &format!("{path}.{key}"),
+                        format!("has {} items, at most {max} are allowed", items.len()),
+                    );
+                }
+
+                items
+            }
+            Some(_) => {
+                self.add(&format!("{path}.{key}"), "has to be a list");
+                &[]
+            }
+        }
+    }
+}
+
+/// Converts the link buttons of discord's component json, webhooks can't post other components.
+fn link_buttons(components: Value) -> serde_json::Result<Vec<LinkButton>> {
+    serde_json::from_value::<Vec<ComponentRow>>(components)?
+        .into_iter()
+        .flat_map(|row| row.components)
+        .map(|button| {
+            let (Some(LINK_BUTTON_STYLE), Some(url)) = (button.style, button.url) else {
+                return Err(serde_json::Error::custom(
+                    "only link buttons can be posted through webhooks",
+                ));
+            };
+
+            let emoji = button.emoji.and_then(|emoji| match (emoji.id, emoji.name) {
+                (Some(id), name) => Some(format!(
+                    "<{}:{}:{id}>",
+                    if emoji.animated { "a" } else { "" },
+                    name.unwrap_or_else(|| "_".to_string())
+                )),
+                (None, name) => name,
+            });
+
+            Ok(LinkButton {
+                label: button.label.unwrap_or_default(),
+                url,
+                emoji,
+            })
+        })
+        .collect()
+}
+
+/// The characters discord counts towards the limit of all embeds in a message.
+fn embed_characters(embed: &Embed) -> usize {
+    let length = |text: &Option<String>| text.as_deref().map_or(0, |text| text.chars().count());
+
+    length(&embed.title)
+        + length(&embed.description)
+        + embed
+            .footer
+            .as_ref()
+            .map_or(0, |footer| footer.text.chars().count())
+        + embed
+            .author
+            .as_ref()
+            .map_or(0, |author| author.name.chars().count())
+        + embed
+            .fields
+            .iter()
+            .map(|field| field.name.chars().count() + field.value.chars().count())
+            .sum::<usize>()
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn parses_single_embeds() {
+        let payload = EmbedPayload::from_value(json!({ "description": "hello" })).unwrap();
+
+        assert_eq!(payload.embeds.len(), 1);
+        assert!(payload.buttons.is_empty());
+        assert!(payload.to_value().unwrap().get("embeds").is_none());
+    }
+
+    #[test]
+    fn validates_limits() {
+        let mut payload = EmbedPayload::from_value(json!({
+            "embeds": [{ "description": "one" }, { "title": "two" }],
+        }))
+        .unwrap();
+
+        assert_eq!(
+            payload.validate(),
+            Err("Embed 2 needs a description or embed fields!".to_string())
+        );
+
+        let mut payload = EmbedPayload::from_value(json!({
+            "embeds": [{ "description": "a".repeat(3001) }, { "description": "b".repeat(3000) }],
+        }))
+        .unwrap();
+
+        assert!(payload.validate().is_err());
+
+        let mut payload = EmbedPayload::from_value(json!({
+            "embeds": [{ "description": "docs" }],
+            "buttons": [{ "label": "Docs", "url": "javascript:alert(1)" }],
+        }))
+        .unwrap();
+
+        assert!(payload.validate().is_err());
+
+        let mut payload = EmbedPayload::from_value(json!({
+            "embeds": [{ "description": "docs" }],
+            "buttons": [{ "label": "Docs", "url": "https://biomejs.dev", "emoji": "📚" }],
+        }))
+        .unwrap();
+
+        assert_eq!(payload.validate(), Ok(()));
+        assert_eq!(payload.components().len(), 1);
+        assert!(payload.to_value().unwrap().get("buttons").is_some());
+    }
+
+    #[test]
+    fn reads_discohook_messages() {
+        let mut payload = EmbedPayload::from_value(json!({
+            "messages": [{
+                "data": {
+                    "content": null,
+                    "embeds": [{ "title": "Biome 2.0", "description": "Out now!", "color": null }],
+                    "username": "Biome Team",
+                    "avatar_url": "https://biomejs.dev/img/logo.png",
+                    "attachments": [],
+                    "components": [{
+                        "type": 1,
+                        "components": [{
+                            "type": 2,
+                            "style": 5,
+                            "label": "Blog",
+                            "url": "https://biomejs.dev/blog",
+                            "emoji": { "id": "123", "name": "biome", "animated": false },
+                        }],
+                    }],
+                },
+            }],
+        }))
+        .unwrap();
+
+        assert_eq!(payload.validate(), Ok(()));
+        assert_eq!(payload.content, None);
+        assert_eq!(payload.embeds[0].title.as_deref(), Some("Biome 2.0"));
+        assert_eq!(payload.username.as_deref(), Some("Biome Team"));
+        assert_eq!(payload.buttons[0].emoji.as_deref(), Some("<:biome:123>"));
+
+        let error = EmbedPayload::from_value(json!({
+            "content": "Vote!",
+            "components": [{ "type": 1, "components": [{ "type": 2, "style": 1, "custom_id": "vote" }] }],
+        }));
+
+        assert!(error.is_err());
+    }
+
+    #[test]
+    fn finds_every_problem() {
+        let problems = EmbedPayload::parse(json!({
+            "embeds": [
+                { "description": "ok" },
+                {
+                    "title": "a".repeat(300),
+                    "color": "#63A3FA",
+                    "fields": [{ "name": "Docs", "value": "" }],
+                    "image": { "url": "ftp://biomejs.dev/banner.png" },
+                },
+            ],
+            "buttons": [{ "url": "https://biomejs.dev" }],
+        }))
+        .unwrap_err();
+
+        let paths = problems
+            .iter()
+            .map(|problem| problem.path.as_str())
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            paths,
+            [
+                "$.embeds[1].title",
+                "$.embeds[1].color",
+                "$.embeds[1].image.url",
+                "$.embeds[1].fields[0].value",
+                "$.buttons[0].label",
+            ]
+        );
+        assert_eq!(
+            problems[1].message,
+            "has to be a number, write 6530042 instead of \"#63A3FA\""
+        );
+    }
+
+    #[test]
+    fn parses_valid_json() {
+        let payload = EmbedPayload::parse(json!({
+            "title": "Biome",
+            "description": "Hello",
+            "color": 6530042,
+            "timestamp": "2027-01-01T17:00:00Z",
+            "thumbnail": { "url": "attachment://logo.png" },
+        }));
+
+        assert!(payload.is_ok());
+        assert!(EmbedPayload::parse(json!([])).is_err());
+    }
+
+    #[test]
+    fn rejects_reserved_usernames() {
+        let mut payload = EmbedPayload {
+            content: Some("hello".to_string()),
+            username: Some("Discord Staff".to_string()),
+            ..Default::default()
+        };
+
+        assert!(payload.validate().is_err());
+    }
+
+    #[test]
+    fn attaches_images() {
+        let mut payload = EmbedPayload::from_value(json!({ "description": "banner" })).unwrap();
+
+        payload.attach_image(CreateAttachment::bytes(vec![0], "banner.png"));
+
+        assert_eq!(
+            payload.embeds[0]
+                .image
+                .as_ref()
+                .map(|image| image.url.as_str()),
+            Some("attachment://banner.png")
+        );
+        assert_eq!(payload.files.len(), 1);
+
+        let mut payload = EmbedPayload::from_value(json!({
+            "description": "banner",
+            "thumbnail": { "url": "attachment://logo.png" },
+        }))
+        .unwrap();
+
+        payload.attach_image(CreateAttachment::bytes(vec![0], "logo.png"));
+
+        assert!(payload.embeds[0].image.is_none());
+    }
+}
@@ -11,6 +11,7 @@ pub async fn error_handler<'a>(
     match error {
         FrameworkError::Command { error, ctx, .. } => {
             tracing::error!("Command error: {:?}", error);
+            ctx.data().metrics.error("command");
 
             match ctx
                 .reply("There was an error trying to execute that command.")
@@ -25,6 +26,7 @@ pub async fn error_handler<'a>(
         }
         FrameworkError::CommandPanic { payload, ctx, .. } => {
             tracing::error!("Command panic: {:?}", payload);
+            ctx.data().metrics.error("command");
 
             match ctx
                 .reply("Oops, something went terribly wrong. Please try again later.")
@@ -68,6 +70,35 @@ pub async fn error_handler<'a>(
                 }
             }
         }
+        FrameworkError::CommandCheckFailed { error, ctx, .. } => {
+            match error {
+                Some(error) => tracing::error!(
+                    "Check for command {} failed: {:?}",
+                    ctx.command().name.clone(),
+                    error
+                ),
+                None => tracing::warn!(
+                    "{} tried to use /{} without permission.",
+                    ctx.author().name,
+                    ctx.command().name.clone()
+                ),
+            }
+
+            match ctx
+                .send(
+                    poise::CreateReply::default()
+                        .content("You are not allowed to use this command.")
+                        .ephemeral(true),
+                )
+                .await
+            {
+                Ok(_) => Ok(()),
+                Err(e) => {
+                    tracing::error!("Failed to send error message: {:?}", e);
+                    Ok(())
+                }
+            }
+        }
         FrameworkError::EventHandler { error, event, .. } => {
             tracing::error!(
                 "Event handler error for {}: {:#?}",
@@ -0,0 +1,109 @@
+use chrono::DateTime;
+use poise::serenity_prelude as serenity;
+use serenity::{CreateEmbed, CreateEmbedFooter, CreateMessage, Member, Mentionable};
+
+use crate::{
+    auto_roles, join_gate, raid,
+    util::{embeds::EmbedColor, format::display_time},
+    Data,
+};
+
+/// Flags new accounts, greets the new member, gives them the automatic roles and logs the join.
+pub async fn handle_guild_member_addition(
+    member: &Member,
+    ctx: &serenity::Context,
+    data: &Data,
+) -> anyhow::Result<()> {
+    let user = &member.user;
+
+    if user.bot || member.guild_id != data.config.bot.guild_id {
+        return Ok(());
+    }
+
+    if let Err(e) = raid::record_join(ctx, data).await {
+        tracing::error!("Failed to start a lockdown: {e:#?}");
+    }
+
+    let quarantined = match join_gate::check_account_age(member, ctx, data).await {
+        Ok(quarantined) => quarantined,
+        Err(e) => {
+            tracing::error!("Failed to check the account age of {}: {e:#?}", user.name);
+            false
+        }
+    };
+
+    // quarantined members only get access once a moderator looked at them.
+    if !quarantined {
+        auto_roles::assign_on_join(member, ctx, data);
+    }
+
+    let member_count = ctx
+        .cache
+        .guild(member.guild_id)
+        .map(|guild| guild.member_count)
+        .unwrap_or_default();
+
+    if let Some(welcome) = &data.config.welcome {
+        let embed = CreateEmbed::new()
+            .color(EmbedColor::Biome)
+            .title(format!("Welcome {}!", member.display_name()))
+            .description(render_welcome(
+                &welcome.message,
+                member.display_name(),
+                &user.mention().to_string(),
+                member_count,
+            ))
+            .thumbnail(user.face());
+
+        welcome
+            .channel
+            .send_message(ctx, CreateMessage::new().embed(embed))
+            .await?;
+    }
+
+    if let Some(member_log) = data.config.main_guild.channels.member_log {
+        let created_at =
+            DateTime::from_timestamp(user.created_at().unix_timestamp(), 0).unwrap_or_default();
+
+        let embed = CreateEmbed::new()
+            .color(0x00FF00)
+            .title("Member joined")
+            .thumbnail(user.face())
+            .field("User", format!("{} (`{}`)", user.mention(), user.id), false)
+            .field("Account created", display_time(created_at), false)
+            .footer(CreateEmbedFooter::new(format!("{member_count} members")))
+            .timestamp(chrono::Utc::now());
+
+        member_log
+            .send_message(ctx, CreateMessage::new().embed(embed))
+            .await?;
+    }
+
+    Ok(())
+}
+
+/// Replaces the template variables of the welcome message, unknown ones are kept as they are.
+fn render_welcome(template: &str, name: &str, mention: &str, member_count: u64) -> String {
+    template
+        .replace("{name}", name)
+        .replace("{mention}", mention)
+        .replace("{member_count}", &member_count.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn renders_welcome_template() {
+        assert_eq!(
+            render_welcome(
+                "Hi {name} ({mention}), member {member_count}. {unknown}",
+                "Ferris",
+                "<@1>",
+                42
+            ),
+            "Hi Ferris (<@1>), member 42. {unknown}"
+        );
+    }
+}
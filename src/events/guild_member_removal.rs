@@ -0,0 +1,41 @@
+use chrono::DateTime;
+use poise::serenity_prelude as serenity;
+use serenity::{CreateEmbed, CreateMessage, GuildId, Member, Mentionable, User};
+
+use crate::{util::format::display_time, Data};
+
+/// Logs members leaving, including kicks and bans.
+pub async fn handle_guild_member_removal(
+    guild_id: GuildId,
+    user: &User,
+    member: Option<&Member>,
+    ctx: &serenity::Context,
+    data: &Data,
+) -> anyhow::Result<()> {
+    let Some(member_log) = data.config.main_guild.channels.member_log else {
+        return Ok(());
+    };
+
+    if user.bot || guild_id != data.config.bot.guild_id {
+        return Ok(());
+    }
+
+    let mut embed = CreateEmbed::new()
+        .color(0xFF0000)
+        .title("Member left")
+        .thumbnail(user.face())
+        .field("User", format!("{} (`{}`)", user.mention(), user.id), false)
+        .timestamp(chrono::Utc::now());
+
+    // discord only sends the user, the member is known if it was cached.
+    if let Some(joined_at) = member.and_then(|member| member.joined_at) {
+        let joined_at = DateTime::from_timestamp(joined_at.unix_timestamp(), 0).unwrap_or_default();
+        embed = embed.field("Joined", display_time(joined_at), false);
+    }
+
+    member_log
+        .send_message(ctx, CreateMessage::new().embed(embed))
+        .await?;
+
+    Ok(())
+}
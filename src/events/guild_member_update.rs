@@ -0,0 +1,13 @@
+use poise::serenity_prelude as serenity;
+use serenity::Member;
+
+use crate::{auto_roles, Data};
+
+pub async fn handle_guild_member_update(
+    old: Option<&Member>,
+    new: &Member,
+    ctx: &serenity::Context,
+    data: &Data,
+) -> anyhow::Result<()> {
+    auto_roles::assign_after_screening(old, new, ctx, data).await
+}
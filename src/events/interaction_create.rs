@@ -1,11 +1,29 @@
 use poise::serenity_prelude as serenity;
 
-use crate::util::format::display;
+use crate::{
+    commands::{poll, report, rolemenu, suggest, verification},
+    util::format::display,
+    Data,
+};
 
 pub async fn handle_interaction_create(
     interaction: &serenity::Interaction,
     ctx: &serenity::Context,
+    data: &Data,
 ) -> anyhow::Result<()> {
+    // buttons of messages that have to keep working after a restart, the buttons of command
+    // replies are handled by the collectors of the commands.
+    if let Some(component) = interaction.as_message_component() {
+        return match component.data.custom_id.split(':').next() {
+            Some("suggestion") => suggest::handle_component(component, ctx, data).await,
+            Some("poll") => poll::handle_component(component, ctx, data).await,
+            Some("report") => report::handle_component(component, ctx, data).await,
+            Some("rolemenu") => rolemenu::handle_component(component, ctx, data).await,
+            Some("verify") => verification::handle_component(component, ctx, data).await,
+            _ => Ok(()),
+        };
+    }
+
     if interaction.kind() != serenity::InteractionType::Command {
         return Ok(());
     }
@@ -0,0 +1,380 @@
+use std::time::{Duration, Instant};
+
+use poise::serenity_prelude as serenity;
+use serenity::{
+    CreateAllowedMentions, CreateEmbed, CreateEmbedFooter, CreateMessage, MessageFlags, MessageType,
+};
+
+use crate::{
+    anti_spam,
+    auto_responder::best_match,
+    code_snippets,
+    database::tags,
+    issue_links, link_filter,
+    message_log::{is_logged, CachedMessage},
+    paste,
+    settings::Setting,
+    util::{embeds::EmbedColor, parse::parse_code_blocks},
+    Data,
+};
+
+pub async fn handle_message_create(
+    message: &serenity::Message,
+    ctx: &serenity::Context,
+    data: &Data,
+) -> anyhow::Result<()> {
+    if anti_spam::check_message(message, ctx, data).await?
+        || link_filter::check_message(message, ctx, data).await?
+    {
+        return Ok(());
+    }
+
+    remember_message(message, ctx, data);
+
+    if let Err(e) = auto_publish(message, ctx, data).await {
+        tracing::error!("Failed to publish an announcement: {e:#?}");
+    }
+
+    if let Err(e) = link_issues(message, ctx, data).await {
+        tracing::error!("Failed to link issue references: {e:#?}");
+    }
+
+    if let Err(e) = expand_permalinks(message, ctx, data).await {
+        tracing::error!("Failed to expand code permalinks: {e:#?}");
+    }
+
+    if let Err(e) = paste_code_blocks(message, ctx, data).await {
+        tracing::error!("Failed to upload long code blocks: {e:#?}");
+    }
+
+    auto_respond(message, ctx, data).await
+}
+
+/// Keeps the content around for the message log, in case the message gets edited or deleted.
+fn remember_message(message: &serenity::Message, ctx: &serenity::Context, data: &Data) {
+    let Some(config) = &data.config.message_log else {
+        return;
+    };
+
+    if message.author.bot || !is_logged(ctx, config, message.guild_id, message.channel_id) {
+        return;
+    }
+
+    data.recent_messages.remember(
+        message.id,
+        CachedMessage {
+            author_id: message.author.id,
+            content: message.content.clone(),
+        },
+        Instant::now(),
+    );
+}
+
+/// Publishes messages of bots and maintainers in announcement channels, so servers following
+/// the channel get them without a manual publish.
+async fn auto_publish(
+    message: &serenity::Message,
+    ctx: &serenity::Context,
+    data: &Data,
+) -> anyhow::Result<()> {
+    let Some(config) = data
+        .config
+        .auto_publish
+        .as_ref()
+        .filter(|_| data.settings.enabled(Setting::AutoPublish))
+    else {
+        return Ok(());
+    };
+
+    if !config.channels.contains(&message.channel_id) || message.kind != MessageType::Regular {
+        return Ok(());
+    }
+
+    // messages that were already published, or came from a channel this one follows.
+    if message.flags.is_some_and(|flags| {
+        flags.intersects(MessageFlags::CROSSPOSTED | MessageFlags::IS_CROSSPOST)
+    }) {
+        return Ok(());
+    }
+
+    let roles = message
+        .guild_id
+        .and_then(|guild_id| data.guild(guild_id))
+        .map(|guild| &guild.roles);
+    let is_maintainer = message
+        .member
+        .as_ref()
+        .zip(roles)
+        .is_some_and(|(member, roles)| roles.is_maintainer(&member.roles));
+
+    if !message.author.bot && !is_maintainer {
+        return Ok(());
+    }
+
+    message.crosspost(ctx).await?;
+
+    tracing::info!(
+        "Published message {} in channel {}.",
+        message.id,
+        message.channel_id
+    );
+
+    Ok(())
+}
+
+/// Replies with the title and state of issues and pull requests the message refers to.
+async fn link_issues(
+    message: &serenity::Message,
+    ctx: &serenity::Context,
+    data: &Data,
+) -> anyhow::Result<()> {
+    let Some(config) = data
+        .config
+        .issue_links
+        .as_ref()
+        .filter(|_| data.settings.enabled(Setting::IssueLinks))
+    else {
+        return Ok(());
+    };
+
+    if message.author.bot || !message.content.contains('#') {
+        return Ok(());
+    }
+
+    if !config.channels.contains(&message.channel_id)
+        && !parent_channel(message, ctx).is_some_and(|parent| config.channels.contains(&parent))
+    {
+        return Ok(());
+    }
+
+    let references = issue_links::references(
+        &message.content,
+        data.github.repository(),
+        config.max_references,
+    );
+
+    if references.is_empty()
+        || !data.issue_linker.try_reply(
+            message.channel_id,
+            Duration::from_secs(config.cooldown_secs),
+        )
+    {
+        return Ok(());
+    }
+
+    let Some(embed) = issue_links::describe(data, &references).await else {
+        return Ok(());
+    };
+
+    message
+        .channel_id
+        .send_message(
+            ctx,
+            CreateMessage::new()
+                .embed(embed)
+                .reference_message(message)
+                .allowed_mentions(CreateAllowedMentions::new()),
+        )
+        .await?;
+
+    Ok(())
+}
+
+/// Replies with the lines of code github permalinks point to.
+async fn expand_permalinks(
+    message: &serenity::Message,
+    ctx: &serenity::Context,
+    data: &Data,
+) -> anyhow::Result<()> {
+    let Some(config) = data
+        .config
+        .code_snippets
+        .as_ref()
+        .filter(|_| data.settings.enabled(Setting::CodeSnippets))
+    else {
+        return Ok(());
+    };
+
+    if message.author.bot || message.guild_id.is_none() {
+        return Ok(());
+    }
+
+    let permalinks = code_snippets::permalinks(&message.content, config.max_links);
+    // every snippet gets the same share, so all of them fit into one message.
+    let max_length = code_snippets::MAX_EMBEDS_LENGTH / permalinks.len().max(1);
+    let mut embeds = Vec::new();
+
+    for permalink in &permalinks {
+        match code_snippets::snippet_embed(permalink, config.max_lines, max_length).await {
+            Ok(Some(embed)) => embeds.push(embed),
+            Ok(None) => {}
+            Err(e) => tracing::warn!("Failed to fetch the snippet of {}: {e:#}", permalink.url),
+        }
+    }
+
+    if embeds.is_empty() {
+        return Ok(());
+    }
+
+    message
+        .channel_id
+        .send_message(
+            ctx,
+            CreateMessage::new()
+                .embeds(embeds)
+                .reference_message(message)
+                .allowed_mentions(CreateAllowedMentions::new()),
+        )
+        .await?;
+
+    Ok(())
+}
+
+/// Uploads long code blocks in the help channels and replies with links, so they don't need to
+/// be scrolled past.
+async fn paste_code_blocks(
+    message: &serenity::Message,
+    ctx: &serenity::Context,
+    data: &Data,
+) -> anyhow::Result<()> {
+    let Some(config) = data
+        .config
+        .paste
+        .as_ref()
+        .filter(|_| data.settings.enabled(Setting::Paste))
+    else {
+        return Ok(());
+    };
+
+    if message.author.bot || !message.content.contains("```") {
+        return Ok(());
+    }
+
+    if !config.channels.contains(&message.channel_id)
+        && !parent_channel(message, ctx).is_some_and(|parent| config.channels.contains(&parent))
+    {
+        return Ok(());
+    }
+
+    let long_blocks = parse_code_blocks(&message.content)
+        .into_iter()
+        .filter(|block| block.code.lines().count() > config.max_lines)
+        .collect::<Vec<_>>();
+
+    if long_blocks.is_empty() {
+        return Ok(());
+    }
+
+    let mut links = Vec::new();
+
+    for block in &long_blocks {
+        links.push(paste::upload(data, &config.backend, block).await?);
+    }
+
+    let content = match links.as_slice() {
+        [link] => format!("That code block is long, it's easier to read here: {link}"),
+        links => format!(
+            "Those code blocks are long, they're easier to read here:\n{}",
+            links.join("\n")
+        ),
+    };
+
+    message
+        .channel_id
+        .send_message(
+            ctx,
+            CreateMessage::new()
+                .content(content)
+                .reference_message(message)
+                .allowed_mentions(CreateAllowedMentions::new()),
+        )
+        .await?;
+
+    Ok(())
+}
+
+/// Answers messages in the help channels that look like a known question.
+async fn auto_respond(
+    message: &serenity::Message,
+    ctx: &serenity::Context,
+    data: &Data,
+) -> anyhow::Result<()> {
+    let Some(config) = data
+        .config
+        .auto_responder
+        .as_ref()
+        .filter(|_| data.settings.enabled(Setting::AutoResponder))
+    else {
+        return Ok(());
+    };
+
+    if message.author.bot || message.content.is_empty() {
+        return Ok(());
+    }
+
+    if !config.channels.contains(&message.channel_id)
+        && !parent_channel(message, ctx).is_some_and(|parent| config.channels.contains(&parent))
+    {
+        return Ok(());
+    }
+
+    let questions = data.auto_responder.questions(data).await?;
+
+    let Some(question) = best_match(&message.content, &questions) else {
+        return Ok(());
+    };
+
+    if !data.auto_responder.try_answer(
+        message.channel_id,
+        Duration::from_secs(config.cooldown_secs),
+    ) {
+        return Ok(());
+    }
+
+    let Some(tag) = tags::use_tag(&data.db_pool, &question.tag_name).await? else {
+        return Ok(());
+    };
+
+    tracing::info!(
+        "Auto-responded to {} in {} with tag {}",
+        message.author.name,
+        message.channel_id,
+        tag.name
+    );
+
+    let embed = CreateEmbed::new()
+        .color(EmbedColor::Biome)
+        .title(&tag.name)
+        .description(&tag.content)
+        .footer(CreateEmbedFooter::new(format!(
+            "Automatic answer, this looked like \"{}\"",
+            question.question
+        )));
+
+    message
+        .channel_id
+        .send_message(
+            ctx,
+            CreateMessage::new()
+                .embed(embed)
+                .reference_message(message)
+                .allowed_mentions(CreateAllowedMentions::new()),
+        )
+        .await?;
+
+    Ok(())
+}
+
+/// The channel a thread or forum post belongs to.
+fn parent_channel(
+    message: &serenity::Message,
+    ctx: &serenity::Context,
+) -> Option<serenity::ChannelId> {
+    let guild = ctx.cache.guild(message.guild_id?)?;
+
+    guild
+        .threads
+        .iter()
+        .find(|thread| thread.id == message.channel_id)
+        .and_then(|thread| thread.parent_id)
+}
@@ -0,0 +1,59 @@
+use std::time::Instant;
+
+use poise::serenity_prelude as serenity;
+use serenity::{ChannelId, CreateEmbed, CreateMessage, GuildId, Mentionable, MessageId};
+
+use crate::{
+    database::message_logs,
+    message_log::{content_field, is_logged},
+    util::embeds::EmbedColor,
+    Data,
+};
+
+/// Logs deleted messages. Only remembered messages can be logged, discord doesn't tell who wrote
+/// the others.
+pub async fn handle_message_delete(
+    channel_id: ChannelId,
+    message_id: MessageId,
+    guild_id: Option<GuildId>,
+    ctx: &serenity::Context,
+    data: &Data,
+) -> anyhow::Result<()> {
+    let Some(config) = &data.config.message_log else {
+        return Ok(());
+    };
+
+    let Some(message) = data.recent_messages.forget(message_id, Instant::now()) else {
+        return Ok(());
+    };
+
+    if !is_logged(ctx, config, guild_id, channel_id) {
+        return Ok(());
+    }
+
+    message_logs::insert(
+        &data.db_pool,
+        message_id.get() as i64,
+        channel_id.get() as i64,
+        message.author_id.get() as i64,
+        "delete",
+        Some(&message.content),
+        None,
+    )
+    .await?;
+
+    let embed = CreateEmbed::new()
+        .color(EmbedColor::Biome)
+        .title("Message deleted")
+        .field("Author", message.author_id.mention().to_string(), true)
+        .field("Channel", channel_id.mention().to_string(), true)
+        .field("Content", content_field(Some(&message.content)), false)
+        .timestamp(chrono::Utc::now());
+
+    config
+        .channel
+        .send_message(ctx, CreateMessage::new().embed(embed))
+        .await?;
+
+    Ok(())
+}
@@ -0,0 +1,83 @@
+use std::time::Instant;
+
+use poise::serenity_prelude as serenity;
+use serenity::{CreateEmbed, CreateMessage, Mentionable, MessageUpdateEvent};
+
+use crate::{
+    database::message_logs,
+    link_filter,
+    message_log::{content_field, is_logged},
+    util::embeds::EmbedColor,
+    Data,
+};
+
+/// Removes edits linking to blocked domains and logs messages whose content was edited.
+pub async fn handle_message_update(
+    event: &MessageUpdateEvent,
+    ctx: &serenity::Context,
+    data: &Data,
+) -> anyhow::Result<()> {
+    if link_filter::check_update(event, ctx, data).await? {
+        return Ok(());
+    }
+
+    let Some(config) = &data.config.message_log else {
+        return Ok(());
+    };
+
+    // resolving the embeds of links also updates a message, without touching the content.
+    let Some(content) = &event.content else {
+        return Ok(());
+    };
+
+    if event.author.as_ref().is_some_and(|author| author.bot)
+        || !is_logged(ctx, config, event.guild_id, event.channel_id)
+    {
+        return Ok(());
+    }
+
+    let previous = data.recent_messages.edit(event.id, content, Instant::now());
+
+    let Some(author_id) = event
+        .author
+        .as_ref()
+        .map(|author| author.id)
+        .or(previous.as_ref().map(|previous| previous.author_id))
+    else {
+        return Ok(());
+    };
+
+    let before = previous.map(|previous| previous.content);
+
+    if before.as_deref() == Some(content.as_str()) {
+        return Ok(());
+    }
+
+    message_logs::insert(
+        &data.db_pool,
+        event.id.get() as i64,
+        event.channel_id.get() as i64,
+        author_id.get() as i64,
+        "edit",
+        before.as_deref(),
+        Some(content),
+    )
+    .await?;
+
+    let embed = CreateEmbed::new()
+        .color(EmbedColor::Biome)
+        .title("Message edited")
+        .url(event.id.link(event.channel_id, event.guild_id))
+        .field("Author", author_id.mention().to_string(), true)
+        .field("Channel", event.channel_id.mention().to_string(), true)
+        .field("Before", content_field(before.as_deref()), false)
+        .field("After", content_field(Some(content)), false)
+        .timestamp(chrono::Utc::now());
+
+    config
+        .channel
+        .send_message(ctx, CreateMessage::new().embed(embed))
+        .await?;
+
+    Ok(())
+}
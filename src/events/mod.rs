@@ -1,5 +1,14 @@
+pub mod guild_member_addition;
+pub mod guild_member_removal;
+pub mod guild_member_update;
 pub mod interaction_create;
+pub mod message_create;
+pub mod message_delete;
+pub mod message_update;
+pub mod reaction_add;
+pub mod reaction_remove;
 pub mod ready;
+pub mod thread_create;
 
 use poise::serenity_prelude as serenity;
 
@@ -9,13 +18,73 @@ pub async fn event_handler(
     ctx: &serenity::Context,
     event: &serenity::FullEvent,
     _framework: poise::FrameworkContext<'_, Data, anyhow::Error>,
+    data: &Data,
 ) -> anyhow::Result<()> {
     match event {
         serenity::FullEvent::Ready { data_about_bot, .. } => {
             ready::handle_ready(data_about_bot, ctx).await?;
         }
         serenity::FullEvent::InteractionCreate { interaction, .. } => {
-            interaction_create::handle_interaction_create(interaction, ctx).await?;
+            interaction_create::handle_interaction_create(interaction, ctx, data).await?;
+        }
+        serenity::FullEvent::Message { new_message } => {
+            message_create::handle_message_create(new_message, ctx, data).await?;
+        }
+        serenity::FullEvent::MessageUpdate { event, .. } => {
+            message_update::handle_message_update(event, ctx, data).await?;
+        }
+        serenity::FullEvent::MessageDelete {
+            channel_id,
+            deleted_message_id,
+            guild_id,
+        } => {
+            message_delete::handle_message_delete(
+                *channel_id,
+                *deleted_message_id,
+                *guild_id,
+                ctx,
+                data,
+            )
+            .await?;
+        }
+        serenity::FullEvent::GuildMemberAddition { new_member } => {
+            guild_member_addition::handle_guild_member_addition(new_member, ctx, data).await?;
+        }
+        serenity::FullEvent::GuildMemberRemoval {
+            guild_id,
+            user,
+            member_data_if_available,
+        } => {
+            guild_member_removal::handle_guild_member_removal(
+                *guild_id,
+                user,
+                member_data_if_available.as_ref(),
+                ctx,
+                data,
+            )
+            .await?;
+        }
+        serenity::FullEvent::GuildMemberUpdate {
+            old_if_available,
+            new: Some(new),
+            ..
+        } => {
+            guild_member_update::handle_guild_member_update(
+                old_if_available.as_ref(),
+                new,
+                ctx,
+                data,
+            )
+            .await?;
+        }
+        serenity::FullEvent::ReactionAdd { add_reaction } => {
+            reaction_add::handle_reaction_add(add_reaction, ctx, data).await?;
+        }
+        serenity::FullEvent::ReactionRemove { removed_reaction } => {
+            reaction_remove::handle_reaction_remove(removed_reaction, ctx, data).await?;
+        }
+        serenity::FullEvent::ThreadCreate { thread, .. } => {
+            thread_create::handle_thread_create(thread, ctx, data).await?;
         }
         _ => {}
     }
@@ -0,0 +1,66 @@
+use poise::serenity_prelude as serenity;
+use serenity::CreateMessage;
+
+use crate::{
+    commands::reactionrole,
+    help_forum::{close_solved_thread, forum_of, record_solved, solved_embed},
+    Data,
+};
+
+pub async fn handle_reaction_add(
+    reaction: &serenity::Reaction,
+    ctx: &serenity::Context,
+    data: &Data,
+) -> anyhow::Result<()> {
+    reactionrole::handle_reaction(reaction, true, ctx, data).await?;
+    mark_solved(reaction, ctx, data).await
+}
+
+/// Marks a help post as solved when its author reacts with a check mark, crediting the author
+/// of the message they reacted to.
+async fn mark_solved(
+    reaction: &serenity::Reaction,
+    ctx: &serenity::Context,
+    data: &Data,
+) -> anyhow::Result<()> {
+    if data.config.help_forums.is_empty() || !reaction.emoji.unicode_eq("\u{2705}") {
+        return Ok(());
+    }
+
+    let Some(user_id) = reaction.user_id else {
+        return Ok(());
+    };
+
+    let Some(thread) = reaction.channel_id.to_channel(ctx).await?.guild() else {
+        return Ok(());
+    };
+
+    let Some(forum) = forum_of(data, &thread) else {
+        return Ok(());
+    };
+
+    if thread.owner_id != Some(user_id) {
+        return Ok(());
+    }
+
+    let message = reaction.message(ctx).await?;
+    let solver = (message.author.id != user_id && !message.author.bot).then_some(message.author.id);
+
+    if !record_solved(data, &thread, user_id, solver, None).await? {
+        return Ok(());
+    }
+
+    tracing::info!(
+        "{user_id} marked thread {} as solved by reaction",
+        thread.id
+    );
+
+    thread
+        .send_message(
+            ctx,
+            CreateMessage::new().embed(solved_embed(user_id, solver, None)),
+        )
+        .await?;
+
+    close_solved_thread(ctx, &thread, forum).await
+}
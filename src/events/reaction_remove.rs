@@ -0,0 +1,11 @@
+use poise::serenity_prelude as serenity;
+
+use crate::{commands::reactionrole, Data};
+
+pub async fn handle_reaction_remove(
+    reaction: &serenity::Reaction,
+    ctx: &serenity::Context,
+    data: &Data,
+) -> anyhow::Result<()> {
+    reactionrole::handle_reaction(reaction, false, ctx, data).await
+}
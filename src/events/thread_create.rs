@@ -0,0 +1,70 @@
+use poise::serenity_prelude as serenity;
+use serenity::{EditThread, MessageId};
+
+use crate::{help_forum::classify, Data};
+
+/// Applies the topic tags matching the title and first message of a new help forum post.
+pub async fn handle_thread_create(
+    thread: &serenity::GuildChannel,
+    ctx: &serenity::Context,
+    data: &Data,
+) -> anyhow::Result<()> {
+    let Some(forum) = data
+        .config
+        .help_forums
+        .iter()
+        .find(|forum| Some(forum.channel) == thread.parent_id)
+    else {
+        return Ok(());
+    };
+
+    if forum.topic_tags.is_empty() {
+        return Ok(());
+    }
+
+    // the first message of a forum post shares its id with the post.
+    let starter = thread
+        .id
+        .message(ctx, MessageId::new(thread.id.get()))
+        .await?;
+
+    let topics = classify(&thread.name, &starter.content, &forum.topic_tags);
+
+    if topics.is_empty() {
+        return Ok(());
+    }
+
+    let Some(forum_channel) = forum.channel.to_channel(ctx).await?.guild() else {
+        return Ok(());
+    };
+
+    let mut applied_tags = thread.applied_tags.clone();
+
+    for tag in forum_channel.available_tags.iter().filter(|tag| {
+        topics
+            .iter()
+            .any(|topic| tag.name.eq_ignore_ascii_case(topic))
+    }) {
+        // a post can't have more than five tags.
+        if applied_tags.len() < 5 && !applied_tags.contains(&tag.id) {
+            applied_tags.push(tag.id);
+        }
+    }
+
+    if applied_tags == thread.applied_tags {
+        return Ok(());
+    }
+
+    thread
+        .id
+        .edit_thread(ctx, EditThread::new().applied_tags(applied_tags))
+        .await?;
+
+    tracing::info!(
+        "Tagged help thread {} with {}",
+        thread.id,
+        topics.join(", ")
+    );
+
+    Ok(())
+}
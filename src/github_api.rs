@@ -0,0 +1,899 @@
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+};
+
+use anyhow::Context;
+use chrono::{DateTime, Duration, TimeZone, Utc};
+use jsonwebtoken::{Algorithm, EncodingKey, Header};
+use reqwest::{
+    header::{HeaderMap, HeaderValue, ACCEPT, AUTHORIZATION, ETAG, IF_NONE_MATCH, LINK},
+    Method, StatusCode,
+};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use serde_json::{json, Value};
+
+const GITHUB_API_URL: &str = "https://api.github.com";
+const DEFAULT_ACCEPT: &str = "application/vnd.github+json";
+const RAW_ACCEPT: &str = "application/vnd.github.raw+json";
+/// Upper bound of remembered ETag responses before the cache gets reset.
+const MAX_CACHED_RESPONSES: usize = 512;
+
+/// Wrapper around reqwest that every GitHub-querying command shares.
+///
+/// It sends conditional requests for everything it has seen before, so unchanged resources are
+/// answered with `304 Not Modified` which doesn't count against the rate limit, and it refuses to
+/// send requests while the rate limit is exhausted.
+///
+/// Cloning is cheap, the connection pool and caches are reference counted.
+#[derive(Debug, Clone)]
+pub struct GithubClient {
+    http: reqwest::Client,
+    repository: String,
+    etag_cache: Arc<Mutex<HashMap<String, CachedResponse>>>,
+    rate_limits: Arc<Mutex<HashMap<String, RateLimit>>>,
+    app: Option<Arc<GithubApp>>,
+}
+
+/// Credentials of the github app installation that performs write actions on behalf of the bot.
+pub struct GithubApp {
+    app_id: u64,
+    installation_id: u64,
+    key: EncodingKey,
+    token: tokio::sync::Mutex<Option<InstallationToken>>,
+}
+
+impl std::fmt::Debug for GithubApp {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("GithubApp")
+            .field("app_id", &self.app_id)
+            .field("installation_id", &self.installation_id)
+            .finish_non_exhaustive()
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct InstallationToken {
+    token: String,
+    expires_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize)]
+struct AppClaims {
+    iat: i64,
+    exp: i64,
+    iss: String,
+}
+
+impl GithubApp {
+    pub fn new(app_id: u64, installation_id: u64, private_key_pem: &[u8]) -> anyhow::Result<Self> {
+        Ok(Self {
+            app_id,
+            installation_id,
+            key: EncodingKey::from_rsa_pem(private_key_pem)
+                .context("Failed to parse the github app private key")?,
+            token: tokio::sync::Mutex::new(None),
+        })
+    }
+
+    /// Returns a cached installation token or exchanges a freshly signed app JWT for a new one.
+    async fn installation_token(&self, http: &reqwest::Client) -> anyhow::Result<String> {
+        let mut token = self.token.lock().await;
+
+        if let Some(token) = token.as_ref() {
+            if token.expires_at - Utc::now() > Duration::minutes(1) {
+                return Ok(token.token.clone());
+            }
+        }
+
+        let now = Utc::now();
+        let claims = AppClaims {
+            // github recommends backdating to allow for clock drift.
+            iat: (now - Duration::seconds(60)).timestamp(),
+            exp: (now + Duration::minutes(9)).timestamp(),
+            iss: self.app_id.to_string(),
+        };
+        let jwt = jsonwebtoken::encode(&Header::new(Algorithm::RS256), &claims, &self.key)?;
+
+        let fresh = http
+            .post(format!(
+                "{GITHUB_API_URL}/app/installations/{}/access_tokens",
+                self.installation_id
+            ))
+            .header(ACCEPT, DEFAULT_ACCEPT)
+            .bearer_auth(jwt)
+            .send()
+            .await?
+            .error_for_status()
+            .context("Failed to create a github app installation token")?
+            .json::<InstallationToken>()
+            .await?;
+
+        let value = fresh.token.clone();
+        *token = Some(fresh);
+
+        Ok(value)
+    }
+}
+
+#[derive(Debug, Clone)]
+struct CachedResponse {
+    etag: HeaderValue,
+    headers: HeaderMap,
+    body: Vec<u8>,
+}
+
+/// The rate limit state of one resource, reported by the last api response that counted against it.
+/// Github limits core, search and graphql requests separately.
+#[derive(Debug, Clone, Copy)]
+pub struct RateLimit {
+    pub limit: u64,
+    pub remaining: u64,
+    pub reset: DateTime<Utc>,
+}
+
+impl RateLimit {
+    fn from_headers(headers: &HeaderMap) -> Option<Self> {
+        let header = |name: &str| -> Option<u64> { headers.get(name)?.to_str().ok()?.parse().ok() };
+
+        Some(Self {
+            limit: header("x-ratelimit-limit")?,
+            remaining: header("x-ratelimit-remaining")?,
+            reset: Utc
+                .timestamp_opt(header("x-ratelimit-reset")? as i64, 0)
+                .single()?,
+        })
+    }
+
+    fn is_exhausted(&self) -> bool {
+        self.remaining == 0 && self.reset > Utc::now()
+    }
+}
+
+/// The `x-ratelimit-resource` a request to the api path counts against.
+fn rate_limit_resource(path: &str) -> &'static str {
+    if path.starts_with("/search/") {
+        "search"
+    } else if path == "/graphql" {
+        "graphql"
+    } else {
+        "core"
+    }
+}
+
+/// A successful response, either fresh or replayed from the ETag cache.
+struct GithubResponse {
+    headers: HeaderMap,
+    body: Vec<u8>,
+}
+
+impl GithubClient {
+    pub fn new(token: Option<&str>, repository: impl Into<String>) -> anyhow::Result<Self> {
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            "x-github-api-version",
+            HeaderValue::from_static("2022-11-28"),
+        );
+
+        if let Some(token) = token {
+            let mut auth = HeaderValue::from_str(&format!("Bearer {token}"))
+                .context("Github api token contains invalid header characters")?;
+            auth.set_sensitive(true);
+            headers.insert(AUTHORIZATION, auth);
+        }
+
+        let http = reqwest::Client::builder()
+            .default_headers(headers)
+            .user_agent("biomebot")
+            .build()?;
+
+        Ok(Self {
+            http,
+            repository: repository.into(),
+            etag_cache: Arc::default(),
+            rate_limits: Arc::default(),
+            app: None,
+        })
+    }
+
+    /// Enables write actions, which are performed as the given github app installation.
+    pub fn with_app(mut self, app: GithubApp) -> Self {
+        self.app = Some(Arc::new(app));
+        self
+    }
+
+    /// Sends a write request authenticated as the github app installation.
+    async fn send_as_app(
+        &self,
+        method: Method,
+        path: &str,
+        body: Option<Value>,
+    ) -> anyhow::Result<()> {
+        let app = self
+            .app
+            .as_ref()
+            .context("No github app is configured, write actions are unavailable")?;
+
+        let token = app.installation_token(&self.http).await?;
+
+        let mut request = self
+            .http
+            .request(method, format!("{GITHUB_API_URL}{path}"))
+            .header(ACCEPT, DEFAULT_ACCEPT)
+            .bearer_auth(token);
+
+        if let Some(body) = body {
+            request = request.json(&body);
+        }
+
+        let response = request.send().await?;
+
+        if !response.status().is_success() {
+            anyhow::bail!(
+                "Github api request to {path} failed with status {}: {}",
+                response.status(),
+                response.text().await.unwrap_or_default()
+            );
+        }
+
+        Ok(())
+    }
+
+    pub async fn add_labels(&self, issue: u64, labels: &[&str]) -> anyhow::Result<()> {
+        self.send_as_app(
+            Method::POST,
+            &format!("/repos/{}/issues/{issue}/labels", self.repository),
+            Some(json!({ "labels": labels })),
+        )
+        .await
+    }
+
+    pub async fn remove_label(&self, issue: u64, label: &str) -> anyhow::Result<()> {
+        self.send_as_app(
+            Method::DELETE,
+            &format!(
+                "/repos/{}/issues/{issue}/labels/{}",
+                self.repository,
+                urlencode_path_segment(label)
+            ),
+            None,
+        )
+        .await
+    }
+
+    pub async fn add_assignees(&self, issue: u64, logins: &[&str]) -> anyhow::Result<()> {
+        self.send_as_app(
+            Method::POST,
+            &format!("/repos/{}/issues/{issue}/assignees", self.repository),
+            Some(json!({ "assignees": logins })),
+        )
+        .await
+    }
+
+    /// Closes the issue. `reason` can be either `completed` or `not_planned`.
+    pub async fn close_issue(&self, issue: u64, reason: &str) -> anyhow::Result<()> {
+        self.send_as_app(
+            Method::PATCH,
+            &format!("/repos/{}/issues/{issue}", self.repository),
+            Some(json!({ "state": "closed", "state_reason": reason })),
+        )
+        .await
+    }
+
+    /// Creates a secret gist with the api token and returns its url. Github apps can't own gists.
+    pub async fn create_gist(&self, file_name: &str, content: &str) -> anyhow::Result<String> {
+        let response = self
+            .http
+            .post(format!("{GITHUB_API_URL}/gists"))
+            .header(ACCEPT, DEFAULT_ACCEPT)
+            .json(&json!({
+                "public": false,
+                "files": { file_name: { "content": content } },
+            }))
+            .send()
+            .await?;
+
+        if !response.status().is_success() {
+            anyhow::bail!("Creating a gist failed with status {}", response.status());
+        }
+
+        let gist = response.json::<Value>().await?;
+
+        gist["html_url"]
+            .as_str()
+            .map(str::to_string)
+            .context("Github didn't return the url of the gist")
+    }
+
+    /// The `owner/name` of the repository the bot is tracking.
+    pub fn repository(&self) -> &str {
+        &self.repository
+    }
+
+    /// The rate limit state of the resource, like `core` or `search`, if any request counted
+    /// against it yet.
+    pub fn rate_limit(&self, resource: &str) -> Option<RateLimit> {
+        self.rate_limits
+            .lock()
+            .expect("rate limit lock poisoned")
+            .get(resource)
+            .copied()
+    }
+
+    /// Refuses requests to the api path while its resource is exhausted.
+    fn check_rate_limit(&self, path: &str) -> anyhow::Result<()> {
+        let resource = rate_limit_resource(path);
+
+        if let Some(rate_limit) = self.rate_limit(resource).filter(RateLimit::is_exhausted) {
+            anyhow::bail!(
+                "Github api rate limit of {} {resource} requests is exhausted until {}",
+                rate_limit.limit,
+                rate_limit.reset
+            );
+        }
+
+        Ok(())
+    }
+
+    /// Remembers the rate limit state of the resource the response counted against.
+    fn record_rate_limit(&self, path: &str, headers: &HeaderMap) {
+        let Some(rate_limit) = RateLimit::from_headers(headers) else {
+            return;
+        };
+
+        let resource = headers
+            .get("x-ratelimit-resource")
+            .and_then(|resource| resource.to_str().ok())
+            .unwrap_or_else(|| rate_limit_resource(path));
+
+        self.rate_limits
+            .lock()
+            .expect("rate limit lock poisoned")
+            .insert(resource.to_string(), rate_limit);
+    }
+
+    fn is_rate_limited(&self, path: &str) -> bool {
+        self.rate_limit(rate_limit_resource(path))
+            .is_some_and(|limit| limit.is_exhausted())
+    }
+
+    /// Sends a GET request to the given api path and returns `None` if the resource does not exist.
+    pub async fn get<T: DeserializeOwned>(&self, path: &str) -> anyhow::Result<Option<T>> {
+        self.get_with_query(path, &[]).await
+    }
+
+    /// Same as [`GithubClient::get`] but appends the url encoded query parameters.
+    pub async fn get_with_query<T: DeserializeOwned>(
+        &self,
+        path: &str,
+        query: &[(&str, &str)],
+    ) -> anyhow::Result<Option<T>> {
+        match self.fetch(path, query, DEFAULT_ACCEPT).await? {
+            Some(response) => Ok(Some(serde_json::from_slice(&response.body)?)),
+            None => Ok(None),
+        }
+    }
+
+    /// Sends a conditional GET request, replaying the cached body when github answers `304 Not Modified`.
+    async fn fetch(
+        &self,
+        path: &str,
+        query: &[(&str, &str)],
+        accept: &'static str,
+    ) -> anyhow::Result<Option<GithubResponse>> {
+        self.check_rate_limit(path)?;
+
+        let mut request = self
+            .http
+            .get(format!("{GITHUB_API_URL}{path}"))
+            .query(query)
+            .header(ACCEPT, accept)
+            .build()?;
+
+        let cache_key = format!("{accept} {}", request.url());
+        let cached = self
+            .etag_cache
+            .lock()
+            .expect("etag cache lock poisoned")
+            .get(&cache_key)
+            .cloned();
+
+        if let Some(cached) = &cached {
+            request
+                .headers_mut()
+                .insert(IF_NONE_MATCH, cached.etag.clone());
+        }
+
+        let response = self.http.execute(request).await?;
+
+        self.record_rate_limit(path, response.headers());
+
+        match response.status() {
+            StatusCode::NOT_MODIFIED => {
+                let cached =
+                    cached.context("Github answered not modified to an unconditional request")?;
+
+                return Ok(Some(GithubResponse {
+                    headers: cached.headers,
+                    body: cached.body,
+                }));
+            }
+            StatusCode::NOT_FOUND => return Ok(None),
+            StatusCode::FORBIDDEN | StatusCode::TOO_MANY_REQUESTS if self.is_rate_limited(path) => {
+                anyhow::bail!("Github api rate limit exceeded while requesting {path}");
+            }
+            status if !status.is_success() => {
+                anyhow::bail!("Github api request to {path} failed with status {status}");
+            }
+            _ => {}
+        }
+
+        let headers = response.headers().clone();
+        let body = response.bytes().await?.to_vec();
+
+        if let Some(etag) = headers.get(ETAG) {
+            let mut etag_cache = self.etag_cache.lock().expect("etag cache lock poisoned");
+
+            if etag_cache.len() >= MAX_CACHED_RESPONSES {
+                etag_cache.clear();
+            }
+
+            etag_cache.insert(
+                cache_key,
+                CachedResponse {
+                    etag: etag.clone(),
+                    headers: headers.clone(),
+                    body: body.clone(),
+                },
+            );
+        }
+
+        Ok(Some(GithubResponse { headers, body }))
+    }
+
+    /// Sends a GraphQL query, which github only answers with an api token.
+    pub async fn graphql<T: DeserializeOwned>(
+        &self,
+        query: &str,
+        variables: Value,
+    ) -> anyhow::Result<T> {
+        self.check_rate_limit("/graphql")?;
+
+        let response = self
+            .http
+            .post(format!("{GITHUB_API_URL}/graphql"))
+            .json(&json!({ "query": query, "variables": variables }))
+            .send()
+            .await?;
+
+        self.record_rate_limit("/graphql", response.headers());
+
+        if !response.status().is_success() {
+            anyhow::bail!(
+                "Github graphql query failed with status {}",
+                response.status()
+            );
+        }
+
+        let response = response.json::<GraphqlResponse<T>>().await?;
+
+        if let Some(error) = response.errors.first() {
+            anyhow::bail!("Github graphql query failed: {}", error.message);
+        }
+
+        response
+            .data
+            .context("Github graphql response contains no data")
+    }
+
+    pub async fn repository_info(&self) -> anyhow::Result<Repository> {
+        self.get(&format!("/repos/{}", self.repository))
+            .await?
+            .context("Configured github repository does not exist")
+    }
+
+    /// Counts the contributors by requesting one per page and reading the number of the last page.
+    pub async fn contributor_count(&self) -> anyhow::Result<u64> {
+        let response = self
+            .fetch(
+                &format!("/repos/{}/contributors", self.repository),
+                &[("per_page", "1"), ("anon", "true")],
+                DEFAULT_ACCEPT,
+            )
+            .await?
+            .context("Configured github repository does not exist")?;
+
+        let last_page = response
+            .headers
+            .get(LINK)
+            .and_then(|link| link.to_str().ok())
+            .and_then(last_page_from_link);
+
+        match last_page {
+            Some(count) => Ok(count),
+            // no link header means everything fit on the single page.
+            None => Ok(serde_json::from_slice::<Vec<Value>>(&response.body)?.len() as u64),
+        }
+    }
+
+    /// Returns the raw contents of a file on the default branch of the repository.
+    pub async fn file_contents(&self, path: &str) -> anyhow::Result<Option<String>> {
+        let response = self
+            .fetch(
+                &format!("/repos/{}/contents/{path}", self.repository),
+                &[],
+                RAW_ACCEPT,
+            )
+            .await?;
+
+        match response {
+            Some(response) => Ok(Some(String::from_utf8(response.body)?)),
+            None => Ok(None),
+        }
+    }
+
+    pub async fn pull_request(&self, number: u64) -> anyhow::Result<Option<PullRequest>> {
+        self.get(&format!("/repos/{}/pulls/{number}", self.repository))
+            .await
+    }
+
+    pub async fn check_runs(&self, git_ref: &str) -> anyhow::Result<CheckRuns> {
+        self.get_with_query(
+            &format!("/repos/{}/commits/{git_ref}/check-runs", self.repository),
+            &[("per_page", "100")],
+        )
+        .await?
+        .context("Github returned no check runs for an existing commit")
+    }
+
+    /// Searches issues and pull requests of the repository. `qualifiers` is appended to the `repo:` qualifier.
+    pub async fn search_issues(
+        &self,
+        qualifiers: &str,
+        per_page: u8,
+    ) -> anyhow::Result<SearchResults<Issue>> {
+        let query = format!("repo:{} {qualifiers}", self.repository);
+        let per_page = per_page.to_string();
+
+        self.get_with_query(
+            "/search/issues",
+            &[
+                ("q", query.as_str()),
+                ("sort", "created"),
+                ("per_page", per_page.as_str()),
+            ],
+        )
+        .await?
+        .context("Github search endpoint returned not found")
+    }
+
+    /// Returns the number of issues and pull requests matching the search qualifiers.
+    pub async fn search_count(&self, qualifiers: &str) -> anyhow::Result<u64> {
+        Ok(self.search_issues(qualifiers, 1).await?.total_count)
+    }
+
+    /// Returns the first page of open issues in the repository that have the given label.
+    pub async fn open_issues_with_label(&self, label: &str) -> anyhow::Result<Vec<Issue>> {
+        let results = self
+            .search_issues(&format!("is:issue is:open label:\"{label}\""), 100)
+            .await?;
+
+        Ok(results.items)
+    }
+
+    pub async fn latest_release(&self) -> anyhow::Result<Option<Release>> {
+        self.get(&format!("/repos/{}/releases/latest", self.repository))
+            .await
+    }
+
+    pub async fn release_by_tag(&self, tag: &str) -> anyhow::Result<Option<Release>> {
+        self.get(&format!("/repos/{}/releases/tags/{tag}", self.repository))
+            .await
+    }
+
+    /// Returns the most recently created releases, newest first.
+    pub async fn recent_releases(&self) -> anyhow::Result<Vec<Release>> {
+        self.get_with_query(
+            &format!("/repos/{}/releases", self.repository),
+            &[("per_page", "30")],
+        )
+        .await?
+        .context("Github returned not found for the releases of the repository")
+    }
+
+    /// Looks up an issue or pull request of any repository, not just the tracked one.
+    pub async fn issue_in(&self, repository: &str, number: u64) -> anyhow::Result<Option<Issue>> {
+        self.get(&format!("/repos/{repository}/issues/{number}"))
+            .await
+    }
+
+    pub async fn user_by_id(&self, id: u64) -> anyhow::Result<Option<User>> {
+        self.get(&format!("/user/{id}")).await
+    }
+
+    /// Whether the user authored a commit on the default branch, so a pull request of theirs got
+    /// merged.
+    pub async fn has_commits(&self, author: &str) -> anyhow::Result<bool> {
+        let commits = self
+            .get_with_query::<Vec<Value>>(
+                &format!("/repos/{}/commits", self.repository),
+                &[("author", author), ("per_page", "1")],
+            )
+            .await?;
+
+        Ok(commits.is_some_and(|commits| !commits.is_empty()))
+    }
+
+    pub async fn open_milestones(&self) -> anyhow::Result<Vec<Milestone>> {
+        self.get_with_query(
+            &format!("/repos/{}/milestones", self.repository),
+            &[("state", "open"), ("sort", "due_on"), ("per_page", "100")],
+        )
+        .await?
+        .context("Github returned not found for the milestones of the repository")
+    }
+
+    /// The addresses github publishes for its services, like the ones webhooks are sent from.
+    pub async fn meta(&self) -> anyhow::Result<Meta> {
+        self.get("/meta")
+            .await?
+            .context("Github returned not found for its meta information")
+    }
+}
+
+/// Percent-encodes everything but unreserved characters, so label names with spaces or slashes stay one segment.
+fn urlencode_path_segment(segment: &str) -> String {
+    segment
+        .bytes()
+        .map(|byte| match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                (byte as char).to_string()
+            }
+            _ => format!("%{byte:02X}"),
+        })
+        .collect()
+}
+
+/// Extracts the page number of the `rel="last"` entry of a github pagination link header.
+fn last_page_from_link(link: &str) -> Option<u64> {
+    link.split(',')
+        .find(|part| part.contains("rel=\"last\""))
+        .and_then(|part| {
+            part.split(['?', '&', '>'])
+                .find_map(|param| param.strip_prefix("page="))
+        })
+        .and_then(|page| page.parse().ok())
+}
+
+#[derive(Debug, Deserialize)]
+struct GraphqlResponse<T> {
+    data: Option<T>,
+    #[serde(default)]
+    errors: Vec<GraphqlError>,
+}
+
+#[derive(Debug, Deserialize)]
+struct GraphqlError {
+    message: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Repository {
+    pub full_name: String,
+    pub html_url: String,
+    pub stargazers_count: u64,
+    pub forks_count: u64,
+    /// Github counts open pull requests as issues as well.
+    pub open_issues_count: u64,
+    pub subscribers_count: Option<u64>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Meta {
+    /// The CIDR ranges webhook deliveries are sent from.
+    pub hooks: Vec<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct User {
+    pub login: String,
+    pub html_url: String,
+    pub avatar_url: Option<String>,
+    #[serde(rename = "type")]
+    /// Can be one of: `Bot`, `User`, `Organization`, `Mannequin`
+    pub user_type: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Team {
+    pub name: String,
+    pub slug: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Label {
+    pub name: String,
+    /// 6-character hex code, without the leading #, identifying the color
+    pub color: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct GitRef {
+    pub sha: String,
+    #[serde(rename = "ref")]
+    pub name: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SearchResults<T> {
+    pub total_count: u64,
+    pub incomplete_results: bool,
+    pub items: Vec<T>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Issue {
+    pub number: u64,
+    pub title: String,
+    pub html_url: String,
+    /// State of the issue; either 'open' or 'closed'
+    pub state: String,
+    pub labels: Vec<Label>,
+    pub user: Option<User>,
+    pub assignees: Option<Vec<User>>,
+    pub comments: u64,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    pub closed_at: Option<chrono::DateTime<chrono::Utc>>,
+    /// Set when the issue is a pull request.
+    pub pull_request: Option<IssuePullRequest>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct IssuePullRequest {
+    pub merged_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Release {
+    pub tag_name: String,
+    pub name: Option<String>,
+    /// The release notes in markdown.
+    pub body: Option<String>,
+    pub html_url: String,
+    pub draft: bool,
+    pub prerelease: bool,
+    pub published_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub author: Option<User>,
+}
+
+impl Release {
+    /// The version number of the release, e.g. `1.7.0` for the tag `cli/v1.7.0`.
+    pub fn version(&self) -> &str {
+        self.tag_name
+            .rsplit('/')
+            .next()
+            .unwrap_or(&self.tag_name)
+            .trim_start_matches('v')
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Milestone {
+    pub number: u64,
+    pub title: String,
+    pub description: Option<String>,
+    pub html_url: String,
+    /// State of the milestone; either 'open' or 'closed'
+    pub state: String,
+    pub open_issues: u64,
+    pub closed_issues: u64,
+    pub due_on: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+impl Milestone {
+    /// Fraction of closed issues, between 0 and 1.
+    pub fn progress(&self) -> f64 {
+        let total = self.open_issues + self.closed_issues;
+
+        if total == 0 {
+            return 0.0;
+        }
+
+        self.closed_issues as f64 / total as f64
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct PullRequest {
+    pub number: u64,
+    pub title: String,
+    pub html_url: String,
+    /// State of the pull request; either 'open' or 'closed'
+    pub state: String,
+    pub draft: Option<bool>,
+    pub merged: Option<bool>,
+    /// `None` while github is still computing the mergeability.
+    pub mergeable: Option<bool>,
+    /// Can be one of `clean`, `dirty`, `blocked`, `unstable`, `behind`, `draft`, `has_hooks` or `unknown`
+    pub mergeable_state: Option<String>,
+    pub user: Option<User>,
+    pub head: GitRef,
+    pub base: GitRef,
+    pub labels: Vec<Label>,
+    pub requested_reviewers: Option<Vec<User>>,
+    pub requested_teams: Option<Vec<Team>>,
+    pub changed_files: Option<u64>,
+    pub additions: Option<u64>,
+    pub deletions: Option<u64>,
+    pub comments: Option<u64>,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    pub merged_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+impl PullRequest {
+    pub fn is_merged(&self) -> bool {
+        self.merged.unwrap_or(false) || self.merged_at.is_some()
+    }
+
+    pub fn is_draft(&self) -> bool {
+        self.draft.unwrap_or(false)
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CheckRuns {
+    pub total_count: u64,
+    pub check_runs: Vec<CheckRun>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CheckRun {
+    pub name: String,
+    /// Can be one of `queued`, `in_progress` or `completed`
+    pub status: String,
+    /// Only set once the run is completed.
+    pub conclusion: Option<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn encodes_label_names() {
+        assert_eq!(
+            urlencode_path_segment("good first issue"),
+            "good%20first%20issue"
+        );
+        assert_eq!(urlencode_path_segment("A-Linter/a11y"), "A-Linter%2Fa11y");
+    }
+
+    #[test]
+    fn tracks_rate_limits_per_resource() {
+        let client = GithubClient::new(None, "biomejs/biome").unwrap();
+        let reset = (Utc::now() + Duration::minutes(1)).timestamp().to_string();
+
+        let mut headers = HeaderMap::new();
+        headers.insert("x-ratelimit-limit", HeaderValue::from_static("30"));
+        headers.insert("x-ratelimit-remaining", HeaderValue::from_static("0"));
+        headers.insert("x-ratelimit-reset", reset.parse().unwrap());
+        headers.insert("x-ratelimit-resource", HeaderValue::from_static("search"));
+        client.record_rate_limit("/search/issues", &headers);
+
+        assert!(client.check_rate_limit("/search/issues").is_err());
+        assert!(client.check_rate_limit("/repos/biomejs/biome").is_ok());
+        assert_eq!(client.rate_limit("search").unwrap().limit, 30);
+        assert!(client.rate_limit("core").is_none());
+    }
+
+    #[test]
+    fn parses_last_page_from_link_header() {
+        let link = r#"<https://api.github.com/repositories/1/contributors?per_page=1&anon=true&page=2>; rel="next", <https://api.github.com/repositories/1/contributors?per_page=1&anon=true&page=412>; rel="last""#;
+
+        assert_eq!(last_page_from_link(link), Some(412));
+        assert_eq!(
+            last_page_from_link(r#"<https://api.github.com/x?page=1>; rel="prev""#),
+            None
+        );
+    }
+}
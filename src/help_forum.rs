@@ -0,0 +1,187 @@
+use anyhow::Context;
+use poise::serenity_prelude as serenity;
+use serenity::{CreateEmbed, EditThread, GuildChannel, Mentionable, UserId};
+
+use crate::{
+    config::{HelpForumConfig, TopicTagConfig},
+    database::solved_threads,
+    util::{embeds::EmbedColor, parse::parse_code_blocks},
+    Data,
+};
+
+/// The configuration of the help forum the thread was posted in, `None` for any other channel.
+pub fn forum_of<'a>(data: &'a Data, thread: &GuildChannel) -> Option<&'a HelpForumConfig> {
+    let parent_id = thread.parent_id?;
+
+    data.config
+        .help_forums
+        .iter()
+        .find(|forum| forum.channel == parent_id)
+}
+
+/// Records who solved the thread, `false` if it was already marked as solved.
+pub async fn record_solved(
+    data: &Data,
+    thread: &GuildChannel,
+    marked_by: UserId,
+    solver: Option<UserId>,
+    summary: Option<&str>,
+) -> anyhow::Result<bool> {
+    solved_threads::insert(
+        &data.db_pool,
+        thread.id.get() as i64,
+        thread
+            .parent_id
+            .map_or(0, |parent_id| parent_id.get() as i64),
+        marked_by.get() as i64,
+        solver.map(|solver| solver.get() as i64),
+        summary,
+    )
+    .await
+}
+
+/// The summary posted into a thread once it is solved.
+pub fn solved_embed(
+    marked_by: UserId,
+    solver: Option<UserId>,
+    summary: Option<&str>,
+) -> CreateEmbed {
+    let mut embed = CreateEmbed::new()
+        .color(EmbedColor::Biome)
+        .title("\u{2705} Solved")
+        .description(summary.unwrap_or(
+            "This post was marked as solved and gets archived. Feel free to open a new post if you run into another problem.",
+        ))
+        .field("Marked by", marked_by.mention().to_string(), true);
+
+    if let Some(solver) = solver {
+        embed = embed.field("Solved by", solver.mention().to_string(), true);
+    }
+
+    embed
+}
+
+/// Applies the solved tag of the forum and archives the thread. Post the summary before, sending
+/// a message into an archived thread unarchives it again.
+pub async fn close_solved_thread(
+    ctx: &serenity::Context,
+    thread: &GuildChannel,
+    forum: &HelpForumConfig,
+) -> anyhow::Result<()> {
+    let forum_channel = forum
+        .channel
+        .to_channel(ctx)
+        .await?
+        .guild()
+        .context("The help forum is not a guild channel")?;
+
+    let mut applied_tags = thread.applied_tags.clone();
+
+    match forum_channel
+        .available_tags
+        .iter()
+        .find(|tag| tag.name.eq_ignore_ascii_case(&forum.solved_tag))
+    {
+        Some(tag) if !applied_tags.contains(&tag.id) => {
+            // a post can't have more than five tags.
+            applied_tags.truncate(4);
+            applied_tags.push(tag.id);
+        }
+        Some(_) => {}
+        None => tracing::warn!(
+            "The help forum {} has no tag named {}.",
+            forum.channel,
+            forum.solved_tag
+        ),
+    }
+
+    thread
+        .id
+        .edit_thread(
+            ctx,
+            EditThread::new().applied_tags(applied_tags).archived(true),
+        )
+        .await?;
+
+    Ok(())
+}
+
+/// The topic tags whose keywords appear in the title or content of a new post.
+pub fn classify<'a>(title: &str, content: &str, topics: &'a [TopicTagConfig]) -> Vec<&'a str> {
+    let text = normalize_words(&format!("{title}\n{content}"));
+    let languages = parse_code_blocks(content)
+        .into_iter()
+        .filter_map(|block| block.language)
+        .collect::<Vec<_>>();
+
+    topics
+        .iter()
+        .filter(|topic| {
+            topic
+                .keywords
+                .iter()
+                .any(|keyword| match keyword.strip_prefix("```") {
+                    Some(language) => languages
+                        .iter()
+                        .any(|block_language| block_language.eq_ignore_ascii_case(language)),
+                    None => text.contains(&normalize_words(keyword)),
+                })
+        })
+        .map(|topic| topic.tag.as_str())
+        .collect()
+}
+
+/// Lowercases the text and separates words by single spaces, padded so that searching for
+/// ` word ` only finds whole words.
+fn normalize_words(text: &str) -> String {
+    let words = text
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ");
+
+    format!(" {} ", words.to_lowercase())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn topic(tag: &str, keywords: &[&str]) -> TopicTagConfig {
+        TopicTagConfig {
+            tag: tag.to_string(),
+            keywords: keywords.iter().map(|keyword| keyword.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn classifies_posts_by_keywords() {
+        let topics = vec![
+            topic("Formatter", &["format", "formatter", "formatting"]),
+            topic("Linter", &["lint", "linter", "rule"]),
+            topic("VS Code", &["vs code", "vscode"]),
+            topic("CI", &["github actions", "```yaml", "```yml"]),
+        ];
+
+        assert_eq!(
+            classify(
+                "Formatting on save doesn't work",
+                "I'm using VS Code with the biome extension.",
+                &topics
+            ),
+            vec!["Formatter", "VS Code"]
+        );
+
+        assert_eq!(
+            classify(
+                "Pipeline fails",
+                "```yaml\n- run: npx biome ci\n```",
+                &topics
+            ),
+            vec!["CI"]
+        );
+
+        // keywords only match whole words.
+        assert!(classify("Unformatted rulers", "vs codes", &topics).is_empty());
+    }
+}
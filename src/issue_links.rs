@@ -0,0 +1,220 @@
+use std::{
+    collections::HashMap,
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+use poise::serenity_prelude as serenity;
+use serenity::{ChannelId, CreateEmbed};
+
+use crate::{
+    github_api::Issue,
+    util::{embeds::EmbedColor, format::truncate},
+    Data,
+};
+
+/// Longer numbers are more likely ids or hex colors than issues.
+const MAX_NUMBER_DIGITS: usize = 7;
+
+/// An issue or pull request mentioned like `#1234` or `biomejs/biome#1234`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IssueReference {
+    pub repository: String,
+    pub number: u64,
+}
+
+/// The cooldown of every channel references are linked in.
+#[derive(Debug, Default)]
+pub struct IssueLinker {
+    last_replies: Mutex<HashMap<ChannelId, Instant>>,
+}
+
+impl IssueLinker {
+    /// Starts the cooldown of the channel, `false` if it is still cooling down.
+    pub fn try_reply(&self, channel_id: ChannelId, cooldown: Duration) -> bool {
+        let mut last_replies = self.last_replies.lock().unwrap();
+
+        match last_replies.get(&channel_id) {
+            Some(replied_at) if replied_at.elapsed() < cooldown => false,
+            _ => {
+                last_replies.insert(channel_id, Instant::now());
+                true
+            }
+        }
+    }
+}
+
+/// The references outside of code in the message. Only repositories of the owner of the tracked
+/// repository are linked, a bare `#1234` refers to the tracked repository itself.
+pub fn references(content: &str, repository: &str, limit: usize) -> Vec<IssueReference> {
+    let owner = repository.split('/').next().unwrap_or_default();
+    let mut references = Vec::new();
+
+    // every odd segment is inside of backticks, which covers inline code and code blocks.
+    for text in content.split('`').step_by(2) {
+        for (index, _) in text.match_indices('#') {
+            let Some(number) = number_after(&text[index + 1..]) else {
+                continue;
+            };
+
+            let before = &text[..index];
+            let repository = if before.is_empty() || before.ends_with(is_boundary) {
+                repository.to_string()
+            } else {
+                match repository_before(before) {
+                    Some(named) if named.split('/').next() == Some(owner) => named.to_string(),
+                    _ => continue,
+                }
+            };
+
+            let reference = IssueReference { repository, number };
+
+            if !references.contains(&reference) {
+                references.push(reference);
+            }
+
+            if references.len() >= limit {
+                return references;
+            }
+        }
+    }
+
+    references
+}
+
+fn number_after(text: &str) -> Option<u64> {
+    let digits = text.chars().take_while(char::is_ascii_digit).count();
+
+    if digits == 0
+        || digits > MAX_NUMBER_DIGITS
+        || text[digits..].starts_with(|c: char| c.is_alphanumeric() || c == '_')
+    {
+        return None;
+    }
+
+    text[..digits].parse().ok()
+}
+
+/// The `owner/name` right before the `#`, surrounded by a boundary so urls don't count.
+fn repository_before(text: &str) -> Option<&str> {
+    let start = text
+        .rfind(|c: char| !(c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.' | '/')))
+        .map_or(0, |index| index + 1);
+
+    if start > 0 && !text[..start].ends_with(is_boundary) {
+        return None;
+    }
+
+    let named = &text[start..];
+    let (owner, name) = named.split_once('/')?;
+
+    (!owner.is_empty() && !name.is_empty() && !name.contains('/')).then_some(named)
+}
+
+/// Characters a reference may follow. Notably not `<`, which starts channel mentions.
+fn is_boundary(c: char) -> bool {
+    c.is_whitespace()
+        || matches!(
+            c,
+            '(' | '[' | '{' | ',' | ';' | ':' | '!' | '?' | '"' | '\''
+        )
+}
+
+/// Looks up the references and describes them, references that don't exist are left out.
+pub async fn describe(data: &Data, references: &[IssueReference]) -> Option<CreateEmbed> {
+    let mut lines = Vec::new();
+
+    for reference in references {
+        match data
+            .github
+            .issue_in(&reference.repository, reference.number)
+            .await
+        {
+            Ok(Some(issue)) => lines.push(describe_issue(data, reference, &issue)),
+            Ok(None) => {}
+            Err(e) => tracing::warn!(
+                "Failed to fetch {}#{}: {e:#}",
+                reference.repository,
+                reference.number
+            ),
+        }
+    }
+
+    if lines.is_empty() {
+        return None;
+    }
+
+    Some(
+        CreateEmbed::new()
+            .color(EmbedColor::Biome)
+            .description(lines.join("\n\n")),
+    )
+}
+
+fn describe_issue(data: &Data, reference: &IssueReference, issue: &Issue) -> String {
+    // the tracked repository is implied, like on github.
+    let name = if reference.repository == data.github.repository() {
+        format!("#{}", issue.number)
+    } else {
+        format!("{}#{}", reference.repository, issue.number)
+    };
+
+    let state = match (&issue.pull_request, issue.state.as_str()) {
+        (Some(pull_request), _) if pull_request.merged_at.is_some() => "Merged pull request",
+        (Some(_), "open") => "Open pull request",
+        (Some(_), _) => "Closed pull request",
+        (None, "open") => "Open issue",
+        (None, _) => "Closed issue",
+    };
+
+    format!(
+        "**[{name}]({})** {}\n{state}",
+        issue.html_url,
+        truncate(&issue.title, 200)
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn reference(repository: &str, number: u64) -> IssueReference {
+        IssueReference {
+            repository: repository.to_string(),
+            number,
+        }
+    }
+
+    #[test]
+    fn finds_short_and_full_references() {
+        assert_eq!(
+            references(
+                "see #1234 and biomejs/biome-vscode#5, also (#1234)",
+                "biomejs/biome",
+                5
+            ),
+            vec![
+                reference("biomejs/biome", 1234),
+                reference("biomejs/biome-vscode", 5)
+            ]
+        );
+    }
+
+    #[test]
+    fn ignores_code_mentions_and_urls() {
+        let content =
+            "<#123456> `#12` ```\n#34\n``` https://example.com/a/b#56 #78abc &#90; #00000001";
+
+        assert!(references(content, "biomejs/biome", 5).is_empty());
+    }
+
+    #[test]
+    fn ignores_other_owners() {
+        assert!(references("prettier/prettier#1", "biomejs/biome", 5).is_empty());
+    }
+
+    #[test]
+    fn stops_at_limit() {
+        assert_eq!(references("#1 #2 #3", "biomejs/biome", 2).len(), 2);
+    }
+}
@@ -0,0 +1,252 @@
+use std::{
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+use anyhow::Context as _;
+use chrono::{DateTime, Utc};
+use poise::serenity_prelude as serenity;
+use serde::{Deserialize, Serialize};
+use serenity::{ChannelId, CreateMessage, Http, UserId};
+use sqlx::PgPool;
+
+use crate::{
+    commands::{lock, remind, slowmode},
+    database::jobs,
+    routes::{github, trace},
+    shutdown::{self, ShutdownReceiver},
+    Data,
+};
+
+/// How often the worker looks for due jobs when the queue is empty.
+const POLL_INTERVAL: Duration = Duration::from_secs(5);
+/// How long a job may run before another worker assumes this one died and claims it again.
+const LOCK_SECS: i64 = 5 * 60;
+const MAX_ATTEMPTS: i32 = 5;
+
+/// Work that has to survive restarts, stored in Postgres until it succeeded.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Job {
+    /// Forwards a github webhook delivery to the activity webhook.
+    ForwardGithubEvent {
+        body: String,
+        headers: Vec<(String, String)>,
+    },
+    DeliverReminder {
+        reminder_id: i64,
+    },
+    DirectMessage {
+        user_id: u64,
+        content: String,
+    },
+    UnlockChannel {
+        lock_id: i64,
+    },
+    ResetSlowmode {
+        channel_id: u64,
+        /// The slowmode that was set, the reset is skipped if a moderator changed it since.
+        seconds: u16,
+    },
+}
+
+impl Job {
+    fn kind(&self) -> &'static str {
+        match self {
+            Self::ForwardGithubEvent { .. } => "forward_github_event",
+            Self::DeliverReminder { .. } => "deliver_reminder",
+            Self::DirectMessage { .. } => "direct_message",
+            Self::UnlockChannel { .. } => "unlock_channel",
+            Self::ResetSlowmode { .. } => "reset_slowmode",
+        }
+    }
+
+    async fn run(self, http: &Http, data: &Data) -> anyhow::Result<()> {
+        match self {
+            Self::ForwardGithubEvent { body, headers } => {
+                // events queued before request ids existed don't have one.
+                let request_id = trace::queued_request_id(&headers).map(str::to_string);
+
+                let result = github::post_to_activity_webhook(
+                    &data.config.github.activity_webhook,
+                    body,
+                    headers,
+                )
+                .await;
+
+                match request_id {
+                    Some(request_id) => result.with_context(|| {
+                        format!("Failed to forward the event of request {request_id}")
+                    }),
+                    None => result,
+                }
+            }
+            Self::DeliverReminder { reminder_id } => {
+                remind::deliver_reminder(http, data, reminder_id).await
+            }
+            Self::DirectMessage { user_id, content } => {
+                UserId::new(user_id)
+                    .direct_message(http, CreateMessage::new().content(content))
+                    .await?;
+
+                Ok(())
+            }
+            Self::UnlockChannel { lock_id } => lock::auto_unlock(http, data, lock_id).await,
+            Self::ResetSlowmode {
+                channel_id,
+                seconds,
+            } => slowmode::reset_slowmode(http, data, ChannelId::new(channel_id), seconds).await,
+        }
+    }
+}
+
+/// Queues the job to run as soon as possible.
+pub async fn enqueue(pool: &PgPool, job: &Job) -> anyhow::Result<()> {
+    enqueue_at(pool, job, Utc::now()).await
+}
+
+/// Queues the job to run once `run_at` passed.
+pub async fn enqueue_at(pool: &PgPool, job: &Job, run_at: DateTime<Utc>) -> anyhow::Result<()> {
+    jobs::insert(
+        pool,
+        job.kind(),
+        &serde_json::to_value(job)?,
+        MAX_ATTEMPTS,
+        run_at,
+    )
+    .await?;
+
+    Ok(())
+}
+
+/// Sends the same direct message to every user, one job each so a user with closed direct
+/// messages doesn't hold up the others.
+pub async fn enqueue_direct_messages(
+    pool: &PgPool,
+    user_ids: impl IntoIterator<Item = UserId>,
+    content: &str,
+) -> anyhow::Result<()> {
+    for user_id in user_ids {
+        enqueue(
+            pool,
+            &Job::DirectMessage {
+                user_id: user_id.get(),
+                content: content.to_string(),
+            },
+        )
+        .await?;
+    }
+
+    Ok(())
+}
+
+/// Runs queued jobs, retrying failed ones with an exponential backoff until they run out of
+/// attempts and get moved into the dead letter table. Once the shutdown started no new job is
+/// claimed, the running one is finished first.
+pub async fn run_worker(http: Arc<Http>, data: Data, shutdown: ShutdownReceiver) {
+    let mut counted_at = None::<Instant>;
+
+    while !*shutdown.borrow() {
+        let idle = match jobs::claim(&data.db_pool, LOCK_SECS).await {
+            Ok(Some(row)) => {
+                run_claimed(&http, &data, row).await;
+
+                // the queue is counted at most once per poll interval while it's worked off.
+                if !counted_at.is_some_and(|at| at.elapsed() < POLL_INTERVAL) {
+                    update_queue_depth(&data).await;
+                    counted_at = Some(Instant::now());
+                }
+
+                continue;
+            }
+            Ok(None) => {
+                // nothing due was left to claim.
+                data.metrics.job_queue_depth.set(0);
+                tokio::time::sleep(POLL_INTERVAL)
+            }
+            Err(e) => {
+                tracing::error!("Failed to claim a job: {e:#?}");
+                tokio::time::sleep(POLL_INTERVAL)
+            }
+        };
+
+        tokio::select! {
+            () = idle => {}
+            () = shutdown::requested(shutdown.clone()) => {}
+        }
+    }
+}
+
+async fn update_queue_depth(data: &Data) {
+    match jobs::count_due(&data.db_pool).await {
+        Ok(count) => data.metrics.job_queue_depth.set(count),
+        Err(e) => tracing::warn!("Failed to count the queued jobs for the metrics: {e:#}"),
+    }
+}
+
+async fn run_claimed(http: &Http, data: &Data, row: jobs::JobRow) {
+    let result = match serde_json::from_value::<Job>(row.payload) {
+        Ok(job) => job.run(http, data).await,
+        Err(e) => Err(e.into()),
+    };
+
+    let pool = &data.db_pool;
+
+    if result.is_err() {
+        data.metrics.error("job");
+    }
+
+    let outcome = match result {
+        Ok(()) => jobs::complete(pool, row.id).await,
+        Err(e) if row.attempts >= row.max_attempts => {
+            tracing::error!(
+                "Job #{} ({}) failed for the last time: {e:#?}",
+                row.id,
+                row.kind
+            );
+            jobs::bury(pool, row.id, &format!("{e:#}")).await
+        }
+        Err(e) => {
+            let retry_at = Utc::now() + backoff(row.attempts);
+
+            tracing::warn!(
+                "Job #{} ({}) failed, retrying at {retry_at}: {e:#}",
+                row.id,
+                row.kind
+            );
+            jobs::retry(pool, row.id, &format!("{e:#}"), retry_at).await
+        }
+    };
+
+    if let Err(e) = outcome {
+        tracing::error!("Failed to update job #{}: {e:#?}", row.id);
+    }
+}
+
+/// Waits 30 seconds after the first attempt and doubles with every further one, up to an hour.
+fn backoff(attempts: i32) -> chrono::Duration {
+    let exponent = attempts.clamp(1, 8) as u32 - 1;
+
+    chrono::Duration::seconds(30 * 2_i64.pow(exponent)).min(chrono::Duration::hours(1))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn backoff_doubles_up_to_an_hour() {
+        assert_eq!(backoff(1), chrono::Duration::seconds(30));
+        assert_eq!(backoff(2), chrono::Duration::seconds(60));
+        assert_eq!(backoff(4), chrono::Duration::seconds(240));
+        assert_eq!(backoff(20), chrono::Duration::hours(1));
+    }
+
+    #[test]
+    fn payload_contains_kind() {
+        let job = Job::DeliverReminder { reminder_id: 3 };
+        let payload = serde_json::to_value(&job).unwrap();
+
+        assert_eq!(payload["kind"], job.kind());
+    }
+}
@@ -0,0 +1,59 @@
+use chrono::{DateTime, Duration, Utc};
+use poise::serenity_prelude as serenity;
+use serenity::{CreateEmbed, CreateMessage, Member, Mentionable};
+
+use crate::{util::format::display_time, Data};
+
+/// Alerts the moderators when an account younger than configured joins and gives it the
+/// quarantine role, if there is one. Returns whether the member was quarantined.
+pub async fn check_account_age(
+    member: &Member,
+    ctx: &serenity::Context,
+    data: &Data,
+) -> anyhow::Result<bool> {
+    let Some(config) = &data.config.new_accounts else {
+        return Ok(false);
+    };
+
+    let user = &member.user;
+    let created_at =
+        DateTime::from_timestamp(user.created_at().unix_timestamp(), 0).unwrap_or_default();
+
+    if Utc::now() - created_at >= Duration::days(config.min_age_days as i64) {
+        return Ok(false);
+    }
+
+    if let Some(role_id) = config.quarantine_role {
+        member.add_role(ctx, role_id).await?;
+    }
+
+    tracing::info!(
+        "{} joined with an account created at {created_at}.",
+        user.name
+    );
+
+    let quarantined = config.quarantine_role.is_some();
+
+    let Some(modlog) = data.log_channel(member.guild_id) else {
+        return Ok(quarantined);
+    };
+
+    let action = match config.quarantine_role {
+        Some(role_id) => format!("Got the {} role", role_id.mention()),
+        None => "None, only flagged".to_string(),
+    };
+
+    let embed = CreateEmbed::new()
+        .color(0xFFA500)
+        .title("New account joined")
+        .thumbnail(user.face())
+        .field("User", format!("{} (`{}`)", user.mention(), user.id), false)
+        .field("Account created", display_time(created_at), false)
+        .field("Action", action, false);
+
+    modlog
+        .send_message(ctx, CreateMessage::new().embed(embed))
+        .await?;
+
+    Ok(quarantined)
+}
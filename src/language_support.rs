@@ -0,0 +1,280 @@
+use std::{fmt::Display, str::FromStr, sync::Arc};
+
+use scraper::{selectable::Selectable, Html, Selector};
+
+use crate::Data;
+
+pub const LANGUAGE_SUPPORT_URL: &str = "https://biomejs.dev/internals/language-support/";
+/// The source of the language support page in the website repository.
+const LANGUAGE_SUPPORT_SOURCE_URL: &str = "https://raw.githubusercontent.com/biomejs/website/main/src/content/docs/internals/language-support.mdx";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LanguageFeature {
+    pub language_name: String,
+    pub parsing: LanguageSupportLevel,
+    pub formatting: LanguageSupportLevel,
+    pub linting: LanguageSupportLevel,
+}
+
+impl LanguageFeature {
+    fn support_level_to_vec(&self) -> Vec<LanguageSupportLevel> {
+        vec![self.parsing, self.formatting, self.linting]
+    }
+
+    pub fn is_fully_supported(&self) -> bool {
+        self.support_level_to_vec()
+            .iter()
+            .all(|i| matches!(i, LanguageSupportLevel::Supported))
+    }
+
+    pub fn is_partially_supported(&self) -> bool {
+        self.support_level_to_vec()
+            .iter()
+            .any(|i| matches!(i, LanguageSupportLevel::PartiallySupported))
+    }
+
+    pub fn is_work_in_progress(&self) -> bool {
+        self.support_level_to_vec()
+            .iter()
+            .any(|i| matches!(i, LanguageSupportLevel::InProgress))
+    }
+
+    pub fn is_not_supported(&self) -> bool {
+        self.support_level_to_vec()
+            .iter()
+            .all(|i| matches!(i, LanguageSupportLevel::NotInProgress))
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Copy, Clone)]
+#[repr(u8)]
+pub enum LanguageSupportLevel {
+    Supported,
+    InProgress,
+    PartiallySupported,
+    NotInProgress,
+}
+
+impl LanguageSupportLevel {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Supported => "Supported",
+            Self::InProgress => "In Progress",
+            Self::PartiallySupported => "Partially Supported",
+            Self::NotInProgress => "Not in Progress",
+        }
+    }
+}
+
+impl TryFrom<i16> for LanguageSupportLevel {
+    type Error = anyhow::Error;
+
+    fn try_from(value: i16) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(Self::Supported),
+            1 => Ok(Self::InProgress),
+            2 => Ok(Self::PartiallySupported),
+            3 => Ok(Self::NotInProgress),
+            _ => anyhow::bail!("Unknown language support level: {value}"),
+        }
+    }
+}
+
+impl Display for LanguageSupportLevel {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Supported => write!(f, "\u{2705}"),
+            Self::InProgress => write!(f, "\u{231B}\u{FE0F}"),
+            Self::PartiallySupported => write!(f, "\u{26A0}\u{FE0F}"),
+            Self::NotInProgress => write!(f, "\u{1F6AB}"),
+        }
+    }
+}
+
+impl FromStr for LanguageSupportLevel {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+
+        match s.trim() {
+            "\u{2705}\u{FE0F}" | "\u{2705}" => Ok(Self::Supported),
+            "\u{231B}\u{FE0F}" => Ok(Self::InProgress),
+            "\u{26A0}\u{FE0F}" => Ok(Self::PartiallySupported),
+            "\u{1F6AB}" => Ok(Self::NotInProgress),
+            _ => {
+                anyhow::bail!("Unsupported Language support level: {}", s,);
+            }
+        }
+    }
+}
+
+/// Returns the cached language support, fetching it again once the cache expired.
+pub async fn cached_language_support(data: &Data) -> anyhow::Result<Arc<Vec<LanguageFeature>>> {
+    data.language_support
+        .get_or_refresh(|| async { Ok(Arc::new(fetch_language_support().await?)) })
+        .await
+}
+
+/// Scrapes the language support page, falling back to the markdown source of the page when the
+/// scrape fails. Website deploys tend to break the HTML structure the scraper relies on.
+pub async fn fetch_language_support() -> anyhow::Result<Vec<LanguageFeature>> {
+    match scrape_language_support().await {
+        Ok(features) => Ok(features),
+        Err(e) => {
+            tracing::warn!(
+                "Failed to scrape language support, falling back to the page source: {e:#?}"
+            );
+
+            let source = reqwest::get(LANGUAGE_SUPPORT_SOURCE_URL)
+                .await?
+                .error_for_status()?
+                .text()
+                .await?;
+
+            parse_markdown_table(&source)
+        }
+    }
+}
+
+/// Scrapes the support table of the language support page.
+async fn scrape_language_support() -> anyhow::Result<Vec<LanguageFeature>> {
+    let response = reqwest::get(LANGUAGE_SUPPORT_URL).await?.text().await?;
+
+    let document = Html::parse_document(&response);
+    let table_selector = parse_selector("table")?;
+
+    let Some(first_table) = document.select(&table_selector).next() else {
+        anyhow::bail!("Failed to find first table in the document")
+    };
+
+    let tr_selector = parse_selector("tr")?;
+    let td_selector = parse_selector("td")?;
+
+    let mut features = Vec::new();
+
+    for (i, element) in first_table.select(&tr_selector).enumerate() {
+        if i == 0 {
+            continue;
+        }; // ain't nobody wanna look at that header.
+
+        let columns = element.select(&td_selector).collect::<Vec<_>>();
+
+        if columns.len() != 4 {
+            anyhow::bail!("Encountered unexpexted HTML in the supported languages table");
+        }
+
+        let language_name = columns[0].text().collect::<String>();
+        let parsing = LanguageSupportLevel::from_str(columns[1].text().collect::<String>().trim())?;
+        let formatting =
+            LanguageSupportLevel::from_str(columns[2].text().collect::<String>().trim())?;
+        let linting = LanguageSupportLevel::from_str(columns[3].text().collect::<String>().trim())?;
+
+        features.push(LanguageFeature {
+            language_name,
+            parsing,
+            formatting,
+            linting,
+        })
+    }
+
+    Ok(features)
+}
+
+/// Parses the first table of the markdown source of the language support page.
+fn parse_markdown_table(markdown: &str) -> anyhow::Result<Vec<LanguageFeature>> {
+    let rows = markdown
+        .lines()
+        .map(str::trim)
+        .skip_while(|line| !line.starts_with('|'))
+        .take_while(|line| line.starts_with('|'))
+        // the first two rows are the header and the alignment row.
+        .skip(2);
+
+    let mut features = Vec::new();
+
+    for row in rows {
+        let columns = row
+            .trim_matches('|')
+            .split('|')
+            .map(markdown_text)
+            .collect::<Vec<_>>();
+
+        if columns.len() != 4 {
+            anyhow::bail!("Encountered unexpected markdown in the supported languages table");
+        }
+
+        features.push(LanguageFeature {
+            language_name: columns[0].clone(),
+            parsing: LanguageSupportLevel::from_str(&columns[1])?,
+            formatting: LanguageSupportLevel::from_str(&columns[2])?,
+            linting: LanguageSupportLevel::from_str(&columns[3])?,
+        })
+    }
+
+    if features.is_empty() {
+        anyhow::bail!("Failed to find the supported languages table in the page source");
+    }
+
+    Ok(features)
+}
+
+/// The visible text of a table cell, without html tags, link targets and emphasis.
+fn markdown_text(cell: &str) -> String {
+    let mut text = String::new();
+    let mut chars = cell.chars();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '<' => {
+                chars.by_ref().find(|&c| c == '>');
+            }
+            // the target of a link directly follows its text.
+            ']' if chars.as_str().starts_with('(') => {
+                chars.by_ref().find(|&c| c == ')');
+            }
+            '[' | ']' | '`' | '*' | '_' => {}
+            c => text.push(c),
+        }
+    }
+
+    text.trim().to_string()
+}
+
+fn parse_selector(sel: &str) -> anyhow::Result<Selector> {
+    match Selector::parse(sel) {
+        Ok(s) => Ok(s),
+        Err(e) => {
+            anyhow::bail!("Error parsing selector: {e}");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_markdown_table() {
+        let markdown = "---\ntitle: Language support\n---\n\n| Language | Parsing | Formatting | Linting |\n|---|:---:|:---:|:---:|\n| [JavaScript](#javascript-support) | <span aria-label=\"Supported\" role=\"img\">\u{2705}</span> | <span aria-label=\"Supported\" role=\"img\">\u{2705}</span> | <span aria-label=\"Supported\" role=\"img\">\u{2705}</span> |\n| `Vue` | \u{26A0}\u{FE0F} | \u{231B}\u{FE0F} | \u{1F6AB} |\n\n| Other | Table |\n";
+
+        assert_eq!(
+            parse_markdown_table(markdown).unwrap(),
+            vec![
+                LanguageFeature {
+                    language_name: "JavaScript".to_string(),
+                    parsing: LanguageSupportLevel::Supported,
+                    formatting: LanguageSupportLevel::Supported,
+                    linting: LanguageSupportLevel::Supported,
+                },
+                LanguageFeature {
+                    language_name: "Vue".to_string(),
+                    parsing: LanguageSupportLevel::PartiallySupported,
+                    formatting: LanguageSupportLevel::InProgress,
+                    linting: LanguageSupportLevel::NotInProgress,
+                },
+            ]
+        );
+        assert!(parse_markdown_table("no table here").is_err());
+    }
+}
@@ -0,0 +1,468 @@
+use std::{
+    collections::{HashMap, HashSet},
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use poise::serenity_prelude as serenity;
+use serenity::{
+    ChannelId, CreateEmbed, CreateMessage, GuildId, Invite, Mentionable, MessageId,
+    MessageUpdateEvent, RoleId, User,
+};
+
+use crate::{
+    database::blocked_domains,
+    util::{cache::TtlCache, format::truncate},
+    Data,
+};
+
+/// What invite links start with, the code follows.
+const INVITE_PREFIXES: &[&str] = &[
+    "discord.gg/",
+    "discord.com/invite/",
+    "discordapp.com/invite/",
+];
+/// How long a resolved invite is trusted before it's looked up again.
+const INVITE_TTL: Duration = Duration::from_secs(60 * 60);
+/// Upper bound of remembered invites before the cache gets reset.
+const MAX_CACHED_INVITES: usize = 1024;
+/// Characters that end the host of a link.
+const HOST_TERMINATORS: &[char] = &['/', '?', '#', '>', ')', ']', '"', '\'', '<', '\\', '|', '*'];
+
+/// The blocked domains and resolved invites, cached because every message is checked against
+/// them.
+#[derive(Debug)]
+pub struct LinkFilter {
+    domains: TtlCache<Arc<HashSet<String>>>,
+    /// Whether an invite code leads to the main server, with when it was resolved.
+    invites: Mutex<HashMap<String, (Instant, bool)>>,
+}
+
+impl Default for LinkFilter {
+    fn default() -> Self {
+        Self {
+            domains: TtlCache::new(Duration::from_secs(10 * 60)),
+            invites: Mutex::default(),
+        }
+    }
+}
+
+impl LinkFilter {
+    pub async fn domains(&self, data: &Data) -> anyhow::Result<Arc<HashSet<String>>> {
+        self.domains
+            .get_or_refresh(|| async {
+                let domains = blocked_domains::all(&data.db_pool)
+                    .await?
+                    .into_iter()
+                    .map(|blocked| blocked.domain)
+                    .collect();
+
+                Ok(Arc::new(domains))
+            })
+            .await
+    }
+
+    /// Makes the next message see added or removed domains.
+    pub async fn reload_domains(&self) {
+        self.domains.invalidate().await;
+    }
+
+    /// Whether the invite leads to the main server, `None` if it wasn't resolved recently.
+    fn cached_invite(&self, code: &str, now: Instant) -> Option<bool> {
+        let invites = self.invites.lock().unwrap();
+        let &(resolved_at, is_own) = invites.get(code)?;
+
+        (now.saturating_duration_since(resolved_at) < INVITE_TTL).then_some(is_own)
+    }
+
+    fn remember_invite(&self, code: &str, is_own: bool, now: Instant) {
+        let mut invites = self.invites.lock().unwrap();
+
+        if invites.len() >= MAX_CACHED_INVITES {
+            invites.clear();
+        }
+
+        invites.insert(code.to_string(), (now, is_own));
+    }
+}
+
+/// What the filter needs to know about a sent or edited message.
+struct ScannedMessage<'a> {
+    author: &'a User,
+    roles: &'a [RoleId],
+    guild_id: Option<GuildId>,
+    channel_id: ChannelId,
+    message_id: MessageId,
+    content: &'a str,
+}
+
+/// Deletes the message if it links to a blocked domain or another server. Returns whether it was
+/// deleted.
+pub async fn check_message(
+    message: &serenity::Message,
+    ctx: &serenity::Context,
+    data: &Data,
+) -> anyhow::Result<bool> {
+    let scanned = ScannedMessage {
+        author: &message.author,
+        roles: message
+            .member
+            .as_ref()
+            .map(|member| member.roles.as_slice())
+            .unwrap_or_default(),
+        guild_id: message.guild_id,
+        channel_id: message.channel_id,
+        message_id: message.id,
+        content: &message.content,
+    };
+
+    check(scanned, ctx, data).await
+}
+
+/// Edits are checked as well, otherwise a harmless message could be edited into a scam.
+pub async fn check_update(
+    event: &MessageUpdateEvent,
+    ctx: &serenity::Context,
+    data: &Data,
+) -> anyhow::Result<bool> {
+    let (Some(author), Some(content)) = (&event.author, &event.content) else {
+        return Ok(false);
+    };
+
+    let roles = match &event.member {
+        Some(Some(member)) => member.roles.as_slice(),
+        _ => &[],
+    };
+
+    let scanned = ScannedMessage {
+        author,
+        roles,
+        guild_id: event.guild_id,
+        channel_id: event.channel_id,
+        message_id: event.id,
+        content,
+    };
+
+    check(scanned, ctx, data).await
+}
+
+async fn check(
+    message: ScannedMessage<'_>,
+    ctx: &serenity::Context,
+    data: &Data,
+) -> anyhow::Result<bool> {
+    if message.author.bot || message.guild_id != Some(data.config.bot.guild_id) {
+        return Ok(false);
+    }
+
+    let roles = &data.config.main_guild.roles;
+    if roles.is_staff(message.roles) {
+        return Ok(false);
+    }
+
+    let Some(violation) = find_violation(&message, ctx, data).await? else {
+        return Ok(false);
+    };
+
+    message
+        .channel_id
+        .delete_message(ctx, message.message_id)
+        .await?;
+
+    tracing::info!(
+        "Deleted a message of {} that {}.",
+        message.author.name,
+        violation.description()
+    );
+
+    let Some(modlog) = data.log_channel(data.config.bot.guild_id) else {
+        return Ok(true);
+    };
+
+    let (title, field_name, field_value) = match &violation {
+        Violation::BlockedDomain(domain) => ("Blocked link removed", "Domain", domain),
+        Violation::ForeignInvite(code) => ("Invite removed", "Invite", code),
+    };
+
+    let embed = CreateEmbed::new()
+        .color(0xFF0000)
+        .title(title)
+        .field(
+            "Author",
+            format!("{} (`{}`)", message.author.mention(), message.author.id),
+            true,
+        )
+        .field("Channel", message.channel_id.mention().to_string(), true)
+        .field(field_name, format!("`{field_value}`"), true)
+        .field("Content", truncate(message.content, 1000), false);
+
+    modlog
+        .send_message(ctx, CreateMessage::new().embed(embed))
+        .await?;
+
+    Ok(true)
+}
+
+/// Why a message gets removed.
+enum Violation {
+    BlockedDomain(String),
+    /// An invite to another server, with its code.
+    ForeignInvite(String),
+}
+
+impl Violation {
+    fn description(&self) -> String {
+        match self {
+            Self::BlockedDomain(domain) => format!("linked to the blocked domain {domain}"),
+            Self::ForeignInvite(code) => format!("contained the foreign invite {code}"),
+        }
+    }
+}
+
+async fn find_violation(
+    message: &ScannedMessage<'_>,
+    ctx: &serenity::Context,
+    data: &Data,
+) -> anyhow::Result<Option<Violation>> {
+    let hosts = link_hosts(message.content);
+
+    if !hosts.is_empty() {
+        let blocked = data.link_filter.domains(data).await?;
+
+        if let Some(domain) = hosts.iter().find_map(|host| blocked_domain(host, &blocked)) {
+            return Ok(Some(Violation::BlockedDomain(domain.to_string())));
+        }
+    }
+
+    Ok(foreign_invite(message, ctx, data)
+        .await
+        .map(Violation::ForeignInvite))
+}
+
+/// The first invite in the message that leads to another server, unless invites are allowed in
+/// the channel or for one of the roles of the author.
+async fn foreign_invite(
+    message: &ScannedMessage<'_>,
+    ctx: &serenity::Context,
+    data: &Data,
+) -> Option<String> {
+    let config = data.config.invite_filter.as_ref()?;
+
+    if message
+        .roles
+        .iter()
+        .any(|role| config.allowed_roles.contains(role))
+    {
+        return None;
+    }
+
+    let parent_id = ctx.cache.guild(data.config.bot.guild_id).and_then(|guild| {
+        guild
+            .threads
+            .iter()
+            .find(|thread| thread.id == message.channel_id)
+            .and_then(|thread| thread.parent_id)
+    });
+
+    if config.allowed_channels.contains(&message.channel_id)
+        || parent_id.is_some_and(|parent_id| config.allowed_channels.contains(&parent_id))
+    {
+        return None;
+    }
+
+    for code in invite_codes(message.content) {
+        if !leads_to_main_server(&code, ctx, data).await {
+            return Some(code);
+        }
+    }
+
+    None
+}
+
+/// Invites that can't be resolved are treated as foreign, so the filter can't be dodged with
+/// invites discord fails to look up.
+async fn leads_to_main_server(code: &str, ctx: &serenity::Context, data: &Data) -> bool {
+    let now = Instant::now();
+
+    if let Some(is_own) = data.link_filter.cached_invite(code, now) {
+        return is_own;
+    }
+
+    match Invite::get(ctx, code, false, false, None).await {
+        Ok(invite) => {
+            let is_own = invite
+                .guild
+                .as_ref()
+                .is_some_and(|guild| guild.id == data.config.bot.guild_id);
+            data.link_filter.remember_invite(code, is_own, now);
+
+            is_own
+        }
+        Err(e) => {
+            tracing::debug!("Failed to resolve the invite {code}: {e}");
+            false
+        }
+    }
+}
+
+/// The codes of all discord invites in the content, links without a scheme included.
+fn invite_codes(content: &str) -> Vec<String> {
+    // ascii lowercasing keeps the byte offsets intact, the codes themselves are case sensitive.
+    let lowercase = content.to_ascii_lowercase();
+
+    INVITE_PREFIXES
+        .iter()
+        .flat_map(|prefix| {
+            lowercase
+                .match_indices(prefix)
+                .map(|(start, _)| start + prefix.len())
+        })
+        .filter_map(|start| {
+            let code = content[start..]
+                .split(|c: char| !c.is_ascii_alphanumeric() && c != '-')
+                .next()?;
+
+            (!code.is_empty()).then(|| code.to_string())
+        })
+        .collect()
+}
+
+/// The lowercase hosts of all links in the content.
+fn link_hosts(content: &str) -> Vec<String> {
+    // ascii lowercasing keeps the byte offsets intact.
+    let content = content.to_ascii_lowercase();
+
+    content
+        .match_indices("http")
+        .filter_map(|(start, _)| {
+            let rest = &content[start + 4..];
+            let rest = rest
+                .strip_prefix("://")
+                .or_else(|| rest.strip_prefix("s://"))?;
+
+            let host = host_of(rest);
+            (!host.is_empty()).then(|| host.to_string())
+        })
+        .collect()
+}
+
+/// The host at the start of a link without its scheme, without credentials, port or trailing dot.
+fn host_of(link: &str) -> &str {
+    let authority = link
+        .split(|c: char| c.is_whitespace() || HOST_TERMINATORS.contains(&c))
+        .next()
+        .unwrap_or_default();
+
+    let host = authority.rsplit('@').next().unwrap_or_default();
+    let host = host.split(':').next().unwrap_or_default();
+
+    host.trim_end_matches('.')
+}
+
+/// The blocked domain the host belongs to, blocking a domain blocks its subdomains as well.
+fn blocked_domain<'a>(host: &'a str, blocked: &HashSet<String>) -> Option<&'a str> {
+    // the host itself, then every parent domain.
+    std::iter::successors(Some(host), |domain| {
+        domain.split_once('.').map(|(_, parent)| parent)
+    })
+    .find(|domain| blocked.contains(*domain))
+}
+
+/// Turns input like `https://Scam.example/path` into the domain `scam.example`, `None` if it
+/// isn't a domain.
+pub fn normalize_domain(input: &str) -> Option<String> {
+    let input = input.trim().to_ascii_lowercase();
+    let link = input
+        .strip_prefix("https://")
+        .or_else(|| input.strip_prefix("http://"))
+        .unwrap_or(&input);
+
+    let domain = host_of(link);
+
+    let is_valid = domain.contains('.')
+        && domain
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '.' || c == '-')
+        && domain
+            .split('.')
+            .all(|label| !label.is_empty() && !label.starts_with('-') && !label.ends_with('-'));
+
+    is_valid.then(|| domain.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn finds_link_hosts() {
+        assert_eq!(
+            link_hosts(
+                "Free nitro: HTTPS://Discord-Gift.example/claim and <http://user@cdn.example:8080>"
+            ),
+            vec!["discord-gift.example", "cdn.example"]
+        );
+        assert_eq!(
+            link_hosts("[docs](https://biomejs.dev.) about https"),
+            vec!["biomejs.dev"]
+        );
+        assert!(link_hosts("no links, just http and https:// here").is_empty());
+    }
+
+    #[test]
+    fn finds_invite_codes() {
+        assert_eq!(
+            invite_codes("join https://discord.gg/AbC-12 or Discord.com/invite/biome!"),
+            vec!["AbC-12", "biome"]
+        );
+        assert!(invite_codes("discord.gg/ and discord.com").is_empty());
+    }
+
+    #[test]
+    fn caches_resolved_invites() {
+        let filter = LinkFilter::default();
+        let now = Instant::now();
+
+        assert_eq!(filter.cached_invite("biome", now), None);
+
+        filter.remember_invite("biome", true, now);
+        filter.remember_invite("scam", false, now);
+
+        assert_eq!(filter.cached_invite("biome", now), Some(true));
+        assert_eq!(filter.cached_invite("scam", now), Some(false));
+        assert_eq!(filter.cached_invite("Biome", now), None);
+        assert_eq!(filter.cached_invite("biome", now + INVITE_TTL), None);
+    }
+
+    #[test]
+    fn blocks_subdomains() {
+        let blocked = HashSet::from(["scam.example".to_string()]);
+
+        assert_eq!(
+            blocked_domain("scam.example", &blocked),
+            Some("scam.example")
+        );
+        assert_eq!(
+            blocked_domain("cdn.scam.example", &blocked),
+            Some("scam.example")
+        );
+        assert_eq!(blocked_domain("notscam.example", &blocked), None);
+        assert_eq!(blocked_domain("example", &blocked), None);
+    }
+
+    #[test]
+    fn normalizes_domains() {
+        assert_eq!(
+            normalize_domain(" https://Scam.Example/claim?gift=1 "),
+            Some("scam.example".to_string())
+        );
+        assert_eq!(
+            normalize_domain("scam.example"),
+            Some("scam.example".to_string())
+        );
+        assert_eq!(normalize_domain("localhost"), None);
+        assert_eq!(normalize_domain("scam..example"), None);
+        assert_eq!(normalize_domain("-scam.example"), None);
+        assert_eq!(normalize_domain("scam example.com"), None);
+    }
+}
@@ -0,0 +1,161 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    sync::Arc,
+};
+
+use anyhow::Context;
+use serde::Deserialize;
+
+use crate::Data;
+
+const RULES_METADATA_URL: &str = "https://biomejs.dev/metadata/rules.json";
+
+/// A lint rule of Biome as listed on the website.
+#[derive(Debug, Clone)]
+pub struct LintRule {
+    pub name: String,
+    /// The rule group, e.g. `a11y`, `correctness` or `style`.
+    pub group: String,
+    /// The languages the rule applies to, e.g. `js` or `css`.
+    pub languages: Vec<String>,
+    pub recommended: bool,
+    pub deprecated: bool,
+    /// The version that introduced the rule.
+    pub version: Option<String>,
+    /// First paragraph of the rule documentation.
+    pub description: Option<String>,
+    pub docs_url: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct RulesMetadata {
+    lints: LintsMetadata,
+}
+
+#[derive(Debug, Deserialize)]
+struct LintsMetadata {
+    /// language -> group -> rule name -> metadata
+    languages: HashMap<String, HashMap<String, HashMap<String, RuleMetadata>>>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RuleMetadata {
+    #[serde(default)]
+    recommended: bool,
+    #[serde(default)]
+    deprecated: bool,
+    version: Option<String>,
+    link: Option<String>,
+    docs: Option<String>,
+}
+
+/// Returns the cached lint rules, fetching them again once the cache expired.
+pub async fn cached_lint_rules(data: &Data) -> anyhow::Result<Arc<Vec<LintRule>>> {
+    data.lint_rules
+        .get_or_refresh(|| async { Ok(Arc::new(fetch_lint_rules().await?)) })
+        .await
+}
+
+/// Fetches the rule metadata the website publishes and flattens it into one entry per rule, sorted by name.
+pub async fn fetch_lint_rules() -> anyhow::Result<Vec<LintRule>> {
+    let metadata = reqwest::get(RULES_METADATA_URL)
+        .await?
+        .error_for_status()
+        .context("Failed to fetch lint rule metadata")?
+        .json::<RulesMetadata>()
+        .await?;
+
+    let mut rules: BTreeMap<String, LintRule> = BTreeMap::new();
+
+    for (language, groups) in metadata.lints.languages {
+        for (group, group_rules) in groups {
+            for (name, rule) in group_rules {
+                // the same rule is listed once for every language it applies to.
+                if let Some(existing) = rules.get_mut(&name) {
+                    existing.languages.push(language.clone());
+                    continue;
+                }
+
+                let docs_url = rule.link.unwrap_or_else(|| rule_docs_url(&name));
+
+                rules.insert(
+                    name.clone(),
+                    LintRule {
+                        name,
+                        group: group.clone(),
+                        languages: vec![language.clone()],
+                        recommended: rule.recommended,
+                        deprecated: rule.deprecated,
+                        version: rule.version,
+                        description: rule.docs.as_deref().and_then(first_paragraph),
+                        docs_url,
+                    },
+                );
+            }
+        }
+    }
+
+    Ok(rules
+        .into_values()
+        .map(|mut rule| {
+            rule.languages.sort();
+            rule
+        })
+        .collect())
+}
+
+/// The documentation page of a rule, derived from its name.
+pub fn rule_docs_url(name: &str) -> String {
+    format!("https://biomejs.dev/linter/rules/{}", kebab_case(name))
+}
+
+/// Converts a rule name like `noAccessKey` into the `no-access-key` slug of its docs page.
+fn kebab_case(name: &str) -> String {
+    let mut output = String::with_capacity(name.len() + 4);
+
+    for c in name.chars() {
+        if c.is_ascii_uppercase() {
+            if !output.is_empty() {
+                output.push('-');
+            }
+            output.push(c.to_ascii_lowercase());
+        } else {
+            output.push(c);
+        }
+    }
+
+    output
+}
+
+fn first_paragraph(docs: &str) -> Option<String> {
+    let paragraph = docs
+        .trim()
+        .lines()
+        .take_while(|line| !line.trim().is_empty())
+        .map(str::trim)
+        .collect::<Vec<_>>()
+        .join(" ");
+
+    (!paragraph.is_empty()).then_some(paragraph)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn converts_rule_names_to_slugs() {
+        assert_eq!(kebab_case("noAccessKey"), "no-access-key");
+        assert_eq!(kebab_case("useJsxKeyInIterable"), "use-jsx-key-in-iterable");
+    }
+
+    #[test]
+    fn takes_first_paragraph_of_docs() {
+        assert_eq!(
+            first_paragraph("\n Enforce that the\naccessKey attribute is not used.\n\nMore text")
+                .as_deref(),
+            Some("Enforce that the accessKey attribute is not used.")
+        );
+        assert_eq!(first_paragraph("  "), None);
+    }
+}
@@ -1,37 +1,173 @@
 #![allow(unused, dead_code)]
 
+mod anti_spam;
+mod auto_responder;
+mod auto_roles;
+mod automod;
+mod biome_cli;
+mod checks;
+mod code_snippets;
 mod commands;
 mod config;
+mod config_schema;
+mod contributors;
+mod database;
+mod embed_payload;
 mod error;
 mod events;
+mod github_api;
+mod help_forum;
+mod issue_links;
+mod jobs;
+mod join_gate;
+mod language_support;
+mod link_filter;
+mod lint_rules;
+mod message_log;
+mod metrics;
+mod npm_api;
+mod paste;
+mod raid;
 mod routes;
+mod scheduler;
+mod settings;
+mod shards;
+mod shutdown;
+mod sponsors;
+mod tasks;
 mod util;
+mod webhooks;
 
-use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+use std::{
+    net::{IpAddr, Ipv4Addr, SocketAddr},
+    sync::Arc,
+    time::Duration,
+};
 
-use axum::{routing::post, Router};
-use commands::{embed, languages};
-use config::Config;
+use anti_spam::SpamTracker;
+use anyhow::Context as _;
+use auto_responder::AutoResponder;
+use axum::{
+    extract::DefaultBodyLimit,
+    middleware,
+    routing::{get, post},
+    Router,
+};
+use axum_server::tls_rustls::RustlsConfig;
+use commands::{
+    announce, automod_rules, blocklist, bot_config, canned, case, changelog, docs, embed, format,
+    gfi, github_link, languages, lint, lock, lockdown, migrate, milestone, moderation, mydata,
+    playground, poll, pr, prettier_map, purge, reactionrole, release, remind, report, rolemenu,
+    rule, rules, schema, slowmode, solved, stats, suggest, tag, triage, twib, validate_config,
+    verification, version, whois,
+};
+use config::{Config, GuildConfig, TlsConfig};
+use config_schema::ConfigSchema;
 use events::event_handler;
+use github_api::{GithubApp, GithubClient};
+use issue_links::IssueLinker;
+use language_support::LanguageFeature;
+use link_filter::LinkFilter;
+use lint_rules::LintRule;
+use message_log::RecentMessages;
+use metrics::Metrics;
+use npm_api::PackageMetadata;
 use poise::serenity_prelude as serenity;
+use raid::JoinTracker;
+use scheduler::Scheduler;
+use serenity::{ChannelId, GuildId};
+use settings::{Setting, Settings};
+use shards::ShardStatuses;
+use shutdown::ShutdownReceiver;
 use sqlx::postgres::PgPoolOptions;
+use tower_http::{
+    request_id::{MakeRequestUuid, PropagateRequestIdLayer, SetRequestIdLayer},
+    trace::TraceLayer,
+};
+use util::{autocomplete::AutocompleteSources, cache::TtlCache};
 
-use crate::routes::github::handle_gh;
+use crate::routes::{
+    deliveries::RecentDeliveries,
+    github::handle_gh,
+    oauth::OAuthStates,
+    rate_limit::RateLimiter,
+    source_ip::HookAddresses,
+    stream::{Activity, ActivityStream},
+    trace::MakeRequestSpan,
+};
 
 #[derive(Debug, Clone)]
 pub struct Data {
     db_pool: sqlx::PgPool,
     config: Config,
+    github: GithubClient,
+    shards: ShardStatuses,
+    stats_cache: Arc<stats::StatsCache>,
+    npm_package: Arc<TtlCache<PackageMetadata>>,
+    lint_rules: Arc<TtlCache<Arc<Vec<LintRule>>>>,
+    config_schema: Arc<TtlCache<Arc<ConfigSchema>>>,
+    language_support: Arc<TtlCache<Arc<Vec<LanguageFeature>>>>,
+    auto_responder: Arc<AutoResponder>,
+    issue_linker: Arc<IssueLinker>,
+    recent_messages: Arc<RecentMessages>,
+    link_filter: Arc<LinkFilter>,
+    /// Set when the anti-spam is configured.
+    spam_tracker: Option<Arc<SpamTracker>>,
+    /// Set when the raid detection is configured.
+    join_tracker: Option<Arc<JoinTracker>>,
+    autocomplete: Arc<AutocompleteSources>,
+    settings: Arc<Settings>,
+    metrics: Arc<Metrics>,
+    rate_limiter: Arc<RateLimiter>,
+    hook_addresses: Arc<HookAddresses>,
+    deliveries: Arc<RecentDeliveries>,
+    activity: Arc<ActivityStream>,
+    oauth_states: Arc<OAuthStates>,
+}
+
+impl Data {
+    /// The roles, webhooks and channels of a server the bot serves, `None` for any other server.
+    pub fn guild(&self, guild_id: GuildId) -> Option<&GuildConfig> {
+        self.config.guild(guild_id)
+    }
+
+    /// Where moderation events of the server get logged. The main server uses the `log_channel`
+    /// setting, the others their configured modlog channel.
+    pub fn log_channel(&self, guild_id: GuildId) -> Option<ChannelId> {
+        if guild_id == self.config.bot.guild_id {
+            self.settings.channel(Setting::LogChannel)
+        } else {
+            self.guild(guild_id)?.channels.modlog
+        }
+    }
 }
 
 pub type Context<'a> = poise::Context<'a, Data, anyhow::Error>;
 
+/// How long the shutdown waits for running jobs before the bot stops anyway.
+const SHUTDOWN_TIMEOUT: Duration = Duration::from_secs(30);
+const CERTIFICATE_RELOAD_INTERVAL: Duration = Duration::from_secs(24 * 60 * 60);
+/// Commands that act on the main server or change what every server shares, like the settings
+/// or the blocklist. They are only registered in the main server.
+const MAIN_GUILD_COMMANDS: &[&str] = &[
+    "lockdown",
+    "config",
+    "blocklist",
+    "tag",
+    "canned",
+    "canned-manage",
+    "triage",
+    "automod",
+    "rolemenu",
+    "reactionrole",
+];
+
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
     tracing::subscriber::set_global_default(tracing_subscriber::fmt().compact().finish())?;
     tracing::info!("Logger initialized.");
 
-    let config = Config::load();
+    let config = Config::load()?;
     tracing::info!("Config loaded.");
 
     let db_pool = PgPoolOptions::new()
@@ -40,33 +176,194 @@ async fn main() -> anyhow::Result<()> {
         .await?;
     tracing::info!("Database connected.");
 
-    let data = Data { config, db_pool };
+    sqlx::migrate!().run(&db_pool).await?;
+    tracing::info!("Database migrations applied.");
+
+    let settings = Settings::new(&config);
+    settings.load(&db_pool).await?;
+    tracing::info!("Settings loaded.");
+
+    let mut github = GithubClient::new(
+        config.github.api_token.as_deref(),
+        config.github.repository.clone(),
+    )?;
+
+    if let Some(app) = &config.github.app {
+        let private_key = std::fs::read(&app.private_key_path)?;
+        github = github.with_app(GithubApp::new(
+            app.app_id,
+            app.installation_id,
+            &private_key,
+        )?);
+        tracing::info!("Github app configured.");
+    }
+
+    let language_support_ttl = Duration::from_secs(config.cache.language_support_ttl_secs);
+    let recent_messages = config
+        .message_log
+        .as_ref()
+        .map(|message_log| {
+            RecentMessages::new(
+                message_log.cached_messages,
+                Duration::from_secs(message_log.cached_message_hours * 60 * 60),
+            )
+        })
+        .unwrap_or_default();
+    let spam_tracker = config.anti_spam.as_ref().map(|anti_spam| {
+        Arc::new(SpamTracker::new(
+            Duration::from_secs(anti_spam.window_secs),
+            anti_spam.channels,
+        ))
+    });
+    let join_tracker = config.raid_detection.as_ref().map(|raid_detection| {
+        Arc::new(JoinTracker::new(
+            Duration::from_secs(raid_detection.window_secs),
+            raid_detection.joins,
+        ))
+    });
+
+    let rate_limiter = Arc::new(RateLimiter::new(&config.webserver.rate_limit));
+
+    let data = Data {
+        config,
+        db_pool,
+        github,
+        shards: ShardStatuses::default(),
+        stats_cache: Arc::default(),
+        npm_package: Arc::new(TtlCache::new(Duration::from_secs(10 * 60))),
+        lint_rules: Arc::new(TtlCache::new(Duration::from_secs(6 * 60 * 60))),
+        config_schema: Arc::new(TtlCache::new(Duration::from_secs(6 * 60 * 60))),
+        language_support: Arc::new(TtlCache::new(language_support_ttl)),
+        auto_responder: Arc::default(),
+        issue_linker: Arc::default(),
+        recent_messages: Arc::new(recent_messages),
+        link_filter: Arc::default(),
+        spam_tracker,
+        join_tracker,
+        autocomplete: Arc::default(),
+        settings: Arc::new(settings),
+        metrics: Arc::new(Metrics::new()?),
+        rate_limiter,
+        hook_addresses: Arc::default(),
+        deliveries: Arc::default(),
+        activity: Arc::default(),
+        oauth_states: Arc::default(),
+    };
+
+    tokio::spawn(tasks::docs_index::run(data.clone()));
+    tokio::spawn(tasks::autocomplete::run(data.clone()));
+    tokio::spawn(tasks::hook_addresses::run(data.clone()));
 
-    let discord_handle = tokio::spawn(setup_bot(data.clone()));
-    let webserver_handle = tokio::spawn(setup_webserver(data));
+    let (shutdown_sender, shutdown) = tokio::sync::watch::channel(false);
+
+    tokio::spawn(async move {
+        shutdown::signal().await;
+        tracing::info!("Shutting down, finishing the running work.");
+        let _ = shutdown_sender.send(true);
+    });
+
+    let discord_handle = tokio::spawn(setup_bot(data.clone(), shutdown.clone()));
+    let webserver_handle = tokio::spawn(setup_webserver(data.clone(), shutdown));
 
     let (discord_result, webserver_result) = tokio::join!(discord_handle, webserver_handle);
 
+    data.db_pool.close().await;
+    tracing::info!("Shut down.");
+
     discord_result??;
     webserver_result??;
 
     Ok(())
 }
 
-async fn setup_bot(data: Data) -> anyhow::Result<()> {
+async fn setup_bot(data: Data, shutdown: ShutdownReceiver) -> anyhow::Result<()> {
     let client_intents = serenity::GatewayIntents::GUILDS
+        | serenity::GatewayIntents::GUILD_MEMBERS
         | serenity::GatewayIntents::MESSAGE_CONTENT
         | serenity::GatewayIntents::GUILD_MESSAGES
         | serenity::GatewayIntents::GUILD_MESSAGE_REACTIONS;
 
-    let register_guild_id = data.config.bot.guild_id;
+    let register_guild_ids = data
+        .config
+        .guilds()
+        .map(|(guild_id, _)| guild_id)
+        .collect::<Vec<_>>();
+    let main_guild_id = data.config.bot.guild_id;
     let bot_token = data.config.bot.token.clone();
+    let shard_count = data.config.bot.shard_count;
+    let shard_statuses = data.shards.clone();
+    let task_data = data.clone();
 
     let framework = poise::Framework::builder()
         .options(poise::FrameworkOptions {
-            commands: vec![languages::languages(), embed::embed()],
-            event_handler: |ctx, event, framework, _data| {
-                Box::pin(event_handler(ctx, event, framework))
+            commands: vec![
+                languages::languages(),
+                embed::embed(),
+                pr::pr(),
+                mydata::mydata(),
+                gfi::gfi(),
+                milestone::milestone(),
+                stats::stats(),
+                twib::twib(),
+                release::release(),
+                changelog::changelog(),
+                version::version(),
+                triage::triage(),
+                rule::rule(),
+                rules::rules(),
+                docs::docs(),
+                schema::schema(),
+                validate_config::validate_config(),
+                playground::playground(),
+                format::format_message(),
+                lint::lint(),
+                migrate::migrate(),
+                prettier_map::prettier_map(),
+                tag::tag(),
+                canned::canned(),
+                canned::canned_manage(),
+                solved::solved(),
+                suggest::suggest(),
+                poll::poll(),
+                remind::remind(),
+                moderation::warn(),
+                moderation::timeout(),
+                moderation::kick(),
+                moderation::ban(),
+                case::case(),
+                verification::setup_verification(),
+                blocklist::blocklist(),
+                lockdown::lockdown(),
+                purge::purge(),
+                lock::lock(),
+                lock::unlock(),
+                slowmode::slowmode(),
+                report::report_message(),
+                automod_rules::automod(),
+                rolemenu::rolemenu(),
+                reactionrole::reactionrole(),
+                github_link::github(),
+                whois::whois(),
+                announce::announce(),
+                bot_config::config(),
+            ],
+            pre_command: |ctx| {
+                Box::pin(async move {
+                    let command = &ctx.command().qualified_name;
+
+                    ctx.data()
+                        .metrics
+                        .command_invocations
+                        .with_label_values(&[command])
+                        .inc();
+                    ctx.data().activity.publish(Activity::Command {
+                        command: command.clone(),
+                        at: chrono::Utc::now(),
+                    });
+                })
+            },
+            event_handler: |ctx, event, framework, data| {
+                Box::pin(event_handler(ctx, event, framework, data))
             },
             on_error: |error| {
                 Box::pin(async move {
@@ -79,48 +376,220 @@ async fn setup_bot(data: Data) -> anyhow::Result<()> {
         })
         .setup(move |ctx, _ready, framework| {
             Box::pin(async move {
-                poise::builtins::register_in_guild(
-                    ctx,
-                    &framework.options().commands,
-                    register_guild_id,
-                )
-                .await?;
+                for guild_id in register_guild_ids {
+                    let commands = framework
+                        .options()
+                        .commands
+                        .iter()
+                        .filter(|command| {
+                            guild_id == main_guild_id
+                                || !MAIN_GUILD_COMMANDS.contains(&command.name.as_str())
+                        })
+                        .flat_map(|command| {
+                            [
+                                command.create_as_slash_command(),
+                                command.create_as_context_menu_command(),
+                            ]
+                        })
+                        .flatten()
+                        .collect::<Vec<_>>();
+
+                    guild_id.set_commands(ctx, commands).await?;
+                }
+
                 Ok(data)
             })
         })
         .build();
 
-    let client = serenity::ClientBuilder::new(bot_token, client_intents)
+    let mut client = serenity::ClientBuilder::new(bot_token, client_intents)
         .framework(framework)
-        .await;
+        .await?;
+
+    tokio::spawn(tasks::language_support::run(
+        client.http.clone(),
+        task_data.clone(),
+    ));
+    tokio::spawn(tasks::polls::run(client.http.clone(), task_data.clone()));
+    let worker = tokio::spawn(jobs::run_worker(
+        client.http.clone(),
+        task_data.clone(),
+        shutdown.clone(),
+    ));
 
-    client?.start().await?;
+    let automod_http = client.http.clone();
+    let automod_data = task_data.clone();
+    tokio::spawn(async move {
+        if let Err(e) = automod::sync_rules(&automod_http, &automod_data).await {
+            tracing::error!("Failed to sync the AutoMod rules: {e:#?}");
+        }
+    });
+
+    let mut scheduler = Scheduler::new(client.http.clone(), task_data.clone());
+    tasks::announcements::schedule(&mut scheduler, &task_data)?;
+    tasks::contributors::schedule(&mut scheduler, &task_data)?;
+    tasks::phishing_feed::schedule(&mut scheduler, &task_data)?;
+    tasks::sponsors::schedule(&mut scheduler, &task_data)?;
+    tasks::stale_threads::schedule(&mut scheduler, &task_data)?;
+    tasks::weekly_digest::schedule(&mut scheduler, &task_data)?;
+    let scheduler = scheduler.start();
+
+    let shard_manager = client.shard_manager.clone();
+    tokio::spawn(async move {
+        shutdown::requested(shutdown).await;
+
+        let drain_worker = async {
+            if tokio::time::timeout(SHUTDOWN_TIMEOUT, worker)
+                .await
+                .is_err()
+            {
+                tracing::warn!("The running job didn't finish within {SHUTDOWN_TIMEOUT:?}, it is retried after the restart.");
+            }
+        };
+
+        tokio::join!(scheduler.shutdown(SHUTDOWN_TIMEOUT), drain_worker);
+
+        // ends `start_shards` below once every shard closed its gateway connection.
+        shard_manager.shutdown_all().await;
+    });
+
+    tokio::spawn(shards::supervise(
+        client.shard_manager.clone(),
+        client.http.clone(),
+        shard_statuses,
+        task_data.metrics.clone(),
+    ));
+
+    match shard_count {
+        Some(shard_count) => client.start_shards(shard_count).await?,
+        None => client.start_autosharded().await?,
+    }
 
     Ok(())
 }
 
-async fn setup_webserver(data: Data) -> anyhow::Result<()> {
+async fn setup_webserver(data: Data, shutdown: ShutdownReceiver) -> anyhow::Result<()> {
+    let admin_routes = Router::new()
+        .route("/deliveries", get(routes::admin::handle_deliveries))
+        .route("/deliveries/:id/replay", post(routes::admin::handle_replay))
+        .route("/queue", get(routes::admin::handle_queue))
+        .route("/errors", get(routes::admin::handle_errors))
+        .route("/metrics", get(routes::metrics::handle_metrics))
+        .route("/stream", get(routes::stream::handle_stream))
+        .route_layer(middleware::from_fn_with_state(
+            data.clone(),
+            routes::admin::require_token,
+        ));
+
+    // everything is served on one listener and reachable from wherever the port is. The probes
+    // are left out of the rate limit, orchestrators poll them often from a single address.
+    let public_routes = Router::new()
+        .route(
+            "/github",
+            post(handle_gh)
+                .layer(DefaultBodyLimit::max(data.config.github.max_payload_bytes))
+                .layer(middleware::from_fn(
+                    routes::github::require_webhook_content_type,
+                )),
+        )
+        .route("/oauth/github", get(routes::oauth::handle_start))
+        .route(
+            "/oauth/github/callback",
+            get(routes::oauth::handle_callback),
+        )
+        .nest("/admin", admin_routes)
+        .route_layer(middleware::from_fn_with_state(
+            data.clone(),
+            routes::rate_limit::limit,
+        ));
+
     let web_app = Router::new()
-        .route("/github", post(handle_gh))
+        .route("/health", get(routes::health::handle_health))
+        .route("/ready", get(routes::health::handle_ready))
+        .merge(public_routes)
         .fallback(routes::not_found::handle_404)
+        // the last layer runs first: the id of the client is dropped, a new one is set, then the
+        // request is traced under it and the id is copied into the response.
+        .layer(PropagateRequestIdLayer::x_request_id())
+        .layer(
+            TraceLayer::new_for_http()
+                .make_span_with(MakeRequestSpan::new(&data.config.webserver.trusted_proxies))
+                .on_response(routes::trace::on_response),
+        )
+        .layer(SetRequestIdLayer::x_request_id(MakeRequestUuid))
+        .layer(middleware::map_request(routes::trace::remove_request_id))
         .with_state(data.clone());
 
-    let listener = tokio::net::TcpListener::bind(SocketAddr::new(
+    let address = SocketAddr::new(
         IpAddr::V4(Ipv4Addr::from(data.config.webserver.host)),
         data.config.webserver.port,
-    ))
-    .await?;
+    );
+    let service = web_app.into_make_service_with_connect_info::<SocketAddr>();
+
+    if let Some(tls) = &data.config.webserver.tls {
+        let rustls_config = RustlsConfig::from_pem_file(&tls.cert_path, &tls.key_path)
+            .await
+            .context("Failed to load the TLS certificate")?;
+        tokio::spawn(reload_certificate(rustls_config.clone(), tls.clone()));
+
+        let handle = axum_server::Handle::new();
+        let shutdown_handle = handle.clone();
+        let activity = data.activity.clone();
+        tokio::spawn(async move {
+            shutdown::requested(shutdown).await;
+            // open streams would keep the server running.
+            activity.close();
+            shutdown_handle.graceful_shutdown(None);
+        });
+
+        tracing::info!(
+            "Webserver listening on port {} with TLS.",
+            data.config.webserver.port
+        );
+
+        axum_server::bind_rustls(address, rustls_config)
+            .handle(handle)
+            .serve(service)
+            .await?;
+
+        return Ok(());
+    }
+
+    let listener = tokio::net::TcpListener::bind(address).await?;
 
     tracing::info!(
         "Webserver listening on port {}.",
         data.config.webserver.port
     );
 
-    axum::serve(
-        listener,
-        web_app.into_make_service_with_connect_info::<SocketAddr>(),
-    )
-    .await?;
+    // stops accepting connections on shutdown and answers the requests that already came in,
+    // open streams would keep the server running.
+    axum::serve(listener, service)
+        .with_graceful_shutdown(async move {
+            shutdown::requested(shutdown).await;
+            data.activity.close();
+        })
+        .await?;
 
     Ok(())
 }
+
+/// Reads the certificate again every day, so renewals are picked up without a restart. A failed
+/// reload keeps serving the certificate from before.
+async fn reload_certificate(rustls_config: RustlsConfig, tls: TlsConfig) {
+    let mut interval = tokio::time::interval(CERTIFICATE_RELOAD_INTERVAL);
+    // the first tick completes right away, the certificate was just loaded.
+    interval.tick().await;
+
+    loop {
+        interval.tick().await;
+
+        match rustls_config
+            .reload_from_pem_file(&tls.cert_path, &tls.key_path)
+            .await
+        {
+            Ok(()) => tracing::info!("Reloaded the TLS certificate."),
+            Err(e) => tracing::error!("Failed to reload the TLS certificate: {e:#?}"),
+        }
+    }
+}
@@ -0,0 +1,198 @@
+use std::{
+    collections::{HashMap, VecDeque},
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+use poise::serenity_prelude as serenity;
+use serenity::{ChannelId, GuildId, MessageId, UserId};
+
+use crate::{config::MessageLogConfig, util::format::truncate};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CachedMessage {
+    pub author_id: UserId,
+    pub content: String,
+}
+
+/// The content of recently sent messages, so edits and deletions can show what a message said
+/// before. Discord only sends the id of a deleted message. At most `capacity` messages are kept,
+/// none of them longer than `max_age`.
+#[derive(Debug, Default)]
+pub struct RecentMessages {
+    capacity: usize,
+    max_age: Duration,
+    inner: Mutex<RecentMessagesInner>,
+}
+
+#[derive(Debug, Default)]
+struct RecentMessagesInner {
+    messages: HashMap<MessageId, CachedMessage>,
+    /// Insertion order, the oldest message is forgotten first.
+    order: VecDeque<(MessageId, Instant)>,
+}
+
+impl RecentMessagesInner {
+    fn forget_expired(&mut self, max_age: Duration, now: Instant) {
+        while let Some(&(oldest, remembered_at)) = self.order.front() {
+            if now.saturating_duration_since(remembered_at) <= max_age {
+                break;
+            }
+
+            self.order.pop_front();
+            self.messages.remove(&oldest);
+        }
+    }
+}
+
+impl RecentMessages {
+    pub fn new(capacity: usize, max_age: Duration) -> Self {
+        Self {
+            capacity,
+            max_age,
+            inner: Mutex::default(),
+        }
+    }
+
+    pub fn remember(&self, id: MessageId, message: CachedMessage, now: Instant) {
+        if self.capacity == 0 {
+            return;
+        }
+
+        let mut inner = self.inner.lock().unwrap();
+        inner.forget_expired(self.max_age, now);
+
+        if inner.messages.insert(id, message).is_none() {
+            inner.order.push_back((id, now));
+        }
+
+        while inner.order.len() > self.capacity {
+            if let Some((oldest, _)) = inner.order.pop_front() {
+                inner.messages.remove(&oldest);
+            }
+        }
+    }
+
+    /// Replaces the content of a remembered message and returns the previous one.
+    pub fn edit(&self, id: MessageId, content: &str, now: Instant) -> Option<CachedMessage> {
+        let mut inner = self.inner.lock().unwrap();
+        inner.forget_expired(self.max_age, now);
+
+        let message = inner.messages.get_mut(&id)?;
+        let previous = message.clone();
+
+        message.content = content.to_string();
+
+        Some(previous)
+    }
+
+    pub fn forget(&self, id: MessageId, now: Instant) -> Option<CachedMessage> {
+        let mut inner = self.inner.lock().unwrap();
+        inner.forget_expired(self.max_age, now);
+
+        let message = inner.messages.remove(&id)?;
+
+        inner.order.retain(|(remembered, _)| *remembered != id);
+
+        Some(message)
+    }
+}
+
+/// Whether edits and deletions in the channel get logged. Threads follow their parent channel.
+pub fn is_logged(
+    ctx: &serenity::Context,
+    config: &MessageLogConfig,
+    guild_id: Option<GuildId>,
+    channel_id: ChannelId,
+) -> bool {
+    let Some(guild_id) = guild_id else {
+        return false;
+    };
+
+    // never log the log channel itself.
+    if channel_id == config.channel || config.ignored_channels.contains(&channel_id) {
+        return false;
+    }
+
+    let parent_id = ctx.cache.guild(guild_id).and_then(|guild| {
+        guild
+            .threads
+            .iter()
+            .find(|thread| thread.id == channel_id)
+            .and_then(|thread| thread.parent_id)
+    });
+
+    !parent_id.is_some_and(|parent_id| config.ignored_channels.contains(&parent_id))
+}
+
+/// The content of a message as shown in the log, `None` when the message wasn't remembered.
+pub fn content_field(content: Option<&str>) -> String {
+    match content {
+        Some("") => "*No text*".to_string(),
+        Some(content) => truncate(content, 1000),
+        None => "*Unknown, the message is too old*".to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn message(content: &str) -> CachedMessage {
+        CachedMessage {
+            author_id: UserId::new(1),
+            content: content.to_string(),
+        }
+    }
+
+    #[test]
+    fn forgets_oldest_messages() {
+        let recent = RecentMessages::new(2, Duration::from_secs(60));
+        let now = Instant::now();
+
+        recent.remember(MessageId::new(1), message("one"), now);
+        recent.remember(MessageId::new(2), message("two"), now);
+        recent.remember(MessageId::new(3), message("three"), now);
+
+        assert_eq!(recent.forget(MessageId::new(1), now), None);
+        assert_eq!(
+            recent.edit(MessageId::new(2), "2", now),
+            Some(message("two"))
+        );
+        assert_eq!(recent.forget(MessageId::new(2), now), Some(message("2")));
+        assert_eq!(
+            recent.forget(MessageId::new(3), now),
+            Some(message("three"))
+        );
+    }
+
+    #[test]
+    fn forgets_expired_messages() {
+        let recent = RecentMessages::new(10, Duration::from_secs(60));
+        let now = Instant::now();
+
+        recent.remember(MessageId::new(1), message("one"), now);
+        recent.remember(
+            MessageId::new(2),
+            message("two"),
+            now + Duration::from_secs(30),
+        );
+
+        let later = now + Duration::from_secs(61);
+        assert_eq!(recent.edit(MessageId::new(1), "1", later), None);
+        assert_eq!(
+            recent.forget(MessageId::new(2), later),
+            Some(message("two"))
+        );
+    }
+
+    #[test]
+    fn remembers_nothing_without_capacity() {
+        let recent = RecentMessages::default();
+        let now = Instant::now();
+
+        recent.remember(MessageId::new(1), message("one"), now);
+
+        assert_eq!(recent.forget(MessageId::new(1), now), None);
+    }
+}
@@ -0,0 +1,123 @@
+use std::collections::BTreeMap;
+
+use prometheus::{
+    Encoder, GaugeVec, Histogram, HistogramOpts, IntCounterVec, IntGauge, Opts, Registry,
+    TextEncoder,
+};
+
+/// Where failures are counted, the `source` label of `errors_total`.
+pub const ERROR_SOURCES: [&str; 3] = ["command", "webhook", "job"];
+
+/// The counters, gauges and histograms scraped from `/admin/metrics`. Counters are increased where
+/// the work happens, the shard latencies are set right before a scrape, the job queue depth by the
+/// worker and the Discord API latency by the shard supervisor.
+#[derive(Debug)]
+pub struct Metrics {
+    registry: Registry,
+    /// Github webhook deliveries by their `x-github-event` header.
+    pub webhook_events: IntCounterVec,
+    /// Slash and context menu commands by their qualified name.
+    pub command_invocations: IntCounterVec,
+    /// Failures by where they happened: `command`, `webhook` or `job`.
+    pub errors: IntCounterVec,
+    /// The time between a heartbeat of every shard and its acknowledgement by the gateway.
+    pub heartbeat_latency: GaugeVec,
+    /// Round trips of the request the shard supervisor sends to the Discord REST API every interval.
+    /// Serenity's http client has no hook to time every request of the bot, so this samples it.
+    pub discord_api_latency: Histogram,
+    /// Jobs that are due and wait for the worker.
+    pub job_queue_depth: IntGauge,
+}
+
+impl Metrics {
+    pub fn new() -> anyhow::Result<Self> {
+        let registry = Registry::new_custom(Some("biomebot".to_string()), None)?;
+
+        let webhook_events = IntCounterVec::new(
+            Opts::new("webhook_events_total", "Received github webhook events."),
+            &["event"],
+        )?;
+        let command_invocations = IntCounterVec::new(
+            Opts::new("command_invocations_total", "Invoked commands."),
+            &["command"],
+        )?;
+        let errors = IntCounterVec::new(Opts::new("errors_total", "Failures."), &["source"])?;
+        let heartbeat_latency = GaugeVec::new(
+            Opts::new(
+                "gateway_heartbeat_latency_seconds",
+                "Time until the Discord gateway acknowledged the last heartbeat of each shard.",
+            ),
+            &["shard"],
+        )?;
+        let discord_api_latency = Histogram::with_opts(HistogramOpts::new(
+            "discord_api_latency_seconds",
+            "Time until the Discord REST API answered a request of the bot.",
+        ))?;
+        let job_queue_depth = IntGauge::new("job_queue_depth", "Jobs waiting in the queue.")?;
+
+        registry.register(Box::new(webhook_events.clone()))?;
+        registry.register(Box::new(command_invocations.clone()))?;
+        registry.register(Box::new(errors.clone()))?;
+        registry.register(Box::new(heartbeat_latency.clone()))?;
+        registry.register(Box::new(discord_api_latency.clone()))?;
+        registry.register(Box::new(job_queue_depth.clone()))?;
+
+        Ok(Self {
+            registry,
+            webhook_events,
+            command_invocations,
+            errors,
+            heartbeat_latency,
+            discord_api_latency,
+            job_queue_depth,
+        })
+    }
+
+    pub fn error(&self, source: &str) {
+        self.errors.with_label_values(&[source]).inc();
+    }
+
+    /// The failures since the start, by source.
+    pub fn error_counts(&self) -> BTreeMap<&'static str, u64> {
+        ERROR_SOURCES
+            .into_iter()
+            .map(|source| (source, self.errors.with_label_values(&[source]).get()))
+            .collect()
+    }
+
+    /// Everything registered, in the Prometheus text format.
+    pub fn encode(&self) -> anyhow::Result<String> {
+        let mut buffer = Vec::new();
+        TextEncoder::new().encode(&self.registry.gather(), &mut buffer)?;
+
+        Ok(String::from_utf8(buffer)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn encodes_metrics() {
+        let metrics = Metrics::new().unwrap();
+
+        metrics
+            .webhook_events
+            .with_label_values(&["pull_request"])
+            .inc();
+        metrics.error("job");
+        metrics.error("job");
+        metrics.job_queue_depth.set(3);
+        metrics.discord_api_latency.observe(0.2);
+
+        let text = metrics.encode().unwrap();
+
+        assert!(text.contains("biomebot_webhook_events_total{event=\"pull_request\"} 1"));
+        assert!(text.contains("biomebot_errors_total{source=\"job\"} 2"));
+        assert!(text.contains("biomebot_job_queue_depth 3"));
+        assert!(text.contains("biomebot_discord_api_latency_seconds_count 1"));
+        assert_eq!(metrics.error_counts()["job"], 2);
+        assert_eq!(metrics.error_counts()["command"], 0);
+    }
+}
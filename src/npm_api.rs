@@ -0,0 +1,95 @@
+use std::collections::HashMap;
+
+use anyhow::Context;
+use serde::{de::IgnoredAny, Deserialize};
+
+const NPM_DOWNLOADS_URL: &str = "https://api.npmjs.org/downloads";
+const NPM_REGISTRY_URL: &str = "https://registry.npmjs.org";
+
+/// The package whose metrics and versions the bot reports.
+pub const BIOME_PACKAGE: &str = "@biomejs/biome";
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct DownloadPoint {
+    pub downloads: u64,
+    pub start: String,
+    pub end: String,
+    pub package: String,
+}
+
+/// Returns the download count of the package over the last seven days.
+pub async fn weekly_downloads(package: &str) -> anyhow::Result<DownloadPoint> {
+    let response = reqwest::get(format!("{NPM_DOWNLOADS_URL}/point/last-week/{package}"))
+        .await?
+        .error_for_status()
+        .context("Failed to fetch download counts from npm")?;
+
+    Ok(response.json::<DownloadPoint>().await?)
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct PackageMetadata {
+    pub name: String,
+    /// Maps release channels like `latest` or `nightly` to a version.
+    #[serde(rename = "dist-tags")]
+    pub dist_tags: HashMap<String, String>,
+    pub versions: HashMap<String, IgnoredAny>,
+}
+
+impl PackageMetadata {
+    /// Every published version without a pre-release suffix, newest first.
+    pub fn stable_versions(&self) -> Vec<String> {
+        let mut versions = self
+            .versions
+            .keys()
+            .filter(|version| !version.contains('-'))
+            .cloned()
+            .collect::<Vec<_>>();
+
+        versions.sort_by_cached_key(|version| std::cmp::Reverse(version_key(version)));
+        versions
+    }
+}
+
+/// Fetches the abbreviated registry document of the package, which lists all versions and dist-tags.
+pub async fn package_metadata(package: &str) -> anyhow::Result<PackageMetadata> {
+    let response = reqwest::Client::new()
+        .get(format!("{NPM_REGISTRY_URL}/{package}"))
+        .header("accept", "application/vnd.npm.install-v1+json")
+        .send()
+        .await?
+        .error_for_status()
+        .context("Failed to fetch package metadata from npm")?;
+
+    Ok(response.json::<PackageMetadata>().await?)
+}
+
+/// Numeric components of a version for ordering, e.g. `1.10.2` as `[1, 10, 2]`.
+pub fn version_key(version: &str) -> Vec<u64> {
+    version
+        .split('-')
+        .next()
+        .unwrap_or_default()
+        .split('.')
+        .map(|part| part.parse().unwrap_or_default())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn versions_sort_numerically() {
+        let metadata = PackageMetadata {
+            name: BIOME_PACKAGE.to_string(),
+            dist_tags: HashMap::new(),
+            versions: ["1.2.0", "1.10.0", "1.9.4", "1.10.0-nightly.abc"]
+                .into_iter()
+                .map(|version| (version.to_string(), IgnoredAny))
+                .collect(),
+        };
+
+        assert_eq!(metadata.stable_versions(), vec!["1.10.0", "1.9.4", "1.2.0"]);
+    }
+}
@@ -0,0 +1,75 @@
+use anyhow::Context;
+use serde::Deserialize;
+
+use crate::{config::PasteBackend, util::parse::CodeBlock, Data};
+
+#[derive(Debug, Deserialize)]
+struct HastebinDocument {
+    key: String,
+}
+
+/// Uploads the code block and returns the url it can be read at.
+pub async fn upload(
+    data: &Data,
+    backend: &PasteBackend,
+    block: &CodeBlock,
+) -> anyhow::Result<String> {
+    let extension = extension(block);
+
+    match backend {
+        PasteBackend::Hastebin { url } => {
+            let url = url.trim_end_matches('/');
+            let document = reqwest::Client::new()
+                .post(format!("{url}/documents"))
+                .body(block.code.clone())
+                .send()
+                .await?
+                .error_for_status()
+                .context("Failed to upload to the paste service")?
+                .json::<HastebinDocument>()
+                .await?;
+
+            Ok(format!("{url}/{}.{extension}", document.key))
+        }
+        PasteBackend::Gist => {
+            data.github
+                .create_gist(&format!("snippet.{extension}"), &block.code)
+                .await
+        }
+    }
+}
+
+/// Highlights the paste like the code block was, plain text if the language is unknown.
+fn extension(block: &CodeBlock) -> String {
+    block
+        .file_extension()
+        .map(str::to_string)
+        .or_else(|| {
+            block
+                .language
+                .clone()
+                .filter(|language| language.chars().all(|c| c.is_ascii_alphanumeric()))
+        })
+        .filter(|extension| !extension.is_empty())
+        .unwrap_or_else(|| "txt".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn block(language: Option<&str>) -> CodeBlock {
+        CodeBlock {
+            language: language.map(str::to_string),
+            code: String::new(),
+        }
+    }
+
+    #[test]
+    fn picks_extension_of_language() {
+        assert_eq!(extension(&block(Some("typescript"))), "ts");
+        assert_eq!(extension(&block(Some("rust"))), "rust");
+        assert_eq!(extension(&block(Some("../etc"))), "txt");
+        assert_eq!(extension(&block(None)), "txt");
+    }
+}
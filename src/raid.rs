@@ -0,0 +1,234 @@
+use std::{
+    collections::VecDeque,
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+use poise::serenity_prelude as serenity;
+use serenity::{
+    CreateAllowedMentions, CreateEmbed, CreateMessage, EditGuild, Http, Mentionable, UserId,
+    VerificationLevel,
+};
+
+use crate::{database::lockdowns, Data};
+
+/// The guild feature that pauses all invites.
+const INVITES_DISABLED: &str = "INVITES_DISABLED";
+
+/// Recent joins, to notice raids.
+#[derive(Debug)]
+pub struct JoinTracker {
+    window: Duration,
+    threshold: usize,
+    joins: Mutex<VecDeque<Instant>>,
+}
+
+impl JoinTracker {
+    pub fn new(window: Duration, threshold: usize) -> Self {
+        Self {
+            window,
+            threshold,
+            joins: Mutex::default(),
+        }
+    }
+
+    /// Remembers the join and returns whether more than `threshold` members joined within the
+    /// window.
+    pub fn record(&self, now: Instant) -> bool {
+        let mut joins = self.joins.lock().unwrap();
+
+        while joins
+            .front()
+            .is_some_and(|joined_at| now.duration_since(*joined_at) >= self.window)
+        {
+            joins.pop_front();
+        }
+
+        joins.push_back(now);
+
+        if joins.len() <= self.threshold {
+            return false;
+        }
+
+        // the raid is reported once, not for every further join.
+        joins.clear();
+        true
+    }
+}
+
+/// Starts a lockdown once members join faster than configured.
+pub async fn record_join(ctx: &serenity::Context, data: &Data) -> anyhow::Result<()> {
+    let Some(tracker) = &data.join_tracker else {
+        return Ok(());
+    };
+
+    if !tracker.record(Instant::now()) {
+        return Ok(());
+    }
+
+    tracing::warn!("Members are joining unusually fast, starting a lockdown.");
+
+    start_lockdown(&ctx.http, data, "Members joined unusually fast.", None).await?;
+
+    Ok(())
+}
+
+/// Raises the verification level and pauses invites. Returns `false` if the server already is in
+/// lockdown.
+pub async fn start_lockdown(
+    http: &Http,
+    data: &Data,
+    reason: &str,
+    started_by: Option<UserId>,
+) -> anyhow::Result<bool> {
+    let pool = &data.db_pool;
+
+    if lockdowns::active(pool).await?.is_some() {
+        return Ok(false);
+    }
+
+    let guild_id = data.config.bot.guild_id;
+    let guild = guild_id.to_partial_guild(http).await?;
+
+    let previous_level = u8::from(guild.verification_level);
+    // members have to wait ten minutes before chatting, the highest level would require a
+    // verified phone number from everyone.
+    let level = previous_level.max(u8::from(VerificationLevel::High));
+
+    let mut features = guild.features.clone();
+    if !features.iter().any(|feature| feature == INVITES_DISABLED) {
+        features.push(INVITES_DISABLED.to_string());
+    }
+
+    guild_id
+        .edit(
+            http,
+            EditGuild::new()
+                .verification_level(VerificationLevel::from(level))
+                .features(features)
+                .audit_log_reason(reason),
+        )
+        .await?;
+
+    lockdowns::start(
+        pool,
+        reason,
+        previous_level.into(),
+        started_by.map(|user_id| user_id.get() as i64),
+    )
+    .await?;
+
+    let started_by = started_by.map_or_else(
+        || "the raid detection".to_string(),
+        |user_id| user_id.mention().to_string(),
+    );
+
+    alert(
+        http,
+        data,
+        CreateEmbed::new()
+            .color(0xFF0000)
+            .title("Lockdown started")
+            .description(format!(
+                "Invites are paused and the verification level is raised. Lift the lockdown with `/lockdown off`.\n**Started by:** {started_by}\n**Reason:** {reason}"
+            )),
+    )
+    .await?;
+
+    Ok(true)
+}
+
+/// Restores the verification level and resumes invites. Returns `false` if the server isn't in
+/// lockdown.
+pub async fn end_lockdown(http: &Http, data: &Data, ended_by: UserId) -> anyhow::Result<bool> {
+    let pool = &data.db_pool;
+
+    let Some(lockdown) = lockdowns::active(pool).await? else {
+        return Ok(false);
+    };
+
+    let guild_id = data.config.bot.guild_id;
+    let guild = guild_id.to_partial_guild(http).await?;
+
+    let features = guild
+        .features
+        .iter()
+        .filter(|feature| *feature != INVITES_DISABLED)
+        .cloned()
+        .collect::<Vec<_>>();
+
+    guild_id
+        .edit(
+            http,
+            EditGuild::new()
+                .verification_level(VerificationLevel::from(
+                    lockdown.previous_verification_level as u8,
+                ))
+                .features(features)
+                .audit_log_reason("The lockdown ended."),
+        )
+        .await?;
+
+    lockdowns::end(pool, lockdown.id, ended_by.get() as i64).await?;
+
+    alert(
+        http,
+        data,
+        CreateEmbed::new()
+            .color(0x00FF00)
+            .title("Lockdown ended")
+            .description(format!(
+                "Invites are resumed and the verification level is restored.\n**Ended by:** {}",
+                ended_by.mention()
+            )),
+    )
+    .await?;
+
+    Ok(true)
+}
+
+/// Pings the moderators in the modlog channel.
+async fn alert(http: &Http, data: &Data, embed: CreateEmbed) -> anyhow::Result<()> {
+    let Some(modlog) = data.log_channel(data.config.bot.guild_id) else {
+        return Ok(());
+    };
+
+    let mut message = CreateMessage::new().embed(embed);
+
+    if let Some(moderator) = data.config.main_guild.roles.moderator {
+        message = message
+            .content(moderator.mention().to_string())
+            .allowed_mentions(CreateAllowedMentions::new().roles([moderator]));
+    }
+
+    modlog.send_message(http, message).await?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn detects_fast_joins_once() {
+        let tracker = JoinTracker::new(Duration::from_secs(10), 2);
+        let now = Instant::now();
+
+        assert!(!tracker.record(now));
+        assert!(!tracker.record(now + Duration::from_secs(1)));
+        assert!(tracker.record(now + Duration::from_secs(2)));
+        assert!(!tracker.record(now + Duration::from_secs(3)));
+    }
+
+    #[test]
+    fn forgets_joins_outside_the_window() {
+        let tracker = JoinTracker::new(Duration::from_secs(10), 2);
+        let now = Instant::now();
+
+        assert!(!tracker.record(now));
+        assert!(!tracker.record(now + Duration::from_secs(5)));
+        assert!(!tracker.record(now + Duration::from_secs(10)));
+        assert!(tracker.record(now + Duration::from_secs(11)));
+    }
+}
@@ -0,0 +1,101 @@
+use std::collections::BTreeMap;
+
+use axum::{
+    extract::{Path, Request, State},
+    http::{header, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Response},
+    Json,
+};
+use serde::Serialize;
+use subtle::ConstantTimeEq;
+
+use crate::{
+    database::jobs,
+    routes::{deliveries::DeliverySummary, github::process_payload},
+    Data,
+};
+
+#[derive(Debug, Serialize)]
+pub struct QueueDepth {
+    due: i64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct Replay {
+    id: String,
+    status: u16,
+}
+
+/// Lets requests with `Authorization: Bearer <webserver.admin_token>` through. The admin api
+/// doesn't exist without a token.
+pub async fn require_token(State(data): State<Data>, request: Request, next: Next) -> Response {
+    let Some(token) = &data.config.webserver.admin_token else {
+        return StatusCode::NOT_FOUND.into_response();
+    };
+
+    let is_authorized = request
+        .headers()
+        .get(header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "))
+        .is_some_and(|provided| provided.as_bytes().ct_eq(token.as_bytes()).into());
+
+    if !is_authorized {
+        tracing::warn!("Unauthorized request at {}!", request.uri().path());
+        return (
+            StatusCode::UNAUTHORIZED,
+            [(header::WWW_AUTHENTICATE, "Bearer")],
+        )
+            .into_response();
+    }
+
+    next.run(request).await
+}
+
+/// The latest github webhook deliveries, newest first.
+pub async fn handle_deliveries(State(data): State<Data>) -> Json<Vec<DeliverySummary>> {
+    Json(data.deliveries.summaries())
+}
+
+/// Handles a recorded delivery again, like github's redeliver button.
+pub async fn handle_replay(State(data): State<Data>, Path(id): Path<String>) -> Response {
+    let Some(delivery) = data.deliveries.get(&id) else {
+        return (StatusCode::NOT_FOUND, "No recent delivery has this id.").into_response();
+    };
+
+    let Some(payload) = delivery.payload else {
+        return (
+            StatusCode::CONFLICT,
+            "The payload of this delivery was too large to keep.",
+        )
+            .into_response();
+    };
+
+    tracing::info!("Replaying the github delivery {id}.");
+
+    let status = process_payload(&data, &delivery.headers, &payload).await;
+    data.deliveries.replayed(&id, status);
+
+    Json(Replay {
+        id,
+        status: status.as_u16(),
+    })
+    .into_response()
+}
+
+/// Jobs that are due and wait for the worker.
+pub async fn handle_queue(State(data): State<Data>) -> Response {
+    match jobs::count_due(&data.db_pool).await {
+        Ok(due) => Json(QueueDepth { due }).into_response(),
+        Err(e) => {
+            tracing::error!("Failed to count the queued jobs: {e:#?}");
+            StatusCode::INTERNAL_SERVER_ERROR.into_response()
+        }
+    }
+}
+
+/// The failures since the start, by where they happened.
+pub async fn handle_errors(State(data): State<Data>) -> Json<BTreeMap<&'static str, u64>> {
+    Json(data.metrics.error_counts())
+}
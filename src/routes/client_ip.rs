@@ -0,0 +1,160 @@
+use std::net::{IpAddr, SocketAddr};
+
+use axum::http::HeaderMap;
+
+use crate::util::ip_range::IpRange;
+
+/// The address of the client. Requests from a trusted proxy carry the addresses they were
+/// forwarded for in `X-Forwarded-For`, or `Forwarded` when that is missing. Every proxy appends
+/// the address it got the request from, so the entries are read from the right and the first one
+/// that isn't a trusted proxy is the client. Entries further left come from the client itself.
+pub fn client_ip(headers: &HeaderMap, peer: SocketAddr, trusted_proxies: &[IpRange]) -> IpAddr {
+    let is_trusted = |ip: IpAddr| trusted_proxies.iter().any(|range| range.contains(ip));
+    let mut client = peer.ip();
+
+    if !is_trusted(client) {
+        return client;
+    }
+
+    for hop in forwarded_for(headers).into_iter().rev() {
+        // a proxy that sends something unreadable can't be followed any further.
+        let Some(ip) = hop else {
+            break;
+        };
+
+        client = ip;
+
+        if !is_trusted(ip) {
+            break;
+        }
+    }
+
+    client
+}
+
+/// The forwarded addresses, from the client to the last proxy.
+fn forwarded_for(headers: &HeaderMap) -> Vec<Option<IpAddr>> {
+    let values = |name: &str| {
+        headers
+            .get_all(name)
+            .iter()
+            .filter_map(|value| value.to_str().ok())
+            .flat_map(|value| value.split(','))
+            .map(str::trim)
+            .filter(|entry| !entry.is_empty())
+            .map(str::to_string)
+            .collect::<Vec<_>>()
+    };
+
+    let x_forwarded_for = values("x-forwarded-for");
+
+    if !x_forwarded_for.is_empty() {
+        return x_forwarded_for
+            .iter()
+            .map(|entry| parse_address(entry))
+            .collect();
+    }
+
+    // like `for=192.0.2.60;proto=https, for="[2001:db8::17]:4711"`.
+    values("forwarded")
+        .iter()
+        .map(|element| {
+            element
+                .split(';')
+                .filter_map(|pair| pair.trim().split_once('='))
+                .find(|(key, _)| key.eq_ignore_ascii_case("for"))
+                .and_then(|(_, value)| parse_address(value.trim_matches('"')))
+        })
+        .collect()
+}
+
+/// Reads an address that may have a port, with brackets around IPv6 addresses that have one.
+fn parse_address(entry: &str) -> Option<IpAddr> {
+    entry
+        .parse::<IpAddr>()
+        .or_else(|_| entry.parse::<SocketAddr>().map(|address| address.ip()))
+        .ok()
+        .or_else(|| {
+            entry
+                .strip_prefix('[')
+                .and_then(|entry| entry.strip_suffix(']'))?
+                .parse()
+                .ok()
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn headers(pairs: &[(&'static str, &'static str)]) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+
+        for (name, value) in pairs {
+            headers.append(*name, value.parse().unwrap());
+        }
+
+        headers
+    }
+
+    #[test]
+    fn ignores_headers_from_untrusted_peers() {
+        let peer = SocketAddr::from(([203, 0, 113, 9], 443));
+        let trusted = ["10.0.0.0/8".parse().unwrap()];
+
+        assert_eq!(
+            client_ip(&headers(&[("x-forwarded-for", "1.1.1.1")]), peer, &trusted),
+            peer.ip()
+        );
+        assert_eq!(
+            client_ip(&headers(&[("x-forwarded-for", "1.1.1.1")]), peer, &[]),
+            peer.ip()
+        );
+    }
+
+    #[test]
+    fn reads_forwarded_addresses() {
+        let peer = SocketAddr::from(([10, 0, 0, 1], 443));
+        let trusted = ["10.0.0.0/8".parse().unwrap()];
+
+        assert_eq!(
+            client_ip(
+                &headers(&[("x-forwarded-for", "1.1.1.1, 2.2.2.2, 10.0.0.2")]),
+                peer,
+                &trusted
+            ),
+            IpAddr::from([2, 2, 2, 2])
+        );
+        assert_eq!(
+            client_ip(
+                &headers(&[
+                    ("x-forwarded-for", "1.1.1.1"),
+                    ("x-forwarded-for", "2.2.2.2:1234")
+                ]),
+                peer,
+                &trusted
+            ),
+            IpAddr::from([2, 2, 2, 2])
+        );
+        assert_eq!(
+            client_ip(
+                &headers(&[(
+                    "forwarded",
+                    "for=1.1.1.1, for=\"[2001:db8::17]:4711\";proto=https"
+                )]),
+                peer,
+                &trusted
+            ),
+            "2001:db8::17".parse::<IpAddr>().unwrap()
+        );
+        assert_eq!(
+            client_ip(
+                &headers(&[("x-forwarded-for", "1.1.1.1, unknown, 10.0.0.2")]),
+                peer,
+                &trusted
+            ),
+            IpAddr::from([10, 0, 0, 2])
+        );
+        assert_eq!(client_ip(&HeaderMap::new(), peer, &trusted), peer.ip());
+    }
+}
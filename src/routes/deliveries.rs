@@ -0,0 +1,156 @@
+use std::{collections::VecDeque, sync::Mutex};
+
+use axum::http::{HeaderMap, StatusCode};
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+
+use crate::routes::trace::REQUEST_ID_HEADER;
+
+/// Github sends a unique id with every webhook delivery.
+const GITHUB_DELIVERY_HEADER: &str = "x-github-delivery";
+/// How many deliveries are kept for the admin api.
+const CAPACITY: usize = 50;
+/// Larger payloads are listed but can't be replayed, so the log stays small.
+const MAX_KEPT_PAYLOAD: usize = 512 * 1024;
+
+#[derive(Debug, Clone)]
+pub struct Delivery {
+    pub summary: DeliverySummary,
+    pub headers: HeaderMap,
+    /// `None` when the payload was too large to keep.
+    pub payload: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct DeliverySummary {
+    /// The request id the delivery was handled under.
+    pub id: String,
+    /// Finds the delivery in the delivery log of the github app. Only recorded for deliveries
+    /// with a valid signature, so it can be trusted.
+    pub delivery_id: String,
+    pub event: String,
+    pub received_at: DateTime<Utc>,
+    /// The status of the latest attempt, replays included.
+    pub status: u16,
+    pub replays: u32,
+    pub replayable: bool,
+}
+
+/// The latest github webhook deliveries with a valid signature, oldest first.
+#[derive(Debug, Default)]
+pub struct RecentDeliveries {
+    deliveries: Mutex<VecDeque<Delivery>>,
+}
+
+impl RecentDeliveries {
+    pub fn record(&self, headers: &HeaderMap, event: &str, payload: String, status: StatusCode) {
+        let id = headers
+            .get(REQUEST_ID_HEADER)
+            .and_then(|id| id.to_str().ok())
+            .unwrap_or_default()
+            .to_string();
+        let delivery_id = headers
+            .get(GITHUB_DELIVERY_HEADER)
+            .and_then(|id| id.to_str().ok())
+            .unwrap_or_default()
+            .to_string();
+        let payload = (payload.len() <= MAX_KEPT_PAYLOAD).then_some(payload);
+
+        let mut deliveries = self.deliveries.lock().unwrap();
+
+        deliveries.push_back(Delivery {
+            summary: DeliverySummary {
+                id,
+                delivery_id,
+                event: event.to_string(),
+                received_at: Utc::now(),
+                status: status.as_u16(),
+                replays: 0,
+                replayable: payload.is_some(),
+            },
+            headers: headers.clone(),
+            payload,
+        });
+
+        while deliveries.len() > CAPACITY {
+            deliveries.pop_front();
+        }
+    }
+
+    /// Newest first.
+    pub fn summaries(&self) -> Vec<DeliverySummary> {
+        self.deliveries
+            .lock()
+            .unwrap()
+            .iter()
+            .rev()
+            .map(|delivery| delivery.summary.clone())
+            .collect()
+    }
+
+    pub fn get(&self, id: &str) -> Option<Delivery> {
+        self.deliveries
+            .lock()
+            .unwrap()
+            .iter()
+            .find(|delivery| delivery.summary.id == id)
+            .cloned()
+    }
+
+    /// Records the outcome of a replay.
+    pub fn replayed(&self, id: &str, status: StatusCode) {
+        if let Some(delivery) = self
+            .deliveries
+            .lock()
+            .unwrap()
+            .iter_mut()
+            .find(|delivery| delivery.summary.id == id)
+        {
+            delivery.summary.status = status.as_u16();
+            delivery.summary.replays += 1;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn headers(id: &'static str) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(REQUEST_ID_HEADER, id.parse().unwrap());
+        headers.insert(
+            GITHUB_DELIVERY_HEADER,
+            format!("github-{id}").parse().unwrap(),
+        );
+        headers
+    }
+
+    #[test]
+    fn keeps_the_latest_deliveries() {
+        let deliveries = RecentDeliveries::default();
+
+        for index in 0..=CAPACITY {
+            let id = if index == 0 { "first" } else { "later" };
+            deliveries.record(&headers(id), "issues", "{}".to_string(), StatusCode::OK);
+        }
+
+        assert_eq!(deliveries.summaries().len(), CAPACITY);
+        assert!(deliveries.get("first").is_none());
+
+        deliveries.record(
+            &headers("large"),
+            "push",
+            "x".repeat(MAX_KEPT_PAYLOAD + 1),
+            StatusCode::INTERNAL_SERVER_ERROR,
+        );
+        deliveries.replayed("large", StatusCode::OK);
+
+        let latest = &deliveries.summaries()[0];
+        assert_eq!(latest.id, "large");
+        assert_eq!(latest.delivery_id, "github-large");
+        assert_eq!((latest.status, latest.replays), (200, 1));
+        assert!(!latest.replayable);
+        assert!(deliveries.get("large").unwrap().payload.is_none());
+    }
+}
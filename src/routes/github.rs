@@ -1,22 +1,32 @@
-use std::{fmt::Display, str::FromStr};
+use std::{fmt::Display, net::SocketAddr, str::FromStr};
 
 use anyhow::Context;
 use axum::{
     body::Bytes,
-    extract::State,
-    http::{HeaderMap, StatusCode},
+    extract::{ConnectInfo, Request, State},
+    http::{header, HeaderMap, HeaderName, HeaderValue, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Response},
 };
 use chrono::{DateTime, Utc};
 use hmac::{Hmac, Mac};
 use serde::Deserialize;
 use serde_json::Value;
 use serenity::all::{
-    CreateEmbed, CreateEmbedAuthor, CreateEmbedFooter, ExecuteWebhook, Http, Webhook,
+    AutoArchiveDuration, ChannelId, CreateEmbed, CreateEmbedAuthor, CreateEmbedFooter,
+    CreateMessage, CreateThread, EditThread, ExecuteWebhook, Http, Webhook,
 };
 use sha2::Sha256;
 use subtle::ConstantTimeEq;
 
-use crate::Data;
+use crate::{
+    database::gfi_threads,
+    jobs::{self, Job},
+    routes::{client_ip::client_ip, stream::Activity},
+    settings::Setting,
+    util::embeds::EmbedColor,
+    Data,
+};
 
 #[derive(Debug)]
 enum GithubEvent {
@@ -140,6 +150,12 @@ impl GithubIssuesAction {
     }
 }
 
+#[derive(Debug, Deserialize)]
+struct GithubIssueEvent {
+    issue: GithubIssue,
+    repository: GithubRepository,
+}
+
 #[derive(Debug, Deserialize)]
 struct GithubIssueLabelEvent {
     action: String,
@@ -153,14 +169,14 @@ impl GithubIssueLabelEvent {
     /// This function returns true when multiple conditions are met at the same time:
     ///
     /// The issue has to be open.
-    /// The label `good-first-issue` was added.
-    fn should_report(&self) -> bool {
+    /// One of the labels of the `gfi_labels` setting was added.
+    fn should_report(&self, labels: &[String]) -> bool {
         self.action == "labeled"
             && self.issue.state == "open"
             && self
                 .label
                 .as_ref()
-                .map_or(false, |label| label.name == "good first issue")
+                .map_or(false, |label| labels.contains(&label.name))
     }
 }
 
@@ -220,9 +236,18 @@ struct GithubRepository {
 
 type HmacSha256 = Hmac<Sha256>;
 
-pub async fn handle_gh(State(data): State<Data>, headers: HeaderMap, body: Bytes) -> StatusCode {
+pub async fn handle_gh(
+    State(data): State<Data>,
+    ConnectInfo(peer): ConnectInfo<SocketAddr>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> StatusCode {
     tracing::info!("Received POST request at /github.");
 
+    if !is_from_github(&data, &headers, peer) {
+        return StatusCode::FORBIDDEN;
+    }
+
     let body_bytes = body.as_ref();
 
     if !is_authorized(&headers, body_bytes, &data.config.github.webhook_secret) {
@@ -230,17 +255,50 @@ pub async fn handle_gh(State(data): State<Data>, headers: HeaderMap, body: Bytes
         return StatusCode::UNAUTHORIZED;
     }
 
-    if is_issues_event(&headers) {
-        match handle_issues(body_bytes, data).await {
+    let event = headers
+        .get("x-github-event")
+        .and_then(|h| h.to_str().ok())
+        .unwrap_or("unknown");
+    data.metrics
+        .webhook_events
+        .with_label_values(&[event])
+        .inc();
+
+    // the signature covers the body as it was sent, so the json is only unpacked after checking it.
+    let payload = match decode_payload(&headers, body_bytes) {
+        Ok(payload) => payload,
+        Err(e) => {
+            tracing::warn!("Failed to decode the payload at /github: {e:#}");
+            return StatusCode::BAD_REQUEST;
+        }
+    };
+
+    let status = process_payload(&data, &headers, &payload).await;
+    data.deliveries.record(&headers, event, payload, status);
+    data.activity.publish(Activity::Webhook {
+        event: event.to_string(),
+        status: status.as_u16(),
+        at: Utc::now(),
+    });
+
+    status
+}
+
+/// Handles the json of a delivery whose signature was checked, again when it is replayed through
+/// the admin api.
+pub async fn process_payload(data: &Data, headers: &HeaderMap, payload: &str) -> StatusCode {
+    if is_issues_event(headers) {
+        match handle_issues(payload.as_bytes(), data.clone()).await {
             Ok(_) => return StatusCode::OK,
             Err(e) => {
                 tracing::error!("Error processing github event: {e}");
+                data.metrics.error("webhook");
                 return StatusCode::INTERNAL_SERVER_ERROR;
             }
         }
     }
 
-    let json: Value = match serde_json::from_slice(body_bytes) {
+    let json: Value = match serde_json::from_str(payload) {
         Ok(json) => json,
         Err(_) => {
             tracing::warn!("Wrong formatted request at /github!");
@@ -252,18 +310,70 @@ pub async fn handle_gh(State(data): State<Data>, headers: HeaderMap, body: Bytes
         return StatusCode::OK;
     }
 
-    match post_to_activity_webhook(data.config.github.activity_webhook, body, headers).await {
+    // forwarding happens in the background, so deliveries aren't lost when discord is down.
+    let job = Job::ForwardGithubEvent {
+        body: payload.to_string(),
+        headers: forward_headers(headers),
+    };
+
+    match jobs::enqueue(&data.db_pool, &job).await {
         Ok(_) => {
-            tracing::info!("Forwarded github event to webhook.");
+            tracing::info!("Queued github event for forwarding.");
             StatusCode::OK
         }
         Err(e) => {
-            tracing::info!("Failed to forward github event: {}", e);
+            tracing::error!("Failed to queue github event: {e:#?}");
+            data.metrics.error("webhook");
             StatusCode::INTERNAL_SERVER_ERROR
         }
     }
 }
 
+/// Github sends either the json itself or a form with the json in its `payload` field, depending
+/// on how the webhook is set up.
+const WEBHOOK_CONTENT_TYPES: [&str; 2] = [JSON_CONTENT_TYPE, FORM_CONTENT_TYPE];
+const JSON_CONTENT_TYPE: &str = "application/json";
+const FORM_CONTENT_TYPE: &str = "application/x-www-form-urlencoded";
+
+#[derive(Debug, Deserialize)]
+struct WebhookForm {
+    payload: String,
+}
+
+/// The media type of the body without parameters like the charset.
+fn content_type(headers: &HeaderMap) -> &str {
+    headers
+        .get(header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.split(';').next())
+        .map(str::trim)
+        .unwrap_or_default()
+}
+
+/// Answers `415` before the body is read when it isn't something github sends.
+pub async fn require_webhook_content_type(request: Request, next: Next) -> Response {
+    let content_type = content_type(request.headers());
+
+    if !WEBHOOK_CONTENT_TYPES
+        .iter()
+        .any(|allowed| content_type.eq_ignore_ascii_case(allowed))
+    {
+        tracing::warn!("Rejected a request at /github with the content type {content_type:?}.");
+        return StatusCode::UNSUPPORTED_MEDIA_TYPE.into_response();
+    }
+
+    next.run(request).await
+}
+
+/// The json of the delivery, unpacked from the form when the webhook sends one.
+fn decode_payload(headers: &HeaderMap, body: &[u8]) -> anyhow::Result<String> {
+    if content_type(headers).eq_ignore_ascii_case(FORM_CONTENT_TYPE) {
+        Ok(serde_urlencoded::from_bytes::<WebhookForm>(body)?.payload)
+    } else {
+        Ok(std::str::from_utf8(body)?.to_string())
+    }
+}
+
 fn is_issues_event(headers: &HeaderMap) -> bool {
     let Some(event_header) = headers.get("x-github-event").and_then(|h| h.to_str().ok()) else {
         return false;
@@ -272,6 +382,32 @@ fn is_issues_event(headers: &HeaderMap) -> bool {
     matches!(GithubEvent::from_str(event_header), Ok(GithubEvent::Issues))
 }
 
+/// Checks the client address against the ranges github sends webhooks from, when enabled. Requests
+/// are let through while the ranges weren't fetched yet, the signature still gets checked.
+fn is_from_github(data: &Data, headers: &HeaderMap, peer: SocketAddr) -> bool {
+    if !data.config.github.verify_source_ip {
+        return true;
+    }
+
+    let ip = client_ip(headers, peer, &data.config.webserver.trusted_proxies);
+
+    match data.hook_addresses.allows(ip) {
+        Some(true) => true,
+        Some(false) => {
+            tracing::warn!(
+                "Rejected a request at /github from {ip}, which isn't a github address."
+            );
+            false
+        }
+        None => {
+            tracing::warn!(
+                "The github webhook addresses aren't loaded yet, accepting the request from {ip}."
+            );
+            true
+        }
+    }
+}
+
 fn is_authorized(headers: &HeaderMap, body: &[u8], secret: &str) -> bool {
     let header_signature = match extract_signature(headers) {
         Some(s) => s,
@@ -308,17 +444,40 @@ fn is_human_user(json: &Value) -> bool {
         .map_or(false, |user_type| user_type == "User")
 }
 
-async fn post_to_activity_webhook(
-    activity_webhook: String,
-    body: Bytes,
-    headers: HeaderMap,
+/// The headers of the delivery that are passed on to the activity webhook.
+/// The payload is forwarded as json even when github sent a form.
+fn forward_headers(headers: &HeaderMap) -> Vec<(String, String)> {
+    headers
+        .iter()
+        .filter(|(key, _)| {
+            ![
+                header::AUTHORIZATION,
+                header::HOST,
+                header::CONTENT_TYPE,
+                header::CONTENT_LENGTH,
+            ]
+            .contains(key)
+        })
+        .filter_map(|(key, value)| Some((key.to_string(), value.to_str().ok()?.to_string())))
+        .chain([(
+            header::CONTENT_TYPE.to_string(),
+            JSON_CONTENT_TYPE.to_string(),
+        )])
+        .collect()
+}
+
+pub async fn post_to_activity_webhook(
+    activity_webhook: &str,
+    body: String,
+    headers: Vec<(String, String)>,
 ) -> anyhow::Result<()> {
     let mut forward_headers = HeaderMap::new();
 
-    for (key, value) in headers.iter() {
-        if key != "authorization" && key != "host" {
-            forward_headers.insert(key.clone(), value.clone());
-        }
+    for (key, value) in headers {
+        forward_headers.insert(
+            HeaderName::from_bytes(key.as_bytes())?,
+            HeaderValue::from_str(&value)?,
+        );
     }
 
     let res = reqwest::Client::new()
@@ -336,19 +495,20 @@ async fn post_to_activity_webhook(
 }
 
 async fn handle_issues(body: &[u8], data: Data) -> anyhow::Result<()> {
-    if !get_issue_action(body)?.is_label() {
+    let action = get_issue_action(body)?;
+
+    if matches!(action, GithubIssuesAction::Closed) {
+        return archive_good_first_issue_thread(serde_json::from_slice(body)?, &data).await;
+    }
+
+    if !action.is_label() {
         return Ok(());
     }
 
     let label_event: GithubIssueLabelEvent = serde_json::from_slice(body)?;
 
-    if label_event.should_report() {
-        post_good_first_issue(
-            label_event,
-            &data.config.github.issues_webhook,
-            &data.config.bot.token,
-        )
-        .await?
+    if label_event.should_report(&data.settings.labels(Setting::GfiLabels)) {
+        post_good_first_issue(label_event, &data).await?
     }
 
     Ok(())
@@ -364,15 +524,30 @@ fn get_issue_action(body: &[u8]) -> anyhow::Result<GithubIssuesAction> {
     )
 }
 
+/// Posts the alert and starts a thread on it, so claiming and coordinating the issue doesn't
+/// clutter the channel.
 async fn post_good_first_issue(
     label_event: GithubIssueLabelEvent,
-    issues_webhook_url: &str,
-    bot_token: &str,
+    data: &Data,
 ) -> anyhow::Result<()> {
-    let http = Http::new(bot_token);
-    let webhook = Webhook::from_url(&http, issues_webhook_url).await?;
+    let http = Http::new(&data.config.bot.token);
+    let webhook = Webhook::from_url(&http, &data.config.github.issues_webhook).await?;
 
-    let description = format!("**{}** just added label `good-first-issue` to [issue #{}]({}) ({}) in the {} repository. This is a good chance to get your first contribution!",
+    let repository = label_event.repository.full_name.clone();
+    let issue_number = label_event.issue.number;
+
+    // thread names are limited to 100 characters.
+    let thread_name = format!("#{issue_number} {}", label_event.issue.title)
+        .chars()
+        .take(100)
+        .collect::<String>();
+
+    let label = label_event
+        .label
+        .as_ref()
+        .map_or("good first issue", |label| label.name.as_str());
+
+    let description = format!("**{}** just added label `{label}` to [issue #{}]({}) ({}) in the {} repository. This is a good chance to get your first contribution!",
         label_event.sender.login,
         label_event.issue.number,
         label_event.issue.html_url,
@@ -387,17 +562,70 @@ async fn post_good_first_issue(
     };
 
     let embed = CreateEmbed::new()
-        .color(6_530_042) // biome logo color
+        .color(EmbedColor::Biome)
         .author(embed_author)
         .title("New good first issue alert")
         .description(description)
         .footer(CreateEmbedFooter::new("Biome Issue Tracker"))
         .timestamp(chrono::Utc::now());
 
-    webhook
-        .execute(&http, false, ExecuteWebhook::default().embed(embed))
+    let message = webhook
+        .execute(&http, true, ExecuteWebhook::default().embed(embed))
+        .await?
+        .context("Discord didn't return the posted good first issue alert")?;
+
+    let thread = message
+        .channel_id
+        .create_thread_from_message(
+            &http,
+            message.id,
+            CreateThread::new(thread_name).auto_archive_duration(AutoArchiveDuration::OneWeek),
+        )
+        .await?;
+
+    gfi_threads::insert(
+        &data.db_pool,
+        &repository,
+        issue_number,
+        thread.id.get() as i64,
+    )
+    .await?;
+
+    Ok(())
+}
+
+async fn archive_good_first_issue_thread(
+    issue_event: GithubIssueEvent,
+    data: &Data,
+) -> anyhow::Result<()> {
+    let repository = &issue_event.repository.full_name;
+    let issue_number = issue_event.issue.number;
+
+    let Some(thread_id) = gfi_threads::thread_id(&data.db_pool, repository, issue_number).await?
+    else {
+        return Ok(());
+    };
+
+    let http = Http::new(&data.config.bot.token);
+    let thread_id = ChannelId::new(thread_id as u64);
+
+    thread_id
+        .send_message(
+            &http,
+            CreateMessage::new().content(format!(
+                "[Issue #{issue_number}]({}) was closed, archiving this thread.",
+                issue_event.issue.html_url
+            )),
+        )
+        .await?;
+
+    thread_id
+        .edit_thread(&http, EditThread::new().archived(true))
         .await?;
 
+    gfi_threads::delete(&data.db_pool, repository, issue_number).await?;
+    tracing::info!("Archived the good first issue thread of {repository}#{issue_number}.");
+
     Ok(())
 }
 
@@ -418,4 +646,29 @@ mod tests {
 
         assert_eq!(result, expected);
     }
+
+    #[test]
+    fn decodes_form_payloads() {
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            header::CONTENT_TYPE,
+            HeaderValue::from_static("application/x-www-form-urlencoded"),
+        );
+
+        assert_eq!(
+            decode_payload(&headers, b"payload=%7B%22action%22%3A%22opened%22%7D").unwrap(),
+            r#"{"action":"opened"}"#
+        );
+        assert!(decode_payload(&headers, b"action=opened").is_err());
+
+        headers.insert(
+            header::CONTENT_TYPE,
+            HeaderValue::from_static("application/json; charset=utf-8"),
+        );
+
+        assert_eq!(
+            decode_payload(&headers, br#"{"action":"opened"}"#).unwrap(),
+            r#"{"action":"opened"}"#
+        );
+    }
 }
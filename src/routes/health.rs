@@ -0,0 +1,97 @@
+use std::time::Duration;
+
+use axum::{extract::State, http::StatusCode, Json};
+use poise::serenity_prelude as serenity;
+use serde::Serialize;
+use serenity::ConnectionStage;
+
+use crate::Data;
+
+/// A probe shouldn't hang on an unreachable database.
+const DATABASE_TIMEOUT: Duration = Duration::from_secs(5);
+
+#[derive(Debug, Serialize)]
+pub struct Liveness {
+    status: &'static str,
+}
+
+#[derive(Debug, Serialize)]
+pub struct Readiness {
+    ready: bool,
+}
+
+/// Liveness probe, answers as long as the webserver runs.
+pub async fn handle_health() -> Json<Liveness> {
+    Json(Liveness { status: "ok" })
+}
+
+/// Readiness probe, `503` unless the database answers, every shard is connected to the gateway and
+/// the config is valid. The probe is public, so why a check failed is only logged.
+pub async fn handle_ready(State(data): State<Data>) -> (StatusCode, Json<Readiness>) {
+    let database = check_database(&data).await;
+    let discord = check_discord(&data).await;
+    let config = check_config(&data);
+
+    let ready = database && discord && config;
+    let status = if ready {
+        StatusCode::OK
+    } else {
+        StatusCode::SERVICE_UNAVAILABLE
+    };
+
+    (status, Json(Readiness { ready }))
+}
+
+async fn check_database(data: &Data) -> bool {
+    let query = sqlx::query("SELECT 1").execute(&data.db_pool);
+
+    match tokio::time::timeout(DATABASE_TIMEOUT, query).await {
+        Ok(Ok(_)) => true,
+        Ok(Err(e)) => {
+            tracing::warn!("Readiness check of the database failed: {e}");
+            false
+        }
+        Err(_) => {
+            tracing::warn!("Readiness check of the database timed out.");
+            false
+        }
+    }
+}
+
+/// Reads the statuses the shard supervisor records, so they lag behind by up to its interval.
+async fn check_discord(data: &Data) -> bool {
+    if data.shards.all_connected().await {
+        return true;
+    }
+
+    let statuses = data.shards.snapshot().await;
+    let connected = statuses
+        .values()
+        .filter(|status| matches!(status.stage, ConnectionStage::Connected))
+        .count();
+
+    tracing::warn!(
+        "Readiness check of discord failed: {connected}/{} shards connected.",
+        statuses.len()
+    );
+
+    false
+}
+
+fn check_config(data: &Data) -> bool {
+    let problems = data.config.validate();
+
+    if !problems.is_empty() {
+        tracing::warn!(
+            "Readiness check of the config failed: {}",
+            problems
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>()
+                .join(", ")
+        );
+        return false;
+    }
+
+    true
+}
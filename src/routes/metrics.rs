@@ -0,0 +1,34 @@
+use axum::{
+    extract::State,
+    http::{header, StatusCode},
+    response::{IntoResponse, Response},
+};
+
+use crate::Data;
+
+/// The Prometheus text format, version 0.0.4.
+const CONTENT_TYPE: &str = "text/plain; version=0.0.4";
+
+/// Prometheus scrape endpoint. The gauges of the shard latencies are updated here, so they are as
+/// fresh as the scrape.
+pub async fn handle_metrics(State(data): State<Data>) -> Response {
+    let metrics = &data.metrics;
+
+    metrics.heartbeat_latency.reset();
+    for (shard_id, status) in data.shards.snapshot().await {
+        if let Some(latency) = status.latency {
+            metrics
+                .heartbeat_latency
+                .with_label_values(&[&shard_id.to_string()])
+                .set(latency.as_secs_f64());
+        }
+    }
+
+    match metrics.encode() {
+        Ok(text) => ([(header::CONTENT_TYPE, CONTENT_TYPE)], text).into_response(),
+        Err(e) => {
+            tracing::error!("Failed to encode the metrics: {e:#?}");
+            StatusCode::INTERNAL_SERVER_ERROR.into_response()
+        }
+    }
+}
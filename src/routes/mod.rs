@@ -1,2 +1,12 @@
+pub mod admin;
+pub mod client_ip;
+pub mod deliveries;
 pub mod github;
+pub mod health;
+pub mod metrics;
 pub mod not_found;
+pub mod oauth;
+pub mod rate_limit;
+pub mod source_ip;
+pub mod stream;
+pub mod trace;
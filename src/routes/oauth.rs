@@ -0,0 +1,377 @@
+use std::{
+    collections::HashMap,
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+use anyhow::Context;
+use axum::{
+    extract::{Query, State},
+    http::{
+        header::{COOKIE, SET_COOKIE},
+        HeaderMap, StatusCode,
+    },
+    response::{IntoResponse, Redirect, Response},
+};
+use reqwest::{header::ACCEPT, Url};
+use serde::Deserialize;
+use serde_json::json;
+use serenity::all::{Http, UserId};
+use subtle::ConstantTimeEq;
+use uuid::Uuid;
+
+use crate::{config::GithubOAuthConfig, contributors, database::github_links, sponsors, Data};
+
+/// How long the link posted by `/github link` stays valid.
+const STATE_TTL: Duration = Duration::from_secs(15 * 60);
+/// Holds the token of the browser that opened the link, the callback has to come from it.
+const BROWSER_COOKIE: &str = "biomebot_oauth";
+
+#[derive(Debug)]
+struct PendingLink {
+    user_id: UserId,
+    expires_at: Instant,
+    /// Set once the link was opened.
+    browser: Option<String>,
+}
+
+/// The OAuth flows started with `/github link`, keyed by their state. A state can only finish
+/// once, and only in the browser that opened the link.
+#[derive(Debug, Default)]
+pub struct OAuthStates {
+    pending: Mutex<HashMap<String, PendingLink>>,
+}
+
+impl OAuthStates {
+    /// A new random state for the user.
+    pub fn issue(&self, user_id: UserId, now: Instant) -> String {
+        let state = random_token();
+
+        let mut pending = self.pending.lock().unwrap();
+        pending.retain(|_, link| link.expires_at > now);
+        pending.insert(
+            state.clone(),
+            PendingLink {
+                user_id,
+                expires_at: now + STATE_TTL,
+                browser: None,
+            },
+        );
+
+        state
+    }
+
+    /// Binds the state to the browser the first time the link is opened and returns the token
+    /// of that browser. `None` if the state is unknown, expired or bound to another browser.
+    pub fn open(&self, state: &str, browser: Option<&str>, now: Instant) -> Option<String> {
+        let mut pending = self.pending.lock().unwrap();
+        let link = pending
+            .get_mut(state)
+            .filter(|link| link.expires_at > now)?;
+
+        match (&link.browser, browser) {
+            (None, _) => Some(link.browser.insert(random_token()).clone()),
+            (Some(bound), Some(browser)) if tokens_match(bound, browser) => Some(bound.clone()),
+            (Some(_), _) => None,
+        }
+    }
+
+    /// Consumes the state, whether the browser matches or not. The discord user who started the
+    /// flow, `None` if the state is unknown, expired or was opened in another browser.
+    pub fn finish(&self, state: &str, browser: Option<&str>, now: Instant) -> Option<UserId> {
+        let link = self.pending.lock().unwrap().remove(state)?;
+
+        if link.expires_at <= now {
+            return None;
+        }
+
+        match (link.browser, browser) {
+            (Some(bound), Some(browser)) if tokens_match(&bound, browser) => Some(link.user_id),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct StartQuery {
+    state: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CallbackQuery {
+    state: String,
+    /// Missing when the user didn't authorize the app.
+    code: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct AccessToken {
+    access_token: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct AuthenticatedUser {
+    id: u64,
+    login: String,
+}
+
+/// Sends the member to github to authorize the OAuth app.
+pub async fn handle_start(
+    State(data): State<Data>,
+    headers: HeaderMap,
+    Query(query): Query<StartQuery>,
+) -> Response {
+    let Some(config) = &data.config.github.oauth else {
+        return (
+            StatusCode::NOT_FOUND,
+            "Linking github accounts is disabled.",
+        )
+            .into_response();
+    };
+
+    let browser = browser_cookie(&headers);
+    let Some(browser) = data
+        .oauth_states
+        .open(&query.state, browser.as_deref(), Instant::now())
+    else {
+        return (
+            StatusCode::BAD_REQUEST,
+            "This link expired, use /github link again.",
+        )
+            .into_response();
+    };
+
+    match Url::parse_with_params(
+        "https://github.com/login/oauth/authorize",
+        &[
+            ("client_id", config.client_id.as_str()),
+            ("redirect_uri", callback_url(config).as_str()),
+            ("state", query.state.as_str()),
+            ("allow_signup", "false"),
+        ],
+    ) {
+        Ok(url) => (
+            [(
+                SET_COOKIE,
+                format!(
+                    "{BROWSER_COOKIE}={browser}; Path=/oauth/github; Max-Age={}; HttpOnly; Secure; SameSite=Lax",
+                    STATE_TTL.as_secs()
+                ),
+            )],
+            Redirect::to(url.as_str()),
+        )
+            .into_response(),
+        Err(e) => {
+            tracing::error!("Failed to build the github authorize url: {e}");
+            StatusCode::INTERNAL_SERVER_ERROR.into_response()
+        }
+    }
+}
+
+/// Github redirects here after the member authorized the OAuth app.
+pub async fn handle_callback(
+    State(data): State<Data>,
+    headers: HeaderMap,
+    Query(query): Query<CallbackQuery>,
+) -> (StatusCode, String) {
+    let Some(config) = &data.config.github.oauth else {
+        return (
+            StatusCode::NOT_FOUND,
+            "Linking github accounts is disabled.".to_string(),
+        );
+    };
+
+    let browser = browser_cookie(&headers);
+    let Some(user_id) = data
+        .oauth_states
+        .finish(&query.state, browser.as_deref(), Instant::now())
+    else {
+        return (
+            StatusCode::BAD_REQUEST,
+            "This link expired, use /github link again.".to_string(),
+        );
+    };
+
+    let Some(code) = query.code else {
+        return (
+            StatusCode::BAD_REQUEST,
+            "Linking was cancelled, use /github link again to retry.".to_string(),
+        );
+    };
+
+    match link_account(&data, config, user_id, &code).await {
+        Ok(Some(login)) => (
+            StatusCode::OK,
+            format!("Linked the github account {login}, you can close this page."),
+        ),
+        Ok(None) => (
+            StatusCode::CONFLICT,
+            "This github account is already linked to another member, unlink it there first."
+                .to_string(),
+        ),
+        Err(e) => {
+            tracing::error!("Failed to link a github account: {e:#?}");
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "Failed to link the github account, try again later.".to_string(),
+            )
+        }
+    }
+}
+
+/// The page members open to link their github account, with a new state for the user.
+pub fn link_url(data: &Data, config: &GithubOAuthConfig, user_id: UserId) -> String {
+    format!(
+        "{}/oauth/github?state={}",
+        config.public_url.trim_end_matches('/'),
+        data.oauth_states.issue(user_id, Instant::now())
+    )
+}
+
+fn callback_url(config: &GithubOAuthConfig) -> String {
+    format!(
+        "{}/oauth/github/callback",
+        config.public_url.trim_end_matches('/')
+    )
+}
+
+/// Exchanges the code for a token, only used once to find out who the member is on github. The
+/// linked login, `None` if the github account is already linked to another member.
+async fn link_account(
+    data: &Data,
+    config: &GithubOAuthConfig,
+    user_id: UserId,
+    code: &str,
+) -> anyhow::Result<Option<String>> {
+    // not the shared github client, it sends the token of the bot with every request.
+    let http = reqwest::Client::builder().user_agent("biomebot").build()?;
+
+    let token = http
+        .post("https://github.com/login/oauth/access_token")
+        .header(ACCEPT, "application/json")
+        .json(&json!({
+            "client_id": config.client_id,
+            "client_secret": config.client_secret,
+            "code": code,
+            "redirect_uri": callback_url(config),
+        }))
+        .send()
+        .await?
+        .error_for_status()?
+        .json::<AccessToken>()
+        .await
+        .context("Github didn't grant an access token")?;
+
+    let user = http
+        .get("https://api.github.com/user")
+        .header(ACCEPT, "application/vnd.github+json")
+        .bearer_auth(&token.access_token)
+        .send()
+        .await?
+        .error_for_status()?
+        .json::<AuthenticatedUser>()
+        .await?;
+
+    let db_user_id = user_id.get() as i64;
+    if !github_links::upsert(&data.db_pool, db_user_id, user.id as i64, &user.login).await? {
+        tracing::info!(
+            "{user_id} tried to link the github account {}, which is linked to another member.",
+            user.login
+        );
+        return Ok(None);
+    }
+
+    tracing::info!("{user_id} linked the github account {}.", user.login);
+
+    // the link is saved either way, the next scheduled sync catches up on failures.
+    if let Some(link) = github_links::get(&data.db_pool, db_user_id).await? {
+        let http = Http::new(&data.config.bot.token);
+
+        if let Err(e) = contributors::sync_member(&http, data, &link).await {
+            tracing::warn!("Failed to sync the contributor role of {user_id}: {e:#}");
+        }
+
+        if let Err(e) = sponsors::sync_linked(&http, data, &link).await {
+            tracing::warn!("Failed to sync the sponsor roles of {user_id}: {e:#}");
+        }
+    }
+
+    Ok(Some(user.login))
+}
+
+/// 122 random bits from the OS, used for states and browser tokens.
+fn random_token() -> String {
+    Uuid::new_v4().simple().to_string()
+}
+
+fn tokens_match(a: &str, b: &str) -> bool {
+    a.as_bytes().ct_eq(b.as_bytes()).into()
+}
+
+fn browser_cookie(headers: &HeaderMap) -> Option<String> {
+    headers
+        .get_all(COOKIE)
+        .iter()
+        .filter_map(|header| header.to_str().ok())
+        .flat_map(|header| header.split(';'))
+        .find_map(|cookie| {
+            let (name, value) = cookie.trim().split_once('=')?;
+            (name == BROWSER_COOKIE).then(|| value.to_string())
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn finishes_state_once_in_the_same_browser() {
+        let states = OAuthStates::default();
+        let now = Instant::now();
+        let state = states.issue(UserId::new(42), now);
+
+        let browser = states.open(&state, None, now).unwrap();
+        assert_eq!(
+            states.open(&state, Some(&browser), now),
+            Some(browser.clone())
+        );
+
+        assert_eq!(
+            states.finish(&state, Some(&browser), now),
+            Some(UserId::new(42))
+        );
+        assert_eq!(states.finish(&state, Some(&browser), now), None);
+    }
+
+    #[test]
+    fn rejects_other_browsers() {
+        let states = OAuthStates::default();
+        let now = Instant::now();
+        let state = states.issue(UserId::new(42), now);
+
+        states.open(&state, None, now).unwrap();
+        assert_eq!(states.open(&state, None, now), None);
+        assert_eq!(states.open(&state, Some("other"), now), None);
+
+        assert_eq!(states.finish(&state, Some("other"), now), None);
+    }
+
+    #[test]
+    fn rejects_unknown_and_expired_states() {
+        let states = OAuthStates::default();
+        let now = Instant::now();
+        let state = states.issue(UserId::new(42), now);
+
+        assert_eq!(states.open("42.1000.abc", None, now), None);
+        assert_eq!(states.open(&state, None, now + STATE_TTL), None);
+    }
+
+    #[test]
+    fn reads_the_browser_cookie() {
+        let mut headers = HeaderMap::new();
+        headers.insert(COOKIE, "theme=dark; biomebot_oauth=abc".parse().unwrap());
+
+        assert_eq!(browser_cookie(&headers), Some("abc".to_string()));
+        assert_eq!(browser_cookie(&HeaderMap::new()), None);
+    }
+}
@@ -0,0 +1,206 @@
+use std::{
+    collections::HashMap,
+    net::{IpAddr, Ipv6Addr, SocketAddr},
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+use axum::{
+    extract::{ConnectInfo, Request, State},
+    http::{header, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+
+use crate::{config::RateLimitConfig, routes::client_ip::client_ip, Data};
+
+/// How often full buckets are dropped, they behave like new ones.
+const PRUNE_INTERVAL: Duration = Duration::from_secs(60);
+/// New clients are limited once this many are tracked, until the next prune makes room. Bounds the
+/// memory a flood of addresses can take.
+const MAX_CLIENTS: usize = 65_536;
+
+#[derive(Debug, Clone, Copy)]
+struct Bucket {
+    tokens: f64,
+    refilled_at: Instant,
+}
+
+#[derive(Debug)]
+struct Buckets {
+    by_client: HashMap<IpAddr, Bucket>,
+    pruned_at: Instant,
+}
+
+/// A token bucket per client address. IPv6 clients usually get a whole /64, so they share one.
+#[derive(Debug)]
+pub struct RateLimiter {
+    capacity: f64,
+    refill_per_sec: f64,
+    max_clients: usize,
+    buckets: Mutex<Buckets>,
+}
+
+impl RateLimiter {
+    pub fn new(config: &RateLimitConfig) -> Self {
+        Self {
+            capacity: f64::from(config.burst),
+            refill_per_sec: f64::from(config.per_minute) / 60.0,
+            max_clients: MAX_CLIENTS,
+            buckets: Mutex::new(Buckets {
+                by_client: HashMap::new(),
+                pruned_at: Instant::now(),
+            }),
+        }
+    }
+
+    /// Takes a token from the bucket of the address, or returns how long until the next one.
+    pub fn acquire(&self, ip: IpAddr, now: Instant) -> Result<(), Duration> {
+        let client = client_key(ip);
+        let mut buckets = self.buckets.lock().unwrap();
+        let since_prune = now.saturating_duration_since(buckets.pruned_at);
+
+        if since_prune >= PRUNE_INTERVAL {
+            buckets
+                .by_client
+                .retain(|_, bucket| self.refill(*bucket, now).tokens < self.capacity);
+            buckets.pruned_at = now;
+        } else if buckets.by_client.len() >= self.max_clients
+            && !buckets.by_client.contains_key(&client)
+        {
+            return Err(PRUNE_INTERVAL - since_prune);
+        }
+
+        let bucket = buckets.by_client.entry(client).or_insert(Bucket {
+            tokens: self.capacity,
+            refilled_at: now,
+        });
+        *bucket = self.refill(*bucket, now);
+
+        if bucket.tokens >= 1.0 {
+            bucket.tokens -= 1.0;
+            Ok(())
+        } else {
+            Err(Duration::from_secs_f64(
+                (1.0 - bucket.tokens) / self.refill_per_sec,
+            ))
+        }
+    }
+
+    fn refill(&self, bucket: Bucket, now: Instant) -> Bucket {
+        let elapsed = now.saturating_duration_since(bucket.refilled_at);
+
+        Bucket {
+            tokens: (bucket.tokens + elapsed.as_secs_f64() * self.refill_per_sec)
+                .min(self.capacity),
+            refilled_at: now,
+        }
+    }
+}
+
+/// The address a client is tracked by, the /64 network for IPv6.
+fn client_key(ip: IpAddr) -> IpAddr {
+    match ip.to_canonical() {
+        IpAddr::V6(ip) => IpAddr::V6(Ipv6Addr::from(u128::from(ip) & (u128::MAX << 64))),
+        ip => ip,
+    }
+}
+
+/// Answers `429` once the client used up its requests.
+pub async fn limit(
+    State(data): State<Data>,
+    ConnectInfo(peer): ConnectInfo<SocketAddr>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let ip = client_ip(
+        request.headers(),
+        peer,
+        &data.config.webserver.trusted_proxies,
+    );
+
+    match data.rate_limiter.acquire(ip, Instant::now()) {
+        Ok(()) => next.run(request).await,
+        Err(retry_after) => {
+            tracing::warn!("Rate limited a request from {ip}.");
+
+            (
+                StatusCode::TOO_MANY_REQUESTS,
+                [(
+                    header::RETRY_AFTER,
+                    (retry_after.as_secs_f64().ceil() as u64).max(1).to_string(),
+                )],
+            )
+                .into_response()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn limiter(burst: u32, per_minute: u32) -> RateLimiter {
+        RateLimiter::new(&RateLimitConfig { burst, per_minute })
+    }
+
+    #[test]
+    fn limits_bursts() {
+        let limiter = limiter(2, 60);
+        let ip = IpAddr::from([127, 0, 0, 1]);
+        let now = Instant::now();
+
+        assert!(limiter.acquire(ip, now).is_ok());
+        assert!(limiter.acquire(ip, now).is_ok());
+        assert_eq!(limiter.acquire(ip, now), Err(Duration::from_secs(1)));
+        assert!(limiter.acquire(IpAddr::from([127, 0, 0, 2]), now).is_ok());
+        assert!(limiter.acquire(ip, now + Duration::from_secs(1)).is_ok());
+        assert!(limiter.acquire(ip, now + Duration::from_secs(1)).is_err());
+    }
+
+    #[test]
+    fn refills_up_to_the_burst() {
+        let limiter = limiter(2, 60);
+        let ip = IpAddr::from([127, 0, 0, 1]);
+        let now = Instant::now();
+
+        assert!(limiter.acquire(ip, now).is_ok());
+
+        let later = now + Duration::from_secs(60);
+        assert!(limiter.acquire(ip, later).is_ok());
+        assert!(limiter.acquire(ip, later).is_ok());
+        assert!(limiter.acquire(ip, later).is_err());
+    }
+
+    #[test]
+    fn shares_buckets_within_ipv6_networks() {
+        let limiter = limiter(1, 60);
+        let now = Instant::now();
+        let ip = |address: &str| address.parse::<IpAddr>().unwrap();
+
+        assert!(limiter.acquire(ip("2001:db8::1"), now).is_ok());
+        assert!(limiter.acquire(ip("2001:db8::ffff:2"), now).is_err());
+        assert!(limiter.acquire(ip("2001:db8:0:1::1"), now).is_ok());
+
+        assert!(limiter.acquire(ip("::ffff:192.0.2.1"), now).is_ok());
+        assert!(limiter.acquire(ip("192.0.2.1"), now).is_err());
+    }
+
+    #[test]
+    fn caps_tracked_clients() {
+        let mut limiter = limiter(2, 60);
+        limiter.max_clients = 2;
+        let now = Instant::now();
+        let ip = |last: u8| IpAddr::from([192, 0, 2, last]);
+
+        assert!(limiter.acquire(ip(1), now).is_ok());
+        assert!(limiter.acquire(ip(2), now).is_ok());
+        assert!(limiter.acquire(ip(3), now).is_err());
+        assert!(limiter.acquire(ip(1), now).is_ok());
+
+        // the buckets refilled by the next prune and are dropped.
+        let later = now + PRUNE_INTERVAL;
+        assert!(limiter.acquire(ip(3), later).is_ok());
+        assert_eq!(limiter.buckets.lock().unwrap().by_client.len(), 1);
+    }
+}
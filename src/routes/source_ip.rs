@@ -0,0 +1,50 @@
+use std::{
+    net::IpAddr,
+    sync::{Arc, RwLock},
+};
+
+use crate::util::ip_range::IpRange;
+
+/// The ranges github sends webhooks from, see `tasks::hook_addresses`.
+#[derive(Debug, Default)]
+pub struct HookAddresses {
+    ranges: RwLock<Option<Arc<Vec<IpRange>>>>,
+}
+
+impl HookAddresses {
+    pub fn replace(&self, ranges: Vec<IpRange>) {
+        *self.ranges.write().unwrap() = Some(Arc::new(ranges));
+    }
+
+    /// Whether github sends webhooks from the address, `None` until the ranges were fetched once.
+    pub fn allows(&self, ip: IpAddr) -> Option<bool> {
+        let ranges = self.ranges.read().unwrap().clone()?;
+
+        Some(ranges.iter().any(|range| range.contains(ip)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn allows_hook_addresses() {
+        let hooks = HookAddresses::default();
+        assert_eq!(hooks.allows(IpAddr::from([192, 30, 252, 1])), None);
+
+        hooks.replace(vec![
+            "192.30.252.0/22".parse().unwrap(),
+            "2a0a:a440::/29".parse().unwrap(),
+        ]);
+
+        assert_eq!(hooks.allows(IpAddr::from([192, 30, 255, 255])), Some(true));
+        assert_eq!(hooks.allows(IpAddr::from([192, 31, 0, 1])), Some(false));
+        assert_eq!(
+            hooks.allows("::ffff:192.30.252.7".parse().unwrap()),
+            Some(true)
+        );
+        assert_eq!(hooks.allows("2a0a:a447::1".parse().unwrap()), Some(true));
+        assert_eq!(hooks.allows("2a0a:a448::1".parse().unwrap()), Some(false));
+    }
+}
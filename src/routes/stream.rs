@@ -0,0 +1,140 @@
+use std::{convert::Infallible, sync::Mutex};
+
+use axum::{
+    extract::State,
+    http::StatusCode,
+    response::sse::{Event, KeepAlive, Sse},
+};
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use tokio::sync::broadcast;
+use tokio_stream::{wrappers::BroadcastStream, Stream, StreamExt};
+
+use crate::Data;
+
+/// Events a slow client hasn't read yet before it misses some.
+const BUFFERED_EVENTS: usize = 100;
+/// Every open stream holds a connection, dashboards are only a handful.
+const MAX_SUBSCRIBERS: usize = 16;
+
+/// Something the bot did, without anything that identifies a user or repeats what they wrote.
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Activity {
+    /// A github delivery with a valid signature was handled.
+    Webhook {
+        event: String,
+        status: u16,
+        at: DateTime<Utc>,
+    },
+    /// Somebody used a command.
+    Command { command: String, at: DateTime<Utc> },
+}
+
+/// Sends the activity to everyone connected to `/admin/stream`.
+#[derive(Debug)]
+pub struct ActivityStream {
+    /// Taken on shutdown, which ends every stream so the webserver can stop.
+    sender: Mutex<Option<broadcast::Sender<Activity>>>,
+}
+
+impl Default for ActivityStream {
+    fn default() -> Self {
+        Self {
+            sender: Mutex::new(Some(broadcast::channel(BUFFERED_EVENTS).0)),
+        }
+    }
+}
+
+impl ActivityStream {
+    pub fn publish(&self, activity: Activity) {
+        if let Some(sender) = &*self.sender.lock().unwrap() {
+            // fails when nobody is connected.
+            let _ = sender.send(activity);
+        }
+    }
+
+    /// `None` once closed or while the most subscribers are connected.
+    fn subscribe(&self) -> Option<broadcast::Receiver<Activity>> {
+        let sender = self.sender.lock().unwrap();
+        let sender = sender.as_ref()?;
+
+        (sender.receiver_count() < MAX_SUBSCRIBERS).then(|| sender.subscribe())
+    }
+
+    pub fn close(&self) {
+        self.sender.lock().unwrap().take();
+    }
+}
+
+/// Server-sent events with a json object for every [`Activity`], for live dashboards. Events a
+/// client was too slow for are skipped.
+pub async fn handle_stream(
+    State(data): State<Data>,
+) -> Result<Sse<impl Stream<Item = Result<Event, Infallible>>>, (StatusCode, &'static str)> {
+    let Some(receiver) = data.activity.subscribe() else {
+        return Err((
+            StatusCode::SERVICE_UNAVAILABLE,
+            "Too many streams are open, try again later.",
+        ));
+    };
+
+    let events = BroadcastStream::new(receiver).filter_map(|activity| {
+        let event = Event::default().json_data(activity.ok()?).ok()?;
+        Some(Ok(event))
+    });
+
+    Ok(Sse::new(events).keep_alive(KeepAlive::default()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn serializes_activity() {
+        let at = DateTime::from_timestamp(0, 0).unwrap();
+
+        assert_eq!(
+            serde_json::to_value(Activity::Command {
+                command: "tag".to_string(),
+                at
+            })
+            .unwrap(),
+            serde_json::json!({ "type": "command", "command": "tag", "at": "1970-01-01T00:00:00Z" })
+        );
+    }
+
+    #[test]
+    fn ends_streams_on_close() {
+        let stream = ActivityStream::default();
+        let mut receiver = stream.subscribe().unwrap();
+
+        stream.publish(Activity::Webhook {
+            event: "issues".to_string(),
+            status: 200,
+            at: Utc::now(),
+        });
+        stream.close();
+
+        assert!(receiver.try_recv().is_ok());
+        assert!(matches!(
+            receiver.try_recv(),
+            Err(broadcast::error::TryRecvError::Closed)
+        ));
+        assert!(stream.subscribe().is_none());
+    }
+
+    #[test]
+    fn limits_subscribers() {
+        let stream = ActivityStream::default();
+        let mut receivers = (0..MAX_SUBSCRIBERS)
+            .map(|_| stream.subscribe().unwrap())
+            .collect::<Vec<_>>();
+
+        assert!(stream.subscribe().is_none());
+
+        receivers.pop();
+        assert!(stream.subscribe().is_some());
+    }
+}
@@ -0,0 +1,78 @@
+use std::{net::SocketAddr, sync::Arc, time::Duration};
+
+use axum::{
+    body::Body,
+    extract::ConnectInfo,
+    http::{Request, Response},
+};
+use tower_http::trace::MakeSpan;
+use tracing::Span;
+
+use crate::{routes::client_ip::client_ip, util::ip_range::IpRange};
+
+/// The header every response echoes the request id in.
+pub const REQUEST_ID_HEADER: &str = "x-request-id";
+
+/// Drops the request id a client sent, so every request gets a fresh id and nobody can make
+/// their request look like another one in the logs or the delivery log.
+pub async fn remove_request_id(mut request: Request<Body>) -> Request<Body> {
+    request.headers_mut().remove(REQUEST_ID_HEADER);
+    request
+}
+
+/// Every log line while a request is handled carries its id, client address, method and path.
+/// The query is left out, the oauth callback gets its code through it.
+#[derive(Debug, Clone)]
+pub struct MakeRequestSpan {
+    trusted_proxies: Arc<[IpRange]>,
+}
+
+impl MakeRequestSpan {
+    pub fn new(trusted_proxies: &[IpRange]) -> Self {
+        Self {
+            trusted_proxies: trusted_proxies.into(),
+        }
+    }
+}
+
+impl<B> MakeSpan<B> for MakeRequestSpan {
+    fn make_span(&mut self, request: &Request<B>) -> Span {
+        let request_id = request
+            .headers()
+            .get(REQUEST_ID_HEADER)
+            .and_then(|id| id.to_str().ok())
+            .unwrap_or_default();
+        let client = request
+            .extensions()
+            .get::<ConnectInfo<SocketAddr>>()
+            .map(|ConnectInfo(peer)| {
+                client_ip(request.headers(), *peer, &self.trusted_proxies).to_string()
+            })
+            .unwrap_or_default();
+
+        tracing::info_span!(
+            "request",
+            request_id,
+            client,
+            method = %request.method(),
+            path = request.uri().path(),
+        )
+    }
+}
+
+pub fn on_response<B>(response: &Response<B>, latency: Duration, _: &Span) {
+    tracing::info!(
+        status = response.status().as_u16(),
+        latency_ms = latency.as_millis() as u64,
+        "Answered request."
+    );
+}
+
+/// The request id among headers stored with a queued job, so its failures can be traced back to
+/// the request.
+pub fn queued_request_id(headers: &[(String, String)]) -> Option<&str> {
+    headers
+        .iter()
+        .find(|(key, _)| key == REQUEST_ID_HEADER)
+        .map(|(_, value)| value.as_str())
+}
@@ -0,0 +1,199 @@
+use anyhow::{bail, Context};
+use chrono::{DateTime, Datelike, Duration, DurationRound, NaiveTime, Timelike, Utc};
+
+/// A standard five field cron expression (`minute hour day-of-month month day-of-week`) in UTC.
+///
+/// Fields support `*`, single values, ranges (`1-5`), steps (`*/15`, `0-30/10`) and lists of
+/// those (`1,15`). Sunday is both `0` and `7` in the day-of-week field.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cron {
+    minutes: u64,
+    hours: u64,
+    days_of_month: u64,
+    months: u64,
+    days_of_week: u64,
+    /// Like cron, a day matches either field when both day fields are restricted.
+    day_of_month_restricted: bool,
+    day_of_week_restricted: bool,
+}
+
+impl Cron {
+    pub fn parse(expression: &str) -> anyhow::Result<Self> {
+        let fields = expression.split_whitespace().collect::<Vec<_>>();
+
+        let [minute, hour, day_of_month, month, day_of_week] = fields[..] else {
+            bail!("Expected 5 fields in cron expression `{expression}`");
+        };
+
+        let mut days_of_week = parse_field(day_of_week, 0, 7).context("Invalid day of week")?;
+
+        // 7 is another way to write sunday.
+        if days_of_week & (1 << 7) != 0 {
+            days_of_week = (days_of_week & !(1 << 7)) | 1;
+        }
+
+        Ok(Self {
+            minutes: parse_field(minute, 0, 59).context("Invalid minute")?,
+            hours: parse_field(hour, 0, 23).context("Invalid hour")?,
+            days_of_month: parse_field(day_of_month, 1, 31).context("Invalid day of month")?,
+            months: parse_field(month, 1, 12).context("Invalid month")?,
+            days_of_week,
+            day_of_month_restricted: day_of_month != "*",
+            day_of_week_restricted: day_of_week != "*",
+        })
+    }
+
+    /// The first time strictly after `after` that matches the expression, `None` if there is none
+    /// within the next five years, like for the 30th of February.
+    pub fn next_after(&self, after: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        let limit = after + Duration::days(5 * 366);
+        let mut time = after.duration_trunc(Duration::minutes(1)).ok()? + Duration::minutes(1);
+
+        while time <= limit {
+            if !contains(self.months, time.month()) {
+                let first_of_month = time.date_naive().with_day(1)?;
+                time = (first_of_month + chrono::Months::new(1))
+                    .and_time(NaiveTime::MIN)
+                    .and_utc();
+            } else if !self.matches_day(time) {
+                time = (time.date_naive() + Duration::days(1))
+                    .and_time(NaiveTime::MIN)
+                    .and_utc();
+            } else if !contains(self.hours, time.hour()) {
+                time = time.with_minute(0)? + Duration::hours(1);
+            } else if !contains(self.minutes, time.minute()) {
+                time += Duration::minutes(1);
+            } else {
+                return Some(time);
+            }
+        }
+
+        None
+    }
+
+    fn matches_day(&self, time: DateTime<Utc>) -> bool {
+        let day_of_month = contains(self.days_of_month, time.day());
+        let day_of_week = contains(self.days_of_week, time.weekday().num_days_from_sunday());
+
+        if self.day_of_month_restricted && self.day_of_week_restricted {
+            day_of_month || day_of_week
+        } else {
+            day_of_month && day_of_week
+        }
+    }
+}
+
+fn contains(set: u64, value: u32) -> bool {
+    set & (1 << value) != 0
+}
+
+/// Parses one field into a bit set of the values it matches.
+fn parse_field(field: &str, min: u32, max: u32) -> anyhow::Result<u64> {
+    let mut set = 0;
+
+    for part in field.split(',') {
+        let (range, step) = match part.split_once('/') {
+            Some((range, step)) => (range, step.parse::<u32>()?),
+            None => (part, 1),
+        };
+
+        if step == 0 {
+            bail!("The step of `{part}` can't be 0");
+        }
+
+        let (start, end) = match range {
+            "*" => (min, max),
+            range => match range.split_once('-') {
+                Some((start, end)) => (start.parse()?, end.parse()?),
+                // `5/10` starts at 5 and repeats until the end of the range.
+                None if part.contains('/') => (range.parse()?, max),
+                None => {
+                    let value = range.parse()?;
+                    (value, value)
+                }
+            },
+        };
+
+        if start < min || end > max || start > end {
+            bail!("`{part}` is outside of {min}-{max}");
+        }
+
+        for value in (start..=end).step_by(step as usize) {
+            set |= 1 << value;
+        }
+    }
+
+    Ok(set)
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+
+    use super::*;
+
+    fn next(expression: &str, after: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        Cron::parse(expression).unwrap().next_after(after)
+    }
+
+    #[test]
+    fn finds_next_matching_time() {
+        // a wednesday
+        let now = Utc.with_ymd_and_hms(2024, 4, 17, 12, 0, 30).unwrap();
+
+        assert_eq!(
+            next("* * * * *", now),
+            Some(Utc.with_ymd_and_hms(2024, 4, 17, 12, 1, 0).unwrap())
+        );
+        assert_eq!(
+            next("0 * * * *", now),
+            Some(Utc.with_ymd_and_hms(2024, 4, 17, 13, 0, 0).unwrap())
+        );
+        assert_eq!(
+            next("0 9 * * 1", now),
+            Some(Utc.with_ymd_and_hms(2024, 4, 22, 9, 0, 0).unwrap())
+        );
+        assert_eq!(
+            next("*/20 3-5 1,15 * *", now),
+            Some(Utc.with_ymd_and_hms(2024, 5, 1, 3, 0, 0).unwrap())
+        );
+        assert_eq!(
+            next("0 0 29 2 *", now),
+            Some(Utc.with_ymd_and_hms(2028, 2, 29, 0, 0, 0).unwrap())
+        );
+        assert_eq!(
+            next("0 0 * * 7", now),
+            Some(Utc.with_ymd_and_hms(2024, 4, 21, 0, 0, 0).unwrap())
+        );
+
+        // either day field matches when both are restricted.
+        assert_eq!(
+            next("0 0 1 * 5", now),
+            Some(Utc.with_ymd_and_hms(2024, 4, 19, 0, 0, 0).unwrap())
+        );
+
+        // a matching time is never returned again.
+        let monday = Utc.with_ymd_and_hms(2024, 4, 22, 9, 0, 0).unwrap();
+        assert_eq!(
+            next("0 9 * * 1", monday),
+            Some(Utc.with_ymd_and_hms(2024, 4, 29, 9, 0, 0).unwrap())
+        );
+
+        assert_eq!(next("0 0 30 2 *", now), None);
+    }
+
+    #[test]
+    fn rejects_invalid_expressions() {
+        for expression in [
+            "* * * *",
+            "60 * * * *",
+            "* 24 * * *",
+            "* * 0 * *",
+            "*/0 * * * *",
+            "5-1 * * * *",
+            "a * * * *",
+        ] {
+            assert!(Cron::parse(expression).is_err(), "{expression}");
+        }
+    }
+}
@@ -0,0 +1,162 @@
+use std::{
+    collections::hash_map::RandomState,
+    future::Future,
+    hash::{BuildHasher, Hasher},
+    pin::Pin,
+    sync::Arc,
+    time::Duration,
+};
+
+use chrono::Utc;
+use poise::serenity_prelude as serenity;
+use serenity::Http;
+use tokio::{sync::watch, task::JoinHandle};
+
+use crate::Data;
+
+pub mod cron;
+
+use cron::Cron;
+
+type JobFuture = Pin<Box<dyn Future<Output = anyhow::Result<()>> + Send>>;
+type JobHandler = Arc<dyn Fn(Arc<Http>, Data) -> JobFuture + Send + Sync>;
+
+struct Job {
+    name: &'static str,
+    cron: Cron,
+    /// Runs are delayed by a random duration up to this, so jobs sharing an expression don't all
+    /// hit the APIs at the same moment.
+    max_jitter: Duration,
+    handler: JobHandler,
+}
+
+/// Runs recurring jobs on cron expressions.
+///
+/// Every job runs on its own task, an error or panic of one run is logged and doesn't affect the
+/// next run or any other job.
+pub struct Scheduler {
+    http: Arc<Http>,
+    data: Data,
+    jobs: Vec<Job>,
+}
+
+impl Scheduler {
+    pub fn new(http: Arc<Http>, data: Data) -> Self {
+        Self {
+            http,
+            data,
+            jobs: Vec::new(),
+        }
+    }
+
+    /// Registers a job that runs whenever the cron expression matches.
+    pub fn add<F, Fut>(
+        &mut self,
+        name: &'static str,
+        expression: &str,
+        max_jitter: Duration,
+        handler: F,
+    ) -> anyhow::Result<()>
+    where
+        F: Fn(Arc<Http>, Data) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = anyhow::Result<()>> + Send + 'static,
+    {
+        let cron = Cron::parse(expression)?;
+
+        self.jobs.push(Job {
+            name,
+            cron,
+            max_jitter,
+            handler: Arc::new(move |http, data| Box::pin(handler(http, data))),
+        });
+
+        Ok(())
+    }
+
+    pub fn start(self) -> SchedulerHandle {
+        let (shutdown_sender, shutdown) = watch::channel(false);
+
+        let tasks = self
+            .jobs
+            .into_iter()
+            .map(|job| {
+                tokio::spawn(run_job(
+                    job,
+                    self.http.clone(),
+                    self.data.clone(),
+                    shutdown.clone(),
+                ))
+            })
+            .collect();
+
+        SchedulerHandle {
+            shutdown: shutdown_sender,
+            tasks,
+        }
+    }
+}
+
+pub struct SchedulerHandle {
+    shutdown: watch::Sender<bool>,
+    tasks: Vec<JoinHandle<()>>,
+}
+
+impl SchedulerHandle {
+    /// Stops scheduling new runs and waits up to `timeout` for the running ones to finish.
+    pub async fn shutdown(self, timeout: Duration) {
+        let _ = self.shutdown.send(true);
+
+        let all_finished = async {
+            for task in self.tasks {
+                let _ = task.await;
+            }
+        };
+
+        if tokio::time::timeout(timeout, all_finished).await.is_err() {
+            tracing::warn!("Scheduled jobs didn't finish within {timeout:?}, stopping anyway.");
+        }
+    }
+}
+
+async fn run_job(job: Job, http: Arc<Http>, data: Data, mut shutdown: watch::Receiver<bool>) {
+    loop {
+        let now = Utc::now();
+
+        let Some(next) = job.cron.next_after(now) else {
+            tracing::warn!("Scheduled job {} never runs again.", job.name);
+            return;
+        };
+
+        let delay = (next - now).to_std().unwrap_or_default() + jitter(job.max_jitter);
+
+        tracing::debug!("Next run of scheduled job {} at {next}.", job.name);
+
+        tokio::select! {
+            _ = tokio::time::sleep(delay) => {}
+            _ = shutdown.changed() => return,
+        }
+
+        // running the job on its own task turns a panic into an error instead of killing the loop.
+        match tokio::spawn((job.handler)(http.clone(), data.clone())).await {
+            Ok(Ok(())) => tracing::debug!("Scheduled job {} finished.", job.name),
+            Ok(Err(e)) => tracing::error!("Scheduled job {} failed: {e:#?}", job.name),
+            Err(e) => tracing::error!("Scheduled job {} panicked: {e}", job.name),
+        }
+
+        if *shutdown.borrow() {
+            return;
+        }
+    }
+}
+
+/// A random duration up to `max`.
+fn jitter(max: Duration) -> Duration {
+    if max.is_zero() {
+        return Duration::ZERO;
+    }
+
+    // every `RandomState` is seeded differently, which is random enough to spread out jobs.
+    let random = RandomState::new().build_hasher().finish();
+
+    Duration::from_millis(random % max.as_millis() as u64)
+}
@@ -0,0 +1,368 @@
+use std::{collections::HashMap, fmt::Display, sync::RwLock};
+
+use poise::serenity_prelude as serenity;
+use serde_json::{json, Value};
+use serenity::{ChannelId, Mentionable};
+use sqlx::PgPool;
+
+use crate::{config::Config, database::settings};
+
+/// The issue label that posts a good first issue alert unless other labels are set.
+const DEFAULT_GFI_LABEL: &str = "good first issue";
+
+/// Github doesn't allow longer label names.
+const MAX_LABEL_LENGTH: usize = 50;
+
+/// Operational settings admins change at runtime with `/config`, without a redeploy. The config
+/// file only provides the defaults.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, poise::ChoiceParameter)]
+pub enum Setting {
+    #[name = "log_channel"]
+    LogChannel,
+    #[name = "digest_channel"]
+    DigestChannel,
+    #[name = "gfi_labels"]
+    GfiLabels,
+    #[name = "auto_responder"]
+    AutoResponder,
+    #[name = "issue_links"]
+    IssueLinks,
+    #[name = "code_snippets"]
+    CodeSnippets,
+    #[name = "paste"]
+    Paste,
+    #[name = "auto_publish"]
+    AutoPublish,
+}
+
+impl Setting {
+    pub const ALL: [Self; 8] = [
+        Self::LogChannel,
+        Self::DigestChannel,
+        Self::GfiLabels,
+        Self::AutoResponder,
+        Self::IssueLinks,
+        Self::CodeSnippets,
+        Self::Paste,
+        Self::AutoPublish,
+    ];
+
+    /// The name in `/config` and the settings table.
+    pub fn key(self) -> &'static str {
+        match self {
+            Self::LogChannel => "log_channel",
+            Self::DigestChannel => "digest_channel",
+            Self::GfiLabels => "gfi_labels",
+            Self::AutoResponder => "auto_responder",
+            Self::IssueLinks => "issue_links",
+            Self::CodeSnippets => "code_snippets",
+            Self::Paste => "paste",
+            Self::AutoPublish => "auto_publish",
+        }
+    }
+
+    pub fn from_key(key: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|setting| setting.key() == key)
+    }
+
+    pub fn description(self) -> &'static str {
+        match self {
+            Self::LogChannel => "Where moderation cases and alerts get logged.",
+            Self::DigestChannel => "Where the weekly project digest gets posted.",
+            Self::GfiLabels => "Issue labels that post a good first issue alert.",
+            Self::AutoResponder => "Answer known questions in help channels.",
+            Self::IssueLinks => "Reply to issue references like `#1234`.",
+            Self::CodeSnippets => "Show the lines github permalinks point to.",
+            Self::Paste => "Upload long code blocks to a paste service.",
+            Self::AutoPublish => "Publish messages in announcement channels.",
+        }
+    }
+
+    fn kind(self) -> SettingKind {
+        match self {
+            Self::LogChannel | Self::DigestChannel => SettingKind::Channel,
+            Self::GfiLabels => SettingKind::Labels,
+            Self::AutoResponder
+            | Self::IssueLinks
+            | Self::CodeSnippets
+            | Self::Paste
+            | Self::AutoPublish => SettingKind::Toggle,
+        }
+    }
+
+    /// What `/config set` accepts, shown when a value is rejected.
+    pub fn expected(self) -> &'static str {
+        match self.kind() {
+            SettingKind::Channel => "a channel mention, a channel id or `none`",
+            SettingKind::Labels => "label names separated by commas",
+            SettingKind::Toggle => "`on` or `off`",
+        }
+    }
+
+    /// The value when nobody changed the setting. Toggles only turn features off, a feature
+    /// still needs its section in the config file.
+    fn default_value(self, config: &Config) -> SettingValue {
+        match self {
+            Self::LogChannel => SettingValue::Channel(config.main_guild.channels.modlog),
+            Self::DigestChannel => SettingValue::Channel(config.main_guild.channels.digest),
+            Self::GfiLabels => SettingValue::Labels(vec![DEFAULT_GFI_LABEL.to_string()]),
+            Self::AutoResponder
+            | Self::IssueLinks
+            | Self::CodeSnippets
+            | Self::Paste
+            | Self::AutoPublish => SettingValue::Toggle(true),
+        }
+    }
+
+    /// Reads what an admin typed into `/config set`.
+    pub fn parse(self, input: &str) -> Option<SettingValue> {
+        let input = input.trim();
+
+        match self.kind() {
+            SettingKind::Channel => {
+                if input.eq_ignore_ascii_case("none") {
+                    return Some(SettingValue::Channel(None));
+                }
+
+                let id = input
+                    .strip_prefix("<#")
+                    .and_then(|mention| mention.strip_suffix('>'))
+                    .unwrap_or(input)
+                    .parse::<u64>()
+                    .ok()
+                    .filter(|id| *id > 0)?;
+
+                Some(SettingValue::Channel(Some(ChannelId::new(id))))
+            }
+            SettingKind::Labels => {
+                let labels = input
+                    .split(',')
+                    .map(str::trim)
+                    .filter(|label| !label.is_empty())
+                    .map(str::to_string)
+                    .collect::<Vec<_>>();
+
+                (!labels.is_empty() && labels.iter().all(|label| label.len() <= MAX_LABEL_LENGTH))
+                    .then_some(SettingValue::Labels(labels))
+            }
+            SettingKind::Toggle => match input.to_ascii_lowercase().as_str() {
+                "on" | "true" | "yes" | "enabled" => Some(SettingValue::Toggle(true)),
+                "off" | "false" | "no" | "disabled" => Some(SettingValue::Toggle(false)),
+                _ => None,
+            },
+        }
+    }
+
+    /// Reads a value stored in the settings table.
+    fn from_json(self, value: &Value) -> Option<SettingValue> {
+        match self.kind() {
+            SettingKind::Channel => match value {
+                Value::Null => Some(SettingValue::Channel(None)),
+                value => value
+                    .as_u64()
+                    .filter(|id| *id > 0)
+                    .map(|id| SettingValue::Channel(Some(ChannelId::new(id)))),
+            },
+            SettingKind::Labels => serde_json::from_value(value.clone())
+                .ok()
+                .map(SettingValue::Labels),
+            SettingKind::Toggle => value.as_bool().map(SettingValue::Toggle),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+enum SettingKind {
+    Channel,
+    Labels,
+    Toggle,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SettingValue {
+    /// `None` turns off what gets posted into the channel.
+    Channel(Option<ChannelId>),
+    Labels(Vec<String>),
+    Toggle(bool),
+}
+
+impl SettingValue {
+    fn to_json(&self) -> Value {
+        match self {
+            Self::Channel(channel_id) => json!(channel_id.map(ChannelId::get)),
+            Self::Labels(labels) => json!(labels),
+            Self::Toggle(enabled) => json!(enabled),
+        }
+    }
+}
+
+impl Display for SettingValue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Channel(Some(channel_id)) => write!(f, "{}", channel_id.mention()),
+            Self::Channel(None) => write!(f, "none"),
+            Self::Labels(labels) => write!(
+                f,
+                "{}",
+                labels
+                    .iter()
+                    .map(|label| format!("`{label}`"))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+            Self::Toggle(true) => write!(f, "on"),
+            Self::Toggle(false) => write!(f, "off"),
+        }
+    }
+}
+
+/// The current settings. Changes go to the database and are kept in memory, so reading a setting
+/// never waits for the database.
+#[derive(Debug)]
+pub struct Settings {
+    defaults: HashMap<Setting, SettingValue>,
+    changed: RwLock<HashMap<Setting, SettingValue>>,
+}
+
+impl Settings {
+    pub fn new(config: &Config) -> Self {
+        Self {
+            defaults: Setting::ALL
+                .into_iter()
+                .map(|setting| (setting, setting.default_value(config)))
+                .collect(),
+            changed: RwLock::default(),
+        }
+    }
+
+    /// Reads the settings changed with `/config`. Values that are no longer valid are skipped.
+    pub async fn load(&self, pool: &PgPool) -> anyhow::Result<()> {
+        let mut changed = HashMap::new();
+
+        for stored in settings::all(pool).await? {
+            match Setting::from_key(&stored.key)
+                .and_then(|setting| Some((setting, setting.from_json(&stored.value)?)))
+            {
+                Some((setting, value)) => {
+                    changed.insert(setting, value);
+                }
+                None => tracing::warn!("Ignoring the stored setting {}.", stored.key),
+            }
+        }
+
+        *self.changed.write().unwrap() = changed;
+
+        Ok(())
+    }
+
+    pub fn get(&self, setting: Setting) -> SettingValue {
+        self.changed
+            .read()
+            .unwrap()
+            .get(&setting)
+            .unwrap_or(&self.defaults[&setting])
+            .clone()
+    }
+
+    /// Whether the setting was changed with `/config`.
+    pub fn is_changed(&self, setting: Setting) -> bool {
+        self.changed.read().unwrap().contains_key(&setting)
+    }
+
+    pub async fn set(
+        &self,
+        pool: &PgPool,
+        setting: Setting,
+        value: SettingValue,
+        updated_by: i64,
+    ) -> anyhow::Result<()> {
+        settings::upsert(pool, setting.key(), &value.to_json(), updated_by).await?;
+        self.changed.write().unwrap().insert(setting, value);
+
+        Ok(())
+    }
+
+    /// Goes back to the default, `false` if the setting wasn't changed.
+    pub async fn reset(&self, pool: &PgPool, setting: Setting) -> anyhow::Result<bool> {
+        let deleted = settings::delete(pool, setting.key()).await?;
+        self.changed.write().unwrap().remove(&setting);
+
+        Ok(deleted)
+    }
+
+    pub fn channel(&self, setting: Setting) -> Option<ChannelId> {
+        match self.get(setting) {
+            SettingValue::Channel(channel_id) => channel_id,
+            _ => None,
+        }
+    }
+
+    pub fn labels(&self, setting: Setting) -> Vec<String> {
+        match self.get(setting) {
+            SettingValue::Labels(labels) => labels,
+            _ => Vec::new(),
+        }
+    }
+
+    pub fn enabled(&self, setting: Setting) -> bool {
+        matches!(self.get(setting), SettingValue::Toggle(true))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_input() {
+        assert_eq!(
+            Setting::LogChannel.parse("<#1234>"),
+            Some(SettingValue::Channel(Some(ChannelId::new(1234))))
+        );
+        assert_eq!(
+            Setting::DigestChannel.parse("None"),
+            Some(SettingValue::Channel(None))
+        );
+        assert_eq!(Setting::LogChannel.parse("#general"), None);
+        assert_eq!(
+            Setting::GfiLabels.parse("good first issue, help wanted ,"),
+            Some(SettingValue::Labels(vec![
+                "good first issue".to_string(),
+                "help wanted".to_string()
+            ]))
+        );
+        assert_eq!(Setting::GfiLabels.parse(" , "), None);
+        assert_eq!(
+            Setting::Paste.parse("OFF"),
+            Some(SettingValue::Toggle(false))
+        );
+        assert_eq!(Setting::Paste.parse("maybe"), None);
+    }
+
+    #[test]
+    fn stores_values_as_json() {
+        for (setting, value) in [
+            (
+                Setting::LogChannel,
+                SettingValue::Channel(Some(ChannelId::new(1234))),
+            ),
+            (Setting::DigestChannel, SettingValue::Channel(None)),
+            (
+                Setting::GfiLabels,
+                SettingValue::Labels(vec!["good first issue".to_string()]),
+            ),
+            (Setting::IssueLinks, SettingValue::Toggle(false)),
+        ] {
+            assert_eq!(setting.from_json(&value.to_json()), Some(value));
+        }
+
+        assert_eq!(Setting::IssueLinks.from_json(&json!("off")), None);
+    }
+
+    #[test]
+    fn keys_round_trip() {
+        for setting in Setting::ALL {
+            assert_eq!(Setting::from_key(setting.key()), Some(setting));
+        }
+    }
+}
@@ -0,0 +1,107 @@
+use std::{
+    collections::BTreeMap,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+use poise::serenity_prelude as serenity;
+use serenity::{ConnectionStage, Http, ShardId, ShardManager};
+use tokio::sync::RwLock;
+
+use crate::metrics::Metrics;
+
+/// How often the supervisor polls the shard runners.
+const SUPERVISOR_INTERVAL: Duration = Duration::from_secs(30);
+/// How long a shard may stay disconnected before the supervisor restarts it.
+const RESTART_AFTER: Duration = Duration::from_secs(120);
+
+#[derive(Debug, Clone)]
+pub struct ShardStatus {
+    pub stage: ConnectionStage,
+    pub latency: Option<Duration>,
+    pub last_connected: Instant,
+    pub restarts: u32,
+}
+
+/// Snapshot of every shard's connection state, shared with commands and the webserver.
+#[derive(Debug, Clone, Default)]
+pub struct ShardStatuses(Arc<RwLock<BTreeMap<u32, ShardStatus>>>);
+
+impl ShardStatuses {
+    pub async fn snapshot(&self) -> BTreeMap<u32, ShardStatus> {
+        self.0.read().await.clone()
+    }
+
+    /// Returns true when at least one shard exists and all of them are connected.
+    pub async fn all_connected(&self) -> bool {
+        let statuses = self.0.read().await;
+
+        !statuses.is_empty()
+            && statuses
+                .values()
+                .all(|status| matches!(status.stage, ConnectionStage::Connected))
+    }
+}
+
+/// Polls the shard manager, records per-shard latency and stage and restarts shards that stay dead.
+/// Also times a request to the Discord REST API, the gateway latency doesn't cover it.
+pub async fn supervise(
+    shard_manager: Arc<ShardManager>,
+    http: Arc<Http>,
+    statuses: ShardStatuses,
+    metrics: Arc<Metrics>,
+) {
+    let mut interval = tokio::time::interval(SUPERVISOR_INTERVAL);
+
+    loop {
+        interval.tick().await;
+
+        let started = Instant::now();
+        match http.get_current_user().await {
+            Ok(_) => metrics
+                .discord_api_latency
+                .observe(started.elapsed().as_secs_f64()),
+            Err(e) => tracing::warn!("Failed to reach the Discord API: {e}"),
+        }
+
+        let runners = shard_manager
+            .runners
+            .lock()
+            .await
+            .iter()
+            .map(|(id, runner)| (id.0, runner.stage, runner.latency))
+            .collect::<Vec<_>>();
+
+        let mut dead_shards = Vec::new();
+
+        {
+            let mut statuses = statuses.0.write().await;
+            let now = Instant::now();
+
+            for (id, stage, latency) in runners {
+                let status = statuses.entry(id).or_insert_with(|| ShardStatus {
+                    stage,
+                    latency,
+                    last_connected: now,
+                    restarts: 0,
+                });
+
+                status.stage = stage;
+                status.latency = latency;
+
+                if matches!(stage, ConnectionStage::Connected) {
+                    status.last_connected = now;
+                } else if now.duration_since(status.last_connected) > RESTART_AFTER {
+                    status.restarts += 1;
+                    status.last_connected = now;
+                    dead_shards.push(id);
+                }
+            }
+        }
+
+        for id in dead_shards {
+            tracing::warn!("Shard {id} has been disconnected for too long, restarting it.");
+            shard_manager.restart(ShardId(id)).await;
+        }
+    }
+}
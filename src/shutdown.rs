@@ -0,0 +1,42 @@
+use tokio::sync::watch;
+
+/// Tells the webserver, the job worker and the scheduler to stop taking new work. Every part
+/// gets its own receiver and finishes what it is doing before it returns.
+pub type ShutdownReceiver = watch::Receiver<bool>;
+
+/// Resolves on ctrl-c or SIGTERM, which docker and kubernetes send to stop the container.
+pub async fn signal() {
+    let ctrl_c = async {
+        if let Err(e) = tokio::signal::ctrl_c().await {
+            tracing::error!("Failed to listen for ctrl-c: {e}");
+            std::future::pending::<()>().await;
+        }
+    };
+
+    #[cfg(unix)]
+    let terminate = async {
+        match tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate()) {
+            Ok(mut terminate) => {
+                terminate.recv().await;
+            }
+            Err(e) => {
+                tracing::error!("Failed to listen for SIGTERM: {e}");
+                std::future::pending::<()>().await;
+            }
+        }
+    };
+
+    #[cfg(not(unix))]
+    let terminate = std::future::pending::<()>();
+
+    tokio::select! {
+        () = ctrl_c => {}
+        () = terminate => {}
+    }
+}
+
+/// Resolves once the shutdown started, right away if it already did.
+pub async fn requested(mut shutdown: ShutdownReceiver) {
+    // an error means the sender is gone, which only happens once main returns.
+    let _ = shutdown.wait_for(|stopping| *stopping).await;
+}
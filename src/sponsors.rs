@@ -0,0 +1,273 @@
+use std::collections::HashMap;
+
+use poise::serenity_prelude as serenity;
+use serde::Deserialize;
+use serde_json::json;
+use serenity::{Http, RoleId, UserId};
+
+use crate::{
+    config::SponsorConfig,
+    database::github_links::{self, GithubLink},
+    Data,
+};
+
+const SPONSORS_QUERY: &str = r#"
+query($organization: String!, $cursor: String) {
+  organization(login: $organization) {
+    sponsorshipsAsMaintainer(first: 100, after: $cursor, activeOnly: true) {
+      pageInfo { hasNextPage endCursor }
+      nodes {
+        sponsorEntity {
+          ... on User { databaseId }
+          ... on Organization { databaseId }
+        }
+        tier { monthlyPriceInCents }
+      }
+    }
+  }
+}
+"#;
+
+#[derive(Debug, Deserialize)]
+struct SponsorsResponse {
+    organization: Option<Sponsorable>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Sponsorable {
+    sponsorships_as_maintainer: SponsorshipConnection,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SponsorshipConnection {
+    page_info: PageInfo,
+    nodes: Vec<Sponsorship>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct PageInfo {
+    has_next_page: bool,
+    end_cursor: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Sponsorship {
+    /// `None` for sponsors that were deleted.
+    sponsor_entity: Option<SponsorEntity>,
+    tier: Option<SponsorTier>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SponsorEntity {
+    database_id: Option<u64>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SponsorTier {
+    monthly_price_in_cents: u32,
+}
+
+/// Gives the sponsor roles to every linked member sponsoring the organization, and takes them
+/// from linked members who stopped.
+pub async fn sync_all(http: &Http, data: &Data) -> anyhow::Result<()> {
+    let Some(config) = &data.config.sponsors else {
+        return Ok(());
+    };
+
+    let sponsors = fetch_sponsors(data, config).await?;
+    let links = github_links::all(&data.db_pool).await?;
+    let mut failed = 0;
+
+    for link in &links {
+        let monthly_cents = sponsors.get(&(link.github_id as u64)).copied();
+
+        if let Err(e) = sync_member(http, data, config, link, monthly_cents).await {
+            tracing::warn!(
+                "Failed to sync the sponsor roles of {}: {e:#}",
+                link.user_id
+            );
+            failed += 1;
+        }
+    }
+
+    tracing::info!(
+        "Synced the sponsor roles, {} sponsors and {} linked accounts.",
+        sponsors.len(),
+        links.len()
+    );
+
+    if failed > 0 {
+        anyhow::bail!("Failed to sync the sponsor roles of {failed} members");
+    }
+
+    Ok(())
+}
+
+/// Syncs the sponsor roles of a member who just linked their account.
+pub async fn sync_linked(http: &Http, data: &Data, link: &GithubLink) -> anyhow::Result<()> {
+    let Some(config) = &data.config.sponsors else {
+        return Ok(());
+    };
+
+    let sponsors = fetch_sponsors(data, config).await?;
+
+    sync_member(
+        http,
+        data,
+        config,
+        link,
+        sponsors.get(&(link.github_id as u64)).copied(),
+    )
+    .await
+}
+
+/// Every role the sponsor sync adds and removes.
+pub fn managed_roles(config: &SponsorConfig) -> Vec<RoleId> {
+    std::iter::once(config.role)
+        .chain(config.tiers.iter().map(|tier| tier.role))
+        .collect()
+}
+
+async fn sync_member(
+    http: &Http,
+    data: &Data,
+    config: &SponsorConfig,
+    link: &GithubLink,
+    monthly_cents: Option<u32>,
+) -> anyhow::Result<()> {
+    let guild_id = data.config.bot.guild_id;
+    let user_id = UserId::new(link.user_id as u64);
+
+    let Ok(member) = guild_id.member(http, user_id).await else {
+        return Ok(());
+    };
+
+    let desired = desired_roles(config, monthly_cents);
+
+    for role_id in managed_roles(config) {
+        match (desired.contains(&role_id), member.roles.contains(&role_id)) {
+            (true, false) => {
+                http.add_member_role(guild_id, user_id, role_id, Some("Github sponsor"))
+                    .await?;
+            }
+            (false, true) => {
+                http.remove_member_role(guild_id, user_id, role_id, Some("Github sponsor"))
+                    .await?;
+            }
+            _ => {}
+        }
+    }
+
+    Ok(())
+}
+
+/// The active sponsors by github id, with their monthly amount in cents.
+async fn fetch_sponsors(data: &Data, config: &SponsorConfig) -> anyhow::Result<HashMap<u64, u32>> {
+    let mut sponsors = HashMap::new();
+    let mut cursor: Option<String> = None;
+
+    loop {
+        let response = data
+            .github
+            .graphql::<SponsorsResponse>(
+                SPONSORS_QUERY,
+                json!({ "organization": config.organization, "cursor": cursor }),
+            )
+            .await?;
+
+        let Some(organization) = response.organization else {
+            anyhow::bail!(
+                "The github organization {} doesn't exist",
+                config.organization
+            );
+        };
+
+        let connection = organization.sponsorships_as_maintainer;
+
+        for sponsorship in connection.nodes {
+            let Some(github_id) = sponsorship
+                .sponsor_entity
+                .and_then(|sponsor| sponsor.database_id)
+            else {
+                continue;
+            };
+
+            let monthly_cents = sponsorship
+                .tier
+                .map_or(0, |tier| tier.monthly_price_in_cents);
+
+            sponsors.insert(github_id, monthly_cents);
+        }
+
+        match connection.page_info.end_cursor {
+            Some(end_cursor) if connection.page_info.has_next_page => cursor = Some(end_cursor),
+            _ => return Ok(sponsors),
+        }
+    }
+}
+
+/// The base role and the role of the highest tier reached, nothing for members not sponsoring.
+fn desired_roles(config: &SponsorConfig, monthly_cents: Option<u32>) -> Vec<RoleId> {
+    let Some(monthly_cents) = monthly_cents else {
+        return Vec::new();
+    };
+
+    let tier = config
+        .tiers
+        .iter()
+        .filter(|tier| u64::from(tier.min_monthly_dollars) * 100 <= u64::from(monthly_cents))
+        .max_by_key(|tier| tier.min_monthly_dollars);
+
+    std::iter::once(config.role)
+        .chain(tier.map(|tier| tier.role))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::SponsorTierConfig;
+
+    fn config() -> SponsorConfig {
+        SponsorConfig {
+            organization: "biomejs".to_string(),
+            role: RoleId::new(1),
+            tiers: vec![
+                SponsorTierConfig {
+                    min_monthly_dollars: 100,
+                    role: RoleId::new(3),
+                },
+                SponsorTierConfig {
+                    min_monthly_dollars: 10,
+                    role: RoleId::new(2),
+                },
+            ],
+        }
+    }
+
+    #[test]
+    fn gives_highest_tier_reached() {
+        let config = config();
+
+        assert_eq!(desired_roles(&config, Some(500)), vec![RoleId::new(1)]);
+        assert_eq!(
+            desired_roles(&config, Some(2_500)),
+            vec![RoleId::new(1), RoleId::new(2)]
+        );
+        assert_eq!(
+            desired_roles(&config, Some(10_000)),
+            vec![RoleId::new(1), RoleId::new(3)]
+        );
+    }
+
+    #[test]
+    fn gives_nothing_without_sponsorship() {
+        assert!(desired_roles(&config(), None).is_empty());
+    }
+}
@@ -0,0 +1,78 @@
+use std::str::FromStr;
+
+use chrono::Utc;
+use poise::serenity_prelude as serenity;
+use serenity::{GuildId, Http};
+
+use crate::{
+    database::announcements::{self, Announcement},
+    embed_payload::EmbedPayload,
+    scheduler::{cron::Cron, Scheduler},
+    webhooks::{self, EmbedTarget},
+    Data,
+};
+
+/// Posts scheduled announcements once their time came.
+pub fn schedule(scheduler: &mut Scheduler, _: &Data) -> anyhow::Result<()> {
+    // every minute, the smallest step of the schedules.
+    scheduler.add(
+        "announcements",
+        "* * * * *",
+        std::time::Duration::ZERO,
+        |http, data| async move { post_due(&http, &data).await },
+    )
+}
+
+/// Announcements that failed to post stay due and are retried the next minute.
+async fn post_due(http: &Http, data: &Data) -> anyhow::Result<()> {
+    let mut failed = 0;
+
+    for announcement in announcements::due(&data.db_pool).await? {
+        // an announcement that failed to post shouldn't hold up the others.
+        if let Err(e) = post(http, data, &announcement).await {
+            tracing::error!("Failed to post announcement #{}: {e:#?}", announcement.id);
+            failed += 1;
+            continue;
+        }
+
+        let next_run_at = announcement
+            .recurrence
+            .as_deref()
+            .and_then(|recurrence| Cron::parse(recurrence).ok())
+            .and_then(|cron| cron.next_after(Utc::now()));
+
+        match next_run_at {
+            Some(next_run_at) => {
+                announcements::reschedule(&data.db_pool, announcement.id, next_run_at).await?;
+            }
+            None => {
+                announcements::delete(&data.db_pool, announcement.id).await?;
+            }
+        }
+    }
+
+    if failed > 0 {
+        anyhow::bail!("Failed to post {failed} announcements");
+    }
+
+    Ok(())
+}
+
+async fn post(http: &Http, data: &Data, announcement: &Announcement) -> anyhow::Result<()> {
+    let target = EmbedTarget::from_str(&announcement.target)?;
+    let payload = EmbedPayload::from_value(announcement.payload.clone())?;
+    let guild_id = announcement
+        .guild_id
+        .map_or(data.config.bot.guild_id, |guild_id| {
+            GuildId::new(guild_id as u64)
+        });
+    let webhook = webhooks::resolve(http, data, guild_id, target).await?;
+
+    webhook
+        .execute(http, false, payload.execute_webhook())
+        .await?;
+
+    tracing::info!("Posted announcement #{} in {target}.", announcement.id);
+
+    Ok(())
+}
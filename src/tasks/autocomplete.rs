@@ -0,0 +1,69 @@
+use std::time::Duration;
+
+use crate::{
+    config_schema::cached_config_schema,
+    database::tags,
+    lint_rules::cached_lint_rules,
+    npm_api::{self, BIOME_PACKAGE},
+    Data,
+};
+
+const REFRESH_INTERVAL: Duration = Duration::from_secs(10 * 60);
+
+/// Keeps the names offered by autocompletes up to date. A source that fails to refresh keeps
+/// offering the names it had before.
+pub async fn run(data: Data) {
+    loop {
+        refresh(&data).await;
+        tokio::time::sleep(REFRESH_INTERVAL).await;
+    }
+}
+
+async fn refresh(data: &Data) {
+    let sources = &data.autocomplete;
+
+    match cached_lint_rules(data).await {
+        Ok(rules) => sources
+            .rules
+            .replace(rules.iter().map(|rule| rule.name.clone()).collect()),
+        Err(e) => tracing::error!("Failed to refresh lint rules for autocomplete: {e:#?}"),
+    }
+
+    if let Err(e) = refresh_tags(data).await {
+        tracing::error!("Failed to refresh tag names for autocomplete: {e:#?}");
+    }
+
+    match data
+        .npm_package
+        .get_or_refresh(|| npm_api::package_metadata(BIOME_PACKAGE))
+        .await
+    {
+        Ok(metadata) => sources.versions.replace(metadata.stable_versions()),
+        Err(e) => tracing::error!("Failed to refresh versions for autocomplete: {e:#?}"),
+    }
+
+    match cached_config_schema(data).await {
+        Ok(schema) => sources.config_keys.replace(
+            schema
+                .entries
+                .iter()
+                .map(|entry| entry.key.clone())
+                .collect(),
+        ),
+        Err(e) => tracing::error!("Failed to refresh configuration keys for autocomplete: {e:#?}"),
+    }
+}
+
+/// Called after tags were created or deleted, so they show up right away.
+pub async fn refresh_tags(data: &Data) -> anyhow::Result<()> {
+    // most used first, like in `/tag list`.
+    let names = tags::all(&data.db_pool)
+        .await?
+        .into_iter()
+        .map(|tag| tag.name)
+        .collect();
+
+    data.autocomplete.tags.replace(names);
+
+    Ok(())
+}
@@ -0,0 +1,17 @@
+use crate::{contributors, scheduler::Scheduler, Data};
+
+/// Keeps the contributor role in sync with the merged commits of linked github accounts.
+pub fn schedule(scheduler: &mut Scheduler, data: &Data) -> anyhow::Result<()> {
+    if data.config.main_guild.roles.contributor.is_none() {
+        tracing::info!("No contributor role configured, contributor syncs are disabled.");
+        return Ok(());
+    }
+
+    // every six hours.
+    scheduler.add(
+        "contributors",
+        "15 */6 * * *",
+        std::time::Duration::from_secs(10 * 60),
+        |http, data| async move { contributors::sync_all(&http, &data).await },
+    )
+}
@@ -0,0 +1,148 @@
+use std::time::Duration;
+
+use anyhow::Context;
+use chrono::Utc;
+use scraper::{Html, Selector};
+
+use crate::{database::docs_pages, Data};
+
+const SITEMAP_URL: &str = "https://biomejs.dev/sitemap-0.xml";
+const CRAWL_INTERVAL: Duration = Duration::from_secs(24 * 60 * 60);
+/// Pause between two page requests so the crawl doesn't hammer the website.
+const REQUEST_DELAY: Duration = Duration::from_millis(250);
+
+/// Crawls the documentation once a day and stores the pages for `/docs search`.
+pub async fn run(data: Data) {
+    loop {
+        match crawl(&data).await {
+            Ok(count) => tracing::info!("Indexed {count} documentation pages."),
+            Err(e) => tracing::error!("Failed to index the documentation: {e:#?}"),
+        }
+
+        tokio::time::sleep(CRAWL_INTERVAL).await;
+    }
+}
+
+async fn crawl(data: &Data) -> anyhow::Result<usize> {
+    let started_at = Utc::now();
+
+    let sitemap = reqwest::get(SITEMAP_URL)
+        .await?
+        .error_for_status()
+        .context("Failed to fetch the sitemap")?
+        .text()
+        .await?;
+
+    let urls = sitemap_urls(&sitemap)
+        .into_iter()
+        .filter(|url| !is_translated(url))
+        .collect::<Vec<_>>();
+
+    let mut indexed = 0;
+
+    for url in urls {
+        tokio::time::sleep(REQUEST_DELAY).await;
+
+        match index_page(data, &url).await {
+            Ok(_) => indexed += 1,
+            Err(e) => tracing::warn!("Failed to index {url}: {e:#?}"),
+        }
+    }
+
+    // only prune when the crawl went through, a website outage shouldn't empty the index.
+    if indexed > 0 {
+        docs_pages::remove_stale(&data.db_pool, started_at).await?;
+    }
+
+    Ok(indexed)
+}
+
+async fn index_page(data: &Data, url: &str) -> anyhow::Result<()> {
+    let html = reqwest::get(url).await?.error_for_status()?.text().await?;
+
+    let Some((title, content)) = extract_page(&html) else {
+        anyhow::bail!("Page has no content");
+    };
+
+    docs_pages::upsert(&data.db_pool, url, &title, &content).await
+}
+
+/// Returns the title and the plain text content of a documentation page.
+fn extract_page(html: &str) -> Option<(String, String)> {
+    let document = Html::parse_document(html);
+
+    let title_selector = Selector::parse("h1").ok()?;
+    // starlight renders the article into `.sl-markdown-content`, other pages only have `main`.
+    let content_selectors = [
+        Selector::parse(".sl-markdown-content").ok()?,
+        Selector::parse("main").ok()?,
+    ];
+
+    let title = normalize_whitespace(
+        &document
+            .select(&title_selector)
+            .next()?
+            .text()
+            .collect::<String>(),
+    );
+    let content_element = content_selectors
+        .iter()
+        .find_map(|selector| document.select(selector).next())?;
+    let content = normalize_whitespace(&content_element.text().collect::<Vec<_>>().join(" "));
+
+    (!title.is_empty() && !content.is_empty()).then_some((title, content))
+}
+
+fn sitemap_urls(sitemap: &str) -> Vec<String> {
+    sitemap
+        .split("<loc>")
+        .skip(1)
+        .filter_map(|part| part.split_once("</loc>"))
+        .map(|(url, _)| url.trim().to_string())
+        .collect()
+}
+
+/// Whether the url points to a translation, e.g. `https://biomejs.dev/zh-cn/...`.
+fn is_translated(url: &str) -> bool {
+    let path = url
+        .trim_start_matches("https://")
+        .split('/')
+        .nth(1)
+        .unwrap_or_default();
+
+    match path.split_once('-') {
+        Some((language, region)) => language.len() == 2 && region.len() == 2,
+        None => path.len() == 2,
+    }
+}
+
+fn normalize_whitespace(input: &str) -> String {
+    input.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn skips_translated_pages() {
+        assert!(is_translated(
+            "https://biomejs.dev/ja/guides/getting-started/"
+        ));
+        assert!(is_translated("https://biomejs.dev/zh-cn/linter/"));
+        assert!(!is_translated(
+            "https://biomejs.dev/guides/getting-started/"
+        ));
+        assert!(!is_translated("https://biomejs.dev/"));
+    }
+
+    #[test]
+    fn reads_sitemap_locations() {
+        let sitemap = "<urlset><url><loc>https://biomejs.dev/</loc></url><url><loc>https://biomejs.dev/linter/</loc></url></urlset>";
+
+        assert_eq!(
+            sitemap_urls(sitemap),
+            vec!["https://biomejs.dev/", "https://biomejs.dev/linter/"]
+        );
+    }
+}
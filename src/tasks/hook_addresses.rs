@@ -0,0 +1,56 @@
+use std::time::Duration;
+
+use crate::{util::ip_range::IpRange, Data};
+
+/// Github rarely changes its addresses.
+const REFRESH_INTERVAL: Duration = Duration::from_secs(6 * 60 * 60);
+/// Until the first fetch succeeded `/github` can't check where requests come from.
+const RETRY_INTERVAL: Duration = Duration::from_secs(5 * 60);
+
+/// Keeps the addresses github sends webhooks from up to date, when `github.verify_source_ip` is
+/// set. A failed refresh keeps the addresses from before.
+pub async fn run(data: Data) {
+    if !data.config.github.verify_source_ip {
+        tracing::info!("Source addresses of github webhooks aren't verified.");
+        return;
+    }
+
+    loop {
+        let wait = match refresh(&data).await {
+            Ok(()) => REFRESH_INTERVAL,
+            Err(e) => {
+                tracing::error!("Failed to refresh the github webhook addresses: {e:#?}");
+                RETRY_INTERVAL
+            }
+        };
+
+        tokio::time::sleep(wait).await;
+    }
+}
+
+async fn refresh(data: &Data) -> anyhow::Result<()> {
+    let ranges = data
+        .github
+        .meta()
+        .await?
+        .hooks
+        .iter()
+        .filter_map(|range| match range.parse::<IpRange>() {
+            Ok(range) => Some(range),
+            Err(e) => {
+                tracing::warn!("Skipping a github webhook address: {e:#}");
+                None
+            }
+        })
+        .collect::<Vec<_>>();
+
+    // an empty list would reject every delivery.
+    if ranges.is_empty() {
+        anyhow::bail!("Github didn't list any webhook addresses");
+    }
+
+    tracing::info!("Loaded {} github webhook address ranges.", ranges.len());
+    data.hook_addresses.replace(ranges);
+
+    Ok(())
+}
@@ -0,0 +1,191 @@
+use std::{sync::Arc, time::Duration};
+
+use chrono::Utc;
+use poise::serenity_prelude as serenity;
+use serenity::{CreateEmbed, CreateMessage, Http};
+
+use crate::{
+    database::language_support,
+    language_support::{
+        fetch_language_support, LanguageFeature, LanguageSupportLevel, LANGUAGE_SUPPORT_URL,
+    },
+    util::{embeds::EmbedColor, format::truncate},
+    Data,
+};
+
+/// A capability of a language whose support level changed between two scrapes.
+#[derive(Debug, PartialEq, Eq)]
+struct SupportChange {
+    language_name: String,
+    capability: &'static str,
+    /// `None` when the language is new to the table.
+    previous: Option<LanguageSupportLevel>,
+    current: LanguageSupportLevel,
+}
+
+/// Keeps the language support cache warm, so `/languages` doesn't have to wait for the website,
+/// and announces support levels that changed since the last scrape.
+pub async fn run(http: Arc<Http>, data: Data) {
+    let ttl = Duration::from_secs(data.config.cache.language_support_ttl_secs);
+    // refresh before the cached entry expires, but don't hammer the website with tiny ttls.
+    let interval = (ttl / 4 * 3).max(Duration::from_secs(60));
+
+    loop {
+        match fetch_language_support().await {
+            Ok(language_features) => {
+                if let Err(e) = track_changes(&http, &data, &language_features).await {
+                    tracing::error!("Failed to track language support changes: {e:#?}");
+                }
+
+                data.language_support.set(Arc::new(language_features)).await;
+                tracing::info!("Refreshed language support cache.");
+            }
+            Err(e) => tracing::error!("Failed to refresh language support cache: {e:#?}"),
+        }
+
+        tokio::time::sleep(interval).await;
+    }
+}
+
+async fn track_changes(
+    http: &Http,
+    data: &Data,
+    language_features: &[LanguageFeature],
+) -> anyhow::Result<()> {
+    let previous = language_support::all(&data.db_pool).await?;
+
+    // the first scrape only establishes the baseline.
+    let changes = if previous.is_empty() {
+        Vec::new()
+    } else {
+        support_changes(&previous, language_features)
+    };
+
+    if !changes.is_empty() {
+        match data.config.main_guild.channels.language_updates {
+            Some(channel_id) => {
+                channel_id
+                    .send_message(http, CreateMessage::new().embed(changes_embed(&changes)))
+                    .await?;
+                tracing::info!("Announced {} language support changes.", changes.len());
+            }
+            None => tracing::info!(
+                "Language support changed, but no announcement channel is configured."
+            ),
+        }
+    }
+
+    language_support::replace_all(&data.db_pool, language_features).await
+}
+
+fn support_changes(
+    previous: &[LanguageFeature],
+    current: &[LanguageFeature],
+) -> Vec<SupportChange> {
+    let mut changes = Vec::new();
+
+    for feature in current {
+        let before = previous
+            .iter()
+            .find(|before| before.language_name == feature.language_name);
+
+        let capabilities = [
+            ("Parsing", before.map(|b| b.parsing), feature.parsing),
+            (
+                "Formatting",
+                before.map(|b| b.formatting),
+                feature.formatting,
+            ),
+            ("Linting", before.map(|b| b.linting), feature.linting),
+        ];
+
+        for (capability, previous, current) in capabilities {
+            if previous != Some(current) {
+                changes.push(SupportChange {
+                    language_name: feature.language_name.clone(),
+                    capability,
+                    previous,
+                    current,
+                });
+            }
+        }
+    }
+
+    changes
+}
+
+fn changes_embed(changes: &[SupportChange]) -> CreateEmbed {
+    let lines = changes
+        .iter()
+        .map(|change| match change.previous {
+            Some(previous) => format!(
+                "**{}** {}: {previous} {} \u{2192} {} {}",
+                change.language_name.trim(),
+                change.capability.to_lowercase(),
+                previous.label(),
+                change.current,
+                change.current.label()
+            ),
+            None => format!(
+                "**{}** {}: {} {}",
+                change.language_name.trim(),
+                change.capability.to_lowercase(),
+                change.current,
+                change.current.label()
+            ),
+        })
+        .collect::<Vec<_>>();
+
+    CreateEmbed::new()
+        .color(EmbedColor::Biome)
+        .title("Language Support Update")
+        .url(LANGUAGE_SUPPORT_URL)
+        .description(truncate(&lines.join("\n"), 4000))
+        .timestamp(Utc::now())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn feature(
+        name: &str,
+        parsing: LanguageSupportLevel,
+        formatting: LanguageSupportLevel,
+        linting: LanguageSupportLevel,
+    ) -> LanguageFeature {
+        LanguageFeature {
+            language_name: name.to_string(),
+            parsing,
+            formatting,
+            linting,
+        }
+    }
+
+    #[test]
+    fn detects_changed_levels() {
+        use LanguageSupportLevel::*;
+
+        let previous = vec![feature("GraphQL", Supported, InProgress, InProgress)];
+        let current = vec![
+            feature("GraphQL", Supported, Supported, InProgress),
+            feature("HTML", InProgress, NotInProgress, NotInProgress),
+        ];
+
+        let changes = support_changes(&previous, &current);
+
+        assert_eq!(changes.len(), 4);
+        assert_eq!(
+            changes[0],
+            SupportChange {
+                language_name: "GraphQL".to_string(),
+                capability: "Formatting",
+                previous: Some(InProgress),
+                current: Supported,
+            }
+        );
+        assert!(changes[1..]
+            .iter()
+            .all(|change| change.language_name == "HTML" && change.previous.is_none()));
+    }
+}
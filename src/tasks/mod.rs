@@ -0,0 +1,11 @@
+pub mod announcements;
+pub mod autocomplete;
+pub mod contributors;
+pub mod docs_index;
+pub mod hook_addresses;
+pub mod language_support;
+pub mod phishing_feed;
+pub mod polls;
+pub mod sponsors;
+pub mod stale_threads;
+pub mod weekly_digest;
@@ -0,0 +1,92 @@
+use anyhow::Context;
+
+use crate::{database::blocked_domains, link_filter::normalize_domain, scheduler::Scheduler, Data};
+
+/// Imports the domains of a public phishing feed into the blocklist.
+pub fn schedule(scheduler: &mut Scheduler, data: &Data) -> anyhow::Result<()> {
+    if data.config.phishing_feed.is_none() {
+        tracing::info!("No phishing feed configured, feed updates are disabled.");
+        return Ok(());
+    }
+
+    // every six hours.
+    scheduler.add(
+        "phishing_feed",
+        "30 */6 * * *",
+        std::time::Duration::from_secs(10 * 60),
+        |_, data| async move { update_domains(&data).await },
+    )
+}
+
+async fn update_domains(data: &Data) -> anyhow::Result<()> {
+    let Some(config) = &data.config.phishing_feed else {
+        return Ok(());
+    };
+
+    let body = reqwest::get(&config.url)
+        .await?
+        .error_for_status()
+        .context("Failed to fetch the phishing feed")?
+        .text()
+        .await?;
+
+    let domains = parse_feed(&body);
+
+    // an empty feed is more likely broken than a sudden end of all phishing.
+    if domains.is_empty() {
+        anyhow::bail!("The phishing feed doesn't contain any domains");
+    }
+
+    let (added, removed) = blocked_domains::sync_feed(&data.db_pool, &domains).await?;
+    data.link_filter.reload_domains().await;
+
+    tracing::info!(
+        "Updated the phishing feed, {} domains, {added} added and {removed} removed.",
+        domains.len()
+    );
+
+    Ok(())
+}
+
+/// Reads the domains of the feed, either a JSON array or one domain per line with `#` comments.
+fn parse_feed(body: &str) -> Vec<String> {
+    let entries = match serde_json::from_str::<Vec<String>>(body) {
+        Ok(entries) => entries,
+        Err(_) => body
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+            .map(str::to_string)
+            .collect(),
+    };
+
+    let mut domains = entries
+        .iter()
+        .filter_map(|entry| normalize_domain(entry))
+        .collect::<Vec<_>>();
+
+    domains.sort_unstable();
+    domains.dedup();
+    domains
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_text_feeds() {
+        assert_eq!(
+            parse_feed("# scam domains\nfree-nitro.example\n\n  Steam-Gift.example \nnot a domain\nfree-nitro.example\n"),
+            vec!["free-nitro.example", "steam-gift.example"]
+        );
+    }
+
+    #[test]
+    fn parses_json_feeds() {
+        assert_eq!(
+            parse_feed(r#"["steam-gift.example", "https://free-nitro.example/claim"]"#),
+            vec!["free-nitro.example", "steam-gift.example"]
+        );
+    }
+}
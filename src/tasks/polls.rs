@@ -0,0 +1,60 @@
+use std::sync::Arc;
+
+use poise::serenity_prelude as serenity;
+use serenity::{ChannelId, CreateMessage, EditMessage, Http, MessageId};
+
+use crate::{
+    commands::poll::results_embed,
+    database::polls::{self, Poll},
+    Data,
+};
+
+/// Closes polls whose time ran out and posts their results.
+pub async fn run(http: Arc<Http>, data: Data) {
+    loop {
+        if let Err(e) = close_due_polls(&http, &data).await {
+            tracing::error!("Failed to close polls: {e:#?}");
+        }
+
+        tokio::time::sleep(std::time::Duration::from_secs(30)).await;
+    }
+}
+
+async fn close_due_polls(http: &Http, data: &Data) -> anyhow::Result<()> {
+    for poll in polls::due(&data.db_pool).await? {
+        // a poll that failed to close shouldn't keep the others open, it's retried next time.
+        match close_poll(http, data, &poll).await {
+            Ok(()) => polls::close(&data.db_pool, poll.id).await?,
+            Err(e) => tracing::error!("Failed to close poll #{}: {e:#?}", poll.id),
+        }
+    }
+
+    Ok(())
+}
+
+async fn close_poll(http: &Http, data: &Data, poll: &Poll) -> anyhow::Result<()> {
+    let (Some(channel_id), Some(message_id)) = (poll.channel_id, poll.message_id) else {
+        return Ok(());
+    };
+
+    let channel_id = ChannelId::new(channel_id as u64);
+    let message_id = MessageId::new(message_id as u64);
+    let counts = polls::tally(&data.db_pool, poll.id, poll.options.len()).await?;
+
+    channel_id
+        .edit_message(http, message_id, EditMessage::new().components(Vec::new()))
+        .await?;
+
+    channel_id
+        .send_message(
+            http,
+            CreateMessage::new()
+                .embed(results_embed(poll, &counts))
+                .reference_message((channel_id, message_id)),
+        )
+        .await?;
+
+    tracing::info!("Closed poll #{}.", poll.id);
+
+    Ok(())
+}
@@ -0,0 +1,21 @@
+use crate::{scheduler::Scheduler, sponsors, Data};
+
+/// Keeps the sponsor roles in sync with the github sponsors of the organization.
+pub fn schedule(scheduler: &mut Scheduler, data: &Data) -> anyhow::Result<()> {
+    if data.config.sponsors.is_none() {
+        tracing::info!("No sponsors configured, sponsor syncs are disabled.");
+        return Ok(());
+    }
+
+    if data.config.github.api_token.is_none() {
+        anyhow::bail!("Syncing sponsor roles needs a github api token");
+    }
+
+    // every hour.
+    scheduler.add(
+        "sponsors",
+        "45 * * * *",
+        std::time::Duration::from_secs(5 * 60),
+        |http, data| async move { sponsors::sync_all(&http, &data).await },
+    )
+}
@@ -0,0 +1,171 @@
+use chrono::{DateTime, Duration, Utc};
+use poise::serenity_prelude as serenity;
+use serenity::{CreateAllowedMentions, CreateMessage, EditThread, GuildChannel, Http, Mentionable};
+
+use crate::{config::StaleThreadConfig, database::stale_thread_bumps, scheduler::Scheduler, Data};
+
+#[derive(Debug, PartialEq, Eq)]
+enum StaleAction {
+    /// Ask the author whether they still need help.
+    Bump,
+    Archive,
+}
+
+/// Bumps help forum posts without recent activity and archives them if the bump went unanswered.
+pub fn schedule(scheduler: &mut Scheduler, data: &Data) -> anyhow::Result<()> {
+    if !data
+        .config
+        .help_forums
+        .iter()
+        .any(|forum| forum.stale.is_some())
+    {
+        tracing::info!(
+            "No help forum has stale posts configured, stale thread checks are disabled."
+        );
+        return Ok(());
+    }
+
+    // every hour.
+    scheduler.add(
+        "stale_threads",
+        "0 * * * *",
+        std::time::Duration::from_secs(5 * 60),
+        |http, data| async move { check_threads(&http, &data).await },
+    )
+}
+
+async fn check_threads(http: &Http, data: &Data) -> anyhow::Result<()> {
+    let active_threads = data.config.bot.guild_id.get_active_threads(http).await?;
+    let now = Utc::now();
+    let mut failed = 0;
+
+    for forum in &data.config.help_forums {
+        let Some(config) = &forum.stale else {
+            continue;
+        };
+
+        for thread in active_threads
+            .threads
+            .iter()
+            .filter(|thread| thread.parent_id == Some(forum.channel))
+        {
+            if let Err(e) = check_thread(http, data, thread, config, now).await {
+                tracing::warn!("Failed to check stale help thread {}: {e:#}", thread.id);
+                failed += 1;
+            }
+        }
+    }
+
+    if failed > 0 {
+        anyhow::bail!("Failed to check {failed} stale help threads");
+    }
+
+    Ok(())
+}
+
+async fn check_thread(
+    http: &Http,
+    data: &Data,
+    thread: &GuildChannel,
+    config: &StaleThreadConfig,
+    now: DateTime<Utc>,
+) -> anyhow::Result<()> {
+    let thread_id = thread.id.get() as i64;
+    let bump_message_id = stale_thread_bumps::message_id(&data.db_pool, thread_id).await?;
+
+    let last_message_is_bump = bump_message_id.is_some()
+        && bump_message_id == thread.last_message_id.map(|id| id.get() as i64);
+
+    match stale_action(last_activity(thread), last_message_is_bump, now, config) {
+        Some(StaleAction::Bump) => {
+            let message = thread
+                .send_message(http, bump_message(thread, config))
+                .await?;
+
+            stale_thread_bumps::upsert(&data.db_pool, thread_id, message.id.get() as i64).await?;
+            tracing::info!("Bumped stale help thread {}.", thread.id);
+        }
+        Some(StaleAction::Archive) => {
+            thread
+                .id
+                .edit_thread(http, EditThread::new().archived(true))
+                .await?;
+
+            stale_thread_bumps::delete(&data.db_pool, thread_id).await?;
+            tracing::info!("Archived stale help thread {}.", thread.id);
+        }
+        None => {}
+    }
+
+    Ok(())
+}
+
+/// When the last message was sent, or the thread was created if it has no messages.
+fn last_activity(thread: &GuildChannel) -> DateTime<Utc> {
+    let timestamp = match thread.last_message_id {
+        Some(message_id) => message_id.created_at(),
+        None => thread.id.created_at(),
+    };
+
+    DateTime::from_timestamp(timestamp.unix_timestamp(), 0).unwrap_or_default()
+}
+
+fn stale_action(
+    last_activity: DateTime<Utc>,
+    last_message_is_bump: bool,
+    now: DateTime<Utc>,
+    config: &StaleThreadConfig,
+) -> Option<StaleAction> {
+    let idle = now - last_activity;
+
+    if last_message_is_bump {
+        (idle >= Duration::days(config.archive_after_days as i64)).then_some(StaleAction::Archive)
+    } else {
+        (idle >= Duration::days(config.bump_after_days as i64)).then_some(StaleAction::Bump)
+    }
+}
+
+fn bump_message(thread: &GuildChannel, config: &StaleThreadConfig) -> CreateMessage {
+    let greeting = match thread.owner_id {
+        Some(owner_id) => format!("Hey {}, do", owner_id.mention()),
+        None => "Do".to_string(),
+    };
+
+    CreateMessage::new()
+        .content(format!(
+            "{greeting} you still need help with this? If it's solved, use `/solved` or react with \u{2705} to the answer that helped. Otherwise this post gets archived in {} days without new activity.",
+            config.archive_after_days
+        ))
+        .allowed_mentions(CreateAllowedMentions::new().users(thread.owner_id))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bumps_then_archives_idle_threads() {
+        let config = StaleThreadConfig {
+            bump_after_days: 3,
+            archive_after_days: 2,
+        };
+        let now = Utc::now();
+
+        assert_eq!(
+            stale_action(now - Duration::days(1), false, now, &config),
+            None
+        );
+        assert_eq!(
+            stale_action(now - Duration::days(3), false, now, &config),
+            Some(StaleAction::Bump)
+        );
+        assert_eq!(
+            stale_action(now - Duration::days(1), true, now, &config),
+            None
+        );
+        assert_eq!(
+            stale_action(now - Duration::days(2), true, now, &config),
+            Some(StaleAction::Archive)
+        );
+    }
+}
@@ -0,0 +1,161 @@
+use chrono::{Duration, Utc};
+use poise::serenity_prelude as serenity;
+use serenity::{ChannelId, CreateEmbed, CreateEmbedFooter, CreateMessage, Http};
+
+use crate::{
+    database::digest_snapshots,
+    npm_api::{self, BIOME_PACKAGE},
+    scheduler::Scheduler,
+    settings::Setting,
+    util::{
+        embeds::EmbedColor,
+        format::{display_count, time, TimestampStyle},
+    },
+    Data,
+};
+
+/// Every monday at 09:00 UTC.
+const SCHEDULE: &str = "0 9 * * 1";
+
+/// Posts the project digest on the schedule into the channel of the `digest_channel` setting.
+/// The channel is looked up on every run, so changing the setting needs no restart.
+pub fn schedule(scheduler: &mut Scheduler, data: &Data) -> anyhow::Result<()> {
+    scheduler.add(
+        "weekly_digest",
+        SCHEDULE,
+        std::time::Duration::ZERO,
+        move |http, data| async move {
+            let Some(channel_id) = data.settings.channel(Setting::DigestChannel) else {
+                tracing::info!("No digest channel set, skipping the weekly digest.");
+                return Ok(());
+            };
+
+            post_digest(&http, &data, channel_id).await?;
+            tracing::info!("Posted weekly digest.");
+
+            Ok(())
+        },
+    )
+}
+
+async fn post_digest(http: &Http, data: &Data, channel_id: ChannelId) -> anyhow::Result<()> {
+    let since = Utc::now() - Duration::days(7);
+    let since_date = since.format("%Y-%m-%d");
+    let github = &data.github;
+
+    let (repository, contributors, downloads, opened, closed, merged) = tokio::try_join!(
+        github.repository_info(),
+        github.contributor_count(),
+        npm_api::weekly_downloads(BIOME_PACKAGE),
+        github.search_count(&format!("is:issue created:>={since_date}")),
+        github.search_count(&format!("is:issue closed:>={since_date}")),
+        github.search_count(&format!("is:pr is:merged merged:>={since_date}")),
+    )?;
+
+    let previous = digest_snapshots::latest(&data.db_pool).await?;
+
+    let stars = repository.stargazers_count as i64;
+    let contributors = contributors as i64;
+    let downloads = downloads.downloads as i64;
+
+    let embed = CreateEmbed::new()
+        .color(EmbedColor::Biome)
+        .title("Weekly Biome Digest")
+        .url(&repository.html_url)
+        .description(format!(
+            "Here is what happened since {}.",
+            time(since, TimestampStyle::LongDate)
+        ))
+        .field(
+            "Stars",
+            display_delta(stars, previous.as_ref().map(|p| p.stars)),
+            true,
+        )
+        .field(
+            "Contributors",
+            display_delta(contributors, previous.as_ref().map(|p| p.contributors)),
+            true,
+        )
+        .field(
+            "Weekly Downloads",
+            display_delta(downloads, previous.as_ref().map(|p| p.weekly_downloads)),
+            true,
+        )
+        .field("Issues Opened", display_count(opened), true)
+        .field("Issues Closed", display_count(closed), true)
+        .field("PRs Merged", display_count(merged), true)
+        .footer(CreateEmbedFooter::new("Biome Weekly Digest"))
+        .timestamp(Utc::now());
+
+    channel_id
+        .send_message(http, CreateMessage::new().embed(embed))
+        .await?;
+
+    digest_snapshots::insert(&data.db_pool, stars, contributors, downloads).await?;
+
+    Ok(())
+}
+
+/// Formats the current value together with the change since the last digest, e.g. `1,234 (+56)`.
+fn display_delta(current: i64, previous: Option<i64>) -> String {
+    let current_display = display_count(current.unsigned_abs());
+
+    match previous {
+        Some(previous) if current >= previous => {
+            format!(
+                "{current_display} (+{})",
+                display_count((current - previous) as u64)
+            )
+        }
+        Some(previous) => {
+            format!(
+                "{current_display} (-{})",
+                display_count((previous - current) as u64)
+            )
+        }
+        None => current_display,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{DateTime, TimeZone};
+
+    use super::*;
+    use crate::scheduler::cron::Cron;
+
+    fn next_digest_time(now: DateTime<Utc>) -> DateTime<Utc> {
+        Cron::parse(SCHEDULE).unwrap().next_after(now).unwrap()
+    }
+
+    #[test]
+    fn next_digest_is_upcoming_monday_morning() {
+        // a wednesday
+        let now = Utc.with_ymd_and_hms(2024, 4, 17, 12, 0, 0).unwrap();
+        assert_eq!(
+            next_digest_time(now),
+            Utc.with_ymd_and_hms(2024, 4, 22, 9, 0, 0).unwrap()
+        );
+
+        // monday after the digest was posted
+        let now = Utc.with_ymd_and_hms(2024, 4, 22, 9, 0, 0).unwrap();
+        assert_eq!(
+            next_digest_time(now),
+            Utc.with_ymd_and_hms(2024, 4, 29, 9, 0, 0).unwrap()
+        );
+
+        // monday before the digest was posted
+        let now = Utc.with_ymd_and_hms(2024, 4, 22, 8, 59, 0).unwrap();
+        assert_eq!(
+            next_digest_time(now),
+            Utc.with_ymd_and_hms(2024, 4, 22, 9, 0, 0).unwrap()
+        );
+    }
+
+    #[test]
+    fn delta_shows_sign() {
+        assert_eq!(display_delta(1500, Some(1000)), "1,500 (+500)");
+        assert_eq!(display_delta(900, Some(1000)), "900 (-100)");
+        assert_eq!(display_delta(900, None), "900");
+    }
+}
@@ -0,0 +1,136 @@
+use std::sync::{Arc, RwLock};
+
+/// Discord shows at most 25 suggestions.
+pub const MAX_CHOICES: usize = 25;
+
+/// Names offered by an autocomplete. Kept in memory and refreshed in the background, so
+/// suggestions never wait for github or the database.
+#[derive(Debug, Default)]
+pub struct Choices {
+    names: RwLock<Arc<Vec<String>>>,
+}
+
+impl Choices {
+    pub fn replace(&self, names: Vec<String>) {
+        *self.names.write().unwrap() = Arc::new(names);
+    }
+
+    pub fn suggest(&self, partial: &str) -> Vec<String> {
+        let names = self.names.read().unwrap().clone();
+
+        fuzzy_match(names.iter(), partial)
+    }
+}
+
+/// The datasets several commands autocomplete from, see `tasks::autocomplete`.
+#[derive(Debug, Default)]
+pub struct AutocompleteSources {
+    pub rules: Choices,
+    pub tags: Choices,
+    /// Stable biome versions, newest first.
+    pub versions: Choices,
+    pub config_keys: Choices,
+}
+
+/// The candidates matching the partial input, best matches first. Exact matches come before
+/// prefixes, word starts, substrings and finally candidates that only contain the letters in
+/// order, so `nxa` still finds `noExplicitAny`. Equally good matches keep their order.
+pub fn fuzzy_match<S: AsRef<str>>(
+    candidates: impl IntoIterator<Item = S>,
+    partial: &str,
+) -> Vec<String> {
+    let query = partial.trim().to_ascii_lowercase();
+
+    let mut matches = candidates
+        .into_iter()
+        .filter_map(|candidate| {
+            let candidate = candidate.as_ref();
+            Some((score(candidate, &query)?, candidate.to_string()))
+        })
+        .collect::<Vec<_>>();
+
+    matches.sort_by_key(|(score, _)| *score);
+
+    matches
+        .into_iter()
+        .map(|(_, candidate)| candidate)
+        .take(MAX_CHOICES)
+        .collect()
+}
+
+/// How well the candidate matches the lowercase query, lower is better.
+fn score(candidate: &str, query: &str) -> Option<u8> {
+    let lowercase = candidate.to_ascii_lowercase();
+
+    if lowercase == query {
+        return Some(0);
+    }
+
+    if lowercase.starts_with(query) {
+        return Some(1);
+    }
+
+    let bytes = candidate.as_bytes();
+    // a word starts after a separator or where camel case goes from lower to upper case.
+    let starts_word = |index: usize| {
+        let previous = bytes[index - 1];
+        !previous.is_ascii_alphanumeric()
+            || (previous.is_ascii_lowercase() && bytes[index].is_ascii_uppercase())
+    };
+
+    let mut positions = lowercase.match_indices(query).map(|(index, _)| index);
+
+    if let Some(first) = positions.next() {
+        return Some(
+            if std::iter::once(first).chain(positions).any(starts_word) {
+                2
+            } else {
+                3
+            },
+        );
+    }
+
+    let mut letters = lowercase.chars();
+
+    query
+        .chars()
+        .all(|wanted| letters.any(|letter| letter == wanted))
+        .then_some(4)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ranks_matches() {
+        let rules = [
+            "useExhaustiveDependencies",
+            "noExplicitAny",
+            "noUnusedVariables",
+            "noVar",
+            "useConst",
+        ];
+
+        assert_eq!(fuzzy_match(rules, "novar"), ["noVar", "noUnusedVariables"]);
+        assert_eq!(fuzzy_match(rules, "no")[0], "noExplicitAny");
+        assert_eq!(fuzzy_match(rules, "var"), ["noUnusedVariables", "noVar"]);
+        assert_eq!(fuzzy_match(rules, "nxa"), ["noExplicitAny"]);
+        assert_eq!(fuzzy_match(rules, "").len(), rules.len());
+        assert!(fuzzy_match(rules, "xyz").is_empty());
+    }
+
+    #[test]
+    fn prefers_word_starts() {
+        let keys = ["formatter.indentStyle", "javascript.formatter.enabled"];
+
+        assert_eq!(
+            fuzzy_match(keys, "enabled"),
+            ["javascript.formatter.enabled"]
+        );
+        assert_eq!(
+            fuzzy_match(["unicorn", "lint.icons"], "icon"),
+            ["lint.icons", "unicorn"]
+        );
+    }
+}
@@ -0,0 +1,55 @@
+use std::{
+    future::Future,
+    time::{Duration, Instant},
+};
+
+use tokio::sync::RwLock;
+
+/// A single cached value that is refreshed lazily once it is older than its time to live.
+#[derive(Debug)]
+pub struct TtlCache<T> {
+    ttl: Duration,
+    entry: RwLock<Option<(Instant, T)>>,
+}
+
+impl<T: Clone> TtlCache<T> {
+    pub fn new(ttl: Duration) -> Self {
+        Self {
+            ttl,
+            entry: RwLock::new(None),
+        }
+    }
+
+    /// Returns the cached value if it is still fresh.
+    pub async fn get(&self) -> Option<T> {
+        match &*self.entry.read().await {
+            Some((fetched_at, value)) if fetched_at.elapsed() < self.ttl => Some(value.clone()),
+            _ => None,
+        }
+    }
+
+    pub async fn set(&self, value: T) {
+        *self.entry.write().await = Some((Instant::now(), value));
+    }
+
+    /// Drops the cached value, so the next access refreshes it.
+    pub async fn invalidate(&self) {
+        *self.entry.write().await = None;
+    }
+
+    /// Returns the cached value or awaits `refresh` and caches its result when the entry is stale.
+    pub async fn get_or_refresh<F, Fut>(&self, refresh: F) -> anyhow::Result<T>
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = anyhow::Result<T>>,
+    {
+        if let Some(value) = self.get().await {
+            return Ok(value);
+        }
+
+        let value = refresh().await?;
+        self.set(value.clone()).await;
+
+        Ok(value)
+    }
+}
@@ -1,6 +1,8 @@
 use poise::serenity_prelude as serenity;
 use serenity::{CreateEmbed, CreateEmbedFooter, User};
 
+use crate::util::parse::parse_hex_color;
+
 pub fn default_embed(user: &User) -> CreateEmbed {
     let footer = CreateEmbedFooter::new(format!(
         "Requested by {}",
@@ -9,7 +11,7 @@ pub fn default_embed(user: &User) -> CreateEmbed {
     .icon_url(user.static_avatar_url().unwrap_or_default());
 
     CreateEmbed::new()
-        .color(6_530_042) // biome logo color
+        .color(EmbedColor::Biome)
         .footer(footer)
         .timestamp(chrono::Utc::now())
 }
@@ -40,3 +42,28 @@ impl From<EmbedColor> for serenity::Colour {
         serenity::Colour(colour as u32)
     }
 }
+
+/// The hex color if one is given, otherwise the named one or the biome color. `None` if the hex
+/// color is invalid.
+pub fn pick_color(colour: Option<EmbedColor>, hex_color: Option<&str>) -> Option<u32> {
+    match hex_color {
+        Some(hex_color) => parse_hex_color(hex_color),
+        None => Some(colour.unwrap_or_default() as u32),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn prefers_hex_colors() {
+        assert_eq!(pick_color(None, None), Some(0x63A3FA));
+        assert_eq!(pick_color(Some(EmbedColor::Red), None), Some(0xFF0000));
+        assert_eq!(
+            pick_color(Some(EmbedColor::Red), Some("#1a2b3c")),
+            Some(0x1A2B3C)
+        );
+        assert_eq!(pick_color(None, Some("#12345")), None);
+    }
+}
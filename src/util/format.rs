@@ -119,3 +119,181 @@ pub fn display_bytes(bytes: u64) -> String {
 
     format!("{:.2} {}", precise_count, sizes[i])
 }
+
+/// Formats a number with thousands separators, e.g. `1234567` as `1,234,567`.
+pub fn display_count(count: u64) -> String {
+    let digits = count.to_string();
+    let mut output = String::with_capacity(digits.len() + digits.len() / 3);
+
+    for (i, c) in digits.chars().enumerate() {
+        if i > 0 && (digits.len() - i) % 3 == 0 {
+            output.push(',');
+        }
+        output.push(c);
+    }
+
+    output
+}
+
+/// Cuts the input off after `max_chars` characters and marks it as truncated.
+pub fn truncate(input: &str, max_chars: usize) -> String {
+    if input.chars().count() <= max_chars {
+        return input.to_string();
+    }
+
+    let mut output = input.chars().take(max_chars).collect::<String>();
+    output.push_str("\n...");
+    output
+}
+
+/// Splits markdown into chunks of at most `max_chars` characters, breaking between lines where
+/// possible and closing and reopening code fences that would otherwise be cut in half.
+pub fn split_markdown(input: &str, max_chars: usize) -> Vec<String> {
+    // every chunk keeps room for a closing fence and its line break.
+    const FENCE_RESERVE: usize = 4;
+
+    let mut chunks = Vec::new();
+    let mut current = String::new();
+    let mut open_fence: Option<String> = None;
+
+    for line in input.lines() {
+        let reopen_len = open_fence
+            .as_ref()
+            .map_or(0, |fence| fence.chars().count() + 1);
+        let piece_len = max_chars
+            .saturating_sub(reopen_len + FENCE_RESERVE + 1)
+            .max(1);
+
+        let chars = line.chars().collect::<Vec<_>>();
+        let pieces = if chars.is_empty() {
+            vec![String::new()]
+        } else {
+            chars
+                .chunks(piece_len)
+                .map(|piece| piece.iter().collect::<String>())
+                .collect()
+        };
+
+        for piece in pieces {
+            let needed = current.chars().count() + piece.chars().count() + 1 + FENCE_RESERVE;
+
+            if needed > max_chars && !current.is_empty() {
+                let mut chunk = std::mem::take(&mut current);
+
+                if open_fence.is_some() {
+                    chunk.push_str("```");
+                }
+
+                chunks.push(chunk.trim_end().to_string());
+
+                if let Some(fence) = &open_fence {
+                    current.push_str(fence);
+                    current.push('\n');
+                }
+            }
+
+            current.push_str(&piece);
+            current.push('\n');
+        }
+
+        if line.trim_start().starts_with("```") {
+            open_fence = match open_fence {
+                Some(_) => None,
+                None => Some(line.trim().to_string()),
+            };
+        }
+    }
+
+    if !current.trim().is_empty() {
+        chunks.push(current.trim_end().to_string());
+    }
+
+    chunks
+}
+
+/// Renders a text progress bar like `▓▓▓▓░░░░░░ 40%` for a fraction between 0 and 1.
+pub fn progress_bar(fraction: f64, width: usize) -> String {
+    let fraction = fraction.clamp(0.0, 1.0);
+    let filled = (fraction * width as f64).round() as usize;
+
+    format!(
+        "{}{} {:.0}%",
+        "\u{2593}".repeat(filled),
+        "\u{2591}".repeat(width - filled),
+        fraction * 100.0
+    )
+}
+
+/// Formats a duration in the notation `parse_duration` reads, e.g. `1d 2h 30m`.
+pub fn display_duration(duration: chrono::Duration) -> String {
+    let mut seconds = duration.num_seconds().max(0);
+    let mut parts = Vec::new();
+
+    for (unit, unit_seconds) in [("d", 24 * 60 * 60), ("h", 60 * 60), ("m", 60), ("s", 1)] {
+        if seconds >= unit_seconds {
+            parts.push(format!("{}{unit}", seconds / unit_seconds));
+            seconds %= unit_seconds;
+        }
+    }
+
+    if parts.is_empty() {
+        "0s".to_string()
+    } else {
+        parts.join(" ")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn progress_bar_rounds_to_width() {
+        assert_eq!(progress_bar(0.0, 4), "\u{2591}\u{2591}\u{2591}\u{2591} 0%");
+        assert_eq!(progress_bar(0.5, 4), "\u{2593}\u{2593}\u{2591}\u{2591} 50%");
+        assert_eq!(
+            progress_bar(1.5, 4),
+            "\u{2593}\u{2593}\u{2593}\u{2593} 100%"
+        );
+    }
+    #[test]
+    fn count_has_thousands_separators() {
+        assert_eq!(display_count(0), "0");
+        assert_eq!(display_count(999), "999");
+        assert_eq!(display_count(1000), "1,000");
+        assert_eq!(display_count(1234567), "1,234,567");
+    }
+
+    #[test]
+    fn split_markdown_respects_limits_and_fences() {
+        let input =
+            "## Title\n\nSome text.\n```js\nconst a = 1;\nconst b = 2;\nconst c = 3;\n```\nAfter.";
+        let chunks = split_markdown(input, 40);
+
+        assert!(chunks.len() > 1);
+
+        for chunk in &chunks {
+            assert!(chunk.chars().count() <= 40, "{chunk:?} is too long");
+            assert_eq!(
+                chunk.matches("```").count() % 2,
+                0,
+                "{chunk:?} has an open fence"
+            );
+        }
+
+        assert_eq!(split_markdown("short", 40), vec!["short"]);
+        assert!(split_markdown(&"x".repeat(100), 40)
+            .iter()
+            .all(|chunk| chunk.chars().count() <= 40));
+    }
+
+    #[test]
+    fn duration_uses_largest_units() {
+        assert_eq!(display_duration(chrono::Duration::seconds(0)), "0s");
+        assert_eq!(display_duration(chrono::Duration::minutes(90)), "1h 30m");
+        assert_eq!(
+            display_duration(chrono::Duration::seconds(2 * 24 * 60 * 60 + 5)),
+            "2d 5s"
+        );
+    }
+}
@@ -0,0 +1,87 @@
+use std::{net::IpAddr, str::FromStr};
+
+use anyhow::Context;
+use serde::Deserialize;
+
+/// A CIDR range like `192.30.252.0/22`, a single address is a range of one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(try_from = "String")]
+pub struct IpRange {
+    network: IpAddr,
+    prefix: u32,
+}
+
+impl IpRange {
+    pub fn contains(&self, ip: IpAddr) -> bool {
+        match (self.network, ip.to_canonical()) {
+            (IpAddr::V4(network), IpAddr::V4(ip)) => {
+                let mask = u32::MAX.checked_shl(32 - self.prefix).unwrap_or(0);
+                u32::from(network) & mask == u32::from(ip) & mask
+            }
+            (IpAddr::V6(network), IpAddr::V6(ip)) => {
+                let mask = u128::MAX.checked_shl(128 - self.prefix).unwrap_or(0);
+                u128::from(network) & mask == u128::from(ip) & mask
+            }
+            _ => false,
+        }
+    }
+}
+
+impl FromStr for IpRange {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (network, prefix) = s.split_once('/').unwrap_or((s, ""));
+        let network = network
+            .parse::<IpAddr>()
+            .with_context(|| format!("Invalid address in the range {s}"))?;
+        let max_prefix = if network.is_ipv4() { 32 } else { 128 };
+
+        let prefix = if prefix.is_empty() {
+            max_prefix
+        } else {
+            prefix
+                .parse::<u32>()
+                .ok()
+                .filter(|prefix| *prefix <= max_prefix)
+                .with_context(|| format!("Invalid prefix length in the range {s}"))?
+        };
+
+        Ok(Self { network, prefix })
+    }
+}
+
+impl TryFrom<String> for IpRange {
+    type Error = anyhow::Error;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_ranges() {
+        assert!("192.30.252.0/22".parse::<IpRange>().is_ok());
+        assert!("2a0a:a440::/29".parse::<IpRange>().is_ok());
+        assert!("140.82.112.1".parse::<IpRange>().is_ok());
+        assert!("192.30.252.0/33".parse::<IpRange>().is_err());
+        assert!("github.com/22".parse::<IpRange>().is_err());
+    }
+
+    #[test]
+    fn matches_addresses() {
+        let range = "10.0.0.0/8".parse::<IpRange>().unwrap();
+
+        assert!(range.contains(IpAddr::from([10, 1, 2, 3])));
+        assert!(range.contains("::ffff:10.0.0.1".parse().unwrap()));
+        assert!(!range.contains(IpAddr::from([11, 0, 0, 1])));
+        assert!("0.0.0.0/0"
+            .parse::<IpRange>()
+            .unwrap()
+            .contains(IpAddr::from([1, 1, 1, 1])));
+    }
+}
@@ -1,2 +1,7 @@
+pub mod autocomplete;
+pub mod cache;
 pub mod embeds;
 pub mod format;
+pub mod ip_range;
+pub mod paginate;
+pub mod parse;
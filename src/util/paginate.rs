@@ -0,0 +1,120 @@
+use std::time::Duration;
+
+use poise::{serenity_prelude as serenity, CreateReply};
+use serenity::{
+    ButtonStyle, ComponentInteractionCollector, CreateActionRow, CreateButton, CreateEmbed,
+    CreateInteractionResponse, CreateInteractionResponseMessage,
+};
+
+use crate::Context;
+
+/// How long the buttons keep working after they were last pressed.
+const TIMEOUT: Duration = Duration::from_secs(300);
+
+/// Sends the first page with buttons to go back and forth or jump to the first or last page.
+/// A single page is sent without buttons, they are removed once they timed out.
+pub async fn paginate(ctx: Context<'_>, pages: Vec<CreateEmbed>) -> anyhow::Result<()> {
+    let Some(first_page) = pages.first() else {
+        return Ok(());
+    };
+
+    if pages.len() == 1 {
+        ctx.send(CreateReply::default().embed(first_page.clone()))
+            .await?;
+        return Ok(());
+    }
+
+    let ctx_id = ctx.id();
+
+    let reply_handle = ctx
+        .send(
+            CreateReply::default()
+                .embed(first_page.clone())
+                .components(buttons(ctx_id, 0, pages.len())),
+        )
+        .await?;
+
+    let mut current_page = 0;
+
+    while let Some(press) = ComponentInteractionCollector::new(ctx)
+        .filter(move |press| press.data.custom_id.starts_with(&ctx_id.to_string()))
+        .timeout(TIMEOUT)
+        .await
+    {
+        let Some(page) = press
+            .data
+            .custom_id
+            .strip_prefix(&ctx_id.to_string())
+            .and_then(|button| target_page(button, current_page, pages.len()))
+        else {
+            continue;
+        };
+
+        current_page = page;
+
+        press
+            .create_response(
+                ctx.serenity_context(),
+                CreateInteractionResponse::UpdateMessage(
+                    CreateInteractionResponseMessage::new()
+                        .embed(pages[current_page].clone())
+                        .components(buttons(ctx_id, current_page, pages.len())),
+                ),
+            )
+            .await?;
+    }
+
+    reply_handle
+        .edit(
+            ctx,
+            CreateReply::default()
+                .embed(pages[current_page].clone())
+                .components(vec![]),
+        )
+        .await?;
+
+    Ok(())
+}
+
+fn buttons(ctx_id: u64, current_page: usize, page_count: usize) -> Vec<CreateActionRow> {
+    let button = |name: &str, label: &str| {
+        CreateButton::new(format!("{ctx_id}{name}"))
+            .label(label)
+            .style(ButtonStyle::Secondary)
+    };
+
+    vec![CreateActionRow::Buttons(vec![
+        button("first", "First").disabled(current_page == 0),
+        button("prev", "Previous"),
+        // only shows where the reader is, it can't be pressed.
+        button("page", &format!("{}/{page_count}", current_page + 1)).disabled(true),
+        button("next", "Next"),
+        button("last", "Last").disabled(current_page + 1 == page_count),
+    ])]
+}
+
+/// The page a button leads to, previous and next wrap around at the ends.
+fn target_page(button: &str, current_page: usize, page_count: usize) -> Option<usize> {
+    match button {
+        "first" => Some(0),
+        "prev" => Some(current_page.checked_sub(1).unwrap_or(page_count - 1)),
+        "next" => Some((current_page + 1) % page_count),
+        "last" => Some(page_count - 1),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn wraps_around() {
+        assert_eq!(target_page("prev", 0, 3), Some(2));
+        assert_eq!(target_page("next", 2, 3), Some(0));
+        assert_eq!(target_page("next", 0, 3), Some(1));
+        assert_eq!(target_page("last", 0, 3), Some(2));
+        assert_eq!(target_page("first", 2, 3), Some(0));
+        assert_eq!(target_page("page", 1, 3), None);
+    }
+}
@@ -0,0 +1,324 @@
+use serenity::all::{ChannelId, MessageId};
+
+/// A message referenced either by a full jump link or only by its ID.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MessageLink {
+    /// Only known when a full jump link was provided.
+    pub channel_id: Option<ChannelId>,
+    pub message_id: MessageId,
+}
+
+/// Parses a discord message jump link (`https://discord.com/channels/<guild>/<channel>/<message>`) or a raw message ID.
+pub fn parse_message_link(input: &str) -> Option<MessageLink> {
+    let input = input.trim();
+
+    if let Some((_, channel_id, message_id)) = serenity::utils::parse_message_url(input) {
+        return Some(MessageLink {
+            channel_id: Some(channel_id),
+            message_id,
+        });
+    }
+
+    input
+        .parse::<u64>()
+        .ok()
+        .filter(|id| *id != 0)
+        .map(|id| MessageLink {
+            channel_id: None,
+            message_id: MessageId::new(id),
+        })
+}
+
+/// A fenced code block of a discord message.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CodeBlock {
+    /// The info string after the opening fence, e.g. `ts`.
+    pub language: Option<String>,
+    pub code: String,
+}
+
+impl CodeBlock {
+    /// The file extension biome needs to pick the right parser, if biome supports the language.
+    pub fn file_extension(&self) -> Option<&'static str> {
+        match self.language.as_deref()? {
+            "js" | "javascript" | "mjs" | "cjs" => Some("js"),
+            "jsx" => Some("jsx"),
+            "ts" | "typescript" | "mts" | "cts" => Some("ts"),
+            "tsx" => Some("tsx"),
+            "json" => Some("json"),
+            "jsonc" => Some("jsonc"),
+            "css" => Some("css"),
+            "graphql" | "gql" => Some("graphql"),
+            _ => None,
+        }
+    }
+}
+
+/// Extracts every ```` ``` ```` fenced code block of a message, an unclosed block runs until the end.
+pub fn parse_code_blocks(content: &str) -> Vec<CodeBlock> {
+    let mut blocks = Vec::new();
+    let mut rest = content;
+
+    while let Some(start) = rest.find("```") {
+        let after_fence = &rest[start + 3..];
+
+        let (info, body) = match after_fence.split_once('\n') {
+            Some((info, body)) if !info.trim().contains([' ', '`']) => (info.trim(), body),
+            // a one line block like ```let a = 1;```
+            _ => ("", after_fence),
+        };
+
+        let (code, remainder) = match body.find("```") {
+            Some(end) => (&body[..end], &body[end + 3..]),
+            None => (body, ""),
+        };
+
+        blocks.push(CodeBlock {
+            language: (!info.is_empty()).then(|| info.to_lowercase()),
+            code: code.trim_end_matches('\n').to_string(),
+        });
+
+        rest = remainder;
+    }
+
+    blocks
+}
+
+/// Removes `//` and `/* */` comments outside of strings, `biome.json` files often contain them.
+pub fn strip_json_comments(input: &str) -> String {
+    let mut output = String::with_capacity(input.len());
+    let mut chars = input.chars().peekable();
+    let mut in_string = false;
+
+    while let Some(c) = chars.next() {
+        if in_string {
+            output.push(c);
+
+            match c {
+                '\\' => output.extend(chars.next()),
+                '"' => in_string = false,
+                _ => {}
+            }
+
+            continue;
+        }
+
+        match (c, chars.peek()) {
+            ('"', _) => {
+                in_string = true;
+                output.push(c);
+            }
+            ('/', Some('/')) => {
+                while chars.peek().is_some_and(|&next| next != '\n') {
+                    chars.next();
+                }
+            }
+            ('/', Some('*')) => {
+                chars.next();
+                let mut previous = ' ';
+
+                for next in chars.by_ref() {
+                    if previous == '*' && next == '/' {
+                        break;
+                    }
+                    // keep line breaks so parse errors still point at the right line.
+                    if next == '\n' {
+                        output.push('\n');
+                    }
+                    previous = next;
+                }
+            }
+            _ => output.push(c),
+        }
+    }
+
+    output
+}
+
+/// Parses a duration like `90m`, `1h 30m` or `2d`. Supports seconds, minutes, hours, days and weeks.
+pub fn parse_duration(input: &str) -> Option<chrono::Duration> {
+    let input = input.trim().to_lowercase();
+    let mut total = chrono::Duration::zero();
+    let mut number = String::new();
+    let mut parsed_any = false;
+
+    for c in input.chars().filter(|c| !c.is_whitespace()) {
+        if c.is_ascii_digit() {
+            number.push(c);
+            continue;
+        }
+
+        let amount = number.parse::<i64>().ok()?;
+        number.clear();
+
+        let unit = match c {
+            's' => chrono::Duration::try_seconds(amount)?,
+            'm' => chrono::Duration::try_minutes(amount)?,
+            'h' => chrono::Duration::try_hours(amount)?,
+            'd' => chrono::Duration::try_days(amount)?,
+            'w' => chrono::Duration::try_weeks(amount)?,
+            _ => return None,
+        };
+
+        total = total.checked_add(&unit)?;
+        parsed_any = true;
+    }
+
+    // a trailing number without a unit is ambiguous.
+    (parsed_any && number.is_empty() && total > chrono::Duration::zero()).then_some(total)
+}
+
+/// Parses a point in time: a duration from now like `2h 30m`, a discord timestamp like
+/// `<t:1767225600:F>`, unix seconds, or a UTC date like `2026-01-01 17:00` or in RFC 3339.
+pub fn parse_datetime(
+    input: &str,
+    now: chrono::DateTime<chrono::Utc>,
+) -> Option<chrono::DateTime<chrono::Utc>> {
+    let input = input.trim();
+
+    if let Some(duration) = parse_duration(input) {
+        return now.checked_add_signed(duration);
+    }
+
+    let unix = input
+        .strip_prefix("<t:")
+        .and_then(|timestamp| timestamp.strip_suffix('>'))
+        .map_or(input, |timestamp| {
+            timestamp.split(':').next().unwrap_or_default()
+        });
+
+    if let Ok(seconds) = unix.parse::<i64>() {
+        return chrono::DateTime::from_timestamp(seconds, 0);
+    }
+
+    if let Ok(time) = chrono::DateTime::parse_from_rfc3339(input) {
+        return Some(time.with_timezone(&chrono::Utc));
+    }
+
+    chrono::NaiveDateTime::parse_from_str(input, "%Y-%m-%d %H:%M")
+        .ok()
+        .map(|time| time.and_utc())
+}
+
+/// Parses a hex color like `#63A3FA` or `63a3fa`.
+pub fn parse_hex_color(input: &str) -> Option<u32> {
+    let input = input.trim();
+    let hex = input.strip_prefix('#').unwrap_or(input);
+
+    // `from_str_radix` would accept a sign as well.
+    if hex.len() != 6 || !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+        return None;
+    }
+
+    u32::from_str_radix(hex, 16).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_links_and_ids() {
+        let link = parse_message_link(
+            "https://discord.com/channels/1132231889290285117/1132231889290285120/1234567890123456789",
+        )
+        .unwrap();
+
+        assert_eq!(link.channel_id, Some(ChannelId::new(1132231889290285120)));
+        assert_eq!(link.message_id, MessageId::new(1234567890123456789));
+
+        let id = parse_message_link(" 1234567890123456789 ").unwrap();
+
+        assert_eq!(id.channel_id, None);
+        assert_eq!(id.message_id, MessageId::new(1234567890123456789));
+
+        assert!(parse_message_link("not a message").is_none());
+        assert!(parse_message_link("0").is_none());
+    }
+
+    #[test]
+    fn parses_code_blocks() {
+        let content = "Why does this fail?\n```ts\nconst a: number = 1;\n```\nand ```let b = 2;```";
+
+        assert_eq!(
+            parse_code_blocks(content),
+            vec![
+                CodeBlock {
+                    language: Some("ts".to_string()),
+                    code: "const a: number = 1;".to_string(),
+                },
+                CodeBlock {
+                    language: None,
+                    code: "let b = 2;".to_string(),
+                },
+            ]
+        );
+
+        assert!(parse_code_blocks("no code here").is_empty());
+    }
+
+    #[test]
+    fn strips_comments_outside_of_strings() {
+        assert_eq!(
+            strip_json_comments("{\n  // comment\n  \"a\": \"//b\" /* c\n */\n}"),
+            "{\n  \n  \"a\": \"//b\" \n\n}"
+        );
+    }
+
+    #[test]
+    fn parses_durations() {
+        assert_eq!(parse_duration("90m"), Some(chrono::Duration::minutes(90)));
+        assert_eq!(
+            parse_duration("1h 30m"),
+            Some(chrono::Duration::minutes(90))
+        );
+        assert_eq!(parse_duration("2D"), Some(chrono::Duration::days(2)));
+        assert_eq!(
+            parse_duration("1w1s"),
+            Some(chrono::Duration::seconds(604_801))
+        );
+
+        assert_eq!(parse_duration("10"), None);
+        assert_eq!(parse_duration("1h 5"), None);
+        assert_eq!(parse_duration("0m"), None);
+        assert_eq!(parse_duration("soon"), None);
+        assert_eq!(parse_duration(""), None);
+    }
+
+    #[test]
+    fn parses_hex_colors() {
+        assert_eq!(parse_hex_color("#63A3FA"), Some(0x63A3FA));
+        assert_eq!(parse_hex_color(" ff0000 "), Some(0xFF0000));
+        assert_eq!(parse_hex_color("#fff"), None);
+        assert_eq!(parse_hex_color("+12345"), None);
+        assert_eq!(parse_hex_color("##abc123"), None);
+        assert_eq!(parse_hex_color("blue"), None);
+    }
+
+    #[test]
+    fn parses_datetimes() {
+        let now = chrono::DateTime::from_timestamp(1_767_225_600, 0).unwrap();
+
+        assert_eq!(
+            parse_datetime("1h 30m", now),
+            Some(now + chrono::Duration::minutes(90))
+        );
+        assert_eq!(
+            parse_datetime("<t:1767229200:F>", now),
+            Some(now + chrono::Duration::hours(1))
+        );
+        assert_eq!(
+            parse_datetime("1767229200", now),
+            Some(now + chrono::Duration::hours(1))
+        );
+        assert_eq!(
+            parse_datetime("2026-01-01T02:00:00+01:00", now),
+            Some(now + chrono::Duration::hours(1))
+        );
+        assert_eq!(
+            parse_datetime("2026-01-01 01:00", now),
+            Some(now + chrono::Duration::hours(1))
+        );
+        assert_eq!(parse_datetime("tomorrow", now), None);
+    }
+}
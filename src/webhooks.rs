@@ -0,0 +1,113 @@
+use std::{fmt::Display, str::FromStr};
+
+use anyhow::Context as _;
+use poise::serenity_prelude as serenity;
+use serenity::{ChannelId, CreateWebhook, GuildId, Http, Mentionable, Webhook};
+
+use crate::{commands::embed::TargetChannelWebhook, database::channel_webhooks, Data};
+
+/// The name of the webhooks the bot creates, every message can override it.
+const WEBHOOK_NAME: &str = "Biome";
+
+/// Where embeds get posted. Stored as text with every embed snapshot.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EmbedTarget {
+    /// One of the webhooks from the config.
+    Webhook(TargetChannelWebhook),
+    /// Any other channel, posted through a webhook the bot creates there.
+    Channel(ChannelId),
+}
+
+impl EmbedTarget {
+    /// How the target is named in replies.
+    pub fn describe(self) -> String {
+        match self {
+            Self::Webhook(webhook) => format!("the {webhook} channel"),
+            Self::Channel(channel_id) => channel_id.mention().to_string(),
+        }
+    }
+}
+
+impl Display for EmbedTarget {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Webhook(webhook) => write!(f, "{webhook}"),
+            Self::Channel(channel_id) => write!(f, "{channel_id}"),
+        }
+    }
+}
+
+impl FromStr for EmbedTarget {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.parse::<u64>() {
+            Ok(channel_id) if channel_id > 0 => Ok(Self::Channel(ChannelId::new(channel_id))),
+            _ => TargetChannelWebhook::from_str(s).map(Self::Webhook),
+        }
+    }
+}
+
+/// The webhook to post through, configured webhooks are those of the server. In channels without
+/// a configured webhook the bot reuses the one it created before, or creates a new one if there is
+/// none or it was deleted.
+pub async fn resolve(
+    http: &Http,
+    data: &Data,
+    guild_id: GuildId,
+    target: EmbedTarget,
+) -> anyhow::Result<Webhook> {
+    let channel_id = match target {
+        EmbedTarget::Webhook(webhook) => {
+            let guild = data
+                .guild(guild_id)
+                .with_context(|| format!("The bot doesn't serve the server {guild_id}"))?;
+
+            return Ok(Webhook::from_url(http, guild.webhook_url(webhook)).await?);
+        }
+        EmbedTarget::Channel(channel_id) => channel_id,
+    };
+
+    if let Some(cached) = channel_webhooks::get(&data.db_pool, channel_id.get() as i64).await? {
+        match Webhook::from_url(http, &cached.webhook_url).await {
+            Ok(webhook) => return Ok(webhook),
+            Err(e) => {
+                tracing::warn!("Webhook of channel {channel_id} is gone, creating a new one: {e}")
+            }
+        }
+    }
+
+    let webhook = channel_id
+        .create_webhook(http, CreateWebhook::new(WEBHOOK_NAME))
+        .await
+        .context("Failed to create a webhook, the bot needs the manage webhooks permission")?;
+
+    // the url contains the token, which discord only returns when the webhook is created.
+    let url = webhook
+        .url()
+        .context("Discord didn't return the token of the new webhook")?;
+
+    channel_webhooks::upsert(&data.db_pool, channel_id.get() as i64, &url).await?;
+
+    tracing::info!("Created a webhook in channel {channel_id}.");
+
+    Ok(webhook)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_stored_targets() {
+        for target in [
+            EmbedTarget::Webhook(TargetChannelWebhook::Rules),
+            EmbedTarget::Channel(ChannelId::new(1234)),
+        ] {
+            assert_eq!(target.to_string().parse::<EmbedTarget>().unwrap(), target);
+        }
+
+        assert!("0".parse::<EmbedTarget>().is_err());
+        assert!("news".parse::<EmbedTarget>().is_err());
+    }
+}